@@ -1,5 +1,7 @@
-use crate::api::client::ApiClient;
-use crate::api::types::{ApiErrorResponse, CreateBundleRequest, CreateBundleResponse};
+use crate::api::client::{ApiClient, ApiRequestError};
+use crate::api::types::{
+    ApiErrorResponse, BUNDLE_FORMAT_VERSION, CreateBundleRequest, CreateBundleResponse,
+};
 use crate::error::GitAiError;
 
 /// Bundle API endpoints
@@ -14,14 +16,29 @@ impl ApiClient {
     /// * `Err(GitAiError)` - Error response
     ///
     /// # Errors
-    /// * Returns `GitAiError::Generic` for HTTP errors
+    /// * Returns `GitAiError::ApiError` when the request is rejected (4xx) or fails
+    ///   transiently; it is not retried, since a retry could create the bundle twice
+    /// * Returns `GitAiError::Generic` asking to upgrade git-ai when the server doesn't
+    ///   accept the request's bundle version
     /// * Returns `GitAiError::JsonError` for JSON parsing errors
-    /// * Returns `GitAiError::Generic` with error details for API errors (400, 500, etc.)
+    /// * Returns `GitAiError::Generic` for any other unexpected status
     pub fn create_bundle(
         &self,
         request: CreateBundleRequest,
     ) -> Result<CreateBundleResponse, GitAiError> {
-        let response = self.context().post_json("/api/bundles", &request)?;
+        let version = request.version;
+        let response = match self.context().post_json("/api/bundles", &request) {
+            Ok(response) => response,
+            Err(err) => {
+                if let GitAiError::ApiError(ApiRequestError::Rejected { body, .. }) = &err
+                    && let Some(supported) = supported_bundle_versions(body)
+                    && !supported.contains(&version)
+                {
+                    return Err(unsupported_bundle_version(version, &supported));
+                }
+                return Err(err);
+            }
+        };
         let status_code = response.status_code;
 
         let body = response
@@ -34,29 +51,6 @@ impl ApiClient {
                     serde_json::from_str(body).map_err(GitAiError::JsonError)?;
                 Ok(bundle_response)
             }
-            400 => {
-                // Try to parse error response
-                let error_response: ApiErrorResponse =
-                    serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
-                        error: "Invalid request body".to_string(),
-                        details: Some(serde_json::Value::String(body.to_string())),
-                    });
-                Err(GitAiError::Generic(format!(
-                    "Bad Request: {}",
-                    error_response.error
-                )))
-            }
-            500 => {
-                let error_response: ApiErrorResponse =
-                    serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
-                        error: "Internal server error".to_string(),
-                        details: None,
-                    });
-                Err(GitAiError::Generic(format!(
-                    "Internal Server Error: {}",
-                    error_response.error
-                )))
-            }
             _ => Err(GitAiError::Generic(format!(
                 "Unexpected status code {}: {}",
                 status_code, body
@@ -64,3 +58,86 @@ impl ApiClient {
         }
     }
 }
+
+impl CreateBundleRequest {
+    /// Parse a bundle, rejecting format versions newer than [`BUNDLE_FORMAT_VERSION`]
+    /// before looking at the rest, whose shape may have changed
+    #[allow(dead_code)]
+    pub fn from_json(json: &str) -> Result<Self, GitAiError> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(GitAiError::JsonError)?;
+        if let Some(version) = value.get("version").and_then(|v| v.as_u64())
+            && version > BUNDLE_FORMAT_VERSION as u64
+        {
+            return Err(GitAiError::Generic(format!(
+                "unsupported bundle version {} (this git-ai reads up to {}); upgrade git-ai",
+                version, BUNDLE_FORMAT_VERSION
+            )));
+        }
+        serde_json::from_value(value).map_err(GitAiError::JsonError)
+    }
+}
+
+/// Bundle versions a server lists when it rejects one it can't accept:
+/// `{"error": "...", "details": {"supported_bundle_versions": [2]}}`
+fn supported_bundle_versions(body: &str) -> Option<Vec<u32>> {
+    let error: ApiErrorResponse = serde_json::from_str(body).ok()?;
+    let versions = error.details?.get("supported_bundle_versions")?.clone();
+    serde_json::from_value(versions).ok()
+}
+
+fn unsupported_bundle_version(version: u32, supported: &[u32]) -> GitAiError {
+    let supported: Vec<String> = supported.iter().map(|v| v.to_string()).collect();
+    GitAiError::Generic(format!(
+        "unsupported bundle version {} (the server accepts {}); upgrade git-ai",
+        version,
+        supported.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::BundleData;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_bundle_version_round_trip() {
+        let request = CreateBundleRequest {
+            version: BUNDLE_FORMAT_VERSION,
+            title: "Fix parser".to_string(),
+            data: BundleData {
+                prompts: HashMap::new(),
+                files: HashMap::new(),
+            },
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"version\":1"), "{}", json);
+        assert_eq!(CreateBundleRequest::from_json(&json).unwrap(), request);
+
+        // Bundles from before versioning read as version 1
+        let legacy =
+            CreateBundleRequest::from_json(r#"{"title":"Old","data":{"prompts":{}}}"#).unwrap();
+        assert_eq!(legacy.version, 1);
+
+        // A newer format is rejected up front, even though its data no longer parses
+        let err =
+            CreateBundleRequest::from_json(r#"{"version":2,"title":"New","data":{"sessions":[]}}"#)
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: unsupported bundle version 2 (this git-ai reads up to 1); upgrade git-ai"
+        );
+    }
+
+    #[test]
+    fn test_supported_bundle_versions() {
+        assert_eq!(
+            supported_bundle_versions(
+                r#"{"error":"unsupported bundle version","details":{"supported_bundle_versions":[2,3]}}"#
+            ),
+            Some(vec![2, 3])
+        );
+        assert_eq!(supported_bundle_versions(r#"{"error":"missing"}"#), None);
+        assert_eq!(supported_bundle_versions("not json"), None);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::api::client::ApiClient;
-use crate::api::types::{ApiErrorResponse, CasUploadRequest, CasUploadResponse};
+use crate::api::types::{CasUploadRequest, CasUploadResponse};
 use crate::error::GitAiError;
 
 /// CAS API endpoints
 impl ApiClient {
-    /// Upload CAS objects to the server
+    /// Upload CAS objects to the server. Objects are content-addressed, so the upload is
+    /// retried like a GET.
     ///
     /// # Arguments
     /// * `request` - The CAS upload request containing objects to upload
@@ -13,7 +14,9 @@ impl ApiClient {
     /// * `Ok(CasUploadResponse)` - Success response
     /// * `Err(GitAiError)` - Error response
     pub fn upload_cas(&self, request: CasUploadRequest) -> Result<CasUploadResponse, GitAiError> {
-        let response = self.context().post_json("/worker/cas/upload", &request)?;
+        let response = self
+            .context()
+            .post_json_idempotent("/worker/cas/upload", &request)?;
         let status_code = response.status_code;
 
         let body = response
@@ -26,28 +29,6 @@ impl ApiClient {
                     serde_json::from_str(body).map_err(GitAiError::JsonError)?;
                 Ok(cas_response)
             }
-            400 => {
-                let error_response: ApiErrorResponse =
-                    serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
-                        error: "Invalid request body".to_string(),
-                        details: Some(serde_json::Value::String(body.to_string())),
-                    });
-                Err(GitAiError::Generic(format!(
-                    "Bad Request: {}",
-                    error_response.error
-                )))
-            }
-            500 => {
-                let error_response: ApiErrorResponse =
-                    serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
-                        error: "Internal server error".to_string(),
-                        details: None,
-                    });
-                Err(GitAiError::Generic(format!(
-                    "Internal Server Error: {}",
-                    error_response.error
-                )))
-            }
             _ => Err(GitAiError::Generic(format!(
                 "Unexpected status code {}: {}",
                 status_code, body
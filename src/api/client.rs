@@ -1,8 +1,12 @@
+use crate::api::types::ApiErrorResponse;
 use crate::auth::{CredentialStore, OAuthClient};
 use crate::config;
 use crate::error::GitAiError;
+use crate::utils::debug_log;
 use once_cell::sync::Lazy;
+use std::fmt;
 use std::sync::Mutex;
+use std::time::Duration;
 use url::Url;
 
 /// Global mutex to prevent multiple threads from refreshing simultaneously.
@@ -59,6 +63,91 @@ fn try_load_auth_token() -> Option<String> {
     // Mutex guard is automatically released when _guard is dropped
 }
 
+/// How idempotent requests made through an `ApiContext` retry transient failures: network
+/// errors, 408, 429 and 5xx responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each retry after it
+    pub initial_backoff: Duration,
+    /// Cap on any single delay, including one asked for with Retry-After
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send each request once
+    #[allow(dead_code)]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Exponential backoff before retry number `retry` (starting at 1)
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Why a request made through an `ApiContext` failed for good
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiRequestError {
+    /// Every attempt hit a network error or a retryable status
+    RetriesExhausted { attempts: u32, last_error: String },
+    /// The server rejected the request with a 4xx status retrying won't change
+    Rejected { status: i32, body: String },
+}
+
+impl fmt::Display for ApiRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiRequestError::RetriesExhausted {
+                attempts,
+                last_error,
+            } => write!(
+                f,
+                "request failed after {} attempt(s): {}",
+                attempts, last_error
+            ),
+            ApiRequestError::Rejected { status, body } => {
+                // Prefer the message from the API's JSON error body
+                let message = serde_json::from_str::<ApiErrorResponse>(body)
+                    .map(|e| e.error)
+                    .unwrap_or_else(|_| body.clone());
+                write!(f, "request rejected with status {}: {}", status, message)
+            }
+        }
+    }
+}
+
+fn is_retryable_status(status: i32) -> bool {
+    status == 408 || status == 429 || (500..600).contains(&status)
+}
+
+/// Delay asked for by a Retry-After header given in seconds (HTTP dates aren't supported)
+fn retry_after(response: &minreq::Response) -> Option<Duration> {
+    response
+        .headers
+        .get("retry-after")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
 /// API client context with optional authentication
 #[derive(Debug, Clone)]
 pub struct ApiContext {
@@ -70,6 +159,8 @@ pub struct ApiContext {
     pub api_key: Option<String>,
     /// Request timeout in seconds
     pub timeout_secs: Option<u64>,
+    /// Retries for transient failures of `get` and `post_json_idempotent`
+    pub retry_policy: RetryPolicy,
 }
 
 impl ApiContext {
@@ -109,6 +200,7 @@ impl ApiContext {
             auth_token: try_load_auth_token(),
             api_key: cfg.api_key().map(|s| s.to_string()),
             timeout_secs: Some(30),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -122,6 +214,7 @@ impl ApiContext {
             auth_token: None,
             api_key: cfg.api_key().map(|s| s.to_string()),
             timeout_secs: Some(30),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -135,6 +228,7 @@ impl ApiContext {
             auth_token: Some(auth_token),
             api_key: cfg.api_key().map(|s| s.to_string()),
             timeout_secs: Some(30),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -144,6 +238,13 @@ impl ApiContext {
         self
     }
 
+    /// Set a custom retry policy
+    #[allow(dead_code)]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Build the full URL for an endpoint
     fn build_url(&self, endpoint: &str) -> Result<String, GitAiError> {
         let base = Url::parse(&self.base_url)
@@ -154,11 +255,31 @@ impl ApiContext {
         Ok(url.to_string())
     }
 
-    /// Make a POST request with JSON body
+    /// Make a POST request with JSON body. Sent once: a retry after a lost response could
+    /// create the resource twice.
     pub fn post_json<T: serde::Serialize>(
         &self,
         endpoint: &str,
         body: &T,
+    ) -> Result<minreq::Response, GitAiError> {
+        self.send_post_json(endpoint, body, 0)
+    }
+
+    /// Make a POST request with JSON body to an endpoint where repeating the request is
+    /// harmless, retrying transient failures per `retry_policy`
+    pub fn post_json_idempotent<T: serde::Serialize>(
+        &self,
+        endpoint: &str,
+        body: &T,
+    ) -> Result<minreq::Response, GitAiError> {
+        self.send_post_json(endpoint, body, self.retry_policy.max_retries)
+    }
+
+    fn send_post_json<T: serde::Serialize>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        max_retries: u32,
     ) -> Result<minreq::Response, GitAiError> {
         let url = self.build_url(endpoint)?;
         let body_json = serde_json::to_string(body).map_err(GitAiError::JsonError)?;
@@ -182,11 +303,7 @@ impl ApiContext {
             request = request.with_timeout(timeout);
         }
 
-        let response = request
-            .send()
-            .map_err(|e| GitAiError::Generic(format!("HTTP request failed: {}", e)))?;
-
-        Ok(response)
+        self.send_with_retry(request, max_retries)
     }
 
     /// Make a GET request
@@ -210,11 +327,53 @@ impl ApiContext {
             request = request.with_timeout(timeout);
         }
 
-        let response = request
-            .send()
-            .map_err(|e| GitAiError::Generic(format!("HTTP request failed: {}", e)))?;
+        self.send_with_retry(request, self.retry_policy.max_retries)
+    }
 
-        Ok(response)
+    /// Send `request`, retrying transient failures up to `max_retries` times with
+    /// `retry_policy`'s backoff. A Retry-After header on a 429/503 replaces the backoff.
+    /// Responses below 400 are returned as they are.
+    fn send_with_retry(
+        &self,
+        request: minreq::Request,
+        max_retries: u32,
+    ) -> Result<minreq::Response, GitAiError> {
+        let policy = &self.retry_policy;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let (last_error, requested_delay) = match request.clone().send() {
+                Ok(response) if is_retryable_status(response.status_code) => (
+                    format!("status {}", response.status_code),
+                    retry_after(&response),
+                ),
+                Ok(response) if response.status_code >= 400 => {
+                    return Err(ApiRequestError::Rejected {
+                        status: response.status_code,
+                        body: response.as_str().unwrap_or_default().to_string(),
+                    }
+                    .into());
+                }
+                Ok(response) => return Ok(response),
+                Err(e) => (format!("HTTP request failed: {}", e), None),
+            };
+
+            if attempts > max_retries {
+                return Err(ApiRequestError::RetriesExhausted {
+                    attempts,
+                    last_error,
+                }
+                .into());
+            }
+            let delay = requested_delay
+                .unwrap_or_else(|| policy.backoff(attempts))
+                .min(policy.max_backoff);
+            debug_log(&format!(
+                "API request attempt {} failed with {}, retrying in {:?}",
+                attempts, last_error, delay
+            ));
+            std::thread::sleep(delay);
+        }
     }
 }
 
@@ -333,6 +492,136 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ============= Retry Tests =============
+
+    /// Serve one canned HTTP response per connection, in order, counting requests.
+    fn serve(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (base_url, requests)
+    }
+
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n";
+    const SERVER_ERROR: &str = "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n";
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}";
+    const NOT_FOUND: &str =
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 19\r\n\r\n{\"error\":\"missing\"}";
+
+    fn fast_retries(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        let delays: Vec<u128> = (1..=5).map(|r| policy.backoff(r).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+    }
+
+    #[test]
+    fn test_retries_transient_failures_until_success() {
+        let (base_url, requests) = serve(vec![UNAVAILABLE, SERVER_ERROR, OK]);
+        let ctx = ApiContext::without_auth(Some(base_url)).with_retry_policy(fast_retries(3));
+        let response = ctx.get("/api/test").unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retries_exhausted() {
+        let (base_url, requests) = serve(vec![SERVER_ERROR, SERVER_ERROR, SERVER_ERROR]);
+        let ctx = ApiContext::without_auth(Some(base_url)).with_retry_policy(fast_retries(2));
+        match ctx.post_json_idempotent("/api/test", &serde_json::json!({})) {
+            Err(GitAiError::ApiError(ApiRequestError::RetriesExhausted {
+                attempts,
+                last_error,
+            })) => {
+                assert_eq!(attempts, 3);
+                assert_eq!(last_error, "status 500");
+            }
+            other => panic!("expected retries to be exhausted, got {:?}", other),
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_post_json_is_sent_once() {
+        let (base_url, requests) = serve(vec![SERVER_ERROR, OK]);
+        let ctx = ApiContext::without_auth(Some(base_url)).with_retry_policy(fast_retries(3));
+        match ctx.post_json("/api/test", &serde_json::json!({})) {
+            Err(GitAiError::ApiError(ApiRequestError::RetriesExhausted { attempts, .. })) => {
+                assert_eq!(attempts, 1)
+            }
+            other => panic!("expected a single failed attempt, got {:?}", other),
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_client_errors_are_not_retried() {
+        let (base_url, requests) = serve(vec![NOT_FOUND, OK]);
+        let ctx = ApiContext::without_auth(Some(base_url)).with_retry_policy(fast_retries(3));
+        let err = ctx.get("/api/test").unwrap_err();
+        match &err {
+            GitAiError::ApiError(ApiRequestError::Rejected { status, .. }) => {
+                assert_eq!(*status, 404)
+            }
+            other => panic!("expected a rejected request, got {:?}", other),
+        }
+        assert_eq!(
+            err.to_string(),
+            "API error: request rejected with status 404: missing"
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_create_bundle_reports_unsupported_bundle_version() {
+        const UNSUPPORTED_VERSION: &str = "HTTP/1.1 422 Unprocessable Entity\r\nContent-Length: 82\r\n\r\n{\"error\":\"unsupported bundle version\",\"details\":{\"supported_bundle_versions\":[2]}}";
+        let (base_url, requests) = serve(vec![UNSUPPORTED_VERSION]);
+        let client = ApiClient::new(
+            ApiContext::without_auth(Some(base_url)).with_retry_policy(fast_retries(3)),
+        );
+        let request = crate::api::types::CreateBundleRequest {
+            version: crate::api::types::BUNDLE_FORMAT_VERSION,
+            title: "Fix parser".to_string(),
+            data: crate::api::types::BundleData {
+                prompts: std::collections::HashMap::new(),
+                files: std::collections::HashMap::new(),
+            },
+        };
+
+        let err = client.create_bundle(request).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: unsupported bundle version 1 (the server accepts 2); upgrade git-ai"
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
     // ============= Mutex Thread Safety Tests =============
 
     #[test]
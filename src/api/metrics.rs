@@ -1,7 +1,6 @@
 //! Metrics API endpoints
 
-use crate::api::client::ApiClient;
-use crate::api::types::ApiErrorResponse;
+use crate::api::client::{ApiClient, ApiRequestError};
 use crate::error::GitAiError;
 use crate::metrics::MetricsBatch;
 use crate::observability::log_error;
@@ -41,7 +40,7 @@ impl MetricsUploadResponse {
 /// Upload metrics batch with retry logic.
 ///
 /// Returns Ok(()) on success (200 response, even with partial errors).
-/// Returns Err on failure after all retries exhausted.
+/// Returns Err when the server rejects the batch, or on failure after all retries exhausted.
 ///
 /// Partial errors (200 + errors array) are logged to Sentry but not retried,
 /// since validation errors won't succeed on retry.
@@ -83,7 +82,12 @@ pub fn upload_metrics_with_retry(
                 return Ok(());
             }
             Err(e) => {
-                // Non-200 - will retry if attempts remain
+                // A rejected batch won't be accepted later either
+                if matches!(e, GitAiError::ApiError(ApiRequestError::Rejected { .. })) {
+                    eprintln!("[metrics] Upload rejected: {}", e);
+                    return Err(e);
+                }
+                // Transient failure - try again after a longer wait
                 if attempt == RETRY_DELAYS_SECS.len() {
                     eprintln!("[metrics] All retries exhausted, giving up");
                     return Err(e);
@@ -125,29 +129,6 @@ impl ApiClient {
                     serde_json::from_str(body).map_err(GitAiError::JsonError)?;
                 Ok(metrics_response)
             }
-            400 => {
-                let error_response: ApiErrorResponse =
-                    serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
-                        error: "Invalid request body".to_string(),
-                        details: Some(serde_json::Value::String(body.to_string())),
-                    });
-                Err(GitAiError::Generic(format!(
-                    "Bad Request: {}",
-                    error_response.error
-                )))
-            }
-            401 => Err(GitAiError::Generic("Unauthorized".to_string())),
-            500 => {
-                let error_response: ApiErrorResponse =
-                    serde_json::from_str(body).unwrap_or_else(|_| ApiErrorResponse {
-                        error: "Internal server error".to_string(),
-                        details: None,
-                    });
-                Err(GitAiError::Generic(format!(
-                    "Internal Server Error: {}",
-                    error_response.error
-                )))
-            }
             _ => Err(GitAiError::Generic(format!(
                 "Unexpected status code {}: {}",
                 status_code, body
@@ -54,9 +54,21 @@ pub struct BundleData {
     pub files: HashMap<String, ApiFileRecord>,
 }
 
+/// Bundle format version this client writes and the newest it reads. Bump it when
+/// `BundleData` changes in a way older readers would misinterpret.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Bundles written before the format was versioned are version 1
+fn legacy_bundle_version() -> u32 {
+    1
+}
+
 /// Request body for creating a bundle
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CreateBundleRequest {
+    /// Format version of `data`, see [`BUNDLE_FORMAT_VERSION`]
+    #[serde(default = "legacy_bundle_version")]
+    pub version: u32,
     /// Bundle title (min 1 character)
     pub title: String,
     /// Bundle data containing prompts and optional files
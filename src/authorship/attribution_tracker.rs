@@ -498,6 +498,9 @@ impl AttributionTracker {
         current_author: &str,
         ts: u128,
     ) -> Result<Vec<Attribution>, GitAiError> {
+        // Stored offsets may be stale or mid-character; never let them leak into the new content
+        let old_attributions = &snap_attributions_to_char_boundaries(old_attributions, old_content);
+
         // Phase 1: Compute diff
         let diff_result = self.compute_diffs(old_content, new_content)?;
 
@@ -519,7 +522,8 @@ impl AttributionTracker {
         );
 
         // Phase 5: Merge and clean up
-        Ok(self.merge_attributions(new_attributions))
+        let merged = self.merge_attributions(new_attributions);
+        Ok(snap_attributions_to_char_boundaries(&merged, new_content))
     }
 
     /// Build catalogs of deletions and insertions from the diff
@@ -1693,6 +1697,37 @@ fn ceil_char_boundary(content: &str, idx: usize) -> usize {
     i
 }
 
+/// Clamp attributions to `content` and widen any offset that falls inside a multibyte
+/// character to the enclosing character, so they can always be used to slice `content`.
+/// Non-empty ranges that lie entirely past the end are dropped; zero-length deletion markers
+/// are kept at the nearest preceding boundary.
+pub fn snap_attributions_to_char_boundaries(
+    attributions: &[Attribution],
+    content: &str,
+) -> Vec<Attribution> {
+    attributions
+        .iter()
+        .filter_map(|attr| {
+            let start = floor_char_boundary(content, attr.start);
+            let end = if attr.start == attr.end {
+                start
+            } else {
+                ceil_char_boundary(content, attr.end)
+            };
+            if attr.start != attr.end && start >= end {
+                return None;
+            }
+            if start == attr.start && end == attr.end {
+                return Some(attr.clone());
+            }
+            let mut snapped = attr.clone();
+            snapped.start = start;
+            snapped.end = end;
+            Some(snapped)
+        })
+        .collect()
+}
+
 /// Convert line-based attributions to character-based attributions.
 ///
 /// # Arguments
@@ -1743,7 +1778,7 @@ pub fn line_attributions_to_attributions(
 /// # Returns
 /// A vector of line attributions with consecutive lines by the same author merged
 pub fn attributions_to_line_attributions(
-    attributions: &Vec<Attribution>,
+    attributions: &[Attribution],
     content: &str,
 ) -> Vec<LineAttribution> {
     if content.is_empty() || attributions.is_empty() {
@@ -1757,6 +1792,8 @@ pub fn attributions_to_line_attributions(
         return Vec::new();
     }
 
+    let attributions = &snap_attributions_to_char_boundaries(attributions, content);
+
     // For each line, determine the dominant author
     let mut line_authors: Vec<Option<(String, Option<String>)>> =
         Vec::with_capacity(line_count as usize);
@@ -1781,7 +1818,7 @@ pub fn attributions_to_line_attributions(
 fn find_dominant_author_for_line(
     line_num: u32,
     boundaries: &LineBoundaries,
-    attributions: &Vec<Attribution>,
+    attributions: &[Attribution],
     full_content: &str,
 ) -> (String, Option<String>) {
     let (line_start, line_end) = boundaries.get_line_range(line_num).unwrap();
@@ -1798,23 +1835,11 @@ fn find_dominant_author_for_line(
         let slice_start = std::cmp::max(line_start, attribution.start);
         let slice_end = std::cmp::min(line_end, attribution.end);
         let mut attr_non_whitespace_count = 0;
+        // Attributions are snapped to char boundaries and lines end on '\n', so this is safe
         if slice_start < slice_end {
-            let safe_start = if full_content.is_char_boundary(slice_start) {
-                slice_start
-            } else {
-                floor_char_boundary(full_content, slice_start).max(line_start)
-            };
-            let safe_end = if full_content.is_char_boundary(slice_end) {
-                slice_end
-            } else {
-                ceil_char_boundary(full_content, slice_end).min(line_end)
-            };
-
-            if safe_start < safe_end {
-                let content_slice = &full_content[safe_start..safe_end];
-                attr_non_whitespace_count =
-                    content_slice.chars().filter(|c| !c.is_whitespace()).count();
-            }
+            let content_slice = &full_content[slice_start..slice_end];
+            attr_non_whitespace_count =
+                content_slice.chars().filter(|c| !c.is_whitespace()).count();
         }
         // Zero-length attributions are deletion markers - they indicate the author
         // deleted content at this position, so they should influence line attribution
@@ -2258,4 +2283,57 @@ mod tests {
         assert_eq!(ai_block.start_line, 2);
         assert_eq!(ai_block.end_line, 17);
     }
+
+    #[test]
+    fn multibyte_content_with_misaligned_attributions_does_not_panic() {
+        let tracker = AttributionTracker::new();
+        let content = "let a = \"héllo\";\n// 日本語のコメント 🚀🚀\nlet b = \"世界\";\n";
+        let emoji = content.find('🚀').unwrap();
+        let cjk = content.find('世').unwrap();
+
+        // Offsets that land inside multibyte characters, overlap, and run past the end
+        let attrs = vec![
+            Attribution::new(0, content.len(), "human".into(), TEST_TS),
+            Attribution::new(emoji + 1, emoji + 6, "ai-1".into(), TEST_TS + 1),
+            Attribution::new(cjk + 2, content.len() + 7, "ai-2".into(), TEST_TS + 2),
+        ];
+
+        let lines = attributions_to_line_attributions(&attrs, content);
+        let author_of = |line: u32| {
+            lines
+                .iter()
+                .find(|l| l.start_line <= line && l.end_line >= line)
+                .map(|l| l.author_id.as_str())
+        };
+        assert_eq!(author_of(1), None);
+        assert_eq!(author_of(2), Some("ai-1"));
+        assert_eq!(author_of(3), Some("ai-2"));
+
+        let snapped = snap_attributions_to_char_boundaries(&attrs, content);
+        for attr in &snapped {
+            assert!(content.is_char_boundary(attr.start) && content.is_char_boundary(attr.end));
+            assert!(attr.start < attr.end && attr.end <= content.len());
+        }
+
+        let new_content = content.replace("世界", "世界 🌍");
+        let updated = tracker
+            .update_attributions(content, &new_content, &attrs, "ai-3", TEST_TS + 3)
+            .unwrap();
+        for attr in &updated {
+            assert!(new_content.is_char_boundary(attr.start));
+            assert!(new_content.is_char_boundary(attr.end));
+        }
+        let updated_lines = attributions_to_line_attributions(&updated, &new_content);
+        assert!(
+            updated_lines
+                .iter()
+                .any(|l| l.start_line <= 3 && l.end_line >= 3)
+        );
+
+        let round_trip = line_attributions_to_attributions(&lines, content, TEST_TS);
+        assert_eq!(
+            attributions_to_line_attributions(&round_trip, content),
+            lines
+        );
+    }
 }
@@ -134,43 +134,65 @@ impl LineRange {
     }
 
     /// Shift line numbers by a given offset
-    /// - For insertions: offset is positive (shift lines down/forward)
-    /// - For deletions: offset is negative (shift lines up/backward)
+    /// - For insertions: offset is positive (shift lines down/forward); `offset` new lines
+    ///   start at insertion_point, so a range containing it is split around them
+    /// - For deletions: offset is negative (shift lines up/backward); `-offset` lines starting
+    ///   at insertion_point are removed, truncating any range that overlaps them
     /// - insertion_point: the line number where the change occurred
-    #[allow(dead_code)]
-    pub fn shift(&self, insertion_point: u32, offset: i32) -> Option<LineRange> {
-        match self {
-            LineRange::Single(l) => {
-                if *l >= insertion_point {
-                    let new_line = (*l as i32 + offset) as u32;
-                    Some(LineRange::Single(new_line))
-                } else {
-                    Some(LineRange::Single(*l))
-                }
-            }
-            LineRange::Range(start, end) => {
-                let new_start = if *start >= insertion_point {
-                    (*start as i32 + offset) as u32
-                } else {
-                    *start
-                };
-                let new_end = if *end >= insertion_point {
-                    (*end as i32 + offset) as u32
-                } else {
-                    *end
-                };
+    ///
+    /// Returns no ranges when every line of the range was deleted.
+    pub fn shift(&self, insertion_point: u32, offset: i32) -> Vec<LineRange> {
+        let (start, end) = match self {
+            LineRange::Single(l) => (*l, *l),
+            LineRange::Range(start, end) => (*start, *end),
+        };
+        let count = offset.unsigned_abs();
+        if count == 0 || end < insertion_point {
+            return vec![self.clone()];
+        }
 
-                // Ensure the range is still valid
-                if new_start <= new_end {
-                    if new_start == new_end {
-                        Some(LineRange::Single(new_start))
-                    } else {
-                        Some(LineRange::Range(new_start, new_end))
-                    }
+        if offset > 0 {
+            if start >= insertion_point {
+                vec![LineRange::span(start + count, end + count)]
+            } else {
+                vec![
+                    LineRange::span(start, insertion_point - 1),
+                    LineRange::span(insertion_point + count, end + count),
+                ]
+            }
+        } else {
+            let deleted_end = insertion_point + count - 1;
+            // Lines before the deletion keep their numbers; lines after it move up by `count`,
+            // so whatever survives is still contiguous
+            let first = if start < insertion_point || start > deleted_end {
+                start
+            } else if end > deleted_end {
+                deleted_end + 1
+            } else {
+                return vec![];
+            };
+            let last = if end > deleted_end {
+                end
+            } else {
+                insertion_point - 1
+            };
+            let renumber = |line: u32| {
+                if line < insertion_point {
+                    line
                 } else {
-                    None
+                    line - count
                 }
-            }
+            };
+            vec![LineRange::span(renumber(first), renumber(last))]
+        }
+    }
+
+    /// The range covering `start..=end`, as a `Single` when it is one line
+    fn span(start: u32, end: u32) -> LineRange {
+        if start == end {
+            LineRange::Single(start)
+        } else {
+            LineRange::Range(start, end)
         }
     }
 }
@@ -201,6 +223,9 @@ pub struct PromptRecord {
     /// Full URL to CAS-stored messages (format: {api_base_url}/cas/{hash})
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub messages_url: Option<String>,
+    /// Whether `messages` lost messages to `gitai.maxTranscriptBytes`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub transcript_truncated: bool,
 }
 
 impl Eq for PromptRecord {}
@@ -254,6 +279,7 @@ mod tests {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            transcript_truncated: false,
         }
     }
 
@@ -281,4 +307,53 @@ mod tests {
                 || records[1].total_deletions > 0
         );
     }
+
+    #[test]
+    fn test_line_range_shift_insert_at_start() {
+        assert_eq!(
+            LineRange::Range(5, 8).shift(5, 2),
+            vec![LineRange::Range(7, 10)]
+        );
+        assert_eq!(LineRange::Single(5).shift(5, 2), vec![LineRange::Single(7)]);
+        assert_eq!(
+            LineRange::Range(5, 8).shift(9, 2),
+            vec![LineRange::Range(5, 8)]
+        );
+    }
+
+    #[test]
+    fn test_line_range_shift_insert_in_middle() {
+        assert_eq!(
+            LineRange::Range(5, 8).shift(7, 3),
+            vec![LineRange::Range(5, 6), LineRange::Range(10, 11)]
+        );
+        assert_eq!(
+            LineRange::Range(5, 6).shift(6, 1),
+            vec![LineRange::Single(5), LineRange::Single(7)]
+        );
+    }
+
+    #[test]
+    fn test_line_range_shift_delete_spanning_boundary() {
+        // Deleting lines 3-6 removes the first two lines of 5-8
+        assert_eq!(
+            LineRange::Range(5, 8).shift(3, -4),
+            vec![LineRange::Range(3, 4)]
+        );
+        // Deleting lines 7-10 removes the last two
+        assert_eq!(
+            LineRange::Range(5, 8).shift(7, -4),
+            vec![LineRange::Range(5, 6)]
+        );
+        // Deleting lines 6-7 from the middle joins what is left
+        assert_eq!(
+            LineRange::Range(5, 8).shift(6, -2),
+            vec![LineRange::Range(5, 6)]
+        );
+        assert_eq!(LineRange::Range(5, 8).shift(4, -6), vec![]);
+        assert_eq!(
+            LineRange::Single(9).shift(2, -3),
+            vec![LineRange::Single(6)]
+        );
+    }
 }
@@ -1,11 +1,14 @@
 use crate::authorship::authorship_log::{Author, LineRange, PromptRecord};
 use crate::authorship::working_log::CheckpointKind;
+use crate::error::GitAiError;
 use crate::git::repository::Repository;
+use crate::utils::{escape_git_path, unescape_git_path};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Authorship log format version identifier
@@ -27,6 +30,12 @@ pub struct AuthorshipMetadata {
     pub git_ai_version: Option<String>,
     pub base_commit_sha: String,
     pub prompts: BTreeMap<String, PromptRecord>,
+    /// ASCII-armored detached GPG or SSH signature over the log serialized without this field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Fingerprint of the key that produced `signature` (`SHA256:...` for SSH keys)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl AuthorshipMetadata {
@@ -36,6 +45,8 @@ impl AuthorshipMetadata {
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
             base_commit_sha: String::new(),
             prompts: BTreeMap::new(),
+            signature: None,
+            signer: None,
         }
     }
 }
@@ -46,6 +57,61 @@ impl Default for AuthorshipMetadata {
     }
 }
 
+/// Major version of an `authorship/<major>.<minor>.<patch>` schema string, or None for
+/// anything not in that shape.
+fn schema_major_version(schema_version: &str) -> Option<u32> {
+    schema_version
+        .strip_prefix("authorship/")?
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Parse the JSON metadata section as v3. Only when that fails is the schema version read on
+/// its own, so a log from another major version gets a version error rather than a field error.
+fn parse_metadata(json: &str) -> Result<AuthorshipMetadata, Box<dyn std::error::Error>> {
+    match serde_json::from_str::<AuthorshipMetadata>(json) {
+        Ok(metadata) => check_schema_version(metadata),
+        Err(e) => {
+            let schema_version = serde_json::from_str::<serde_json::Value>(json)
+                .ok()
+                .and_then(|value| value.get("schema_version")?.as_str().map(str::to_string));
+            let Some(version) = schema_version else {
+                return Err(e.into());
+            };
+            match schema_major_version(&version) {
+                Some(major) if major > 3 => Err(unsupported_version_error(&version)),
+                Some(major) if major < 3 => Err(format!(
+                    "Cannot read authorship log version {}: it predates {} and doesn't match its layout ({})",
+                    version, AUTHORSHIP_LOG_VERSION, e
+                )
+                .into()),
+                _ => Err(e.into()),
+            }
+        }
+    }
+}
+
+/// Reject metadata from a newer major schema version. Older logs that still match the v3
+/// layout are read as they always were, as are schema strings that don't name a major version.
+fn check_schema_version(
+    metadata: AuthorshipMetadata,
+) -> Result<AuthorshipMetadata, Box<dyn std::error::Error>> {
+    match schema_major_version(&metadata.schema_version) {
+        Some(major) if major > 3 => Err(unsupported_version_error(&metadata.schema_version)),
+        _ => Ok(metadata),
+    }
+}
+
+fn unsupported_version_error(schema_version: &str) -> Box<dyn std::error::Error> {
+    format!(
+        "Unsupported authorship log version: {} (this git-ai reads up to {}; upgrade git-ai to read it)",
+        schema_version, AUTHORSHIP_LOG_VERSION
+    )
+    .into()
+}
+
 /// Attestation entry: short hash followed by line ranges
 ///
 /// IMPORTANT: The hash ALWAYS corresponds to a prompt in the prompts section.
@@ -59,8 +125,51 @@ pub struct AttestationEntry {
 }
 
 impl AttestationEntry {
+    /// Ranges are normalized on construction: sorted, with overlapping or adjacent ranges
+    /// merged. Every writer already emits ranges in this form, so parsing a stored note and
+    /// serializing it again reproduces the same bytes.
     pub fn new(hash: String, line_ranges: Vec<LineRange>) -> Self {
-        Self { hash, line_ranges }
+        let mut entry = Self {
+            hash,
+            line_ranges: Vec::new(),
+        };
+        entry.merge_line_ranges(&line_ranges);
+        entry
+    }
+
+    /// Merge `ranges` into this entry, keeping `line_ranges` sorted with no two ranges
+    /// overlapping or touching. Each range costs a binary search plus the ranges it absorbs,
+    /// so attributing a file one line at a time stays as compact as attributing it at once.
+    pub fn merge_line_ranges(&mut self, ranges: &[LineRange]) {
+        for range in ranges {
+            let (mut start, mut end) = match *range {
+                LineRange::Single(line) => (line, line),
+                LineRange::Range(start, end) => (start.min(end), start.max(end)),
+            };
+
+            // First existing range that overlaps or touches the new one, if any
+            let first = self
+                .line_ranges
+                .partition_point(|r| range_bounds(r).1.saturating_add(1) < start);
+            let mut last = first;
+            while let Some(existing) = self.line_ranges.get(last) {
+                let (existing_start, existing_end) = range_bounds(existing);
+                if existing_start > end.saturating_add(1) {
+                    break;
+                }
+                start = start.min(existing_start);
+                end = end.max(existing_end);
+                last += 1;
+            }
+
+            let merged = if start == end {
+                LineRange::Single(start)
+            } else {
+                LineRange::Range(start, end)
+            };
+            self.line_ranges
+                .splice(first..last, std::iter::once(merged));
+        }
     }
 
     #[allow(dead_code)]
@@ -78,16 +187,17 @@ impl AttestationEntry {
         self.line_ranges = current_ranges;
     }
 
-    /// Shift line ranges by a given offset starting at insertion_point
+    /// Shift line ranges by a given offset starting at insertion_point, splitting ranges an
+    /// insertion lands inside (see [`LineRange::shift`]). The rewrite paths (rebase,
+    /// cherry-pick, merge) recompute attestations by blaming the new commit rather than
+    /// shifting stored ranges, so nothing calls this yet.
     #[allow(dead_code)]
     pub fn shift_line_ranges(&mut self, insertion_point: u32, offset: i32) {
-        let mut shifted_ranges = Vec::new();
-        for range in &self.line_ranges {
-            if let Some(shifted) = range.shift(insertion_point, offset) {
-                shifted_ranges.push(shifted);
-            }
-        }
-        self.line_ranges = shifted_ranges;
+        self.line_ranges = self
+            .line_ranges
+            .iter()
+            .flat_map(|range| range.shift(insertion_point, offset))
+            .collect();
     }
 }
 
@@ -106,8 +216,13 @@ impl FileAttestation {
         }
     }
 
+    /// Append an entry. When the latest entry belongs to the same session the ranges are
+    /// merged into it instead, which keeps lookups (latest entry wins) unchanged.
     pub fn add_entry(&mut self, entry: AttestationEntry) {
-        self.entries.push(entry);
+        match self.entries.last_mut() {
+            Some(last) if last.hash == entry.hash => last.merge_line_ranges(&entry.line_ranges),
+            _ => self.entries.push(entry),
+        }
     }
 }
 
@@ -152,14 +267,17 @@ impl AuthorshipLog {
     }
 
     /// Serialize to the new text format
+    ///
+    /// Identical logs serialize to identical bytes: prompts and JSON objects are
+    /// written in key order, and attestations in the order they were added.
     pub fn serialize_to_string(&self) -> Result<String, fmt::Error> {
         let mut output = String::new();
 
         // Write attestation section
         for file_attestation in &self.attestations {
-            // Quote file names that contain spaces or whitespace
+            // C-quote file names the parser couldn't otherwise read back
             let file_path = if needs_quoting(&file_attestation.file_path) {
-                format!("\"{}\"", &file_attestation.file_path)
+                escape_git_path(&file_attestation.file_path)
             } else {
                 file_attestation.file_path.clone()
             };
@@ -194,7 +312,7 @@ impl AuthorshipLog {
         Ok(())
     }
 
-    /// Deserialize from the new text format
+    /// Deserialize from the text format. Logs from a newer major schema version are rejected.
     pub fn deserialize_from_string(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let lines: Vec<&str> = content.lines().collect();
 
@@ -211,7 +329,7 @@ impl AuthorshipLog {
         // Parse JSON metadata section (after divider)
         let json_lines = &lines[divider_pos + 1..];
         let json_content = json_lines.join("\n");
-        let metadata: AuthorshipMetadata = serde_json::from_str(&json_content)?;
+        let metadata = parse_metadata(&json_content)?;
 
         Ok(Self {
             attestations,
@@ -219,22 +337,65 @@ impl AuthorshipLog {
         })
     }
 
-    /// Read from a reader in the new format
-    pub fn _deserialize_from_reader<R: BufRead>(
-        reader: R,
+    /// Read from a reader in the new format without buffering the whole log: attestation
+    /// lines are parsed one at a time up to the `---` divider, and the JSON metadata is
+    /// deserialized straight from the rest of the stream.
+    #[allow(dead_code)]
+    pub fn deserialize_from_reader<R: BufRead>(
+        mut reader: R,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let content: Result<String, _> = reader.lines().collect();
-        let content = content?;
-        Self::deserialize_from_string(&content)
+        let mut parser = AttestationSectionParser::default();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err("Missing divider '---' in authorship log".into());
+            }
+            let content = line
+                .strip_suffix('\n')
+                .map(|l| l.strip_suffix('\r').unwrap_or(l))
+                .unwrap_or(&line);
+            if content == "---" {
+                break;
+            }
+            parser.push_line(content)?;
+        }
+
+        let metadata = check_schema_version(serde_json::from_reader(reader)?)?;
+        Ok(Self {
+            attestations: parser.finish(),
+            metadata,
+        })
+    }
+
+    /// Session hashes attested to lines but missing from `metadata.prompts`, sorted. Blame
+    /// looks these up in other commits' notes; lines of a hash no note has a prompt for are
+    /// left unattributed.
+    pub fn dangling_prompt_hashes(&self) -> Vec<String> {
+        let hashes: BTreeSet<&String> = self
+            .attestations
+            .iter()
+            .flat_map(|file| file.entries.iter())
+            .map(|entry| &entry.hash)
+            .filter(|hash| !self.metadata.prompts.contains_key(*hash))
+            .collect();
+        hashes.into_iter().cloned().collect()
     }
 
     /// Lookup the author and optional prompt for a given file and line
+    ///
+    /// When `merge_prompts` is false, hashes missing from this log's own metadata are not
+    /// searched for in other notes; the raw hash is returned as the author with no prompt.
+    /// Once `resolve_deadline` has passed, uncached hashes are likewise returned raw, with a
+    /// `[timeout]` marker.
     pub fn get_line_attribution(
         &self,
         repo: &Repository,
         file: &str,
         line: u32,
         foreign_prompts_cache: &mut HashMap<String, Option<PromptRecord>>,
+        merge_prompts: bool,
+        resolve_deadline: Option<std::time::Instant>,
     ) -> Option<(Author, Option<String>, Option<PromptRecord>)> {
         // Find the file attestation
         let file_attestation = self.attestations.iter().find(|f| f.file_path == file)?;
@@ -248,7 +409,7 @@ impl AuthorshipLog {
                 if let Some(prompt_record) = self.metadata.prompts.get(&entry.hash) {
                     // Create author info from the prompt record
                     let author = Author {
-                        username: prompt_record.agent_id.tool.clone(),
+                        username: prompt_record.agent_id.tool_label().to_string(),
                         email: String::new(), // AI agents don't have email
                     };
 
@@ -258,6 +419,20 @@ impl AuthorshipLog {
                         Some(entry.hash.clone()),
                         Some(prompt_record.clone()),
                     ));
+                } else if !merge_prompts {
+                    let author = Author {
+                        username: entry.hash.clone(),
+                        email: String::new(),
+                    };
+                    return Some((author, Some(entry.hash.clone()), None));
+                } else if !foreign_prompts_cache.contains_key(&entry.hash)
+                    && resolve_deadline.is_some_and(|d| std::time::Instant::now() >= d)
+                {
+                    let author = Author {
+                        username: format!("{} [timeout]", entry.hash),
+                        email: String::new(),
+                    };
+                    return Some((author, Some(entry.hash.clone()), None));
                 } else {
                     // Check cache first before grepping
                     let prompt_record = if let Some(cached_result) =
@@ -287,7 +462,7 @@ impl AuthorshipLog {
 
                     if let Some(prompt_record) = prompt_record {
                         let author = Author {
-                            username: prompt_record.agent_id.tool.clone(),
+                            username: prompt_record.agent_id.tool_label().to_string(),
                             email: String::new(), // AI agents don't have email
                         };
                         return Some((author, Some(entry.hash.clone()), Some(prompt_record)));
@@ -298,6 +473,95 @@ impl AuthorshipLog {
         None
     }
 
+    /// Lines of `file` this log still credits to AI, and the lines of those sessions a human
+    /// overrode. Notes record overrides per session rather than per file, so each session's
+    /// overrides are split across files in proportion to its attested lines there. `None`
+    /// when no session with a prompt record touched `file`.
+    pub fn file_acceptance(&self, file: &str) -> Option<FileAcceptance> {
+        fn line_count(ranges: &[LineRange]) -> u32 {
+            ranges
+                .iter()
+                .map(|range| match range {
+                    LineRange::Single(_) => 1,
+                    LineRange::Range(start, end) => end.saturating_sub(*start) + 1,
+                })
+                .sum()
+        }
+
+        let file_attestation = self.attestations.iter().find(|f| f.file_path == file)?;
+        let mut acceptance: Option<FileAcceptance> = None;
+        for entry in &file_attestation.entries {
+            let Some(prompt) = self.metadata.prompts.get(&entry.hash) else {
+                continue;
+            };
+            let accepted = line_count(&entry.line_ranges);
+            let session_accepted: u32 = self
+                .attestations
+                .iter()
+                .flat_map(|f| f.entries.iter().filter(|e| e.hash == entry.hash))
+                .map(|e| line_count(&e.line_ranges))
+                .sum();
+            let overridden = if session_accepted == 0 {
+                0
+            } else {
+                (prompt.overriden_lines as u64 * accepted as u64 / session_accepted as u64) as u32
+            };
+            let totals = acceptance.get_or_insert_with(FileAcceptance::default);
+            totals.accepted += accepted;
+            totals.overridden += overridden;
+        }
+        acceptance
+    }
+
+    /// Union another log for the same commit into this one.
+    ///
+    /// Prompts missing here are copied over, and every line attributed in either log stays
+    /// attributed. When both logs attribute a line to different sessions this log wins; the
+    /// number of such lines is returned. Any signature is dropped if the contents change.
+    pub fn merge(&mut self, other: &AuthorshipLog) -> usize {
+        let before = self.clone();
+        let mut conflicts = 0;
+
+        for (hash, prompt) in &other.metadata.prompts {
+            self.metadata
+                .prompts
+                .entry(hash.clone())
+                .or_insert_with(|| prompt.clone());
+        }
+
+        for other_file in other.attestations.iter().filter(|f| !f.entries.is_empty()) {
+            let file = self.get_or_create_file(&other_file.file_path);
+            let ours = line_owners(&file.entries);
+            let mut merged = line_owners(&other_file.entries);
+            for (line, hash) in ours {
+                if let Some(theirs) = merged.insert(line, hash.clone())
+                    && theirs != hash
+                {
+                    conflicts += 1;
+                }
+            }
+
+            let mut lines_by_hash: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+            for (line, hash) in merged {
+                lines_by_hash.entry(hash).or_default().push(line);
+            }
+            let mut entries: Vec<AttestationEntry> = lines_by_hash
+                .into_iter()
+                .map(|(hash, lines)| AttestationEntry::new(hash, LineRange::compress_lines(&lines)))
+                .collect();
+            entries.sort_by_key(|e| e.line_ranges.first().cloned());
+            file.entries = entries;
+        }
+
+        if *self != before {
+            self.metadata.signature = None;
+            self.metadata.signer = None;
+            self.metadata.schema_version = AUTHORSHIP_LOG_VERSION.to_string();
+        }
+
+        conflicts
+    }
+
     /// Convert authorship log to working log checkpoints for merge --squash
     ///
     /// Creates one checkpoint per file per session that touched that file. This ensures that:
@@ -369,7 +633,7 @@ impl AuthorshipLog {
             session_entries.sort_by(|a, b| a.0.cmp(&b.0));
 
             let mut combined_line_attributions: Vec<LineAttribution> = Vec::new();
-            let mut session_prompt_records: Vec<PromptRecord> = Vec::new();
+            let mut session_prompt_records: Vec<(String, PromptRecord)> = Vec::new();
 
             for (session_hash, ranges) in &session_entries {
                 let prompt_record = self
@@ -390,16 +654,14 @@ impl AuthorshipLog {
                 all_lines.sort_unstable();
                 all_lines.dedup();
 
-                // IMPORTANT: Use the session_hash that will be regenerated from agent_id when applying checkpoint
-                // This ensures line attributions match the prompts in metadata after apply_checkpoint
-                let prompt_hash =
-                    generate_short_hash(&prompt_record.agent_id.id, &prompt_record.agent_id.tool);
+                // Keep the note's hash, which the checkpoint records as its session hash, so
+                // the line attributions match the prompt whatever gitai.hashLength is now
                 // TODO Update authorship to store overridden state for line ranges
                 let line_attributions =
-                    compress_lines_to_working_log_format(&all_lines, &prompt_hash, None);
+                    compress_lines_to_working_log_format(&all_lines, session_hash, None);
 
                 combined_line_attributions.extend(line_attributions);
-                session_prompt_records.push(prompt_record);
+                session_prompt_records.push((session_hash.clone(), prompt_record));
             }
 
             if combined_line_attributions.is_empty() {
@@ -419,7 +681,7 @@ impl AuthorshipLog {
                 ts,
             );
 
-            for prompt_record in session_prompt_records {
+            for (session_hash, prompt_record) in session_prompt_records {
                 let entry = WorkingLogEntry::new(
                     file_path.clone(),
                     String::new(), // Empty blob_sha - will be set by caller
@@ -434,6 +696,7 @@ impl AuthorshipLog {
                     vec![entry],
                 );
                 ai_checkpoint.agent_id = Some(prompt_record.agent_id.clone());
+                ai_checkpoint.session_hash = Some(session_hash);
 
                 // TODO Fill in the LineStats
 
@@ -496,12 +759,35 @@ fn compress_lines_to_working_log_format(
     result
 }
 
+/// AI lines kept versus overridden in one file, from [`AuthorshipLog::file_acceptance`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileAcceptance {
+    pub accepted: u32,
+    pub overridden: u32,
+}
+
+impl FileAcceptance {
+    /// Share of the AI lines that survived, from 0.0 to 1.0
+    pub fn ratio(&self) -> Option<f64> {
+        let total = self.accepted + self.overridden;
+        (total > 0).then(|| self.accepted as f64 / total as f64)
+    }
+}
+
 impl Default for AuthorshipLog {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Inclusive (start, end) bounds of a range
+fn range_bounds(range: &LineRange) -> (u32, u32) {
+    match *range {
+        LineRange::Single(line) => (line, line),
+        LineRange::Range(start, end) => (start, end),
+    }
+}
+
 /// Format line ranges as comma-separated values with ranges as "start-end"
 /// Sorts ranges first: Single ranges by their value, Range ones by their lowest bound
 fn format_line_ranges(ranges: &[LineRange]) -> String {
@@ -542,12 +828,15 @@ fn parse_line_ranges(input: &str) -> Result<Vec<LineRange>, Box<dyn std::error::
             // Range format: "start-end"
             let start_str = &part[..dash_pos];
             let end_str = &part[dash_pos + 1..];
-            let start: u32 = start_str.parse()?;
-            let end: u32 = end_str.parse()?;
+            let (Ok(start), Ok(end)) = (start_str.parse::<u32>(), end_str.parse::<u32>()) else {
+                return Err(GitAiError::InvalidLineRange(part.to_string()).into());
+            };
             ranges.push(LineRange::Range(start, end));
         } else {
             // Single line format: "line"
-            let line: u32 = part.parse()?;
+            let line: u32 = part
+                .parse()
+                .map_err(|_| GitAiError::InvalidLineRange(part.to_string()))?;
             ranges.push(LineRange::Single(line));
         }
     }
@@ -559,14 +848,26 @@ fn parse_line_ranges(input: &str) -> Result<Vec<LineRange>, Box<dyn std::error::
 fn parse_attestation_section(
     lines: &[&str],
 ) -> Result<Vec<FileAttestation>, Box<dyn std::error::Error>> {
-    let mut attestations = Vec::new();
-    let mut current_file: Option<FileAttestation> = None;
-
+    let mut parser = AttestationSectionParser::default();
     for line in lines {
+        parser.push_line(line)?;
+    }
+    Ok(parser.finish())
+}
+
+/// Incremental parser for the attestation section, fed one line at a time.
+#[derive(Default)]
+struct AttestationSectionParser {
+    attestations: Vec<FileAttestation>,
+    current_file: Option<FileAttestation>,
+}
+
+impl AttestationSectionParser {
+    fn push_line(&mut self, line: &str) -> Result<(), Box<dyn std::error::Error>> {
         let line = line.trim_end(); // Remove trailing whitespace but preserve leading
 
         if line.is_empty() {
-            continue;
+            return Ok(());
         }
 
         if let Some(entry_line) = line.strip_prefix("  ") {
@@ -581,7 +882,7 @@ fn parse_attestation_section(
 
                 let entry = AttestationEntry::new(hash, line_ranges);
 
-                if let Some(ref mut file_attestation) = current_file {
+                if let Some(ref mut file_attestation) = self.current_file {
                     file_attestation.add_entry(entry);
                 } else {
                     return Err("Attestation entry found without a file path".into());
@@ -591,48 +892,111 @@ fn parse_attestation_section(
             }
         } else {
             // File path line (not indented)
-            if let Some(file_attestation) = current_file.take()
-                && !file_attestation.entries.is_empty()
-            {
-                attestations.push(file_attestation);
-            }
+            self.flush_current_file();
 
-            // Parse file path, handling quoted paths
-            let file_path = if line.starts_with('"') && line.ends_with('"') {
-                // Quoted path - remove quotes (no unescaping needed since quotes aren't allowed in file names)
-                line[1..line.len() - 1].to_string()
-            } else {
-                // Unquoted path
-                line.to_string()
-            };
+            // Quoted paths are C-escaped; unquoted ones are taken literally
+            let file_path = unescape_git_path(line);
 
-            current_file = Some(FileAttestation::new(file_path));
+            self.current_file = Some(FileAttestation::new(file_path));
         }
+        Ok(())
     }
 
-    // Don't forget the last file
-    if let Some(file_attestation) = current_file
-        && !file_attestation.entries.is_empty()
-    {
-        attestations.push(file_attestation);
+    fn flush_current_file(&mut self) {
+        if let Some(file_attestation) = self.current_file.take()
+            && !file_attestation.entries.is_empty()
+        {
+            self.attestations.push(file_attestation);
+        }
     }
 
-    Ok(attestations)
+    fn finish(mut self) -> Vec<FileAttestation> {
+        // Don't forget the last file
+        self.flush_current_file();
+        self.attestations
+    }
 }
 
-/// Check if a file path needs quoting (contains spaces or whitespace)
+/// Map each attributed line to its session hash; later entries win, matching lookups.
+fn line_owners(entries: &[AttestationEntry]) -> BTreeMap<u32, String> {
+    let mut owners = BTreeMap::new();
+    for entry in entries {
+        for range in &entry.line_ranges {
+            for line in range.expand() {
+                owners.insert(line, entry.hash.clone());
+            }
+        }
+    }
+    owners
+}
+
+/// Check if a file path needs quoting: it contains whitespace or control characters, or
+/// starts with a quote and would be read back as a quoted path
 fn needs_quoting(path: &str) -> bool {
-    path.contains(' ') || path.contains('\t') || path.contains('\n')
+    path.starts_with('"') || path.chars().any(|c| c.is_whitespace() || c.is_control())
 }
 
-/// Generate a short hash (7 characters) from agent_id and tool
+/// Git config key for the number of hex characters kept in session hashes.
+pub const HASH_LENGTH_CONFIG_KEY: &str = "gitai.hashLength";
+pub const DEFAULT_SHORT_HASH_LENGTH: usize = 16;
+/// Legacy working logs use 7-character hashes, so nothing shorter is accepted
+pub const MIN_SHORT_HASH_LENGTH: usize = 7;
+pub const MAX_SHORT_HASH_LENGTH: usize = 40;
+
+static SHORT_HASH_LENGTH: AtomicUsize = AtomicUsize::new(DEFAULT_SHORT_HASH_LENGTH);
+
+/// Parse a `gitai.hashLength` value.
+pub fn parse_hash_length(value: &str) -> Result<usize, GitAiError> {
+    match value.trim().parse::<usize>() {
+        Ok(length) if (MIN_SHORT_HASH_LENGTH..=MAX_SHORT_HASH_LENGTH).contains(&length) => {
+            Ok(length)
+        }
+        _ => Err(GitAiError::Generic(format!(
+            "{} must be a number from {} to {}, got '{}'",
+            HASH_LENGTH_CONFIG_KEY,
+            MIN_SHORT_HASH_LENGTH,
+            MAX_SHORT_HASH_LENGTH,
+            value.trim()
+        ))),
+    }
+}
+
+/// Use `repo`'s `gitai.hashLength` for every new session hash generated from now on. Unset
+/// or invalid values fall back to [`DEFAULT_SHORT_HASH_LENGTH`]. Sessions already in the
+/// working log keep the hash stored with their checkpoints (see
+/// [`crate::authorship::working_log::Checkpoint::session_hash`]), and notes key each prompt
+/// by the hash it was written with.
+pub fn configure_short_hash_length(repo: &Repository) {
+    let length = match repo.config_get_str(HASH_LENGTH_CONFIG_KEY) {
+        Ok(Some(value)) => parse_hash_length(&value).unwrap_or_else(|e| {
+            crate::utils::debug_log(&format!("Ignoring {}", e));
+            DEFAULT_SHORT_HASH_LENGTH
+        }),
+        _ => DEFAULT_SHORT_HASH_LENGTH,
+    };
+    SHORT_HASH_LENGTH.store(length, Ordering::Relaxed);
+}
+
+/// Generate the session hash for an agent_id and tool, truncated to the configured length
+/// (see [`configure_short_hash_length`])
 pub fn generate_short_hash(agent_id: &str, tool: &str) -> String {
+    generate_short_hash_with_length(agent_id, tool, SHORT_HASH_LENGTH.load(Ordering::Relaxed))
+}
+
+/// Whether `hash` is the session hash of `agent_id` and `tool` at any supported length.
+/// Hashes of one session only differ in how many characters they keep.
+pub fn is_session_hash_of(hash: &str, agent_id: &str, tool: &str) -> bool {
+    (MIN_SHORT_HASH_LENGTH..=MAX_SHORT_HASH_LENGTH).contains(&hash.len())
+        && generate_short_hash_with_length(agent_id, tool, MAX_SHORT_HASH_LENGTH).starts_with(hash)
+}
+
+pub fn generate_short_hash_with_length(agent_id: &str, tool: &str, length: usize) -> String {
     let combined = format!("{}:{}", tool, agent_id);
     let mut hasher = Sha256::new();
     hasher.update(combined.as_bytes());
     let result = hasher.finalize();
-    // Take first 7 characters of the hex representation
-    format!("{:x}", result)[..16].to_string()
+    let length = length.clamp(MIN_SHORT_HASH_LENGTH, MAX_SHORT_HASH_LENGTH);
+    format!("{:x}", result)[..length].to_string()
 }
 
 #[cfg(test)]
@@ -657,6 +1021,15 @@ mod tests {
         assert_debug_snapshot!(ranges);
     }
 
+    #[test]
+    fn test_parse_line_ranges_rejects_malformed_range() {
+        let err = parse_line_ranges("1,4-x").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GitAiError>(),
+            Some(GitAiError::InvalidLineRange(range)) if range == "4-x"
+        ));
+    }
+
     #[test]
     fn test_serialize_deserialize_roundtrip() {
         let mut log = AuthorshipLog::new();
@@ -699,6 +1072,100 @@ mod tests {
         assert_debug_snapshot!(deserialized);
     }
 
+    #[test]
+    fn test_short_hash_length() {
+        assert_eq!(
+            generate_short_hash_with_length("session", "claude", DEFAULT_SHORT_HASH_LENGTH),
+            generate_short_hash("session", "claude")
+        );
+        let long = generate_short_hash_with_length("session", "claude", MAX_SHORT_HASH_LENGTH);
+        assert_eq!(long.len(), 40);
+        assert!(long.starts_with(&generate_short_hash_with_length("session", "claude", 7)));
+        // Out-of-range lengths are clamped rather than panicking on the slice
+        assert_eq!(
+            generate_short_hash_with_length("session", "claude", 64).len(),
+            40
+        );
+        assert_eq!(
+            generate_short_hash_with_length("session", "claude", 0).len(),
+            7
+        );
+
+        assert_eq!(parse_hash_length(" 12 ").unwrap(), 12);
+        assert_eq!(parse_hash_length("40").unwrap(), 40);
+        for invalid in ["6", "41", "sixteen", ""] {
+            assert!(parse_hash_length(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_from_reader_matches_string() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "abc123".to_string();
+        let mut file = FileAttestation::new("src/with space.rs".to_string());
+        file.add_entry(AttestationEntry::new(
+            "xyzAbc".to_string(),
+            vec![LineRange::Single(3), LineRange::Range(7, 9)],
+        ));
+        log.attestations.push(file);
+        let serialized = log.serialize_to_string().unwrap();
+
+        let from_reader = AuthorshipLog::deserialize_from_reader(serialized.as_bytes()).unwrap();
+        assert_eq!(from_reader, log);
+
+        // CRLF line endings, as written by some editors and git configs
+        let crlf = serialized.replace('\n', "\r\n");
+        let from_reader = AuthorshipLog::deserialize_from_reader(crlf.as_bytes()).unwrap();
+        assert_eq!(from_reader, log);
+
+        let no_divider = serialized.replace("---", "");
+        assert!(AuthorshipLog::deserialize_from_reader(no_divider.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_future_major_version_is_rejected() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.schema_version = "authorship/4.0.0".to_string();
+        let serialized = log.serialize_to_string().unwrap();
+
+        let err = AuthorshipLog::deserialize_from_string(&serialized).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unsupported authorship log version: authorship/4.0.0"),
+            "{}",
+            err
+        );
+        assert!(AuthorshipLog::deserialize_from_reader(serialized.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_older_major_version_reads_when_layout_matches() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.schema_version = "authorship/2.1.0".to_string();
+        log.metadata.base_commit_sha = "abc123".to_string();
+        let serialized = log.serialize_to_string().unwrap();
+
+        assert_eq!(
+            AuthorshipLog::deserialize_from_string(&serialized).unwrap(),
+            log
+        );
+        assert_eq!(
+            AuthorshipLog::deserialize_from_reader(serialized.as_bytes()).unwrap(),
+            log
+        );
+
+        // A layout it can't read is reported as too old, not as needing a newer git-ai
+        let incompatible =
+            "src/main.rs\n  a1b2c3d 1\n---\n{\"schema_version\": \"authorship/2.1.0\"}";
+        let err = AuthorshipLog::deserialize_from_string(incompatible).unwrap_err();
+        assert!(
+            err.to_string().contains("predates authorship/3.0.0"),
+            "{}",
+            err
+        );
+        assert!(!err.to_string().contains("upgrade git-ai"), "{}", err);
+    }
+
     #[test]
     fn test_expected_format() {
         let mut log = AuthorshipLog::new();
@@ -775,6 +1242,7 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                transcript_truncated: false,
             },
         );
 
@@ -819,6 +1287,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unusual_file_names_round_trip() {
+        let paths = [
+            "src/tab\there.rs",
+            "  leading spaces.rs",
+            "\"quoted\".rs",
+            "\"",
+            "back\\slash dir/file.rs",
+            "trailing space ",
+            "line\nbreak.rs",
+            "中文 文件.rs",
+            "plain/path.rs",
+        ];
+
+        let mut log = AuthorshipLog::new();
+        for path in paths {
+            let mut file = FileAttestation::new(path.to_string());
+            file.add_entry(AttestationEntry::new(
+                "abcd1234abcd1234".to_string(),
+                vec![LineRange::Range(1, 3)],
+            ));
+            log.attestations.push(file);
+        }
+
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(serialized.starts_with("\"src/tab\\there.rs\"\n"));
+        assert!(serialized.contains("\n\"  leading spaces.rs\"\n"));
+        assert!(serialized.contains("\n\"\\\"quoted\\\".rs\"\n"));
+        assert!(serialized.contains("\nplain/path.rs\n"));
+
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        let round_tripped: Vec<&str> = deserialized
+            .attestations
+            .iter()
+            .map(|f| f.file_path.as_str())
+            .collect();
+        assert_eq!(round_tripped, paths);
+    }
+
+    #[test]
+    fn test_legacy_quoted_paths_still_parse() {
+        // Older notes quoted paths with spaces without escaping anything inside
+        let content = "\"src/my file.rs\"\n  abcd1234abcd1234 1-2\nunquoted\\path.rs\n  abcd1234abcd1234 3\n---\n{\"schema_version\":\"authorship/3.0.0\",\"base_commit_sha\":\"\",\"prompts\":{}}";
+        let log = AuthorshipLog::deserialize_from_string(content).unwrap();
+        assert_eq!(log.attestations[0].file_path, "src/my file.rs");
+        assert_eq!(log.attestations[1].file_path, "unquoted\\path.rs");
+    }
+
     #[test]
     fn test_hash_always_maps_to_prompt() {
         // Demonstrate that every hash in attestation section maps to prompts section
@@ -842,6 +1358,7 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                transcript_truncated: false,
             },
         );
 
@@ -868,6 +1385,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dangling_prompt_hashes() {
+        let content = "a.rs\n  bbbb2222bbbb2222 1-2\n  aaaa1111aaaa1111 3\nb.rs\n  bbbb2222bbbb2222 5\n---\n{\"schema_version\":\"authorship/3.0.0\",\"base_commit_sha\":\"\",\"prompts\":{}}";
+        let mut log = AuthorshipLog::deserialize_from_string(content).unwrap();
+        assert_eq!(
+            log.dangling_prompt_hashes(),
+            vec![
+                "aaaa1111aaaa1111".to_string(),
+                "bbbb2222bbbb2222".to_string()
+            ]
+        );
+
+        log.metadata.prompts.insert(
+            "aaaa1111aaaa1111".to_string(),
+            crate::authorship::authorship_log::PromptRecord {
+                agent_id: crate::authorship::working_log::AgentId {
+                    tool: "cursor".to_string(),
+                    id: "session_123".to_string(),
+                    model: "claude-3-sonnet".to_string(),
+                },
+                human_author: None,
+                messages: vec![],
+                total_additions: 1,
+                total_deletions: 0,
+                accepted_lines: 1,
+                overriden_lines: 0,
+                messages_url: None,
+                transcript_truncated: false,
+            },
+        );
+        assert_eq!(
+            log.dangling_prompt_hashes(),
+            vec!["bbbb2222bbbb2222".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_serialize_is_byte_stable_for_identical_content() {
+        use crate::authorship::transcript::Message;
+
+        // Same prompts, inserted in opposite orders, with tool inputs whose
+        // JSON objects list their keys differently
+        let build = |order: &[&str], input: &str| {
+            let mut log = AuthorshipLog::new();
+            log.metadata.base_commit_sha = "abc123".to_string();
+            for id in order {
+                let agent_id = crate::authorship::working_log::AgentId {
+                    tool: "cursor".to_string(),
+                    id: id.to_string(),
+                    model: "claude-3-sonnet".to_string(),
+                };
+                log.metadata.prompts.insert(
+                    generate_short_hash(&agent_id.id, &agent_id.tool),
+                    crate::authorship::authorship_log::PromptRecord {
+                        agent_id,
+                        human_author: None,
+                        messages: vec![
+                            Message::user("Edit it".to_string(), None),
+                            Message::tool_use(
+                                "edit".to_string(),
+                                serde_json::from_str(input).unwrap(),
+                            ),
+                        ],
+                        total_additions: 1,
+                        total_deletions: 0,
+                        accepted_lines: 1,
+                        overriden_lines: 0,
+                        messages_url: None,
+                        transcript_truncated: false,
+                    },
+                );
+            }
+            log.serialize_to_string().unwrap()
+        };
+
+        assert_eq!(
+            build(
+                &["session_a", "session_b", "session_c"],
+                r#"{"path":"a.rs","opts":{"z":1,"a":2}}"#
+            ),
+            build(
+                &["session_c", "session_a", "session_b"],
+                r#"{"opts":{"a":2,"z":1},"path":"a.rs"}"#
+            )
+        );
+    }
+
     #[test]
     fn test_serialize_deserialize_no_attestations() {
         // Test that serialization and deserialization work correctly when there are no attestations
@@ -891,6 +1495,7 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                transcript_truncated: false,
             },
         );
 
@@ -908,6 +1513,36 @@ mod tests {
         assert_eq!(deserialized.attestations.len(), 0);
     }
 
+    #[test]
+    fn test_shift_line_ranges_splits_and_truncates() {
+        let mut entry = AttestationEntry::new(
+            "abc1234".to_string(),
+            vec![LineRange::Range(2, 4), LineRange::Range(8, 10)],
+        );
+
+        // Two lines inserted at line 3 split the first range and move the second
+        entry.shift_line_ranges(3, 2);
+        assert_eq!(
+            entry.line_ranges,
+            vec![
+                LineRange::Single(2),
+                LineRange::Range(5, 6),
+                LineRange::Range(10, 12)
+            ]
+        );
+
+        // Deleting lines 6-10 truncates the ranges on either side of the gap
+        entry.shift_line_ranges(6, -5);
+        assert_eq!(
+            entry.line_ranges,
+            vec![
+                LineRange::Single(2),
+                LineRange::Single(5),
+                LineRange::Range(6, 7)
+            ]
+        );
+    }
+
     #[test]
     fn test_remove_line_ranges_complete_removal() {
         let mut entry =
@@ -938,6 +1573,171 @@ mod tests {
         assert_eq!(entry.line_ranges[1], LineRange::Range(8, 10));
     }
 
+    #[test]
+    fn test_merge_line_ranges_keeps_ranges_sorted_and_merged() {
+        let mut entry = AttestationEntry::new(
+            "test_hash".to_string(),
+            vec![
+                LineRange::Range(20, 25),
+                LineRange::Single(2),
+                LineRange::Single(1),
+            ],
+        );
+        assert_eq!(
+            entry.line_ranges,
+            vec![LineRange::Range(1, 2), LineRange::Range(20, 25)]
+        );
+
+        entry.merge_line_ranges(&[LineRange::Single(10), LineRange::Single(26)]);
+        entry.merge_line_ranges(&[LineRange::Range(3, 9)]);
+        assert_eq!(
+            entry.line_ranges,
+            vec![LineRange::Range(1, 10), LineRange::Range(20, 26)]
+        );
+
+        // Spanning the gap absorbs both neighbours
+        entry.merge_line_ranges(&[LineRange::Range(8, 22)]);
+        assert_eq!(entry.line_ranges, vec![LineRange::Range(1, 26)]);
+
+        // Consecutive entries for one session collapse; other sessions stay separate
+        let mut file = FileAttestation::new("src/lib.rs".to_string());
+        for line in 1..=5 {
+            file.add_entry(AttestationEntry::new(
+                "a".to_string(),
+                vec![LineRange::Single(line)],
+            ));
+        }
+        file.add_entry(AttestationEntry::new(
+            "b".to_string(),
+            vec![LineRange::Single(3)],
+        ));
+        file.add_entry(AttestationEntry::new(
+            "a".to_string(),
+            vec![LineRange::Single(9)],
+        ));
+        assert_eq!(file.entries.len(), 3);
+        assert_eq!(file.entries[0].line_ranges, vec![LineRange::Range(1, 5)]);
+    }
+
+    #[test]
+    fn test_merge_unions_lines_and_keeps_local_on_conflict() {
+        let mut ours = AuthorshipLog::new();
+        let file = ours.get_or_create_file("src/main.rs");
+        file.add_entry(AttestationEntry::new(
+            "ours_hash".to_string(),
+            vec![LineRange::Range(1, 3)],
+        ));
+
+        let mut theirs = AuthorshipLog::new();
+        let file = theirs.get_or_create_file("src/main.rs");
+        file.add_entry(AttestationEntry::new(
+            "theirs_hash".to_string(),
+            vec![LineRange::Range(3, 5)],
+        ));
+        theirs.metadata.signature = Some("sig".to_string());
+
+        ours.metadata.signature = Some("stale".to_string());
+        let conflicts = ours.merge(&theirs);
+
+        assert_eq!(conflicts, 1);
+        assert_eq!(ours.metadata.signature, None);
+        assert_eq!(
+            ours.attestations[0].entries,
+            vec![
+                AttestationEntry::new("ours_hash".to_string(), vec![LineRange::Range(1, 3)]),
+                AttestationEntry::new("theirs_hash".to_string(), vec![LineRange::Range(4, 5)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_acceptance_splits_session_overrides_across_files() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(
+            "session".to_string(),
+            crate::authorship::authorship_log::PromptRecord {
+                agent_id: crate::authorship::working_log::AgentId {
+                    tool: "cursor".to_string(),
+                    id: "session_123".to_string(),
+                    model: "claude-3-sonnet".to_string(),
+                },
+                human_author: None,
+                messages: vec![],
+                total_additions: 10,
+                total_deletions: 0,
+                accepted_lines: 10,
+                overriden_lines: 5,
+                messages_url: None,
+                transcript_truncated: false,
+            },
+        );
+        log.get_or_create_file("a.rs")
+            .add_entry(AttestationEntry::new(
+                "session".to_string(),
+                vec![LineRange::Range(1, 8)],
+            ));
+        log.get_or_create_file("b.rs")
+            .add_entry(AttestationEntry::new(
+                "session".to_string(),
+                vec![LineRange::Single(1), LineRange::Single(4)],
+            ));
+
+        let a = log.file_acceptance("a.rs").unwrap();
+        assert_eq!(
+            a,
+            FileAcceptance {
+                accepted: 8,
+                overridden: 4
+            }
+        );
+        assert_eq!(a.ratio(), Some(8.0 / 12.0));
+        assert_eq!(
+            log.file_acceptance("b.rs"),
+            Some(FileAcceptance {
+                accepted: 2,
+                overridden: 1
+            })
+        );
+        assert_eq!(log.file_acceptance("c.rs"), None);
+    }
+
+    #[test]
+    fn test_file_acceptance_tolerates_reversed_range() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(
+            "session".to_string(),
+            crate::authorship::authorship_log::PromptRecord {
+                agent_id: crate::authorship::working_log::AgentId {
+                    tool: "cursor".to_string(),
+                    id: "session_123".to_string(),
+                    model: "claude-3-sonnet".to_string(),
+                },
+                human_author: None,
+                messages: vec![],
+                total_additions: 1,
+                total_deletions: 0,
+                accepted_lines: 1,
+                overriden_lines: 0,
+                messages_url: None,
+                transcript_truncated: false,
+            },
+        );
+        // A hand-edited note can carry a reversed range that `AttestationEntry::new` would
+        // never produce
+        log.get_or_create_file("a.rs").add_entry(AttestationEntry {
+            hash: "session".to_string(),
+            line_ranges: vec![LineRange::Range(5, 2)],
+        });
+
+        assert_eq!(
+            log.file_acceptance("a.rs"),
+            Some(FileAcceptance {
+                accepted: 1,
+                overridden: 0
+            })
+        );
+    }
+
     // Commenting out because working log to authorship helper deprecated in favor
     // of virtual attribution
     // #[test]
@@ -1070,6 +1870,7 @@ mod tests {
                 accepted_lines: 11,
                 overriden_lines: 0,
                 messages_url: None,
+                transcript_truncated: false,
             },
         );
 
@@ -1241,6 +2042,7 @@ mod tests {
                 accepted_lines: 10,
                 overriden_lines: 0,
                 messages_url: None,
+                transcript_truncated: false,
             },
         );
 
@@ -1265,6 +2067,7 @@ mod tests {
                 accepted_lines: 20,
                 overriden_lines: 0,
                 messages_url: None,
+                transcript_truncated: false,
             },
         );
 
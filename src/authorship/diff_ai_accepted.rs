@@ -10,6 +10,8 @@ pub struct DiffAiAcceptedStats {
     pub total_ai_accepted: u32,
     pub per_tool_model: BTreeMap<String, u32>,
     pub per_prompt: BTreeMap<String, u32>,
+    /// Added lines considered (after ignore patterns), AI-attributed or not
+    pub total_added: u32,
 }
 
 pub fn diff_ai_accepted_stats(
@@ -34,6 +36,7 @@ pub fn diff_ai_accepted_stats(
 
         lines.sort_unstable();
         lines.dedup();
+        stats.total_added += lines.len() as u32;
         let line_ranges = lines_to_ranges(&lines);
 
         if line_ranges.is_empty() {
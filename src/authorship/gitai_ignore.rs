@@ -0,0 +1,165 @@
+//! `.gitai-ignore`: gitignore-style patterns for files that checkpoints never track.
+//!
+//! Lives at the repository root. Blank lines and `#` comments are skipped, `!` re-includes,
+//! a trailing `/` only matches directories, and a pattern containing any other `/` is
+//! matched against the whole repo-relative path instead of a single name. As with git, a
+//! file inside an excluded directory can't be re-included.
+
+use crate::utils::debug_log;
+use glob::{MatchOptions, Pattern};
+use std::path::Path;
+
+pub const GITAI_IGNORE_FILE: &str = ".gitai-ignore";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Matched against the full path rather than each path component's name
+    anchored: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GitAiIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl GitAiIgnore {
+    /// Rules from `<workdir>/.gitai-ignore`, or none when the file is missing.
+    pub fn load(workdir: &Path) -> Self {
+        match std::fs::read_to_string(workdir.join(GITAI_IGNORE_FILE)) {
+            Ok(contents) => Self::parse(&contents),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    // `\!` and `\#` start a literal pattern
+                    None => (false, line.strip_prefix('\\').unwrap_or(line)),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                let line = line.strip_prefix('/').unwrap_or(line);
+                match Pattern::new(line) {
+                    Ok(pattern) => Some(IgnoreRule {
+                        pattern,
+                        negated,
+                        dir_only,
+                        anchored,
+                    }),
+                    Err(e) => {
+                        debug_log(&format!(
+                            "Skipping invalid {} pattern {:?}: {}",
+                            GITAI_IGNORE_FILE, line, e
+                        ));
+                        None
+                    }
+                }
+            })
+            .collect();
+        GitAiIgnore { rules }
+    }
+
+    /// Whether the repo-relative, `/`-separated `path` is excluded, either itself or through
+    /// one of its parent directories.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let path = path.trim_start_matches("./");
+        let excluded_dir = path
+            .match_indices('/')
+            .any(|(idx, _)| self.last_match(&path[..idx], true) == Some(true));
+        excluded_dir || self.last_match(path, false) == Some(true)
+    }
+
+    /// Verdict of the last rule matching `path` (true = excluded), like git's "last match wins".
+    fn last_match(&self, path: &str, is_dir: bool) -> Option<bool> {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                (is_dir || !rule.dir_only)
+                    && rule
+                        .pattern
+                        .matches_with(if rule.anchored { path } else { name }, MATCH_OPTIONS)
+            })
+            .map(|rule| !rule.negated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_match_at_any_depth() {
+        let ignore = GitAiIgnore::parse("# lockfiles\n*.lock\n\npackage-lock.json\n");
+        assert!(ignore.is_ignored("Cargo.lock"));
+        assert!(ignore.is_ignored("crates/foo/Cargo.lock"));
+        assert!(ignore.is_ignored("web/package-lock.json"));
+        assert!(!ignore.is_ignored("src/lock.rs"));
+    }
+
+    #[test]
+    fn test_directory_patterns() {
+        let ignore = GitAiIgnore::parse("build/\n/dist\ngenerated/**/*.ts\n");
+        assert!(ignore.is_ignored("build/out.txt"));
+        assert!(ignore.is_ignored("web/build/app.js"));
+        // A file named like the directory pattern isn't a directory
+        assert!(!ignore.is_ignored("build"));
+        assert!(ignore.is_ignored("dist/bundle.js"));
+        // Leading slash anchors to the root
+        assert!(!ignore.is_ignored("web/dist/bundle.js"));
+        assert!(ignore.is_ignored("generated/api.ts"));
+        assert!(ignore.is_ignored("generated/v1/models/user.ts"));
+        assert!(!ignore.is_ignored("generated/README.md"));
+        assert!(!ignore.is_ignored("src/generated/api.ts"));
+    }
+
+    #[test]
+    fn test_negation_last_match_wins() {
+        let ignore = GitAiIgnore::parse("*.json\n!config.json\nfixtures/config.json\n");
+        assert!(ignore.is_ignored("data.json"));
+        assert!(!ignore.is_ignored("config.json"));
+        assert!(!ignore.is_ignored("app/config.json"));
+        assert!(ignore.is_ignored("fixtures/config.json"));
+
+        // Files inside an excluded directory stay excluded
+        let ignore = GitAiIgnore::parse("vendor/\n!vendor/keep.rs\n");
+        assert!(ignore.is_ignored("vendor/keep.rs"));
+
+        let ignore = GitAiIgnore::parse("\\!important.txt\n\\#notes.txt\n");
+        assert!(ignore.is_ignored("!important.txt"));
+        assert!(ignore.is_ignored("#notes.txt"));
+    }
+
+    #[test]
+    fn test_missing_file_ignores_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!GitAiIgnore::load(dir.path()).is_ignored("Cargo.lock"));
+
+        std::fs::write(dir.path().join(GITAI_IGNORE_FILE), "*.lock\n").unwrap();
+        assert!(GitAiIgnore::load(dir.path()).is_ignored("Cargo.lock"));
+    }
+}
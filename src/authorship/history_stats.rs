@@ -0,0 +1,356 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::range_authorship::should_ignore_file;
+use crate::commands::blame::{GitAiBlameOptions, split_name_email, team_for};
+use crate::error::GitAiError;
+use crate::git::refs::get_reference_as_authorship_log_v3;
+use crate::git::repository::{Repository, exec_git, spawn_git};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Prompt counters summed over every note on the walked commits, for one agent/model or
+/// one human author.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HistoryBreakdown {
+    pub name: String,
+    pub sessions: usize,
+    pub total_additions: u32,
+    pub total_deletions: u32,
+    pub accepted_lines: u32,
+}
+
+/// `git-ai stats --history`: what the AI sessions on a branch produced, and how much of
+/// the tree at HEAD they still account for.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HistoryStats {
+    pub commits: usize,
+    pub commits_with_notes: usize,
+    /// Keyed `<tool>/<model>`
+    pub by_model: Vec<HistoryBreakdown>,
+    /// Keyed by the human who ran the session ("unknown" when not recorded)
+    pub by_author: Vec<HistoryBreakdown>,
+    /// Keyed by the team `--team-map` resolves that human to
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub by_team: Vec<HistoryBreakdown>,
+    /// Lines at HEAD blamed to the counted sessions
+    pub ai_lines: u32,
+    /// All other text lines at HEAD
+    pub human_lines: u32,
+}
+
+impl HistoryStats {
+    pub fn ai_percent(&self) -> f64 {
+        let total = self.ai_lines + self.human_lines;
+        if total == 0 {
+            0.0
+        } else {
+            self.ai_lines as f64 * 100.0 / total as f64
+        }
+    }
+}
+
+/// `--model` matches a model name exactly or by prefix, so `claude` covers every Claude model.
+fn model_matches(model: &str, filter: Option<&str>) -> bool {
+    filter.is_none_or(|f| model.starts_with(f))
+}
+
+/// Walk the commits reachable from HEAD (only those after `since` when given), summing the
+/// prompt counters of each commit's authorship note per agent/model and per author (and per
+/// team, given a `team_map`), then blame the files those notes touch at HEAD to count the
+/// lines the sessions still own.
+pub fn history_stats(
+    repo: &Repository,
+    since: Option<&str>,
+    model: Option<&str>,
+    ignore_patterns: &[String],
+    team_map: Option<&HashMap<String, String>>,
+) -> Result<HistoryStats, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    match since {
+        Some(rev) => args.push(format!("{}..HEAD", rev)),
+        None => args.push("HEAD".to_string()),
+    }
+    let output = exec_git(&args)?;
+    let commits: Vec<String> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    let mut stats = HistoryStats {
+        commits: commits.len(),
+        ..Default::default()
+    };
+    let mut by_model: BTreeMap<String, (BTreeSet<String>, HistoryBreakdown)> = BTreeMap::new();
+    let mut by_author: BTreeMap<String, (BTreeSet<String>, HistoryBreakdown)> = BTreeMap::new();
+    let mut by_team: BTreeMap<String, (BTreeSet<String>, HistoryBreakdown)> = BTreeMap::new();
+    let mut sessions: HashSet<String> = HashSet::new();
+    let mut files: BTreeSet<String> = BTreeSet::new();
+
+    for commit in &commits {
+        let Ok(log) = get_reference_as_authorship_log_v3(repo, commit) else {
+            continue;
+        };
+        stats.commits_with_notes += 1;
+
+        for (hash, prompt) in &log.metadata.prompts {
+            if !model_matches(&prompt.agent_id.model, model) {
+                continue;
+            }
+            sessions.insert(hash.clone());
+            let model_key = format!("{}/{}", prompt.agent_id.tool_label(), prompt.agent_id.model);
+            let author_key = prompt
+                .human_author
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string());
+            let team_key = team_map.map(|team_map| {
+                let (name, email) = split_name_email(&author_key);
+                team_for(team_map, name, email).to_string()
+            });
+            let mut groups = vec![(model_key, &mut by_model), (author_key, &mut by_author)];
+            if let Some(team_key) = team_key {
+                groups.push((team_key, &mut by_team));
+            }
+            for (key, groups) in groups {
+                let (hashes, breakdown) = groups.entry(key.clone()).or_default();
+                breakdown.name = key;
+                hashes.insert(hash.clone());
+                breakdown.total_additions += prompt.total_additions;
+                breakdown.total_deletions += prompt.total_deletions;
+                breakdown.accepted_lines += prompt.accepted_lines;
+            }
+        }
+
+        for attestation in &log.attestations {
+            if !should_ignore_file(&attestation.file_path, ignore_patterns)
+                && attestation
+                    .entries
+                    .iter()
+                    .any(|e| sessions.contains(&e.hash))
+            {
+                files.insert(attestation.file_path.clone());
+            }
+        }
+    }
+
+    let finish = |groups: BTreeMap<String, (BTreeSet<String>, HistoryBreakdown)>| {
+        let mut rows: Vec<HistoryBreakdown> = groups
+            .into_values()
+            .map(|(hashes, mut breakdown)| {
+                breakdown.sessions = hashes.len();
+                breakdown
+            })
+            .collect();
+        rows.sort_by_key(|r| std::cmp::Reverse(r.accepted_lines));
+        rows
+    };
+    stats.by_model = finish(by_model);
+    stats.by_author = finish(by_author);
+    stats.by_team = finish(by_team);
+
+    let head_lines = text_lines_at_head(repo, ignore_patterns)?;
+    for file in files {
+        if !head_lines.contains_key(&file) {
+            continue;
+        }
+        let options = GitAiBlameOptions {
+            newest_commit: Some("HEAD".to_string()),
+            no_output: true,
+            use_prompt_hashes_as_names: true,
+            ..Default::default()
+        };
+        let Ok((line_authors, prompt_records)) = repo.blame(&file, &options) else {
+            continue;
+        };
+        stats.ai_lines += line_authors
+            .values()
+            .filter(|author| {
+                sessions.contains(*author)
+                    && prompt_records
+                        .get(*author)
+                        .is_some_and(|p| model_matches(&p.agent_id.model, model))
+            })
+            .count() as u32;
+    }
+    let total: u32 = head_lines.values().sum();
+    stats.human_lines = total.saturating_sub(stats.ai_lines);
+
+    Ok(stats)
+}
+
+/// Prompt counters of one agent/model in a single commit's note.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AgentLineDeltas {
+    /// `<tool>/<model>`
+    pub name: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub accepted_lines: u32,
+    pub overridden_lines: u32,
+}
+
+/// One line of `git-ai stats --history --stream`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitStatsRecord {
+    pub commit: String,
+    pub has_note: bool,
+    pub agents: Vec<AgentLineDeltas>,
+}
+
+/// Like [`history_stats`], but write one JSON line per walked commit as soon as its note is
+/// read, so memory use stays flat however long the history is. Notes are read in the same
+/// `git log` pass as the commits, and no totals are kept.
+pub fn stream_history_stats(
+    repo: &Repository,
+    since: Option<&str>,
+    model: Option<&str>,
+    out: &mut impl Write,
+) -> Result<(), GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--notes=ai".to_string());
+    // Note text can span lines, so records end with a record separator instead
+    args.push("--format=%H%x00%N%x1e".to_string());
+    match since {
+        Some(rev) => args.push(format!("{}..HEAD", rev)),
+        None => args.push("HEAD".to_string()),
+    }
+
+    let mut child = spawn_git(&args)?;
+    let Some(stdout) = child.stdout.take() else {
+        return Err(GitAiError::Generic(
+            "git log produced no output".to_string(),
+        ));
+    };
+    let mut reader = BufReader::new(stdout);
+    let mut record = Vec::new();
+    loop {
+        record.clear();
+        if reader.read_until(0x1e, &mut record)? == 0 {
+            break;
+        }
+        let text = String::from_utf8_lossy(&record);
+        let Some((commit, note)) = text.trim_start().split_once('\0') else {
+            continue;
+        };
+        let line = serde_json::to_string(&commit_stats_record(
+            commit,
+            note.trim_end_matches('\u{1e}'),
+            model,
+        ))?;
+        if let Err(e) = writeln!(out, "{}", line) {
+            let _ = child.kill();
+            let _ = child.wait();
+            // The consumer stopped reading (e.g. `| head`); that's not an error
+            if e.kind() == std::io::ErrorKind::BrokenPipe {
+                return Ok(());
+            }
+            return Err(e.into());
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(GitAiError::GitCliError {
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            args,
+        });
+    }
+    Ok(())
+}
+
+fn commit_stats_record(commit: &str, note: &str, model: Option<&str>) -> CommitStatsRecord {
+    let Ok(log) = AuthorshipLog::deserialize_from_string(note.trim()) else {
+        return CommitStatsRecord {
+            commit: commit.to_string(),
+            has_note: false,
+            agents: Vec::new(),
+        };
+    };
+
+    let mut agents: BTreeMap<String, AgentLineDeltas> = BTreeMap::new();
+    for prompt in log.metadata.prompts.values() {
+        if !model_matches(&prompt.agent_id.model, model) {
+            continue;
+        }
+        let name = format!("{}/{}", prompt.agent_id.tool_label(), prompt.agent_id.model);
+        let deltas = agents.entry(name.clone()).or_default();
+        deltas.name = name;
+        deltas.additions += prompt.total_additions;
+        deltas.deletions += prompt.total_deletions;
+        deltas.accepted_lines += prompt.accepted_lines;
+        deltas.overridden_lines += prompt.overriden_lines;
+    }
+    CommitStatsRecord {
+        commit: commit.to_string(),
+        has_note: true,
+        agents: agents.into_values().collect(),
+    }
+}
+
+/// Line count of every text file at HEAD (binary files are left out).
+fn text_lines_at_head(
+    repo: &Repository,
+    ignore_patterns: &[String],
+) -> Result<BTreeMap<String, u32>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("diff".to_string());
+    args.push("--numstat".to_string());
+    // NUL-terminated records keep paths with tabs, quotes or non-ASCII bytes unquoted
+    args.push("-z".to_string());
+    args.push("--no-renames".to_string());
+    args.push(EMPTY_TREE_HASH.to_string());
+    args.push("HEAD".to_string());
+    let output = exec_git(&args)?;
+
+    Ok(String::from_utf8(output.stdout)?
+        .split('\0')
+        .filter_map(|record| {
+            let mut parts = record.splitn(3, '\t');
+            let added = parts.next()?.parse::<u32>().ok()?;
+            let _deleted = parts.next()?;
+            let file = parts.next()?;
+            (!should_ignore_file(file, ignore_patterns)).then(|| (file.to_string(), added))
+        })
+        .collect())
+}
+
+pub fn print_history_stats(stats: &HistoryStats) {
+    println!(
+        "AI-authored lines at HEAD: {} of {} ({:.1}%), {} human",
+        stats.ai_lines,
+        stats.ai_lines + stats.human_lines,
+        stats.ai_percent(),
+        stats.human_lines
+    );
+    println!(
+        "{} commit(s) walked, {} with authorship notes",
+        stats.commits, stats.commits_with_notes
+    );
+    for (title, rows) in [
+        ("By model", &stats.by_model),
+        ("By author", &stats.by_author),
+        ("By team", &stats.by_team),
+    ] {
+        if rows.is_empty() {
+            continue;
+        }
+        println!("\n{}:", title);
+        let width = rows.iter().map(|r| r.name.len()).max().unwrap_or(0);
+        for row in rows {
+            println!(
+                "  {:<width$}  {} session(s)  +{} -{}  {} accepted",
+                row.name,
+                row.sessions,
+                row.total_additions,
+                row.total_deletions,
+                row.accepted_lines,
+                width = width
+            );
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use crate::authorship::authorship_log_serialization::generate_short_hash;
 use crate::authorship::transcript::AiTranscript;
 use crate::authorship::working_log::Checkpoint;
 use crate::error::GitAiError;
@@ -104,7 +103,7 @@ impl PromptDbRecord {
         let agent_id = checkpoint.agent_id.as_ref()?;
         let transcript = checkpoint.transcript.as_ref()?;
 
-        let short_hash = generate_short_hash(&agent_id.id, &agent_id.tool);
+        let short_hash = checkpoint.session_hash()?;
 
         // Use first message timestamp for created_at, fall back to checkpoint timestamp
         let created_at = transcript
@@ -153,6 +152,7 @@ impl PromptDbRecord {
             accepted_lines: self.accepted_lines.unwrap_or(0),
             overriden_lines: self.overridden_lines.unwrap_or(0),
             messages_url: None,
+            transcript_truncated: self.messages.truncated,
         }
     }
 
@@ -2,6 +2,8 @@ pub mod attribution_tracker;
 pub mod authorship_log;
 pub mod authorship_log_serialization;
 pub mod diff_ai_accepted;
+pub mod gitai_ignore;
+pub mod history_stats;
 pub mod imara_diff_utils;
 pub mod internal_db;
 pub mod move_detection;
@@ -11,6 +13,7 @@ pub mod prompt_utils;
 pub mod range_authorship;
 pub mod rebase_authorship;
 pub mod secrets;
+pub mod signing;
 pub mod stats;
 pub mod transcript;
 pub mod virtual_attribution;
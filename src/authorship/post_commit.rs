@@ -1,15 +1,19 @@
 use crate::api::{ApiClient, ApiContext};
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::authorship::prompt_utils::{PromptUpdateResult, update_prompt_from_tool};
-use crate::authorship::secrets::{redact_secrets_from_prompts, strip_prompt_messages};
+use crate::authorship::secrets::{
+    IgnorePrompts, redact_secrets_from_prompts, strip_ignored_prompt_messages,
+    strip_prompt_messages,
+};
 use crate::authorship::stats::{stats_for_commit_stats, write_stats_to_terminal};
+use crate::authorship::transcript::{MAX_TRANSCRIPT_BYTES_CONFIG_KEY, max_transcript_bytes};
 use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::authorship::working_log::{Checkpoint, CheckpointKind};
 use crate::config::{Config, PromptStorageMode};
 use crate::error::GitAiError;
-use crate::git::refs::notes_add;
+use crate::git::refs::notes_add_authorship_log;
 use crate::git::repository::Repository;
-use crate::utils::debug_log;
+use crate::utils::{debug_log, info_log};
 use std::collections::{HashMap, HashSet};
 use std::io::IsTerminal;
 
@@ -27,6 +31,9 @@ pub fn post_commit(
     // Initialize the new storage system
     let repo_storage = &repo.storage;
     let working_log = repo_storage.working_log_for_base_commit(&parent_sha);
+    // Hold the parent's lock from reading its checkpoints until the log is deleted, so a
+    // checkpoint landing mid-commit isn't overwritten or lost
+    let lock = working_log.lock()?;
 
     // Pull all working log entries from the parent commit
 
@@ -41,6 +48,21 @@ pub fn post_commit(
     // Do this BEFORE filtering so that all checkpoints (including untracked files) are updated
     update_prompts_to_latest(&mut parent_working_log)?;
 
+    // The refresh reloads whole transcripts from the tools, so cap them again
+    if let Some(max_bytes) = max_transcript_bytes(repo) {
+        for checkpoint in parent_working_log.iter_mut() {
+            if let Some(transcript) = checkpoint.transcript.as_mut()
+                && transcript.truncate_to_bytes(max_bytes)
+            {
+                debug_log(&format!(
+                    "Truncated refreshed transcript to {} messages per {}",
+                    transcript.messages().len(),
+                    MAX_TRANSCRIPT_BYTES_CONFIG_KEY
+                ));
+            }
+        }
+    }
+
     // Batch upsert all prompts to database after refreshing (non-fatal if it fails)
     if let Err(e) = batch_upsert_prompts_to_db(&parent_working_log, &working_log, &commit_sha) {
         debug_log(&format!(
@@ -56,7 +78,7 @@ pub fn post_commit(
         );
     }
 
-    working_log.write_all_checkpoints(&parent_working_log)?;
+    working_log.write_all_checkpoints(&parent_working_log, &lock)?;
 
     // Create VirtualAttributions from working log (fast path - no blame)
     // We don't need to run blame because we only care about the working log data
@@ -94,6 +116,19 @@ pub fn post_commit(
 
     authorship_log.metadata.base_commit_sha = commit_sha.clone();
 
+    // Drop transcripts of the agents listed in gitai.ignorePrompts before any storage mode
+    // can keep or upload them. Their line counts stay in the log.
+    let stripped = strip_ignored_prompt_messages(
+        &mut authorship_log.metadata.prompts,
+        &IgnorePrompts::from_repo(repo),
+    );
+    if stripped > 0 {
+        debug_log(&format!(
+            "Stripped transcripts from {} prompt(s) per gitai.ignorePrompts",
+            stripped
+        ));
+    }
+
     // Handle prompts based on effective prompt storage mode for this repository
     // The effective mode considers include/exclude lists and fallback settings
     let effective_storage = Config::get().effective_prompt_storage(&Some(repo.clone()));
@@ -149,12 +184,7 @@ pub fn post_commit(
         }
     }
 
-    // Serialize the authorship log
-    let authorship_json = authorship_log
-        .serialize_to_string()
-        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
-
-    notes_add(repo, &commit_sha, &authorship_json)?;
+    notes_add_authorship_log(repo, &commit_sha, &mut authorship_log)?;
 
     // Compute stats once (needed for both metrics and terminal output)
     let stats = stats_for_commit_stats(repo, &commit_sha, &[])?;
@@ -178,16 +208,67 @@ pub fn post_commit(
     }
 
     // // Clean up old working log
-    repo_storage.delete_working_log_for_base_commit(&parent_sha)?;
-
-    if !supress_output && !Config::get().is_quiet() {
-        // Only print stats if we're in an interactive terminal and quiet mode is disabled
-        let is_interactive = std::io::stdout().is_terminal();
-        write_stats_to_terminal(&stats, is_interactive);
+    repo_storage.delete_working_log_for_base_commit_locked(&parent_sha, &lock)?;
+    repo_storage.write_last_checkpoint_base(&commit_sha)?;
+
+    // The stats chart and summary are only for an interactive terminal, so scripts reading
+    // git's output never see them
+    if !supress_output && std::io::stderr().is_terminal() {
+        let chart = write_stats_to_terminal(&stats, false);
+        info_log(chart.trim_end_matches('\n'));
+        info_log(&authorship_summary(&authorship_log, stats.human_additions));
     }
     Ok((commit_sha.to_string(), authorship_log))
 }
 
+/// One-line summary of the attribution just recorded, e.g.
+/// `git-ai: 1 AI session (claude-sonnet-4), 3 AI lines, 2 human lines`.
+pub fn authorship_summary(authorship_log: &AuthorshipLog, human_lines: u32) -> String {
+    let mut sessions: Vec<&str> = Vec::new();
+    let mut ai_lines = 0;
+    for attestation in &authorship_log.attestations {
+        let mut lines = HashSet::new();
+        for entry in &attestation.entries {
+            if !authorship_log.metadata.prompts.contains_key(&entry.hash) {
+                continue;
+            }
+            if !sessions.contains(&entry.hash.as_str()) {
+                sessions.push(&entry.hash);
+            }
+            lines.extend(entry.line_ranges.iter().flat_map(|r| r.expand()));
+        }
+        ai_lines += lines.len();
+    }
+
+    let mut models: Vec<&str> = Vec::new();
+    for hash in &sessions {
+        let model = authorship_log.metadata.prompts[*hash]
+            .agent_id
+            .model
+            .as_str();
+        if !models.contains(&model) {
+            models.push(model);
+        }
+    }
+
+    let mut summary = format!(
+        "git-ai: {} AI session{}",
+        sessions.len(),
+        if sessions.len() == 1 { "" } else { "s" }
+    );
+    if !models.is_empty() {
+        summary.push_str(&format!(" ({})", models.join(", ")));
+    }
+    summary.push_str(&format!(
+        ", {} AI line{}, {} human line{}",
+        ai_lines,
+        if ai_lines == 1 { "" } else { "s" },
+        human_lines,
+        if human_lines == 1 { "" } else { "s" }
+    ));
+    summary
+}
+
 /// Update prompts/transcripts in working log checkpoints to their latest versions.
 /// This helps prevent race conditions where we miss the last message in a conversation.
 ///
@@ -356,7 +437,7 @@ fn enqueue_prompt_messages_to_cas(
                 messages: prompt.messages.clone(),
             };
             let messages_json = serde_json::to_value(&messages_obj)
-                .map_err(|e| GitAiError::Generic(format!("Failed to serialize messages: {}", e)))?;
+                .map_err(|e| GitAiError::Serialization(format!("messages: {}", e)))?;
 
             // Enqueue to CAS (returns hash)
             let hash = db_lock.enqueue_cas_object(&messages_json, Some(&metadata))?;
@@ -467,8 +548,66 @@ fn record_commit_metrics(
 
 #[cfg(test)]
 mod tests {
+    use super::authorship_summary;
+    use crate::authorship::authorship_log::{LineRange, PromptRecord};
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+    use crate::authorship::working_log::AgentId;
     use crate::git::test_utils::TmpRepo;
 
+    fn prompt(model: &str) -> PromptRecord {
+        PromptRecord {
+            agent_id: AgentId {
+                tool: "cursor".to_string(),
+                id: model.to_string(),
+                model: model.to_string(),
+            },
+            human_author: None,
+            messages: vec![],
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+            transcript_truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_authorship_summary() {
+        let mut log = AuthorshipLog::new();
+        assert_eq!(
+            authorship_summary(&log, 1),
+            "git-ai: 0 AI sessions, 0 AI lines, 1 human line"
+        );
+
+        log.metadata
+            .prompts
+            .insert("session1".to_string(), prompt("claude-sonnet-4"));
+        log.metadata
+            .prompts
+            .insert("session2".to_string(), prompt("gpt-5"));
+        log.get_or_create_file("a.rs")
+            .add_entry(AttestationEntry::new(
+                "session1".to_string(),
+                vec![LineRange::Range(1, 2)],
+            ));
+        log.get_or_create_file("b.rs")
+            .add_entry(AttestationEntry::new(
+                "session2".to_string(),
+                vec![LineRange::Single(4)],
+            ));
+        // Attestations without a prompt record aren't counted
+        log.get_or_create_file("b.rs")
+            .add_entry(AttestationEntry::new(
+                "orphan".to_string(),
+                vec![LineRange::Single(9)],
+            ));
+        assert_eq!(
+            authorship_summary(&log, 0),
+            "git-ai: 2 AI sessions (claude-sonnet-4, gpt-5), 3 AI lines, 0 human lines"
+        );
+    }
+
     #[test]
     fn test_post_commit_empty_repo_with_checkpoint() {
         // Create an empty repo (no commits yet)
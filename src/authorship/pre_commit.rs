@@ -4,7 +4,7 @@ use crate::git::repository::Repository;
 
 pub fn pre_commit(repo: &Repository, default_author: String) -> Result<(), GitAiError> {
     // Run checkpoint as human editor.
-    let result: Result<(usize, usize, usize), GitAiError> = crate::commands::checkpoint::run(
+    let result = crate::commands::checkpoint::run(
         repo,
         &default_author,
         CheckpointKind::Human,
@@ -13,7 +13,8 @@ pub fn pre_commit(repo: &Repository, default_author: String) -> Result<(), GitAi
         true,
         None,
         true, // should skip if NO AI CHECKPOINTS
-              // also there's a bug around clearing state...maybe INITAL doesn't get deleted when nuking other stuff
+        // also there's a bug around clearing state...maybe INITAL doesn't get deleted when nuking other stuff
+        false,
     );
     result.map(|_| ())
 }
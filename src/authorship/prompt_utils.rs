@@ -75,10 +75,9 @@ pub fn find_prompt_in_history(
     let shas = grep_ai_notes(repo, &format!("\"{}\"", prompt_id)).unwrap_or_default();
 
     if shas.is_empty() {
-        return Err(GitAiError::Generic(format!(
-            "Prompt not found in history: {}",
-            prompt_id
-        )));
+        return Err(GitAiError::MissingPromptRecord {
+            hash: prompt_id.to_string(),
+        });
     }
 
     // Iterate through commits, looking for the prompt and counting occurrences
@@ -96,10 +95,9 @@ pub fn find_prompt_in_history(
 
     // If we get here, we didn't find enough occurrences
     if found_count == 0 {
-        Err(GitAiError::Generic(format!(
-            "Prompt not found in history: {}",
-            prompt_id
-        )))
+        Err(GitAiError::MissingPromptRecord {
+            hash: prompt_id.to_string(),
+        })
     } else {
         Err(GitAiError::Generic(format!(
             "Prompt '{}' found {} time(s), but offset {} requested (max offset: {})",
@@ -136,16 +134,14 @@ pub fn find_prompt_with_db_fallback(
         // Try to find in history (most recent occurrence)
         match find_prompt_in_history(repo, prompt_id, 0) {
             Ok((commit_sha, prompt)) => Ok((Some(commit_sha), prompt)),
-            Err(_) => Err(GitAiError::Generic(format!(
-                "Prompt '{}' not found in database or repository",
-                prompt_id
-            ))),
+            Err(_) => Err(GitAiError::MissingPromptRecord {
+                hash: prompt_id.to_string(),
+            }),
         }
     } else {
-        Err(GitAiError::Generic(format!(
-            "Prompt '{}' not found in database and no repository provided",
-            prompt_id
-        )))
+        Err(GitAiError::MissingPromptRecord {
+            hash: prompt_id.to_string(),
+        })
     }
 }
 
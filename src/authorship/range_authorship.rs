@@ -4,12 +4,13 @@ use std::collections::HashSet;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::authorship::authorship_log::LineRange;
 use crate::authorship::diff_ai_accepted::diff_ai_accepted_stats;
 use crate::authorship::stats::{CommitStats, stats_for_commit_stats, stats_from_authorship_log};
 use crate::error::GitAiError;
-use crate::git::refs::{CommitAuthorship, get_commits_with_notes_from_list};
+use crate::git::refs::{CommitAuthorship, get_authorship, get_commits_with_notes_from_list};
 use crate::git::repository::{CommitRange, Repository};
-use crate::utils::debug_log;
+use crate::utils::{csv_field, debug_log};
 
 use std::io::IsTerminal;
 
@@ -219,6 +220,8 @@ fn create_authorship_log_for_range(
                     ),
                     base_commit_sha: end_sha.to_string(),
                     prompts: std::collections::BTreeMap::new(),
+                    signature: None,
+                    signer: None,
                 },
             },
         );
@@ -439,6 +442,201 @@ fn calculate_range_stats_direct(
     Ok(stats)
 }
 
+/// AI-authored line totals for a single file, used by `stats --by-file`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileAuthorshipRank {
+    pub file_path: String,
+    pub ai_lines: u32,
+    /// Model responsible for the most AI lines in this file
+    pub dominant_model: Option<String>,
+}
+
+/// Rank files by AI-authored line count across the given commits, aggregating the
+/// per-commit authorship logs by file path. Renames are followed best-effort so lines
+/// attributed before a rename are counted under the file's newest path.
+/// `commit_shas` is expected newest-first (as returned by `git rev-list`).
+pub fn rank_files_by_ai_lines(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    top: Option<usize>,
+) -> Result<Vec<FileAuthorshipRank>, GitAiError> {
+    // file_path -> model -> ai lines
+    let mut per_file: HashMap<String, HashMap<String, u32>> = HashMap::new();
+
+    for sha in commit_shas.iter().rev() {
+        if !per_file.is_empty() {
+            for (old_path, new_path) in commit_renames(repo, sha)? {
+                if let Some(models) = per_file.remove(&old_path) {
+                    let target = per_file.entry(new_path).or_default();
+                    for (model, lines) in models {
+                        *target.entry(model).or_default() += lines;
+                    }
+                }
+            }
+        }
+
+        let Some(authorship_log) = get_authorship(repo, sha) else {
+            continue;
+        };
+
+        for file_attestation in &authorship_log.attestations {
+            if should_ignore_file(&file_attestation.file_path, ignore_patterns) {
+                continue;
+            }
+            for entry in &file_attestation.entries {
+                let lines: u32 = entry.line_ranges.iter().map(line_range_len).sum();
+                if lines == 0 {
+                    continue;
+                }
+                let model = authorship_log
+                    .metadata
+                    .prompts
+                    .get(&entry.hash)
+                    .map(|prompt| prompt.agent_id.model.clone())
+                    .unwrap_or_else(|| "unknown".to_string());
+                *per_file
+                    .entry(file_attestation.file_path.clone())
+                    .or_default()
+                    .entry(model)
+                    .or_default() += lines;
+            }
+        }
+    }
+
+    let mut ranks: Vec<FileAuthorshipRank> = per_file
+        .into_iter()
+        .map(|(file_path, models)| {
+            let ai_lines = models.values().sum();
+            let dominant_model = models
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map(|(model, _)| model);
+            FileAuthorshipRank {
+                file_path,
+                ai_lines,
+                dominant_model,
+            }
+        })
+        .collect();
+
+    // Sort descending by AI lines, ties broken by path for deterministic output
+    ranks.sort_by(|a, b| {
+        b.ai_lines
+            .cmp(&a.ai_lines)
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+
+    if let Some(n) = top {
+        ranks.truncate(n);
+    }
+
+    Ok(ranks)
+}
+
+fn line_range_len(range: &LineRange) -> u32 {
+    match range {
+        LineRange::Single(_) => 1,
+        LineRange::Range(start, end) => end.saturating_sub(*start) + 1,
+    }
+}
+
+/// Get (old_path, new_path) pairs for files renamed by a commit relative to its first parent
+fn commit_renames(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<Vec<(String, String)>, GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    if commit.parent_count()? == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("diff-tree".to_string());
+    args.push("--no-commit-id".to_string());
+    args.push("--name-status".to_string());
+    args.push("-r".to_string());
+    args.push("-M".to_string());
+    args.push("-z".to_string());
+    args.push(format!("{}^", commit_sha));
+    args.push(commit_sha.to_string());
+
+    let output = crate::git::repository::exec_git(&args)?;
+
+    // With -z, each record is: status NUL path [NUL new_path] NUL
+    let fields: Vec<String> = output
+        .stdout
+        .split(|&b| b == 0)
+        .filter(|bytes| !bytes.is_empty())
+        .map(|bytes| String::from_utf8_lossy(bytes).to_string())
+        .collect();
+
+    let mut renames = Vec::new();
+    let mut i = 0;
+    while i < fields.len() {
+        let status = &fields[i];
+        if status.starts_with('R') || status.starts_with('C') {
+            // Renames and copies carry both the source and destination paths
+            if status.starts_with('R') && i + 2 < fields.len() {
+                renames.push((fields[i + 1].clone(), fields[i + 2].clone()));
+            }
+            i += 3;
+        } else {
+            i += 2;
+        }
+    }
+
+    Ok(renames)
+}
+
+/// `file,ai_lines,dominant_model` rows, with the header row unless `header` is false (for
+/// appending to an existing file).
+pub fn file_authorship_ranks_csv(ranks: &[FileAuthorshipRank], header: bool) -> String {
+    let mut csv = String::new();
+    if header {
+        csv.push_str("file,ai_lines,dominant_model\n");
+    }
+    for rank in ranks {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&rank.file_path),
+            rank.ai_lines,
+            csv_field(rank.dominant_model.as_deref().unwrap_or(""))
+        ));
+    }
+    csv
+}
+
+pub fn print_file_authorship_ranks(ranks: &[FileAuthorshipRank]) {
+    if ranks.is_empty() {
+        println!("No AI-authored lines found");
+        return;
+    }
+
+    let model_width = ranks
+        .iter()
+        .map(|rank| rank.dominant_model.as_deref().unwrap_or("-").len())
+        .max()
+        .unwrap_or(0)
+        .max("Model".len());
+
+    println!(
+        "{:>8}  {:<width$}  File",
+        "AI lines",
+        "Model",
+        width = model_width
+    );
+    for rank in ranks {
+        println!(
+            "{:>8}  {:<width$}  {}",
+            rank.ai_lines,
+            rank.dominant_model.as_deref().unwrap_or("-"),
+            rank.file_path,
+            width = model_width
+        );
+    }
+}
+
 pub fn print_range_authorship_stats(stats: &RangeAuthorshipStats) {
     println!("\n");
 
@@ -1443,4 +1641,62 @@ mod tests {
         assert_eq!(stats.range_stats.git_diff_added_lines, 1);
         assert_eq!(stats.range_stats.ai_additions, 1);
     }
+
+    #[test]
+    fn test_rank_files_by_ai_lines_orders_by_ai_count() {
+        let tmp_repo = TmpRepo::new().unwrap();
+
+        tmp_repo.write_file("base.txt", "Human\n", true).unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_author("test_user")
+            .unwrap();
+        tmp_repo.commit_with_message("Initial commit").unwrap();
+        let first_sha = tmp_repo.get_head_commit_sha().unwrap();
+
+        // small.txt: 1 AI line, big.txt: 3 AI lines
+        tmp_repo.write_file("small.txt", "AI 1\n", true).unwrap();
+        tmp_repo
+            .write_file("big.txt", "AI 1\nAI 2\nAI 3\n", true)
+            .unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_ai("Claude", Some("claude-3-sonnet"), Some("cursor"))
+            .unwrap();
+        tmp_repo.commit_with_message("First AI commit").unwrap();
+
+        // medium.txt: 2 AI lines from a different model
+        tmp_repo
+            .write_file("medium.txt", "AI 1\nAI 2\n", true)
+            .unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_ai("GPT-4o", Some("gpt-4o"), Some("cursor"))
+            .unwrap();
+        tmp_repo.commit_with_message("Second AI commit").unwrap();
+        let head_sha = tmp_repo.get_head_commit_sha().unwrap();
+
+        let commit_range = CommitRange::new(
+            tmp_repo.gitai_repo(),
+            first_sha,
+            head_sha,
+            "HEAD".to_string(),
+        )
+        .unwrap();
+        let commit_shas = commit_range.all_commits();
+
+        let ranks = rank_files_by_ai_lines(tmp_repo.gitai_repo(), &commit_shas, &[], None).unwrap();
+        let ordered: Vec<(&str, u32)> = ranks
+            .iter()
+            .map(|r| (r.file_path.as_str(), r.ai_lines))
+            .collect();
+        assert_eq!(
+            ordered,
+            vec![("big.txt", 3), ("medium.txt", 2), ("small.txt", 1)]
+        );
+        assert_eq!(ranks[0].dominant_model.as_deref(), Some("claude-3-sonnet"));
+        assert_eq!(ranks[1].dominant_model.as_deref(), Some("gpt-4o"));
+
+        let top =
+            rank_files_by_ai_lines(tmp_repo.gitai_repo(), &commit_shas, &[], Some(2)).unwrap();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[1].file_path, "medium.txt");
+    }
 }
@@ -5,7 +5,7 @@ use crate::git::authorship_traversal::{
     commits_have_authorship_notes, load_ai_touched_files_for_commits,
 };
 use crate::git::refs::get_reference_as_authorship_log_v3;
-use crate::git::repository::{CommitRange, Repository};
+use crate::git::repository::{CommitRange, Repository, exec_git, exec_git_stdin};
 use crate::git::rewrite_log::RewriteLogEvent;
 use crate::utils::debug_log;
 use std::collections::{HashMap, HashSet};
@@ -28,6 +28,24 @@ pub fn rewrite_authorship_if_needed(
                 commit_author,
                 supress_output,
             )?;
+
+            if repo
+                .find_commit(commit.commit_sha.clone())?
+                .parent_count()?
+                > 1
+            {
+                rewrite_authorship_after_merge(repo, &commit.commit_sha)?;
+            }
+        }
+        RewriteLogEvent::Merge { merge } => {
+            if let (true, Some(merge_commit_sha)) = (merge.success, &merge.merge_commit_sha) {
+                rewrite_authorship_after_merge(repo, merge_commit_sha)?;
+
+                debug_log(&format!(
+                    "✓ Combined authorship for merge of {} into {}",
+                    merge.source_branch, merge.target_branch
+                ));
+            }
         }
         RewriteLogEvent::CommitAmend { commit_amend } => {
             rewrite_authorship_after_commit_amend(
@@ -68,6 +86,7 @@ pub fn rewrite_authorship_if_needed(
                 &rebase_complete.original_commits,
                 &rebase_complete.new_commits,
                 &commit_author,
+                None,
             )?;
 
             debug_log(&format!(
@@ -254,10 +273,11 @@ pub fn rewrite_authorship_after_squash_or_rebase(
             );
             let mut authorship_log = AuthorshipLog::new();
             authorship_log.metadata.base_commit_sha = merge_commit_sha.to_string();
-            let authorship_json = authorship_log.serialize_to_string().map_err(|_| {
-                GitAiError::Generic("Failed to serialize authorship log".to_string())
-            })?;
-            crate::git::refs::notes_add(repo, merge_commit_sha, &authorship_json)?;
+            crate::git::refs::notes_add_authorship_log(
+                repo,
+                merge_commit_sha,
+                &mut authorship_log,
+            )?;
         } else {
             // No files changed, nothing to do
             debug_log("No files changed in merge, skipping authorship rewrite");
@@ -336,11 +356,7 @@ pub fn rewrite_authorship_after_squash_or_rebase(
     ));
 
     // Step 7: Save authorship log to git notes
-    let authorship_json = authorship_log
-        .serialize_to_string()
-        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
-
-    crate::git::refs::notes_add(repo, merge_commit_sha, &authorship_json)?;
+    crate::git::refs::notes_add_authorship_log(repo, merge_commit_sha, &mut authorship_log)?;
 
     debug_log(&format!(
         "✓ Saved authorship log for merge commit {}",
@@ -350,12 +366,18 @@ pub fn rewrite_authorship_after_squash_or_rebase(
     Ok(())
 }
 
+/// Write authorship notes for the commits a rebase created, carrying over the attributions
+/// `original_commits` had. `author_map` (original commit SHA -> author identity) overrides,
+/// in the note of each commit rewritten from a mapped original, the `human_author` of the
+/// sessions that original's note records, for histories where the recorded author differs
+/// from who really committed the work.
 pub fn rewrite_authorship_after_rebase_v2(
     repo: &Repository,
     original_head: &str,
     original_commits: &[String],
     new_commits: &[String],
     _human_author: &str,
+    author_map: Option<&HashMap<String, String>>,
 ) -> Result<(), GitAiError> {
     // Handle edge case: no commits to process
     if new_commits.is_empty() {
@@ -422,7 +444,7 @@ pub fn rewrite_authorship_after_rebase_v2(
             repo_clone,
             original_head_clone,
             &pathspecs_clone,
-            merge_base,
+            merge_base.clone(),
         )
         .await
     })?;
@@ -451,6 +473,14 @@ pub fn rewrite_authorship_after_rebase_v2(
         )
     };
 
+    // Each rewritten commit takes the mapped author of the original it came from
+    let rewritten_from = match author_map {
+        Some(_) => pair_rewritten_commits(repo, original_commits, new_commits)?,
+        None => HashMap::new(),
+    };
+    // Mapped author of each session, from the latest rewritten commit whose original recorded it
+    let mut session_authors: HashMap<String, String> = HashMap::new();
+
     // Step 3: Process each new commit in order (oldest to newest)
     for (idx, new_commit) in commits_to_process.iter().enumerate() {
         debug_log(&format!(
@@ -462,45 +492,60 @@ pub fn rewrite_authorship_after_rebase_v2(
 
         // Get the DIFF for this commit (what actually changed)
         let commit_obj = repo.find_commit(new_commit.clone())?;
-        let parent_obj = commit_obj.parent(0)?;
-
         let commit_tree = commit_obj.tree()?;
-        let parent_tree = parent_obj.tree()?;
 
-        let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit_tree), None, None)?;
+        // Merge commits (from --rebase-merges) are diffed against every parent, otherwise
+        // files brought in from the second parent's side would be left out of the note
+        let parent_count = commit_obj.parent_count()?;
 
         // Identify which tracked files actually changed in this commit
         let mut changed_files_in_commit = std::collections::HashSet::new();
         let mut new_content_for_changed_files = HashMap::new();
 
-        for delta in diff.deltas() {
-            let file_path = delta
-                .new_file()
-                .path()
-                .or(delta.old_file().path())
-                .ok_or_else(|| GitAiError::Generic("File path not available".to_string()))?;
-            let file_path_str = file_path.to_string_lossy().to_string();
-
-            // Only process files we're tracking
-            if !pathspecs.contains(&file_path_str) {
-                continue;
-            }
-
-            changed_files_in_commit.insert(file_path_str.clone());
+        for parent_idx in 0..parent_count.max(1) {
+            let parent_tree = commit_obj.parent(parent_idx)?.tree()?;
+            let diff =
+                repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit_tree), None, None)?;
+
+            for delta in diff.deltas() {
+                let file_path = delta
+                    .new_file()
+                    .path()
+                    .or(delta.old_file().path())
+                    .ok_or_else(|| GitAiError::Generic("File path not available".to_string()))?;
+                let file_path_str = file_path.to_string_lossy().to_string();
+
+                // Only process files we're tracking
+                if !pathspecs.contains(&file_path_str)
+                    || !changed_files_in_commit.insert(file_path_str.clone())
+                {
+                    continue;
+                }
 
-            // Get new content for this file from the commit
-            let new_content = if let Ok(entry) = commit_tree.get_path(file_path) {
-                if let Ok(blob) = repo.find_blob(entry.id()) {
-                    let content = blob.content()?;
-                    String::from_utf8_lossy(&content).to_string()
+                // Get new content for this file from the commit
+                let new_content = if let Ok(entry) = commit_tree.get_path(file_path) {
+                    if let Ok(blob) = repo.find_blob(entry.id()) {
+                        let content = blob.content()?;
+                        String::from_utf8_lossy(&content).to_string()
+                    } else {
+                        String::new()
+                    }
                 } else {
                     String::new()
-                }
-            } else {
-                String::new()
-            };
+                };
 
-            new_content_for_changed_files.insert(file_path_str, new_content);
+                new_content_for_changed_files.insert(file_path_str, new_content);
+            }
+        }
+
+        if parent_count > 1 && !changed_files_in_commit.is_empty() {
+            current_va = union_merge_parent_attributions(
+                repo,
+                &commit_obj,
+                current_va,
+                &new_content_for_changed_files,
+                merge_base.clone(),
+            )?;
         }
 
         // Only transform attributions for files that actually changed
@@ -537,12 +582,33 @@ pub fn rewrite_authorship_after_rebase_v2(
 
         authorship_log.metadata.base_commit_sha = new_commit.clone();
 
-        // Save authorship log
-        let authorship_json = authorship_log
-            .serialize_to_string()
-            .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+        // The VA carries every session of the rebased range; keep the ones this note attests
+        let attested: HashSet<String> = authorship_log
+            .attestations
+            .iter()
+            .flat_map(|a| a.entries.iter().map(|e| e.hash.clone()))
+            .collect();
+        authorship_log
+            .metadata
+            .prompts
+            .retain(|hash, _| attested.contains(hash));
+
+        if let Some(original) = rewritten_from.get(new_commit)
+            && let Some(author) = author_map.and_then(|map| map.get(original))
+            && let Ok(original_log) = get_reference_as_authorship_log_v3(repo, original)
+        {
+            for hash in original_log.metadata.prompts.into_keys() {
+                session_authors.insert(hash, author.clone());
+            }
+        }
+        for (hash, prompt) in authorship_log.metadata.prompts.iter_mut() {
+            if let Some(author) = session_authors.get(hash) {
+                prompt.human_author = Some(author.clone());
+            }
+        }
 
-        crate::git::refs::notes_add(repo, new_commit, &authorship_json)?;
+        // Save authorship log
+        crate::git::refs::notes_add_authorship_log(repo, new_commit, &mut authorship_log)?;
 
         debug_log(&format!(
             "Saved authorship log for commit {} ({} files)",
@@ -554,6 +620,232 @@ pub fn rewrite_authorship_after_rebase_v2(
     Ok(())
 }
 
+/// Union the attributions each non-first parent of a rebased merge commit carries for
+/// `merge_content`'s files into `current_va`, which only follows the first parent's line.
+/// `current_va` wins where both sides attribute the same lines.
+fn union_merge_parent_attributions(
+    repo: &Repository,
+    merge_commit: &crate::git::repository::Commit,
+    mut current_va: crate::authorship::virtual_attribution::VirtualAttributions,
+    merge_content: &HashMap<String, String>,
+    blame_start_commit: Option<String>,
+) -> Result<crate::authorship::virtual_attribution::VirtualAttributions, GitAiError> {
+    use crate::authorship::virtual_attribution::{
+        VirtualAttributions, merge_attributions_favoring_first,
+    };
+
+    let files: Vec<String> = merge_content.keys().cloned().collect();
+    for parent in merge_commit.parents().skip(1) {
+        let parent_va = smol::block_on(VirtualAttributions::new_for_base_commit(
+            repo.clone(),
+            parent.id().to_string(),
+            &files,
+            blame_start_commit.clone(),
+        ))?;
+        current_va =
+            merge_attributions_favoring_first(current_va, parent_va, merge_content.clone())?;
+    }
+    Ok(current_va)
+}
+
+/// Pair each rewritten commit with the original it came from (new SHA -> original SHA).
+/// Commits are matched by `git patch-id`, so originals the rewrite dropped (empty picks) or
+/// reordered don't shift the pairing. Commits whose patch changed, such as resolved conflicts,
+/// are paired in order when the same number of them is left on each side.
+pub fn pair_rewritten_commits(
+    repo: &Repository,
+    original_commits: &[String],
+    new_commits: &[String],
+) -> Result<HashMap<String, String>, GitAiError> {
+    let original_ids = patch_ids(repo, original_commits)?;
+    let new_ids = patch_ids(repo, new_commits)?;
+
+    let mut pairs = HashMap::new();
+    let mut unmatched_originals: Vec<&String> = original_commits.iter().collect();
+    let mut unmatched_new = Vec::new();
+    for new_commit in new_commits {
+        let position = new_ids.get(new_commit).and_then(|id| {
+            unmatched_originals
+                .iter()
+                .position(|original| original_ids.get(*original) == Some(id))
+        });
+        match position {
+            Some(position) => {
+                let original = unmatched_originals.remove(position);
+                pairs.insert(new_commit.clone(), original.clone());
+            }
+            None => unmatched_new.push(new_commit),
+        }
+    }
+    if unmatched_new.len() == unmatched_originals.len() {
+        for (new_commit, original) in unmatched_new.into_iter().zip(unmatched_originals) {
+            pairs.insert(new_commit.clone(), original.clone());
+        }
+    }
+    Ok(pairs)
+}
+
+/// Stable patch id of each commit (commit SHA -> patch id). Merge commits have no patch and
+/// are left out.
+fn patch_ids(repo: &Repository, commits: &[String]) -> Result<HashMap<String, String>, GitAiError> {
+    if commits.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut args = repo.global_args_for_exec();
+    args.extend(["diff-tree", "-p", "--root", "--stdin"].map(String::from));
+    let diff = exec_git_stdin(&args, format!("{}\n", commits.join("\n")).as_bytes())?;
+
+    let mut args = repo.global_args_for_exec();
+    args.extend(["patch-id", "--stable"].map(String::from));
+    let output = exec_git_stdin(&args, &diff.stdout)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (patch_id, commit) = line.split_once(' ')?;
+            Some((commit.to_string(), patch_id.to_string()))
+        })
+        .collect())
+}
+
+/// Union the authorship every parent of a merge commit carries for the files the merge
+/// changed into `merge_sha`'s authorship log. The parents' attributions are combined with
+/// `merge_attributions_favoring_first`, so the first parent's lines win over later
+/// parents', and lines the merge commit attributes itself (e.g. AI-resolved conflicts) win
+/// over both. Lines that match no parent, like hand-resolved conflict hunks, get no
+/// attestation and so stay human. Only files some commit since the merge base attributes
+/// to AI are blamed, so merging branches without AI edits costs one notes lookup.
+pub fn rewrite_authorship_after_merge(
+    repo: &Repository,
+    merge_sha: &str,
+) -> Result<(), GitAiError> {
+    use crate::authorship::virtual_attribution::VirtualAttributions;
+
+    let merge_commit = repo.find_commit(merge_sha.to_string())?;
+    let parent_count = merge_commit.parent_count()?;
+    if parent_count < 2 {
+        return Ok(());
+    }
+    let parent_shas: Vec<String> = merge_commit
+        .parents()
+        .map(|parent| parent.id().to_string())
+        .collect();
+
+    // Files that differ from at least one parent
+    let merge_tree = merge_commit.tree()?;
+    let mut changed_files: Vec<String> = Vec::new();
+    for parent in merge_commit.parents() {
+        let parent_tree = parent.tree()?;
+        let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&merge_tree), None, None)?;
+        for delta in diff.deltas() {
+            let Some(file_path) = delta.new_file().path() else {
+                continue;
+            };
+            let file_path_str = file_path.to_string_lossy().to_string();
+            if !changed_files.contains(&file_path_str) {
+                changed_files.push(file_path_str);
+            }
+        }
+    }
+
+    // No parent can carry AI lines from before the merge base, so only the commits since it
+    // decide which files are worth blaming
+    let blame_start_commit = merge_base_of(repo, &parent_shas);
+    let side_commits = commits_since(repo, &parent_shas, blame_start_commit.as_deref())?;
+    let files = filter_pathspecs_to_ai_touched_files(repo, &side_commits, &changed_files)?;
+    if files.is_empty() {
+        debug_log("No parent of the merge carries authorship for the files it changed; skipping");
+        return Ok(());
+    }
+
+    let mut merge_content: HashMap<String, String> = HashMap::new();
+    for file_path in &files {
+        let content = match merge_tree.get_path(std::path::Path::new(file_path)) {
+            Ok(entry) => match repo.find_blob(entry.id()) {
+                Ok(blob) => String::from_utf8_lossy(&blob.content()?).to_string(),
+                Err(_) => String::new(),
+            },
+            Err(_) => String::new(),
+        };
+        merge_content.insert(file_path.clone(), content);
+    }
+    let first_parent_sha = parent_shas[0].clone();
+
+    let first_parent_va = smol::block_on(VirtualAttributions::new_for_base_commit(
+        repo.clone(),
+        first_parent_sha,
+        &files,
+        blame_start_commit.clone(),
+    ))?;
+    let parents_va = union_merge_parent_attributions(
+        repo,
+        &merge_commit,
+        first_parent_va,
+        &merge_content,
+        blame_start_commit,
+    )?;
+
+    let mut parents_log = parents_va.to_authorship_log()?;
+    parents_log.attestations.retain(|attestation| {
+        merge_content
+            .get(&attestation.file_path)
+            .is_some_and(|content| !content.is_empty())
+    });
+    // The parents stay reachable and already count these sessions' lines
+    for prompt in parents_log.metadata.prompts.values_mut() {
+        prompt.total_additions = 0;
+        prompt.total_deletions = 0;
+        prompt.accepted_lines = 0;
+        prompt.overriden_lines = 0;
+    }
+
+    let mut authorship_log = get_reference_as_authorship_log_v3(repo, merge_sha)
+        .unwrap_or_else(|_| AuthorshipLog::new());
+    let conflicts = authorship_log.merge(&parents_log);
+    if authorship_log.attestations.is_empty() && authorship_log.metadata.prompts.is_empty() {
+        debug_log("No parent of the merge carries authorship; skipping");
+        return Ok(());
+    }
+    authorship_log.metadata.base_commit_sha = merge_sha.to_string();
+
+    crate::git::refs::notes_add_authorship_log(repo, merge_sha, &mut authorship_log)?;
+
+    debug_log(&format!(
+        "Combined authorship from {} parents into merge {} ({} lines kept the merge's own attribution)",
+        parent_count, merge_sha, conflicts
+    ));
+    Ok(())
+}
+
+/// The best common ancestor of all `commits` (`git merge-base --octopus`), if they have one.
+fn merge_base_of(repo: &Repository, commits: &[String]) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["merge-base", "--octopus"].map(String::from));
+    args.extend(commits.iter().cloned());
+    let output = exec_git(&args).ok()?;
+    let base = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!base.is_empty()).then_some(base)
+}
+
+/// Commits reachable from any of `heads` but not from `base` (all of their history when
+/// there is no base).
+fn commits_since(
+    repo: &Repository,
+    heads: &[String],
+    base: Option<&str>,
+) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.extend(heads.iter().cloned());
+    if let Some(base) = base {
+        args.push(format!("^{}", base));
+    }
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
 /// Rewrite authorship logs after cherry-pick using VirtualAttributions
 ///
 /// This is the new implementation that uses VirtualAttributions to transform authorship
@@ -604,47 +896,36 @@ pub fn rewrite_authorship_after_cherry_pick(
         source_commits.len()
     ));
 
-    // Step 2: Create VirtualAttributions from the LAST source commit
-    // This is the key difference from rebase: cherry-pick applies patches sequentially,
-    // so the last source commit contains all the accumulated changes being cherry-picked
-    let source_head = source_commits.last().unwrap();
-    let repo_clone = repo.clone();
-    let source_head_clone = source_head.clone();
-    let pathspecs_clone = pathspecs.clone();
+    // Step 2: Create the VirtualAttributions each new commit is restored from. Each new
+    // commit is paired with its own source commit by patch id, so a range whose commits
+    // rewrite the same lines keeps each commit's own authorship even when some picks were
+    // dropped as empty. A new commit with no pair (its patch changed and the counts left
+    // over differ) falls back to the LAST source commit: cherry-pick applies patches
+    // sequentially, so it contains all the accumulated changes being cherry-picked.
+    let sources = pair_rewritten_commits(repo, source_commits, new_commits)?;
+    let last_source = source_commits.last().unwrap();
+    let source_for = |new_commit: &String| sources.get(new_commit).unwrap_or(last_source);
+    let load_source_va = |source_commit: &String| {
+        let repo_clone = repo.clone();
+        let source_commit = source_commit.clone();
+        let pathspecs_clone = pathspecs.clone();
+        smol::block_on(async {
+            crate::authorship::virtual_attribution::VirtualAttributions::new_for_base_commit(
+                repo_clone,
+                source_commit,
+                &pathspecs_clone,
+                None,
+            )
+            .await
+        })
+    };
 
-    let mut current_va = smol::block_on(async {
-        crate::authorship::virtual_attribution::VirtualAttributions::new_for_base_commit(
-            repo_clone,
-            source_head_clone,
-            &pathspecs_clone,
-            None,
-        )
-        .await
-    })?;
+    let mut loaded_source = source_for(&new_commits[0]);
+    let mut current_va = load_source_va(loaded_source)?;
 
     // Clone the source VA to use for restoring attributions when content reappears
     // This handles commit splitting where content from source gets re-applied
-    let source_head_state_va = {
-        let mut attrs = HashMap::new();
-        let mut contents = HashMap::new();
-        for file in current_va.files() {
-            if let Some(char_attrs) = current_va.get_char_attributions(&file)
-                && let Some(line_attrs) = current_va.get_line_attributions(&file)
-            {
-                attrs.insert(file.clone(), (char_attrs.clone(), line_attrs.clone()));
-            }
-            if let Some(content) = current_va.get_file_content(&file) {
-                contents.insert(file, content.clone());
-            }
-        }
-        crate::authorship::virtual_attribution::VirtualAttributions::new(
-            current_va.repo().clone(),
-            current_va.base_commit().to_string(),
-            attrs,
-            contents,
-            current_va.timestamp(),
-        )
-    };
+    let mut source_state_va = copy_virtual_attributions(&current_va);
 
     // Step 3: Process each new commit in order (oldest to newest)
     for (idx, new_commit) in new_commits.iter().enumerate() {
@@ -655,6 +936,12 @@ pub fn rewrite_authorship_after_cherry_pick(
             new_commit
         ));
 
+        let source = source_for(new_commit);
+        if source != loaded_source {
+            source_state_va = load_source_va(source)?;
+            loaded_source = source;
+        }
+
         // Get the DIFF for this commit (what actually changed)
         let commit_obj = repo.find_commit(new_commit.clone())?;
         let parent_obj = commit_obj.parent(0)?;
@@ -704,11 +991,11 @@ pub fn rewrite_authorship_after_cherry_pick(
         }
 
         // Transform attributions based on the new content state
-        // Pass source_head state to restore attributions for content that existed before cherry-pick
+        // Pass the source state to restore attributions for content that existed before cherry-pick
         current_va = transform_attributions_to_final_state(
             &current_va,
             new_content_state.clone(),
-            Some(&source_head_state_va),
+            Some(&source_state_va),
         )?;
 
         // Convert to AuthorshipLog, but filter to only files that exist in this commit
@@ -726,11 +1013,7 @@ pub fn rewrite_authorship_after_cherry_pick(
         authorship_log.metadata.base_commit_sha = new_commit.clone();
 
         // Save authorship log
-        let authorship_json = authorship_log
-            .serialize_to_string()
-            .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
-
-        crate::git::refs::notes_add(repo, new_commit, &authorship_json)?;
+        crate::git::refs::notes_add_authorship_log(repo, new_commit, &mut authorship_log)?;
 
         debug_log(&format!(
             "Saved authorship log for cherry-picked commit {} ({} files)",
@@ -742,6 +1025,31 @@ pub fn rewrite_authorship_after_cherry_pick(
     Ok(())
 }
 
+/// Independent copy of `va`'s attributions and file contents
+fn copy_virtual_attributions(
+    va: &crate::authorship::virtual_attribution::VirtualAttributions,
+) -> crate::authorship::virtual_attribution::VirtualAttributions {
+    let mut attrs = HashMap::new();
+    let mut contents = HashMap::new();
+    for file in va.files() {
+        if let Some(char_attrs) = va.get_char_attributions(&file)
+            && let Some(line_attrs) = va.get_line_attributions(&file)
+        {
+            attrs.insert(file.clone(), (char_attrs.clone(), line_attrs.clone()));
+        }
+        if let Some(content) = va.get_file_content(&file) {
+            contents.insert(file, content.clone());
+        }
+    }
+    crate::authorship::virtual_attribution::VirtualAttributions::new(
+        va.repo().clone(),
+        va.base_commit().to_string(),
+        attrs,
+        contents,
+        va.timestamp(),
+    )
+}
+
 /// Get file contents from a commit tree for specified pathspecs
 fn get_committed_files_content(
     repo: &Repository,
@@ -840,10 +1148,7 @@ pub fn rewrite_authorship_after_commit_amend(
     authorship_log.metadata.base_commit_sha = amended_commit.to_string();
 
     // Save authorship log
-    let authorship_json = authorship_log
-        .serialize_to_string()
-        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
-    crate::git::refs::notes_add(repo, amended_commit, &authorship_json)?;
+    crate::git::refs::notes_add_authorship_log(repo, amended_commit, &mut authorship_log)?;
 
     // Save INITIAL file for uncommitted attributions
     if !initial_attributions.files.is_empty() {
@@ -1068,16 +1373,22 @@ pub fn reconstruct_working_log_after_reset(
 
     // Step 7: Write INITIAL file
     let new_working_log = repo.storage.working_log_for_base_commit(target_commit_sha);
-    new_working_log.reset_working_log()?;
+    let lock = new_working_log.lock()?;
+    new_working_log.reset_working_log(&lock)?;
 
     if !initial_attributions.files.is_empty() {
-        new_working_log
-            .write_initial_attributions(initial_attributions.files, initial_attributions.prompts)?;
+        new_working_log.write_initial_attributions_locked(
+            initial_attributions.files,
+            initial_attributions.prompts,
+            &lock,
+        )?;
     }
+    drop(lock);
 
     // Delete old working log
     repo.storage
         .delete_working_log_for_base_commit(old_head_sha)?;
+    repo.storage.write_last_checkpoint_base(target_commit_sha)?;
 
     debug_log(&format!(
         "✓ Wrote INITIAL attributions to working log for {}",
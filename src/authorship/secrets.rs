@@ -464,6 +464,7 @@ pub fn redact_secrets_in_text(text: &str) -> (String, usize) {
 
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::transcript::Message;
+use crate::git::repository::Repository;
 use std::collections::BTreeMap;
 
 /// Redact secrets from all prompt messages using entropy-based detection.
@@ -499,6 +500,67 @@ pub fn strip_prompt_messages(prompts: &mut BTreeMap<String, PromptRecord>) {
     }
 }
 
+/// Git config key naming the agents whose transcripts are dropped before notes are written
+pub const IGNORE_PROMPTS_CONFIG_KEY: &str = "gitai.ignorePrompts";
+
+/// Which prompt records `gitai.ignorePrompts` strips messages from: `none` (the default),
+/// `all`, or a comma- or space-separated list of tool names such as `cursor, copilot`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum IgnorePrompts {
+    #[default]
+    None,
+    All,
+    Tools(Vec<String>),
+}
+
+impl IgnorePrompts {
+    pub fn parse(value: &str) -> Self {
+        let tools: Vec<String> = value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|tool| !tool.is_empty())
+            .map(|tool| tool.to_lowercase())
+            .collect();
+        match tools.as_slice() {
+            [] => IgnorePrompts::None,
+            [only] if only == "none" => IgnorePrompts::None,
+            _ if tools.iter().any(|tool| tool == "all") => IgnorePrompts::All,
+            _ => IgnorePrompts::Tools(tools),
+        }
+    }
+
+    pub fn from_repo(repo: &Repository) -> Self {
+        match repo.config_get_str(IGNORE_PROMPTS_CONFIG_KEY) {
+            Ok(Some(value)) => Self::parse(&value),
+            _ => IgnorePrompts::None,
+        }
+    }
+
+    /// Whether the transcript of a session run by `tool` is dropped (case-insensitive)
+    pub fn matches(&self, tool: &str) -> bool {
+        match self {
+            IgnorePrompts::None => false,
+            IgnorePrompts::All => true,
+            IgnorePrompts::Tools(tools) => tools.iter().any(|t| t.eq_ignore_ascii_case(tool)),
+        }
+    }
+}
+
+/// Clear the messages of every prompt whose tool `ignore` matches, leaving its line counts
+/// alone. Returns how many prompts were stripped.
+pub fn strip_ignored_prompt_messages(
+    prompts: &mut BTreeMap<String, PromptRecord>,
+    ignore: &IgnorePrompts,
+) -> usize {
+    let mut stripped = 0;
+    for record in prompts.values_mut() {
+        if ignore.matches(&record.agent_id.tool) && !record.messages.is_empty() {
+            record.messages.clear();
+            stripped += 1;
+        }
+    }
+    stripped
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_debug_snapshot;
@@ -668,6 +730,29 @@ fn main() {
         assert!(redacted.contains("info"));
     }
 
+    #[test]
+    fn test_ignore_prompts_parse() {
+        assert_eq!(IgnorePrompts::parse(""), IgnorePrompts::None);
+        assert_eq!(IgnorePrompts::parse(" none "), IgnorePrompts::None);
+        assert_eq!(IgnorePrompts::parse("ALL"), IgnorePrompts::All);
+        assert_eq!(IgnorePrompts::parse("cursor, all"), IgnorePrompts::All);
+
+        let tools = IgnorePrompts::parse("Cursor, github-copilot windsurf");
+        assert_eq!(
+            tools,
+            IgnorePrompts::Tools(vec![
+                "cursor".to_string(),
+                "github-copilot".to_string(),
+                "windsurf".to_string(),
+            ])
+        );
+        assert!(tools.matches("cursor"));
+        assert!(tools.matches("GitHub-Copilot"));
+        assert!(!tools.matches("claude"));
+        assert!(!IgnorePrompts::None.matches("cursor"));
+        assert!(IgnorePrompts::All.matches("claude"));
+    }
+
     #[test]
     fn test_redact_secret_in_env_file() {
         let env_content = r#"
@@ -0,0 +1,344 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by `git-ai commit --sign-authorship` to sign notes for this invocation only.
+static SIGNING_FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Sign every authorship note this process writes, regardless of the `sign_authorship` config.
+pub fn force_signing() {
+    SIGNING_FORCED.store(true, Ordering::Relaxed);
+}
+
+/// Whether authorship notes written by this process should be signed.
+pub fn signing_enabled() -> bool {
+    SIGNING_FORCED.load(Ordering::Relaxed) || crate::config::Config::get().sign_authorship()
+}
+
+/// Outcome of checking an authorship log's signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// The log carries no signature
+    Unsigned,
+    /// gpg accepted the signature; holds the signing key's fingerprint
+    Valid { signer: String },
+    /// The signature does not match the log contents or its recorded signer
+    Invalid,
+}
+
+/// Bytes covered by an authorship signature: the log serialized with its signature and
+/// signer cleared.
+pub fn signing_payload(authorship_log: &AuthorshipLog) -> Result<String, GitAiError> {
+    let mut unsigned = authorship_log.clone();
+    unsigned.metadata.signature = None;
+    unsigned.metadata.signer = None;
+    unsigned
+        .serialize_to_string()
+        .map_err(|_| GitAiError::Serialization("authorship log".to_string()))
+}
+
+/// Namespace SSH signatures are made in, so they can't be replayed as commit or tag signatures.
+const SSH_SIGNATURE_NAMESPACE: &str = "git-ai";
+
+const SSH_SIGNATURE_HEADER: &str = "-----BEGIN SSH SIGNATURE-----";
+
+/// Detach-sign the authorship log with the repository's `user.signingkey` and store the armored
+/// signature and signer fingerprint in its metadata. Follows `gpg.format` like `git commit -S`:
+/// `ssh` signs with `ssh-keygen`, anything else with gpg (its default key when no key is set).
+pub fn sign_authorship_log(
+    repo: &Repository,
+    authorship_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    let payload = signing_payload(authorship_log)?;
+
+    let (signature, signer) = if repo.config_get_str("gpg.format")?.as_deref() == Some("ssh") {
+        ssh_sign(repo, &payload)?
+    } else {
+        gpg_sign(repo, &payload)?
+    };
+    authorship_log.metadata.signature = Some(signature);
+    authorship_log.metadata.signer = signer;
+    Ok(())
+}
+
+fn gpg_sign(repo: &Repository, payload: &str) -> Result<(String, Option<String>), GitAiError> {
+    let mut args = vec![
+        "--batch".to_string(),
+        "--status-fd=2".to_string(),
+        "--armor".to_string(),
+        "--detach-sign".to_string(),
+    ];
+    if let Some(key) = repo
+        .config_get_str("user.signingkey")?
+        .filter(|k| !k.is_empty())
+    {
+        args.push("--local-user".to_string());
+        args.push(key);
+    }
+
+    let output = run_gpg(repo, &args, payload.as_bytes())?;
+    if !output.status.success() {
+        return Err(GitAiError::Generic(format!(
+            "gpg failed to sign authorship log: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let signature = String::from_utf8(output.stdout)
+        .map_err(|_| GitAiError::Generic("gpg produced a non-UTF-8 signature".to_string()))?;
+    // "[GNUPG:] SIG_CREATED <type> <pk_algo> <hash_algo> <class> <timestamp> <fingerprint>"
+    let signer = status_field(&String::from_utf8_lossy(&output.stderr), "SIG_CREATED", 5);
+    Ok((signature, signer))
+}
+
+/// Sign with `ssh-keygen -Y sign`. `user.signingkey` is a key file, or `key::<public key>` for a
+/// key held by ssh-agent, as git accepts.
+fn ssh_sign(repo: &Repository, payload: &str) -> Result<(String, Option<String>), GitAiError> {
+    let key = repo
+        .config_get_str("user.signingkey")?
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| {
+            GitAiError::Generic("user.signingkey must be set to sign with gpg.format=ssh".into())
+        })?;
+
+    let literal_key = key.strip_prefix("key::").map(|k| k.to_string());
+    let key_file = match &literal_key {
+        Some(public_key) => TempFile::new("pub", public_key)?,
+        None => TempFile::existing(key.into()),
+    };
+    let key_path = key_file.path.to_string_lossy().to_string();
+
+    let mut args = vec![
+        "-Y".to_string(),
+        "sign".to_string(),
+        "-n".to_string(),
+        SSH_SIGNATURE_NAMESPACE.to_string(),
+        "-f".to_string(),
+        key_path.clone(),
+    ];
+    if literal_key.is_some() {
+        args.push("-U".to_string());
+    }
+
+    let output = run_ssh_keygen(repo, &args, payload.as_bytes())?;
+    if !output.status.success() {
+        return Err(GitAiError::Generic(format!(
+            "ssh-keygen failed to sign authorship log: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let signature = String::from_utf8(output.stdout).map_err(|_| {
+        GitAiError::Generic("ssh-keygen produced a non-UTF-8 signature".to_string())
+    })?;
+
+    // "256 SHA256:<hash> comment (ED25519)"
+    let fingerprint = run_ssh_keygen(repo, &["-l".to_string(), "-f".to_string(), key_path], &[])?;
+    let signer = String::from_utf8_lossy(&fingerprint.stdout)
+        .split_whitespace()
+        .nth(1)
+        .map(|f| f.to_string());
+    Ok((signature, signer))
+}
+
+/// Check the stored signature against the log's current contents and recorded signer.
+pub fn verify_authorship_log_signature(
+    repo: &Repository,
+    authorship_log: &AuthorshipLog,
+) -> Result<SignatureStatus, GitAiError> {
+    let Some(signature) = authorship_log.metadata.signature.as_ref() else {
+        return Ok(SignatureStatus::Unsigned);
+    };
+    let payload = signing_payload(authorship_log)?;
+
+    // Both tools only read one of signature/data from stdin, so the signature goes through a file
+    let sig_file = TempFile::new("asc", signature)?;
+    let valid_signer = if signature.trim_start().starts_with(SSH_SIGNATURE_HEADER) {
+        ssh_verify(repo, &sig_file.path, &payload)?
+    } else {
+        gpg_verify(repo, &sig_file.path, &payload)?
+    };
+
+    match valid_signer {
+        Some(signer)
+            if authorship_log
+                .metadata
+                .signer
+                .as_ref()
+                .is_none_or(|recorded| recorded.eq_ignore_ascii_case(&signer)) =>
+        {
+            Ok(SignatureStatus::Valid { signer })
+        }
+        _ => Ok(SignatureStatus::Invalid),
+    }
+}
+
+/// Fingerprint of the key behind a good gpg signature, None when gpg rejects it.
+fn gpg_verify(
+    repo: &Repository,
+    sig_path: &Path,
+    payload: &str,
+) -> Result<Option<String>, GitAiError> {
+    let args = vec![
+        "--batch".to_string(),
+        "--status-fd=1".to_string(),
+        "--verify".to_string(),
+        sig_path.to_string_lossy().to_string(),
+        "-".to_string(),
+    ];
+    let output = run_gpg(repo, &args, payload.as_bytes())?;
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    let good = status.lines().any(|l| l.starts_with("[GNUPG:] GOODSIG "));
+    // "[GNUPG:] VALIDSIG <fingerprint> ..."
+    let valid_signer = status_field(&status, "VALIDSIG", 0);
+    Ok(valid_signer.filter(|_| output.status.success() && good))
+}
+
+/// Fingerprint of the key behind a good SSH signature, None when `ssh-keygen` rejects it or
+/// the key isn't listed in `gpg.ssh.allowedSignersFile`, which is required as the trust anchor.
+fn ssh_verify(
+    repo: &Repository,
+    sig_path: &Path,
+    payload: &str,
+) -> Result<Option<String>, GitAiError> {
+    let allowed_signers = repo
+        .config_get_str("gpg.ssh.allowedSignersFile")?
+        .filter(|f| !f.is_empty())
+        .ok_or_else(|| {
+            GitAiError::Generic(
+                "gpg.ssh.allowedSignersFile must be set to verify SSH signatures".to_string(),
+            )
+        })?;
+    let sig_path = sig_path.to_string_lossy().to_string();
+
+    let output = run_ssh_keygen(
+        repo,
+        &[
+            "-Y".to_string(),
+            "find-principals".to_string(),
+            "-f".to_string(),
+            allowed_signers.clone(),
+            "-s".to_string(),
+            sig_path.clone(),
+        ],
+        &[],
+    )?;
+    let principals = String::from_utf8_lossy(&output.stdout).to_string();
+    let Some(principal) = principals
+        .lines()
+        .next()
+        .filter(|_| output.status.success())
+    else {
+        return Ok(None);
+    };
+
+    let output = run_ssh_keygen(
+        repo,
+        &[
+            "-Y".to_string(),
+            "verify".to_string(),
+            "-f".to_string(),
+            allowed_signers,
+            "-I".to_string(),
+            principal.to_string(),
+            "-n".to_string(),
+            SSH_SIGNATURE_NAMESPACE.to_string(),
+            "-s".to_string(),
+            sig_path,
+        ],
+        payload.as_bytes(),
+    )?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    // "Good "git-ai" signature for <principal> with ED25519 key SHA256:<hash>"
+    let report = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(report
+        .lines()
+        .find(|l| l.starts_with("Good "))
+        .and_then(|l| l.split_whitespace().last())
+        .map(|f| f.to_string()))
+}
+
+/// Field `index` (after the keyword) of the first `[GNUPG:] <keyword>` status line.
+fn status_field(status: &str, keyword: &str, index: usize) -> Option<String> {
+    status.lines().find_map(|line| {
+        let mut fields = line.strip_prefix("[GNUPG:] ")?.split_whitespace();
+        if fields.next()? != keyword {
+            return None;
+        }
+        fields.nth(index).map(|f| f.to_string())
+    })
+}
+/// Run the configured `gpg.program` (default `gpg`) with `stdin_data` piped in.
+fn run_gpg(repo: &Repository, args: &[String], stdin_data: &[u8]) -> Result<Output, GitAiError> {
+    let program = repo
+        .config_get_str("gpg.program")?
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| "gpg".to_string());
+    run_program(&program, args, stdin_data)
+}
+
+/// Run the configured `gpg.ssh.program` (default `ssh-keygen`) with `stdin_data` piped in.
+fn run_ssh_keygen(
+    repo: &Repository,
+    args: &[String],
+    stdin_data: &[u8],
+) -> Result<Output, GitAiError> {
+    let program = repo
+        .config_get_str("gpg.ssh.program")?
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| "ssh-keygen".to_string());
+    run_program(&program, args, stdin_data)
+}
+
+fn run_program(program: &str, args: &[String], stdin_data: &[u8]) -> Result<Output, GitAiError> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| GitAiError::Generic(format!("Failed to run {}: {}", program, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(stdin_data)?;
+    }
+
+    Ok(child.wait_with_output()?)
+}
+
+/// A file handed to gpg or ssh-keygen, removed on drop when this created it.
+struct TempFile {
+    path: PathBuf,
+    owned: bool,
+}
+
+impl TempFile {
+    fn new(extension: &str, contents: &str) -> Result<Self, GitAiError> {
+        let path =
+            std::env::temp_dir().join(format!("git-ai-sig-{}.{}", uuid::Uuid::new_v4(), extension));
+        std::fs::write(&path, contents)?;
+        Ok(TempFile { path, owned: true })
+    }
+
+    fn existing(path: PathBuf) -> Self {
+        TempFile { path, owned: false }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if self.owned {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
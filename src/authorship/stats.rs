@@ -488,6 +488,7 @@ pub fn stats_from_authorship_log(
             // Create a transcript from the messages
             let transcript = crate::authorship::transcript::AiTranscript {
                 messages: prompt_record.messages.clone(),
+                truncated: prompt_record.transcript_truncated,
             };
             let waiting = calculate_waiting_time(&transcript);
             commit_stats.time_waiting_for_ai += waiting;
@@ -569,6 +570,63 @@ pub fn stats_for_commit_stats(
     ))
 }
 
+/// AI-attributed share of the lines added between two commits.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct DiffAiShare {
+    pub base: String,
+    pub head: String,
+    pub ai_lines: u32,
+    pub added_lines: u32,
+}
+
+impl DiffAiShare {
+    /// Percentage of added lines attributed to AI (0 when nothing was added).
+    pub fn ai_percent(&self) -> f64 {
+        if self.added_lines == 0 {
+            0.0
+        } else {
+            self.ai_lines as f64 * 100.0 / self.added_lines as f64
+        }
+    }
+
+    /// `base,head,ai_lines,added_lines,ai_percent`, with the header row unless `header` is
+    /// false (for appending to an existing file).
+    pub fn to_csv(&self, header: bool) -> String {
+        let mut csv = String::new();
+        if header {
+            csv.push_str("base,head,ai_lines,added_lines,ai_percent\n");
+        }
+        csv.push_str(&format!(
+            "{},{},{},{},{:.1}\n",
+            self.base,
+            self.head,
+            self.ai_lines,
+            self.added_lines,
+            self.ai_percent()
+        ));
+        csv
+    }
+}
+
+/// Count the lines added in `base..head` and how many of them blame to an AI session,
+/// ignoring attribution from commits at or before `base`.
+pub fn diff_ai_share(
+    repo: &Repository,
+    base: &str,
+    head: &str,
+    ignore_patterns: &[String],
+) -> Result<DiffAiShare, GitAiError> {
+    let base = repo.revparse_single(base)?.id();
+    let head = repo.revparse_single(head)?.id();
+    let stats = diff_ai_accepted_stats(repo, &base, &head, Some(&base), ignore_patterns)?;
+    Ok(DiffAiShare {
+        base,
+        head,
+        ai_lines: stats.total_ai_accepted,
+        added_lines: stats.total_added,
+    })
+}
+
 /// Get git diff statistics between commit and its parent
 pub fn get_git_diff_stats(
     repo: &Repository,
@@ -1,6 +1,11 @@
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 
+/// Git config key for the most bytes of messages stored per session transcript.
+pub const MAX_TRANSCRIPT_BYTES_CONFIG_KEY: &str = "gitai.maxTranscriptBytes";
+
 /// Represents a single message in an AI transcript
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -99,6 +104,9 @@ impl Message {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AiTranscript {
     pub messages: Vec<Message>,
+    /// Whether messages were dropped to fit `gitai.maxTranscriptBytes`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 
 impl AiTranscript {
@@ -106,6 +114,7 @@ impl AiTranscript {
     pub fn new() -> Self {
         Self {
             messages: Vec::new(),
+            truncated: false,
         }
     }
 
@@ -131,6 +140,7 @@ impl AiTranscript {
 
         Self {
             messages: filtered_messages,
+            truncated: self.truncated,
         }
     }
 
@@ -153,6 +163,88 @@ impl AiTranscript {
             .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
             .map(|dt| dt.timestamp())
     }
+
+    /// Drop messages until the transcript's messages serialize to at most `max_bytes`,
+    /// keeping the first user message and as many of the most recent messages as fit, and
+    /// marking the transcript truncated. Returns whether any message was dropped.
+    pub fn truncate_to_bytes(&mut self, max_bytes: usize) -> bool {
+        let sizes: Vec<usize> = self
+            .messages
+            .iter()
+            .map(|message| serde_json::to_string(message).map_or(0, |json| json.len()))
+            .collect();
+        if sizes.iter().sum::<usize>() <= max_bytes {
+            return false;
+        }
+
+        let first_user = self
+            .messages
+            .iter()
+            .position(|message| matches!(message, Message::User { .. }));
+        let mut used = first_user.map_or(0, |i| sizes[i]);
+        let mut tail_start = self.messages.len();
+        while tail_start > 0 {
+            let i = tail_start - 1;
+            if Some(i) == first_user || used + sizes[i] > max_bytes {
+                break;
+            }
+            used += sizes[i];
+            tail_start = i;
+        }
+
+        let tail = self.messages.split_off(tail_start);
+        let first = first_user
+            .filter(|&i| i < tail_start)
+            .map(|i| self.messages[i].clone());
+        let omitted = tail_start - usize::from(first.is_some());
+        if omitted == 0 {
+            self.messages.extend(tail);
+            return false;
+        }
+
+        self.messages = first.into_iter().collect();
+        self.messages.extend(tail);
+        self.truncated = true;
+        true
+    }
+}
+
+/// Parse a `gitai.maxTranscriptBytes` value: a byte count, optionally suffixed with `k`,
+/// `m` or `g` like git's integer config values.
+pub fn parse_max_transcript_bytes(value: &str) -> Result<usize, GitAiError> {
+    let trimmed = value.trim();
+    let (digits, multiplier) = match trimmed.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => (&trimmed[..trimmed.len() - 1], 1024),
+        Some('m') => (&trimmed[..trimmed.len() - 1], 1024 * 1024),
+        Some('g') => (&trimmed[..trimmed.len() - 1], 1024 * 1024 * 1024),
+        _ => (trimmed, 1),
+    };
+    digits
+        .parse::<usize>()
+        .ok()
+        .and_then(|bytes| bytes.checked_mul(multiplier))
+        .ok_or_else(|| {
+            GitAiError::Generic(format!(
+                "{} must be a byte count such as 65536 or 64k, got '{}'",
+                MAX_TRANSCRIPT_BYTES_CONFIG_KEY, trimmed
+            ))
+        })
+}
+
+/// `repo`'s transcript budget. Unset, zero or invalid values leave transcripts uncapped.
+pub fn max_transcript_bytes(repo: &Repository) -> Option<usize> {
+    let value = repo
+        .config_get_str(MAX_TRANSCRIPT_BYTES_CONFIG_KEY)
+        .ok()
+        .flatten()?;
+    match parse_max_transcript_bytes(&value) {
+        Ok(0) => None,
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            crate::utils::debug_log(&format!("Ignoring {}", e));
+            None
+        }
+    }
 }
 
 impl Default for AiTranscript {
@@ -160,3 +252,43 @@ impl Default for AiTranscript {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_to_bytes_keeps_first_user_message_and_recent_tail() {
+        let mut transcript = AiTranscript::new();
+        transcript.add_message(Message::user("fix the parser".to_string(), None));
+        for i in 0..20 {
+            transcript.add_message(Message::assistant(
+                format!("step {} {}", i, "x".repeat(200)),
+                None,
+            ));
+        }
+        let untouched = transcript.clone();
+        assert!(!transcript.clone().truncate_to_bytes(1_000_000));
+
+        assert!(transcript.truncate_to_bytes(1024));
+        let bytes: usize = transcript
+            .messages()
+            .iter()
+            .map(|m| serde_json::to_string(m).unwrap().len())
+            .sum();
+        assert!(bytes <= 1024, "{} bytes", bytes);
+        assert_eq!(transcript.messages()[0], untouched.messages()[0]);
+        assert!(transcript.truncated);
+        assert_eq!(transcript.messages().last(), untouched.messages().last());
+        let kept = transcript.messages().len();
+        assert_eq!(
+            transcript.messages()[1..],
+            untouched.messages()[untouched.messages().len() - (kept - 1)..]
+        );
+        assert!(!untouched.truncated);
+
+        assert_eq!(parse_max_transcript_bytes("64k").unwrap(), 65536);
+        assert_eq!(parse_max_transcript_bytes(" 2048 ").unwrap(), 2048);
+        assert!(parse_max_transcript_bytes("lots").is_err());
+    }
+}
@@ -162,10 +162,9 @@ impl VirtualAttributions {
             return Ok((latest_sha.clone(), prompt.clone()));
         }
 
-        Err(GitAiError::Generic(format!(
-            "Prompt not found in history: {}",
-            prompt_id
-        )))
+        Err(GitAiError::MissingPromptRecord {
+            hash: prompt_id.to_string(),
+        })
     }
 
     /// Add a single pathspec to the virtual attributions
@@ -340,12 +339,9 @@ impl VirtualAttributions {
         // Collect attributions from all checkpoints (later checkpoints override earlier ones)
         for checkpoint in &checkpoints {
             // Add prompts from checkpoint
-            if let Some(agent_id) = &checkpoint.agent_id {
-                let author_id =
-                    crate::authorship::authorship_log_serialization::generate_short_hash(
-                        &agent_id.id,
-                        &agent_id.tool,
-                    );
+            if let Some(agent_id) = &checkpoint.agent_id
+                && let Some(author_id) = checkpoint.session_hash()
+            {
                 // For working log checkpoints, use empty string as commit_sha since they're uncommitted
                 // Always overwrite with the latest checkpoint for this agent so refreshed
                 // transcripts/models from post-commit aren't lost.
@@ -362,6 +358,10 @@ impl VirtualAttributions {
                     accepted_lines: 0,
                     overriden_lines: 0,
                     messages_url: None,
+                    transcript_truncated: checkpoint
+                        .transcript
+                        .as_ref()
+                        .is_some_and(|t| t.truncated),
                 };
 
                 prompts
@@ -495,6 +495,17 @@ impl VirtualAttributions {
         }
     }
 
+    /// Line attributions sorted by file path
+    fn line_attributions_by_path(&self) -> Vec<(&String, &Vec<LineAttribution>)> {
+        let mut files: Vec<_> = self
+            .attributions
+            .iter()
+            .map(|(path, (_, line_attrs))| (path, line_attrs))
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(b.0));
+        files
+    }
+
     /// Convert this VirtualAttributions to an AuthorshipLog
     pub fn to_authorship_log(
         &self,
@@ -516,14 +527,14 @@ impl VirtualAttributions {
             })
             .collect();
 
-        // Process each file
-        for (file_path, (_, line_attrs)) in &self.attributions {
+        // Process each file in path order, so the same attributions always serialize the same way
+        for (file_path, line_attrs) in self.line_attributions_by_path() {
             if line_attrs.is_empty() {
                 continue;
             }
 
             // Group line attributions by author
-            let mut author_lines: HashMap<String, Vec<u32>> = HashMap::new();
+            let mut author_lines: BTreeMap<String, Vec<u32>> = BTreeMap::new();
             for line_attr in line_attrs {
                 // Skip human attributions - we only track AI attributions
                 if line_attr.author_id == CheckpointKind::Human.to_str() {
@@ -801,8 +812,8 @@ impl VirtualAttributions {
         // Remove files with no unstaged hunks
         unstaged_hunks.retain(|_, ranges| !ranges.is_empty());
 
-        // Process each file
-        for (file_path, (_, line_attrs)) in &self.attributions {
+        // Process each file in path order, so the same attributions always serialize the same way
+        for (file_path, line_attrs) in self.line_attributions_by_path() {
             if line_attrs.is_empty() {
                 continue;
             }
@@ -819,8 +830,8 @@ impl VirtualAttributions {
             // Split line attributions into committed and uncommitted
             // VirtualAttributions has line numbers in working directory coordinates,
             // so we need to convert to commit coordinates before comparing with committed hunks
-            let mut committed_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
-            let mut uncommitted_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
+            let mut committed_lines_map: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+            let mut uncommitted_lines_map: BTreeMap<String, Vec<u32>> = BTreeMap::new();
 
             // Get the committed hunks for this file (if any) - these are in commit coordinates
             let file_committed_hunks = committed_hunks.get(file_path);
@@ -1017,7 +1028,6 @@ impl VirtualAttributions {
         pathspecs: Option<&HashSet<String>>,
     ) -> Result<crate::authorship::authorship_log_serialization::AuthorshipLog, GitAiError> {
         use crate::authorship::authorship_log_serialization::AuthorshipLog;
-        use std::collections::HashMap as StdHashMap;
 
         let mut authorship_log = AuthorshipLog::new();
         authorship_log.metadata.base_commit_sha = self.base_commit.clone();
@@ -1037,8 +1047,8 @@ impl VirtualAttributions {
         // Get committed hunks only (no need to check working copy)
         let committed_hunks = collect_committed_hunks(repo, parent_sha, commit_sha, pathspecs)?;
 
-        // Process each file
-        for (file_path, (_, line_attrs)) in &self.attributions {
+        // Process each file in path order, so the same attributions always serialize the same way
+        for (file_path, line_attrs) in self.line_attributions_by_path() {
             if line_attrs.is_empty() {
                 continue;
             }
@@ -1050,7 +1060,7 @@ impl VirtualAttributions {
             };
 
             // Map author_id -> line numbers (in commit coordinates)
-            let mut committed_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
+            let mut committed_lines_map: BTreeMap<String, Vec<u32>> = BTreeMap::new();
 
             for line_attr in line_attrs {
                 // Since we're not dealing with unstaged hunks, the line numbers in VirtualAttributions
@@ -1523,6 +1533,7 @@ pub fn restore_stashed_va(
             debug_log(&format!("Failed to write INITIAL attributions: {}", e));
             return;
         }
+        let _ = repository.storage.write_last_checkpoint_base(new_head);
 
         debug_log(&format!(
             "✓ Restored AI attributions to INITIAL for new HEAD {}",
@@ -1809,4 +1820,49 @@ mod tests {
 
         assert!(!virtual_attributions.files().is_empty());
     }
+
+    #[test]
+    fn test_to_authorship_log_serializes_byte_stable() {
+        let repo = TmpRepo::new().unwrap();
+
+        // Each HashMap gets its own random seed, so two builds of the same
+        // attributions iterate files and authors in different orders
+        let build = || {
+            let mut attributions = HashMap::new();
+            for file in ["src/z.rs", "src/a.rs", "lib.rs", "m/n.rs", "b.rs"] {
+                let line_attrs = ["p3", "p1", "p2", "human"]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, author)| LineAttribution {
+                        start_line: i as u32 * 3 + 1,
+                        end_line: i as u32 * 3 + 2,
+                        author_id: author.to_string(),
+                        overrode: None,
+                    })
+                    .collect();
+                attributions.insert(file.to_string(), (Vec::new(), line_attrs));
+            }
+            VirtualAttributions::new(
+                repo.gitai_repo().clone(),
+                "base".to_string(),
+                attributions,
+                HashMap::new(),
+                0,
+            )
+            .to_authorship_log()
+            .unwrap()
+        };
+
+        let first = build();
+        let paths: Vec<&str> = first
+            .attestations
+            .iter()
+            .map(|f| f.file_path.as_str())
+            .collect();
+        assert_eq!(paths, ["b.rs", "lib.rs", "m/n.rs", "src/a.rs", "src/z.rs"]);
+        let serialized = first.serialize_to_string().unwrap();
+        for _ in 0..5 {
+            assert_eq!(build().serialize_to_string().unwrap(), serialized);
+        }
+    }
 }
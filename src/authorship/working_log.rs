@@ -1,5 +1,7 @@
 use crate::authorship::attribution_tracker::{Attribution, LineAttribution};
-use crate::authorship::authorship_log_serialization::GIT_AI_VERSION;
+use crate::authorship::authorship_log_serialization::{
+    GIT_AI_VERSION, generate_short_hash, is_session_hash_of,
+};
 use crate::authorship::transcript::AiTranscript;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -46,6 +48,37 @@ pub struct AgentId {
     pub model: String,
 }
 
+impl AgentId {
+    /// Name to show for this agent: the tool, else the model, else "ai", so malformed
+    /// records with an empty tool never render as a blank author.
+    pub fn tool_label(&self) -> &str {
+        let tool = self.tool.trim();
+        if !tool.is_empty() {
+            return tool;
+        }
+        let model = self.model.trim();
+        if !model.is_empty() && model != "unknown" {
+            return model;
+        }
+        "ai"
+    }
+
+    /// Replace an empty tool with [`AgentId::tool_label`] before it is recorded.
+    pub fn normalize_tool(&mut self) {
+        if self.tool.trim().is_empty() {
+            self.tool = self.tool_label().to_string();
+        }
+    }
+
+    /// Replace an empty model with an explicit "unknown" before it is recorded. Presets fill
+    /// the model from their hook input or the transcript they parsed.
+    pub fn normalize_model(&mut self) {
+        if self.model.trim().is_empty() {
+            self.model = "unknown".to_string();
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckpointKind {
     Human,
@@ -118,6 +151,10 @@ pub struct Checkpoint {
     pub api_version: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_ai_version: Option<String>,
+    /// Short hash the session is recorded under, fixed by its first checkpoint so a later
+    /// `gitai.hashLength` change doesn't split it. Absent in older working logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_hash: Option<String>,
 }
 
 impl Checkpoint {
@@ -144,7 +181,30 @@ impl Checkpoint {
             line_stats: CheckpointLineStats::default(),
             api_version: CHECKPOINT_API_VERSION.to_string(),
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
+            session_hash: None,
+        }
+    }
+
+    /// The session hash this checkpoint's attributions use, or None for human checkpoints.
+    /// Older checkpoints don't store it, so it's taken from their attributions, falling back
+    /// to the configured length.
+    pub fn session_hash(&self) -> Option<String> {
+        let agent_id = self.agent_id.as_ref()?;
+        if let Some(hash) = &self.session_hash {
+            return Some(hash.clone());
         }
+        self.entries
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .line_attributions
+                    .iter()
+                    .map(|a| a.author_id.as_str())
+                    .chain(entry.attributions.iter().map(|a| a.author_id.as_str()))
+            })
+            .find(|author_id| is_session_hash_of(author_id, &agent_id.id, &agent_id.tool))
+            .map(str::to_string)
+            .or_else(|| Some(generate_short_hash(&agent_id.id, &agent_id.tool)))
     }
 }
 
@@ -172,6 +172,7 @@ impl CiContext {
                             &original_commits,
                             &new_commits,
                             "", // human_author not used
+                            None,
                         )?;
                     } else {
                         println!(
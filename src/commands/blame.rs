@@ -1,10 +1,16 @@
-use crate::authorship::authorship_log::PromptRecord;
-use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::authorship_log::{Author, PromptRecord};
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, FileAcceptance};
+use crate::authorship::internal_db::InternalDatabase;
+use crate::authorship::secrets::IgnorePrompts;
+use crate::authorship::transcript::Message;
 use crate::authorship::working_log::CheckpointKind;
 use crate::error::GitAiError;
-use crate::git::refs::get_reference_as_authorship_log_v3;
+use crate::git::refs::{
+    CommitAuthorship, get_commits_with_notes_from_list, get_reference_as_authorship_log_v3,
+};
 use crate::git::repository::Repository;
 use crate::git::repository::{exec_git, exec_git_stdin};
+use crate::utils::debug_log;
 #[cfg(windows)]
 use crate::utils::normalize_to_posix;
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
@@ -12,6 +18,7 @@ use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
 use std::io::{self, IsTerminal, Write};
+use std::path::Path;
 use std::sync::LazyLock;
 
 //🐰🥚 @todo use actual date Git AI was installed in each repo
@@ -55,18 +62,42 @@ pub struct BlameHunk {
     pub is_boundary: bool,
 }
 
+/// When `--color` colors AI lines by agent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitAiBlameOptions {
     // Line range options
     pub line_ranges: Vec<(u32, u32)>,
 
     pub newest_commit: Option<String>,
+    /// Lower bound of the walk: lines last touched at or before this commit are reported as
+    /// boundary lines of it rather than traced further back
     pub oldest_commit: Option<String>,
     pub oldest_date: Option<DateTime<FixedOffset>>,
 
     // Output format options
     pub porcelain: bool,
     pub line_porcelain: bool,
+    // Compact porcelain grouping lines by session, see format_grouped_porcelain
+    pub porcelain_v2: bool,
     pub incremental: bool,
     pub show_name: bool,
     pub show_number: bool,
@@ -134,6 +165,53 @@ pub struct GitAiBlameOptions {
     // JSON output format
     pub json: bool,
 
+    // With --json, one object per line instead of AI line ranges, from --json-per-line
+    pub json_per_line: bool,
+
+    // Print only "<ai_lines> <total_lines>"
+    pub count_only: bool,
+
+    // Footer keying each session shown to its tool and model; None = only on a terminal
+    pub agent_legend: Option<bool>,
+
+    // Session hash -> label shown instead of the tool name, from --map-session
+    pub session_labels: HashMap<String, String>,
+
+    // Footer with the first user message of each session shown, from --show-prompt
+    pub show_prompt: bool,
+
+    // Footer with the share of AI lines in the file that were kept, from --show-acceptance
+    pub show_acceptance: bool,
+
+    // Model name (or prefix) -> ANSI color code, from --model-colors; colors AI lines by model
+    pub model_colors: Option<HashMap<String, String>>,
+
+    // Color AI lines by agent and model in the default format, from --color/--no-color.
+    // --model-colors takes precedence
+    pub color: ColorChoice,
+
+    // Focus on human-authored lines: dim AI lines (drop them when not a terminal), and make
+    // --count-only report "<human_lines> <total_lines> <percent>%"
+    pub human_only: bool,
+
+    // Only resolve prompts from each commit's own note (no grep across other notes)
+    pub no_merge_prompts: bool,
+
+    // Wall-clock budget (ms) for resolving prompts from other commits' notes
+    pub resolve_timeout: Option<u64>,
+
+    // Stop after this many annotated lines and report how many were cut
+    pub limit_lines: Option<u32>,
+
+    // Lowercased author email/name -> team, from --team-map; prints a per-team summary
+    pub team_map: Option<HashMap<String, String>>,
+
+    // Print tombstones where AI-authored lines were deleted from the file's history
+    pub attribute_deleted: bool,
+
+    // Break a squashed commit's attribution for the file down by its original sessions
+    pub squash_view: Option<String>,
+
     // Mark lines from commits without authorship logs as "Unknown"
     pub mark_unknown: bool,
 
@@ -152,6 +230,7 @@ impl Default for GitAiBlameOptions {
             oldest_commit: None,
             oldest_date: None,
             line_porcelain: false,
+            porcelain_v2: false,
             incremental: false,
             show_name: false,
             show_number: false,
@@ -183,12 +262,146 @@ impl Default for GitAiBlameOptions {
             no_output: false,
             ignore_whitespace: false,
             json: false,
+            json_per_line: false,
+            count_only: false,
+            human_only: false,
+            agent_legend: None,
+            session_labels: HashMap::new(),
+            show_prompt: false,
+            show_acceptance: false,
+            model_colors: None,
+            color: ColorChoice::Auto,
+            no_merge_prompts: false,
+            team_map: None,
+            attribute_deleted: false,
+            squash_view: None,
+            resolve_timeout: None,
+            limit_lines: None,
             mark_unknown: false,
             split_hunks_by_ai_author: true,
         }
     }
 }
 
+/// Per-run lookup cache shared by every blame pass over a file, so each commit's
+/// authorship log and each foreign prompt hash is resolved at most once.
+#[derive(Debug, Default)]
+pub struct BlameCache {
+    /// Authorship logs keyed by commit SHA (None when the commit has no note)
+    authorship_logs: HashMap<String, Option<AuthorshipLog>>,
+    /// Prompt records resolved from other commits' notes, keyed by prompt hash
+    foreign_prompts: HashMap<String, Option<PromptRecord>>,
+    /// Commit summaries keyed by commit SHA
+    commit_summaries: HashMap<String, String>,
+    /// When foreign prompt resolution must stop (from --resolve-timeout), set on first use
+    resolve_deadline: Option<std::time::Instant>,
+    /// Whether `foreign_prompts` was seeded from repo storage yet
+    stored_prompts_loaded: bool,
+    /// refs/notes/ai tip `foreign_prompts` was seeded for, when loaded from repo storage
+    notes_tip: Option<String>,
+    /// Number of foreign prompts already on disk
+    persisted_prompts: usize,
+    /// Commits already warned about for attesting sessions no note has a prompt for
+    warned_dangling_commits: std::collections::HashSet<String>,
+}
+
+impl BlameCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Before resolving `file_path`'s lines in `log`: when the log attests a session it has no
+    /// prompt for, seed the foreign prompts from the cache earlier blames left in repo storage,
+    /// so hashes they already resolved aren't grepped for again. The stored cache only applies
+    /// while refs/notes/ai still points at the tip it was written for.
+    fn load_stored_prompts_if_needed(
+        &mut self,
+        repo: &Repository,
+        log: &AuthorshipLog,
+        file_path: &str,
+        options: &GitAiBlameOptions,
+    ) {
+        if !self.stored_prompts_loaded
+            && !options.no_merge_prompts
+            && log
+                .attestations
+                .iter()
+                .filter(|a| a.file_path == file_path)
+                .flat_map(|a| &a.entries)
+                .any(|e| {
+                    !log.metadata.prompts.contains_key(&e.hash)
+                        && !self.foreign_prompts.contains_key(&e.hash)
+                })
+        {
+            self.stored_prompts_loaded = true;
+            self.notes_tip = crate::git::refs::ai_notes_tip(repo);
+            if let Some(tip) = self.notes_tip.as_deref() {
+                let stored = repo.storage.read_blame_prompt_cache(tip);
+                self.persisted_prompts = stored.len();
+                for (hash, prompt) in stored {
+                    self.foreign_prompts.entry(hash).or_insert(prompt);
+                }
+            }
+        }
+    }
+
+    /// Write newly resolved foreign prompts back to repo storage for the next blame. Skipped
+    /// when the stored cache was never needed, nothing new was resolved or the notes ref
+    /// moved during this run.
+    pub fn persist(&mut self, repo: &Repository) {
+        let Some(tip) = self.notes_tip.as_deref() else {
+            return;
+        };
+        if self.foreign_prompts.len() == self.persisted_prompts
+            || crate::git::refs::ai_notes_tip(repo).as_deref() != Some(tip)
+        {
+            return;
+        }
+        match repo
+            .storage
+            .write_blame_prompt_cache(tip, &self.foreign_prompts)
+        {
+            Ok(()) => self.persisted_prompts = self.foreign_prompts.len(),
+            Err(e) => debug_log(&format!("Failed to write blame prompt cache: {}", e)),
+        }
+    }
+
+    /// Start the --resolve-timeout budget if it isn't running yet and return its deadline.
+    fn resolve_deadline(&mut self, options: &GitAiBlameOptions) -> Option<std::time::Instant> {
+        if self.resolve_deadline.is_none()
+            && let Some(ms) = options.resolve_timeout
+        {
+            self.resolve_deadline =
+                Some(std::time::Instant::now() + std::time::Duration::from_millis(ms));
+        }
+        self.resolve_deadline
+    }
+
+    fn authorship_log(&mut self, repo: &Repository, commit_sha: &str) -> Option<AuthorshipLog> {
+        if let Some(cached) = self.authorship_logs.get(commit_sha) {
+            return cached.clone();
+        }
+        let authorship = get_reference_as_authorship_log_v3(repo, commit_sha).ok();
+        self.authorship_logs
+            .insert(commit_sha.to_string(), authorship.clone());
+        authorship
+    }
+
+    fn commit_summary(
+        &mut self,
+        repo: &Repository,
+        commit_sha: &str,
+    ) -> Result<String, GitAiError> {
+        if let Some(summary) = self.commit_summaries.get(commit_sha) {
+            return Ok(summary.clone());
+        }
+        let summary = repo.find_commit(commit_sha.to_string())?.summary()?;
+        self.commit_summaries
+            .insert(commit_sha.to_string(), summary.clone());
+        Ok(summary)
+    }
+}
+
 impl Repository {
     #[allow(clippy::type_complexity)]
     pub fn blame(
@@ -256,6 +469,44 @@ impl Repository {
                 .to_string()
         };
 
+        if let Some(ref commit) = options.squash_view {
+            print!("{}", self.squash_view(commit, &relative_file_path)?);
+            return Ok((HashMap::new(), HashMap::new()));
+        }
+
+        let default_format = !(options.json
+            || options.count_only
+            || options.team_map.is_some()
+            || options.porcelain_v2
+            || options.porcelain
+            || options.line_porcelain
+            || options.incremental);
+        // Internal no_output callers read tool names back, so nothing display-only may switch
+        // them to prompt hashes
+        let text_output = default_format && !options.no_output;
+        let show_legend = text_output
+            && options
+                .agent_legend
+                .unwrap_or_else(|| io::stdout().is_terminal());
+        let color = text_output && options.color.enabled();
+        // --human-only, the agent legend, --show-prompt, --map-session and --model-colors need
+        // prompt hashes to tell sessions apart, but the default format still shows tool names (or
+        // the mapped labels)
+        let track_sessions = options.human_only
+            || show_legend
+            || options.show_prompt
+            || !options.session_labels.is_empty()
+            || options.model_colors.is_some()
+            || color;
+        let restore_tool_names =
+            track_sessions && default_format && !options.use_prompt_hashes_as_names;
+        // Internal callers read any author other than "human" as AI, and the machine-readable
+        // formats report authors verbatim, so only the text output gets the shallow clone label
+        let label_shallow_boundary = default_format
+            && !options.json_per_line
+            && !options.no_output
+            && !options.return_human_authors_as_human;
+
         // For JSON output, default to HEAD to exclude uncommitted changes
         // and use prompt hashes as names so we can correlate with prompt_records
         let options = if options.json {
@@ -265,6 +516,17 @@ impl Repository {
             }
             opts.use_prompt_hashes_as_names = true;
             opts
+        } else if options.count_only
+            || (track_sessions && default_format)
+            || options.team_map.is_some()
+            || options.porcelain_v2
+            || options.porcelain
+            || options.line_porcelain
+        {
+            // Prompt hashes as names let AI lines be told apart from human names
+            let mut opts = options.clone();
+            opts.use_prompt_hashes_as_names = true;
+            opts
         } else {
             options.clone()
         };
@@ -299,10 +561,10 @@ impl Repository {
                     }
                 }
                 Err(_) => {
-                    return Err(GitAiError::Generic(format!(
-                        "File '{}' not found in commit {}",
-                        relative_file_path, commit
-                    )));
+                    return Err(GitAiError::FileContentMissing {
+                        path: relative_file_path.clone(),
+                        commit: commit.to_string(),
+                    });
                 }
             }
         } else {
@@ -330,48 +592,137 @@ impl Repository {
             options.line_ranges.clone()
         };
 
-        // Validate line ranges
-        for (start, end) in &line_ranges {
-            if *start == 0 || *end == 0 || start > end || *end > total_lines {
+        // Validate line ranges, clamping ends past the last line like git does
+        let mut clamped_ranges = Vec::with_capacity(line_ranges.len());
+        for (start, end) in line_ranges {
+            if start == 0 || start > end {
                 return Err(GitAiError::Generic(format!(
                     "Invalid line range: {}:{}. File has {} lines",
                     start, end, total_lines
                 )));
             }
+            if start > total_lines {
+                return Err(GitAiError::Generic(format!(
+                    "Line range starts at line {} but {} has only {} lines",
+                    start, file_path, total_lines
+                )));
+            }
+            clamped_ranges.push((start, end.min(total_lines)));
         }
+        let line_ranges = clamped_ranges;
+
+        // --limit-lines only guards per-line output; summaries still cover every line
+        let (line_ranges, truncated_lines) = match options.limit_lines {
+            Some(limit) if !options.count_only && options.team_map.is_none() => {
+                truncate_line_ranges(&line_ranges, limit)
+            }
+            _ => (line_ranges, 0),
+        };
+
+        // Shared across every pass below so each commit note and prompt hash is resolved once,
+        // with prompt hashes resolved by earlier runs loaded from repo storage when needed
+        let mut cache = BlameCache::new();
 
         // Step 1: Get Git's native blame for all ranges
         let mut all_blame_hunks = Vec::new();
         for (start_line, end_line) in &line_ranges {
-            let hunks = self.blame_hunks(&relative_file_path, *start_line, *end_line, &options)?;
+            let hunks = self.blame_hunks_cached(
+                &relative_file_path,
+                *start_line,
+                *end_line,
+                &options,
+                &mut cache,
+            )?;
             all_blame_hunks.extend(hunks);
         }
 
         // Step 2: Overlay AI authorship information
         let (line_authors, prompt_records, authorship_logs, prompt_commits) =
-            overlay_ai_authorship(self, &all_blame_hunks, &relative_file_path, &options)?;
+            overlay_ai_authorship(
+                self,
+                &all_blame_hunks,
+                &relative_file_path,
+                &options,
+                label_shallow_boundary,
+                &mut cache,
+            )?;
+        cache.persist(self);
 
         if options.no_output {
             return Ok((line_authors, prompt_records));
         }
 
+        // Authors were resolved as prompt hashes; note each AI line's session, then restore the
+        // tool names for display
+        let mut line_authors = line_authors;
+        let mut ai_sessions: HashMap<u32, String> = HashMap::new();
+        if track_sessions {
+            for (line, author) in line_authors.iter_mut() {
+                if let Some(prompt) = prompt_records.get(author) {
+                    ai_sessions.insert(*line, author.clone());
+                    if restore_tool_names {
+                        *author = match options.session_labels.get(author) {
+                            Some(label) => label.clone(),
+                            None => prompt.agent_id.tool_label().to_string(),
+                        };
+                    }
+                }
+            }
+        }
+
         // Output based on format
-        if options.json {
+        if options.count_only {
+            let total_lines: u32 = line_ranges.iter().map(|(start, end)| end - start + 1).sum();
+            if options.human_only {
+                let human_lines = total_lines - ai_sessions.len() as u32;
+                let percent = if total_lines == 0 {
+                    0.0
+                } else {
+                    human_lines as f64 * 100.0 / total_lines as f64
+                };
+                println!("{} {} {:.1}%", human_lines, total_lines, percent);
+            } else {
+                let ai_lines = line_authors
+                    .values()
+                    .filter(|author| prompt_records.contains_key(*author))
+                    .count();
+                println!("{} {}", ai_lines, total_lines);
+            }
+        } else if let Some(ref team_map) = options.team_map {
+            output_team_summary(&all_blame_hunks, &line_authors, &prompt_records, team_map);
+        } else if options.json_per_line {
+            output_json_per_line_format(
+                &line_authors,
+                &prompt_records,
+                &all_blame_hunks,
+                &lines,
+                &line_ranges,
+                truncated_lines > 0,
+            )?;
+        } else if options.json {
             output_json_format(
                 &line_authors,
                 &prompt_records,
                 &authorship_logs,
                 &prompt_commits,
                 &relative_file_path,
+                truncated_lines > 0,
             )?;
+        } else if options.porcelain_v2 {
+            print!(
+                "{}",
+                format_grouped_porcelain(&relative_file_path, &line_authors, &prompt_records)
+            );
         } else if options.porcelain || options.line_porcelain {
             output_porcelain_format(
                 self,
                 &line_authors,
+                &prompt_records,
                 &relative_file_path,
                 &lines,
                 &line_ranges,
                 &options,
+                &mut cache,
             )?;
         } else if options.incremental {
             output_incremental_format(
@@ -383,6 +734,38 @@ impl Repository {
                 &options,
             )?;
         } else {
+            let tombstones = if options.attribute_deleted {
+                collect_deleted_ai_tombstones(
+                    self,
+                    &relative_file_path,
+                    options.newest_commit.as_deref(),
+                )?
+            } else {
+                HashMap::new()
+            };
+            let mut legend = if show_legend {
+                format_agent_legend(&ai_sessions, &prompt_records, options.human_only)
+            } else {
+                String::new()
+            };
+            if options.show_prompt {
+                legend.push_str(&format_prompt_section(
+                    &ai_sessions,
+                    &prompt_records,
+                    &IgnorePrompts::from_repo(self),
+                    options.human_only,
+                ));
+            }
+            if options.show_acceptance {
+                legend.push_str(&format_acceptance(&authorship_logs, &relative_file_path));
+            }
+            let line_colors = match options.model_colors {
+                Some(ref model_colors) => {
+                    model_line_colors(&ai_sessions, &prompt_records, model_colors)
+                }
+                None if color => agent_line_colors(&ai_sessions, &prompt_records),
+                None => HashMap::new(),
+            };
             output_default_format(
                 self,
                 &line_authors,
@@ -390,18 +773,146 @@ impl Repository {
                 &lines,
                 &line_ranges,
                 &options,
+                &tombstones,
+                &ai_sessions,
+                &line_colors,
+                &legend,
             )?;
         }
 
+        if truncated_lines > 0 && !options.json {
+            let footer = format!("\u{2026} (truncated, {} more lines)", truncated_lines);
+            // Keep machine-readable formats parseable
+            if options.porcelain
+                || options.line_porcelain
+                || options.incremental
+                || options.porcelain_v2
+            {
+                eprintln!("{}", footer);
+            } else {
+                println!("{}", footer);
+            }
+        }
+
         Ok((line_authors, prompt_records))
     }
 
+    /// Attribution for `file_path` in `commit` grouped by the sessions that produced it,
+    /// using the per-session checkpoints [`AuthorshipLog::convert_to_checkpoints_for_squash`]
+    /// reconstructs. For a squash commit this shows each agent that fed into it.
+    ///
+    /// ```text
+    /// squash <sha7> <file>: <n> session(s)
+    /// session <hash> <tool> <model>: <count> line(s)
+    ///   lines <ranges>
+    /// ```
+    pub fn squash_view(&self, commit: &str, file_path: &str) -> Result<String, GitAiError> {
+        let commit_sha = self.revparse_single(commit)?.id();
+        let short_sha = &commit_sha[..7.min(commit_sha.len())];
+
+        let Some(mut log) = crate::git::refs::get_authorship(self, &commit_sha) else {
+            return Ok(format!(
+                "squash {} {}: no authorship note\n",
+                short_sha, file_path
+            ));
+        };
+        // Only this file's content is loaded, so only convert its attestation
+        log.attestations.retain(|a| a.file_path == file_path);
+
+        let mut file_contents = HashMap::new();
+        if !log.attestations.is_empty() {
+            let tree = self.find_commit(commit_sha.clone())?.tree()?;
+            let entry = tree
+                .get_path(std::path::Path::new(file_path))
+                .map_err(|_| GitAiError::FileContentMissing {
+                    path: file_path.to_string(),
+                    commit: commit_sha.clone(),
+                })?;
+            let content = self.find_blob(entry.id())?.content().unwrap_or_default();
+            file_contents.insert(
+                file_path.to_string(),
+                String::from_utf8_lossy(&content).to_string(),
+            );
+        }
+        let checkpoints = log
+            .convert_to_checkpoints_for_squash(&file_contents)
+            .map_err(|e| GitAiError::Generic(format!("Failed to reconstruct sessions: {}", e)))?;
+
+        let mut sessions = Vec::new();
+        for checkpoint in &checkpoints {
+            let (Some(agent_id), Some(hash)) = (&checkpoint.agent_id, checkpoint.session_hash())
+            else {
+                continue;
+            };
+            let mut lines: Vec<u32> = checkpoint
+                .entries
+                .iter()
+                .filter(|e| e.file == file_path)
+                .flat_map(|e| e.line_attributions.iter())
+                .filter(|la| la.author_id == hash)
+                .flat_map(|la| la.start_line..=la.end_line)
+                .collect();
+            lines.sort_unstable();
+            lines.dedup();
+            if !lines.is_empty() {
+                sessions.push((hash, agent_id, lines));
+            }
+        }
+        sessions.sort_by_key(|(_, _, lines)| lines[0]);
+
+        let mut output = format!(
+            "squash {} {}: {} session(s)\n",
+            short_sha,
+            file_path,
+            sessions.len()
+        );
+        for (hash, agent_id, lines) in &sessions {
+            let ranges: Vec<String> = deleted_line_ranges(lines)
+                .iter()
+                .map(|(start, end)| {
+                    if start == end {
+                        start.to_string()
+                    } else {
+                        format!("{}-{}", start, end)
+                    }
+                })
+                .collect();
+            output.push_str(&format!(
+                "session {} {} {}: {} line(s)\n  lines {}\n",
+                hash,
+                agent_id.tool_label(),
+                agent_id.model,
+                lines.len(),
+                ranges.join(",")
+            ));
+        }
+        Ok(output)
+    }
+
     pub fn blame_hunks(
         &self,
         file_path: &str,
         start_line: u32,
         end_line: u32,
         options: &GitAiBlameOptions,
+    ) -> Result<Vec<BlameHunk>, GitAiError> {
+        self.blame_hunks_cached(
+            file_path,
+            start_line,
+            end_line,
+            options,
+            &mut BlameCache::new(),
+        )
+    }
+
+    /// Same as `blame_hunks`, but resolves authorship logs and prompts through `cache`
+    pub fn blame_hunks_cached(
+        &self,
+        file_path: &str,
+        start_line: u32,
+        end_line: u32,
+        options: &GitAiBlameOptions,
+        cache: &mut BlameCache,
     ) -> Result<Vec<BlameHunk>, GitAiError> {
         // Build git blame --line-porcelain command
         let mut args = self.global_args_for_exec();
@@ -446,9 +957,9 @@ impl Repository {
                 // Only newest_commit set, use it as the commit to blame at
                 args.push(newest.clone());
             }
-            (Some(_oldest), None) => {
-                // oldest_commit without newest_commit doesn't make sense for blame
-                // Just ignore oldest_commit in this case
+            (Some(oldest), None) => {
+                // Bound the walk from HEAD (or the --contents snapshot on top of it)
+                args.push(format!("{}..HEAD", oldest));
             }
             (None, None) => {
                 // No commit specified, blame at HEAD (default)
@@ -685,7 +1196,7 @@ impl Repository {
         }
 
         // Post-process hunks to populate ai_human_author from authorship logs
-        let hunks = self.populate_ai_human_authors(hunks, file_path, options)?;
+        let hunks = self.populate_ai_human_authors(hunks, file_path, options, cache)?;
 
         Ok(hunks)
     }
@@ -700,40 +1211,33 @@ impl Repository {
         hunks: Vec<BlameHunk>,
         file_path: &str,
         options: &GitAiBlameOptions,
+        cache: &mut BlameCache,
     ) -> Result<Vec<BlameHunk>, GitAiError> {
-        // Cache authorship logs by commit SHA to avoid repeated lookups
-        let mut commit_authorship_cache: HashMap<String, Option<AuthorshipLog>> = HashMap::new();
-        // Cache for foreign prompts to avoid repeated grepping
-        let mut foreign_prompts_cache: HashMap<String, Option<PromptRecord>> = HashMap::new();
-
         let mut result_hunks: Vec<BlameHunk> = Vec::new();
 
         for hunk in hunks {
             // Get or fetch the authorship log for this commit
-            let authorship_log = if let Some(cached) = commit_authorship_cache.get(&hunk.commit_sha)
-            {
-                cached.clone()
-            } else {
-                let authorship = get_reference_as_authorship_log_v3(self, &hunk.commit_sha).ok();
-                commit_authorship_cache.insert(hunk.commit_sha.clone(), authorship.clone());
-                authorship
-            };
+            let authorship_log = cache.authorship_log(self, &hunk.commit_sha);
 
             // If we have an authorship log, look up human_author for each line
             if let Some(ref authorship_log) = authorship_log {
+                cache.load_stored_prompts_if_needed(self, authorship_log, file_path, options);
                 // Collect human_author for each line in this hunk
                 let num_lines = hunk.range.1 - hunk.range.0 + 1;
                 let mut line_authors: Vec<Option<String>> = Vec::with_capacity(num_lines as usize);
 
                 for i in 0..num_lines {
                     let orig_line_num = hunk.orig_range.0 + i;
+                    let deadline = cache.resolve_deadline(options);
 
                     let human_author = if let Some((_author, _prompt_hash, Some(prompt_record))) =
                         authorship_log.get_line_attribution(
                             self,
                             file_path,
                             orig_line_num,
-                            &mut foreign_prompts_cache,
+                            &mut cache.foreign_prompts,
+                            !options.no_merge_prompts,
+                            deadline,
                         ) {
                         prompt_record.human_author.clone()
                     } else {
@@ -795,12 +1299,17 @@ impl Repository {
     }
 }
 
+/// Author shown for lines blamed on a shallow clone's boundary commit
+const SHALLOW_CLONE_AUTHOR: &str = "(attribution unavailable in shallow clone)";
+
 #[allow(clippy::type_complexity)]
 fn overlay_ai_authorship(
     repo: &Repository,
     blame_hunks: &[BlameHunk],
     file_path: &str,
     options: &GitAiBlameOptions,
+    label_shallow_boundary: bool,
+    cache: &mut BlameCache,
 ) -> Result<
     (
         HashMap<u32, String>,
@@ -814,37 +1323,40 @@ fn overlay_ai_authorship(
     let mut prompt_records: HashMap<String, PromptRecord> = HashMap::new();
     // Track which commits contain each prompt hash
     let mut prompt_commits: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
-
-    // Group hunks by commit SHA to avoid repeated lookups
-    let mut commit_authorship_cache: HashMap<String, Option<AuthorshipLog>> = HashMap::new();
-    // Cache for foreign prompts to avoid repeated grepping
-    let mut foreign_prompts_cache: HashMap<String, Option<PromptRecord>> = HashMap::new();
+    // Commits seen by these hunks, used to collect their authorship logs for JSON output
+    let mut seen_commits: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let shallow_commits = if label_shallow_boundary {
+        repo.shallow_commits().unwrap_or_default()
+    } else {
+        Default::default()
+    };
 
     for hunk in blame_hunks {
-        // Check if we've already looked up this commit's authorship
-        let authorship_log = if let Some(cached) = commit_authorship_cache.get(&hunk.commit_sha) {
-            cached.clone()
-        } else {
-            // Try to get authorship log for this commit
-            let authorship = get_reference_as_authorship_log_v3(repo, &hunk.commit_sha).ok();
-            commit_authorship_cache.insert(hunk.commit_sha.clone(), authorship.clone());
-            authorship
-        };
+        seen_commits.insert(hunk.commit_sha.clone());
+        let authorship_log = cache.authorship_log(repo, &hunk.commit_sha);
+        // The boundary commit of a shallow clone is blamed for every line from the missing
+        // history, so only the lines its own note credits to AI can be trusted
+        let shallow_boundary = shallow_commits.contains(&hunk.commit_sha);
+        let mut ai_lines: std::collections::HashSet<u32> = std::collections::HashSet::new();
 
         // If we have AI authorship data, look up the author for lines in this hunk
         if let Some(authorship_log) = authorship_log {
+            cache.load_stored_prompts_if_needed(repo, &authorship_log, file_path, options);
             // Check each line in this hunk for AI authorship using compact schema
             // IMPORTANT: Use the original line numbers from the commit, not the current line numbers
             let num_lines = hunk.range.1 - hunk.range.0 + 1;
             for i in 0..num_lines {
                 let current_line_num = hunk.range.0 + i;
                 let orig_line_num = hunk.orig_range.0 + i;
+                let deadline = cache.resolve_deadline(options);
 
                 if let Some((author, prompt_hash, prompt)) = authorship_log.get_line_attribution(
                     repo,
                     file_path,
                     orig_line_num,
-                    &mut foreign_prompts_cache,
+                    &mut cache.foreign_prompts,
+                    !options.no_merge_prompts,
+                    deadline,
                 ) {
                     // If this line is AI-assisted, display the tool name; otherwise the human username
                     if let Some(prompt_record) = prompt {
@@ -854,11 +1366,14 @@ fn overlay_ai_authorship(
                             .entry(prompt_hash.clone())
                             .or_default()
                             .insert(hunk.commit_sha.clone());
+                        ai_lines.insert(current_line_num);
                         if options.use_prompt_hashes_as_names {
                             line_authors.insert(current_line_num, prompt_hash.clone());
                         } else {
-                            line_authors
-                                .insert(current_line_num, prompt_record.agent_id.tool.clone());
+                            line_authors.insert(
+                                current_line_num,
+                                prompt_record.agent_id.tool_label().to_string(),
+                            );
                         }
                         prompt_records.insert(prompt_hash, prompt_record.clone());
                     } else {
@@ -895,14 +1410,54 @@ fn overlay_ai_authorship(
                 }
             }
         }
+
+        if shallow_boundary {
+            for line_num in hunk.range.0..=hunk.range.1 {
+                if !ai_lines.contains(&line_num) {
+                    line_authors.insert(line_num, SHALLOW_CLONE_AUTHOR.to_string());
+                }
+            }
+        }
     }
 
     // Collect all authorship logs we've seen (for JSON output to find other files)
-    let authorship_logs: Vec<AuthorshipLog> = commit_authorship_cache
-        .into_iter()
-        .filter_map(|(_, log)| log)
+    let authorship_logs: Vec<AuthorshipLog> = seen_commits
+        .iter()
+        .filter_map(|sha| cache.authorship_logs.get(sha).cloned().flatten())
         .collect();
 
+    // Sessions whose prompt no note carries leave their lines looking human-written
+    if !options.no_merge_prompts && !options.no_output {
+        let mut commits: Vec<&String> = seen_commits.iter().collect();
+        commits.sort();
+        for sha in commits {
+            let Some(Some(authorship_log)) = cache.authorship_logs.get(sha) else {
+                continue;
+            };
+            let dangling: Vec<String> = authorship_log
+                .dangling_prompt_hashes()
+                .into_iter()
+                .filter(|hash| matches!(cache.foreign_prompts.get(hash), Some(None)))
+                .collect();
+            if dangling.is_empty() || !cache.warned_dangling_commits.insert(sha.clone()) {
+                continue;
+            }
+            if let [hash] = dangling.as_slice() {
+                eprintln!(
+                    "Warning: authorship note for {} attests session {}, but no note has its prompt; its lines are shown as human (see git-ai verify {})",
+                    sha, hash, sha
+                );
+            } else {
+                eprintln!(
+                    "Warning: authorship note for {} attests sessions {}, but no note has their prompts; their lines are shown as human (see git-ai verify {})",
+                    sha,
+                    dangling.join(", "),
+                    sha
+                );
+            }
+        }
+    }
+
     // Convert HashSet to Vec and sort for deterministic output
     let prompt_commits_vec: HashMap<String, Vec<String>> = prompt_commits
         .into_iter()
@@ -926,6 +1481,66 @@ fn overlay_ai_authorship(
 struct JsonBlameOutput {
     lines: std::collections::BTreeMap<String, String>,
     prompts: HashMap<String, PromptRecordWithOtherFiles>,
+    /// Set when --limit-lines cut the blamed lines short
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+}
+
+/// One line of `--json-per-line` output, tagged by `kind`
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JsonBlameLine {
+    Human {
+        line: u32,
+        content: String,
+        author: Author,
+        commit: String,
+    },
+    Ai {
+        line: u32,
+        content: String,
+        /// The agent, named by its tool as in the text output
+        author: Author,
+        model: String,
+        prompt_hash: String,
+        /// Who was prompting the agent, when the note records it
+        human_author: Option<String>,
+        commit: String,
+    },
+}
+
+/// JSON output structure for blame --json-per-line
+#[derive(Debug, Serialize)]
+struct JsonPerLineBlameOutput {
+    lines: Vec<JsonBlameLine>,
+    /// Sessions behind the AI lines. Overrides are only recorded per session, in each
+    /// record's `overriden_lines`.
+    prompts: std::collections::BTreeMap<String, PromptRecord>,
+    /// Set when --limit-lines cut the blamed lines short
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+}
+
+/// Keep the first `limit` lines of `ranges`, returning the clipped ranges and how many lines
+/// were dropped.
+fn truncate_line_ranges(ranges: &[(u32, u32)], limit: u32) -> (Vec<(u32, u32)>, u32) {
+    let mut kept = Vec::new();
+    let mut remaining = limit;
+    let mut dropped = 0;
+    for (start, end) in ranges {
+        let len = end - start + 1;
+        if remaining >= len {
+            kept.push((*start, *end));
+            remaining -= len;
+        } else {
+            if remaining > 0 {
+                kept.push((*start, start + remaining - 1));
+            }
+            dropped += len - remaining;
+            remaining = 0;
+        }
+    }
+    (kept, dropped)
 }
 
 /// Read model that patches PromptRecord with other_files and commits fields
@@ -975,6 +1590,7 @@ fn output_json_format(
     authorship_logs: &[AuthorshipLog],
     prompt_commits: &HashMap<String, Vec<String>>,
     current_file: &str,
+    truncated: bool,
 ) -> Result<(), GitAiError> {
     // Filter to only AI lines (where author is a prompt_id in prompt_records)
     let mut ai_lines: Vec<(u32, String)> = line_authors
@@ -1047,22 +1663,108 @@ fn output_json_format(
     let output = JsonBlameOutput {
         lines: lines_map,
         prompts: filtered_prompts,
+        truncated,
     };
 
     let json_str = serde_json::to_string_pretty(&output)
-        .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON output: {}", e)))?;
+        .map_err(|e| GitAiError::Serialization(format!("JSON output: {}", e)))?;
+
+    println!("{}", json_str);
+    Ok(())
+}
+
+/// Every blamed line as a `kind: "human"` or `kind: "ai"` object, followed by the prompt
+/// records of the sessions involved. `line_authors` must use prompt hashes as names for AI
+/// lines.
+fn output_json_per_line_format(
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    blame_hunks: &[BlameHunk],
+    lines: &[&str],
+    line_ranges: &[(u32, u32)],
+    truncated: bool,
+) -> Result<(), GitAiError> {
+    let mut output = JsonPerLineBlameOutput {
+        lines: Vec::new(),
+        prompts: std::collections::BTreeMap::new(),
+        truncated,
+    };
+
+    let mut line_to_hunk: HashMap<u32, &BlameHunk> = HashMap::new();
+    for hunk in blame_hunks {
+        for line_num in hunk.range.0..=hunk.range.1 {
+            line_to_hunk.insert(line_num, hunk);
+        }
+    }
+
+    for (start, end) in line_ranges {
+        for line in *start..=*end {
+            let Some(hunk) = line_to_hunk.get(&line) else {
+                continue;
+            };
+            let content = lines
+                .get(line as usize - 1)
+                .map(|l| l.to_string())
+                .unwrap_or_default();
+            let prompt = line_authors
+                .get(&line)
+                .and_then(|hash| Some((hash, prompt_records.get(hash)?)));
+
+            output.lines.push(match prompt {
+                Some((hash, prompt)) => {
+                    output
+                        .prompts
+                        .entry(hash.clone())
+                        .or_insert_with(|| prompt.clone());
+                    JsonBlameLine::Ai {
+                        line,
+                        content,
+                        author: Author {
+                            username: prompt.agent_id.tool_label().to_string(),
+                            email: String::new(),
+                        },
+                        model: prompt.agent_id.model.clone(),
+                        prompt_hash: hash.clone(),
+                        human_author: prompt
+                            .human_author
+                            .clone()
+                            .or_else(|| hunk.ai_human_author.clone()),
+                        commit: hunk.commit_sha.clone(),
+                    }
+                }
+                None => JsonBlameLine::Human {
+                    line,
+                    content,
+                    author: Author {
+                        username: hunk.original_author.clone(),
+                        email: hunk.author_email.clone(),
+                    },
+                    commit: hunk.commit_sha.clone(),
+                },
+            });
+        }
+    }
 
+    let json_str = serde_json::to_string_pretty(&output)
+        .map_err(|e| GitAiError::Serialization(format!("JSON output: {}", e)))?;
     println!("{}", json_str);
     Ok(())
 }
 
+/// Git's porcelain formats, plus `ai-agent`, `ai-model` and `ai-prompt-hash` header lines
+/// (after any commit block, before the content line) for each AI-attributed line. Human
+/// lines are exactly what `git blame --porcelain` prints. `line_authors` must use prompt
+/// hashes as names for AI lines.
+#[allow(clippy::too_many_arguments)]
 fn output_porcelain_format(
     repo: &Repository,
-    _line_authors: &HashMap<u32, String>,
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
     file_path: &str,
     lines: &[&str],
     line_ranges: &[(u32, u32)],
     options: &GitAiBlameOptions,
+    cache: &mut BlameCache,
 ) -> Result<(), GitAiError> {
     // Use options that don't split hunks to match git's native porcelain output
     let mut no_split_options = options.clone();
@@ -1071,7 +1773,8 @@ fn output_porcelain_format(
     // Build a map from line number to BlameHunk for fast lookup
     let mut line_to_hunk: HashMap<u32, BlameHunk> = HashMap::new();
     for (start_line, end_line) in line_ranges {
-        let h = repo.blame_hunks(file_path, *start_line, *end_line, &no_split_options)?;
+        let h =
+            repo.blame_hunks_cached(file_path, *start_line, *end_line, &no_split_options, cache)?;
         for hunk in h {
             for line_num in hunk.range.0..=hunk.range.1 {
                 line_to_hunk.insert(line_num, hunk.clone());
@@ -1079,6 +1782,9 @@ fn output_porcelain_format(
         }
     }
 
+    // Like git, --porcelain emits the commit metadata block only the first time a commit
+    // appears in the stream; --line-porcelain repeats it for every line
+    let mut emitted_commits: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut last_hunk_id = None;
     for (start_line, end_line) in line_ranges {
         for line_num in *start_line..=*end_line {
@@ -1090,89 +1796,59 @@ fn output_porcelain_format(
             };
 
             if let Some(hunk) = line_to_hunk.get(&line_num) {
-                let author_name = &hunk.original_author;
                 let commit_sha = &hunk.commit_sha;
-                let author_email = &hunk.author_email;
-                let author_time = hunk.author_time;
-                let author_tz = &hunk.author_tz;
-                let committer_name = &hunk.committer;
-                let committer_email = &hunk.committer_email;
-                let committer_time = hunk.committer_time;
-                let committer_tz = &hunk.committer_tz;
-                let boundary = hunk.is_boundary;
-                let filename = file_path;
-
-                // Retrieve the commit summary directly from the commit object
-                let commit = repo.find_commit(commit_sha.clone())?;
-                let summary = commit.summary()?;
+                let orig_line_num = hunk.orig_range.0 + (line_num - hunk.range.0);
 
                 let hunk_id = (commit_sha.clone(), hunk.range.0);
-                if options.line_porcelain {
-                    if last_hunk_id.as_ref() != Some(&hunk_id) {
-                        // First line of hunk: 4-field header
-                        println!(
-                            "{} {} {} {}",
-                            commit_sha,
-                            line_num,
-                            line_num,
-                            hunk.range.1 - hunk.range.0 + 1
-                        );
-                        last_hunk_id = Some(hunk_id);
-                    } else {
-                        // Subsequent lines: 3-field header
-                        println!("{} {} {}", commit_sha, line_num, line_num);
-                    }
-                    println!("author {}", author_name);
-                    println!("author-mail <{}>", author_email);
-                    println!("author-time {}", author_time);
-                    println!("author-tz {}", author_tz);
-                    println!("committer {}", committer_name);
-                    println!("committer-mail <{}>", committer_email);
-                    println!("committer-time {}", committer_time);
-                    println!("committer-tz {}", committer_tz);
-                    println!("summary {}", summary);
-                    if boundary {
-                        println!("boundary");
-                    }
-                    println!("filename {}", filename);
-                    println!("\t{}", line_content);
-                } else if options.porcelain {
-                    if last_hunk_id.as_ref() != Some(&hunk_id) {
-                        // Print full block for first line of hunk
-                        println!(
-                            "{} {} {} {}",
-                            commit_sha,
-                            line_num,
-                            line_num,
-                            hunk.range.1 - hunk.range.0 + 1
-                        );
-                        println!("author {}", author_name);
-                        println!("author-mail <{}>", author_email);
-                        println!("author-time {}", author_time);
-                        println!("author-tz {}", author_tz);
-                        println!("committer {}", committer_name);
-                        println!("committer-mail <{}>", committer_email);
-                        println!("committer-time {}", committer_time);
-                        println!("committer-tz {}", committer_tz);
-                        println!("summary {}", summary);
-                        if boundary {
-                            println!("boundary");
-                        }
-                        println!("filename {}", filename);
-                        println!("\t{}", line_content);
-                        last_hunk_id = Some(hunk_id);
-                    } else {
-                        // For subsequent lines, print only the header and content (no metadata block)
-                        println!("{} {} {}", commit_sha, line_num, line_num);
-                        println!("\t{}", line_content);
-                    }
+                if last_hunk_id.as_ref() != Some(&hunk_id) {
+                    // First line of hunk: 4-field header
+                    println!(
+                        "{} {} {} {}",
+                        commit_sha,
+                        orig_line_num,
+                        line_num,
+                        hunk.range.1 - hunk.range.0 + 1
+                    );
+                    last_hunk_id = Some(hunk_id);
+                } else {
+                    // Subsequent lines: 3-field header
+                    println!("{} {} {}", commit_sha, orig_line_num, line_num);
                 }
+
+                if options.line_porcelain || emitted_commits.insert(commit_sha.clone()) {
+                    let summary = cache.commit_summary(repo, commit_sha)?;
+                    print_porcelain_commit_block(hunk, &summary, file_path);
+                }
+                if let Some(hash) = line_authors.get(&line_num)
+                    && let Some(prompt) = prompt_records.get(hash)
+                {
+                    println!("ai-agent {}", prompt.agent_id.tool_label());
+                    println!("ai-model {}", prompt.agent_id.model);
+                    println!("ai-prompt-hash {}", hash);
+                }
+                println!("\t{}", line_content);
             }
         }
     }
     Ok(())
 }
 
+fn print_porcelain_commit_block(hunk: &BlameHunk, summary: &str, file_path: &str) {
+    println!("author {}", hunk.original_author);
+    println!("author-mail <{}>", hunk.author_email);
+    println!("author-time {}", hunk.author_time);
+    println!("author-tz {}", hunk.author_tz);
+    println!("committer {}", hunk.committer);
+    println!("committer-mail <{}>", hunk.committer_email);
+    println!("committer-time {}", hunk.committer_time);
+    println!("committer-tz {}", hunk.committer_tz);
+    println!("summary {}", summary);
+    if hunk.is_boundary {
+        println!("boundary");
+    }
+    println!("filename {}", file_path);
+}
+
 fn output_incremental_format(
     repo: &Repository,
     _line_authors: &HashMap<u32, String>,
@@ -1260,6 +1936,7 @@ fn output_incremental_format(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn output_default_format(
     repo: &Repository,
     line_authors: &HashMap<u32, String>,
@@ -1267,8 +1944,14 @@ fn output_default_format(
     lines: &[&str],
     line_ranges: &[(u32, u32)],
     options: &GitAiBlameOptions,
+    tombstones: &HashMap<u32, Vec<String>>,
+    ai_sessions: &HashMap<u32, String>,
+    line_colors: &HashMap<u32, String>,
+    legend: &str,
 ) -> Result<(), GitAiError> {
     let mut output = String::new();
+    // With --human-only, AI lines are dimmed on a terminal and left out otherwise
+    let dim_ai_lines = io::stdout().is_terminal();
 
     // Use options that don't split hunks for formatting purposes
     let mut no_split_options = options.clone();
@@ -1309,13 +1992,28 @@ fn output_default_format(
     }
 
     for (start_line, end_line) in line_ranges {
-        for line_num in *start_line..=*end_line {
+        // Deletions past the last line are shown once the range reaches the end of the file
+        let tombstone_end = if *end_line as usize == lines.len() {
+            end_line + 1
+        } else {
+            *end_line
+        };
+        for line_num in *start_line..=tombstone_end {
+            for tombstone in tombstones.get(&line_num).into_iter().flatten() {
+                output.push_str(tombstone);
+                output.push('\n');
+            }
+            if line_num > *end_line {
+                break;
+            }
+
             let line_index = (line_num - 1) as usize;
             let line_content = if line_index < lines.len() {
                 lines[line_index]
             } else {
                 ""
             };
+            let line_start = output.len();
 
             if let Some(hunk) = line_to_hunk.get(&line_num) {
                 // Determine hash length - match git blame default (7 chars)
@@ -1431,9 +2129,27 @@ fn output_default_format(
                     width = line_num_width
                 ));
             }
+
+            if options.human_only && ai_sessions.contains_key(&line_num) {
+                if dim_ai_lines {
+                    let line = output.split_off(line_start);
+                    output.push_str(&format!("\x1b[2m{}\x1b[0m\n", line.trim_end_matches('\n')));
+                } else {
+                    output.truncate(line_start);
+                }
+            } else if let Some(color) = line_colors.get(&line_num) {
+                let line = output.split_off(line_start);
+                output.push_str(&format!(
+                    "\x1b[{}m{}\x1b[0m\n",
+                    color,
+                    line.trim_end_matches('\n')
+                ));
+            }
         }
     }
 
+    output.push_str(legend);
+
     // Print stats if requested (at the end, like git blame)
     if options.show_stats {
         // Append git-like stats lines to output string
@@ -1480,7 +2196,633 @@ fn output_default_format(
     Ok(())
 }
 
-fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOptions) -> String {
+/// Key for the tool names in the default format: one row per session shown, in order of
+/// first appearance, with its tool, model and line count. Empty when no AI lines are shown.
+fn format_agent_legend(
+    ai_sessions: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    human_only: bool,
+) -> String {
+    // --human-only drops AI lines when not writing to a terminal
+    if human_only && !io::stdout().is_terminal() {
+        return String::new();
+    }
+
+    let mut lines: Vec<(&u32, &String)> = ai_sessions.iter().collect();
+    lines.sort();
+    let mut sessions: Vec<(&String, usize)> = Vec::new();
+    for (_, hash) in lines {
+        match sessions.iter_mut().find(|(h, _)| *h == hash) {
+            Some((_, count)) => *count += 1,
+            None => sessions.push((hash, 1)),
+        }
+    }
+    if sessions.is_empty() {
+        return String::new();
+    }
+
+    let mut legend = String::from("\nAgents:\n");
+    for (hash, count) in sessions {
+        let Some(prompt) = prompt_records.get(hash) else {
+            continue;
+        };
+        legend.push_str(&format!(
+            "  {}  {} {}  {} line(s)\n",
+            hash,
+            prompt.agent_id.tool_label(),
+            prompt.agent_id.model,
+            count
+        ));
+    }
+    legend
+}
+
+/// Longest first user message `--show-prompt` prints before truncating it
+const SHOW_PROMPT_MAX_CHARS: usize = 80;
+
+/// Footer for `--show-prompt`: one row per session shown, in order of first appearance, with
+/// the first line of its first user message. Notes written with prompt storage other than
+/// `notes` carry no messages, so those come from the local prompt database when it has them.
+/// Sessions without a transcript, or whose tool `gitai.ignorePrompts` matches, show
+/// "(prompt unavailable)".
+fn format_prompt_section(
+    ai_sessions: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    ignore: &IgnorePrompts,
+    human_only: bool,
+) -> String {
+    // --human-only drops AI lines when not writing to a terminal
+    if human_only && !io::stdout().is_terminal() {
+        return String::new();
+    }
+
+    let mut lines: Vec<(&u32, &String)> = ai_sessions.iter().collect();
+    lines.sort();
+    let mut hashes: Vec<&String> = Vec::new();
+    for (_, hash) in lines {
+        if !hashes.contains(&hash) {
+            hashes.push(hash);
+        }
+    }
+    if hashes.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("\nPrompts:\n");
+    for hash in hashes {
+        let Some(prompt) = prompt_records.get(hash) else {
+            continue;
+        };
+        let first_message = if ignore.matches(&prompt.agent_id.tool) {
+            None
+        } else {
+            first_user_message(&prompt.messages).or_else(|| {
+                let db = InternalDatabase::global().ok()?;
+                let record = db.lock().ok()?.get_prompt(hash).ok()??;
+                first_user_message(record.messages.messages())
+            })
+        };
+        let text = match first_message {
+            Some(text) if text.chars().count() > SHOW_PROMPT_MAX_CHARS => format!(
+                "{}\u{2026}",
+                text.chars().take(SHOW_PROMPT_MAX_CHARS).collect::<String>()
+            ),
+            Some(text) => text,
+            None => "(prompt unavailable)".to_string(),
+        };
+        let truncated = if prompt.transcript_truncated {
+            "  (transcript truncated)"
+        } else {
+            ""
+        };
+        section.push_str(&format!("  {}  {}{}\n", hash, text, truncated));
+    }
+    section
+}
+
+/// Footer for `--show-acceptance`: across the notes of the commits shown, how many of the
+/// AI lines written to `file_path` survived rather than being overridden by a human
+fn format_acceptance(authorship_logs: &[AuthorshipLog], file_path: &str) -> String {
+    let mut totals = FileAcceptance::default();
+    for acceptance in authorship_logs
+        .iter()
+        .filter_map(|log| log.file_acceptance(file_path))
+    {
+        totals.accepted += acceptance.accepted;
+        totals.overridden += acceptance.overridden;
+    }
+    match totals.ratio() {
+        Some(ratio) => format!(
+            "\nacceptance: {:.0}% ({} AI lines kept, {} overridden)\n",
+            ratio * 100.0,
+            totals.accepted,
+            totals.overridden
+        ),
+        None => String::new(),
+    }
+}
+
+/// First non-blank line of the first user message in a transcript
+fn first_user_message(messages: &[Message]) -> Option<String> {
+    messages.iter().find_map(|message| match message {
+        Message::User { text, .. } => text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string),
+        _ => None,
+    })
+}
+
+/// Colors for `--model-colors`, ordered so auto-assigned models rotate through distinct hues.
+const MODEL_COLOR_PALETTE: &[(&str, &str)] = &[
+    ("cyan", "36"),
+    ("magenta", "35"),
+    ("yellow", "33"),
+    ("green", "32"),
+    ("blue", "34"),
+    ("red", "31"),
+    ("bright-cyan", "96"),
+    ("bright-magenta", "95"),
+    ("bright-yellow", "93"),
+    ("bright-green", "92"),
+    ("bright-blue", "94"),
+    ("bright-red", "91"),
+    ("white", "37"),
+    ("black", "30"),
+];
+
+/// Parse `--model-colors`: comma-separated `<model>=<color>` pairs, where the color is a
+/// name from [`MODEL_COLOR_PALETTE`] or a 256-color index. Returns model -> ANSI code.
+fn parse_model_colors(spec: &str) -> Result<HashMap<String, String>, GitAiError> {
+    let mut colors = HashMap::new();
+    for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let Some((model, color)) = pair
+            .split_once('=')
+            .map(|(m, c)| (m.trim(), c.trim()))
+            .filter(|(m, c)| !m.is_empty() && !c.is_empty())
+        else {
+            return Err(GitAiError::Generic(format!(
+                "--model-colors expects <model>=<color> pairs, got '{}'",
+                pair
+            )));
+        };
+        let code = match MODEL_COLOR_PALETTE
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(color))
+        {
+            Some((_, code)) => code.to_string(),
+            None => match color.parse::<u8>() {
+                Ok(index) => format!("38;5;{}", index),
+                Err(_) => {
+                    return Err(GitAiError::Generic(format!(
+                        "Unknown color '{}' for model '{}'",
+                        color, model
+                    )));
+                }
+            },
+        };
+        colors.insert(model.to_string(), code);
+    }
+    Ok(colors)
+}
+
+/// ANSI code for each AI line's model. A mapped model name wins, then the longest mapped
+/// prefix (so `claude` covers `claude-sonnet-4`); anything else gets a palette color picked
+/// from its name, skipping colors already mapped so auto-assigned models stand apart.
+fn model_line_colors(
+    ai_sessions: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    model_colors: &HashMap<String, String>,
+) -> HashMap<u32, String> {
+    let mut free: Vec<&str> = MODEL_COLOR_PALETTE
+        .iter()
+        .map(|(_, code)| *code)
+        .filter(|code| !model_colors.values().any(|c| c == code))
+        .collect();
+    if free.is_empty() {
+        free = MODEL_COLOR_PALETTE.iter().map(|(_, code)| *code).collect();
+    }
+
+    let color_for = |model: &str| -> String {
+        if let Some(code) = model_colors.get(model) {
+            return code.clone();
+        }
+        if let Some((_, code)) = model_colors
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+        {
+            return code.clone();
+        }
+        free[(stable_hash(model) % free.len() as u64) as usize].to_string()
+    };
+
+    ai_sessions
+        .iter()
+        .filter_map(|(line, hash)| {
+            let prompt = prompt_records.get(hash)?;
+            Some((*line, color_for(&prompt.agent_id.model)))
+        })
+        .collect()
+}
+
+/// ANSI code for each AI line under `--color`: a palette color picked from the line's agent
+/// and model, found through its prompt hash (or the hash itself when the prompt is unknown),
+/// so every session of the same agent and model shares a color. Human lines keep the
+/// terminal's default color.
+fn agent_line_colors(
+    ai_sessions: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+) -> HashMap<u32, String> {
+    // White and black disappear on one terminal background or the other
+    let palette: Vec<&str> = MODEL_COLOR_PALETTE
+        .iter()
+        .filter(|(name, _)| *name != "white" && *name != "black")
+        .map(|(_, code)| *code)
+        .collect();
+
+    ai_sessions
+        .iter()
+        .map(|(line, hash)| {
+            let key = match prompt_records.get(hash) {
+                Some(prompt) => format!("{}/{}", prompt.agent_id.tool, prompt.agent_id.model),
+                None => hash.clone(),
+            };
+            let code = palette[(stable_hash(&key) % palette.len() as u64) as usize];
+            (*line, code.to_string())
+        })
+        .collect()
+}
+
+/// FNV-1a, which unlike the std hasher keeps color assignments stable across runs
+fn stable_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Compact porcelain that names each author once and lists the lines it owns.
+///
+/// ```text
+/// output = "file " path LF *group
+/// group  = ( "ai " hash " " tool / "human " name ) LF "lines " ranges LF
+/// ranges = range *( "," range )
+/// range  = line [ "-" line ]        ; inclusive, 1-based
+/// ```
+///
+/// Groups appear in order of their first line and every blamed line belongs to exactly
+/// one group, so [`parse_grouped_porcelain`] recovers the per-line attribution.
+/// `line_authors` must use prompt hashes as names for AI lines.
+pub fn format_grouped_porcelain(
+    file_path: &str,
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+) -> String {
+    let mut lines: Vec<(&u32, &String)> = line_authors.iter().collect();
+    lines.sort();
+
+    let mut groups: Vec<(&String, Vec<(u32, u32)>)> = Vec::new();
+    let mut group_index: HashMap<&String, usize> = HashMap::new();
+    for (&line, author) in lines {
+        let index = *group_index.entry(author).or_insert_with(|| {
+            groups.push((author, Vec::new()));
+            groups.len() - 1
+        });
+        let ranges = &mut groups[index].1;
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+
+    let mut output = format!("file {}\n", file_path);
+    for (author, ranges) in groups {
+        match prompt_records.get(author) {
+            Some(record) => {
+                output.push_str(&format!("ai {} {}\n", author, record.agent_id.tool_label()));
+            }
+            None => output.push_str(&format!("human {}\n", author)),
+        }
+        let ranges: Vec<String> = ranges
+            .iter()
+            .map(|(start, end)| {
+                if start == end {
+                    start.to_string()
+                } else {
+                    format!("{}-{}", start, end)
+                }
+            })
+            .collect();
+        output.push_str(&format!("lines {}\n", ranges.join(",")));
+    }
+    output
+}
+
+/// Expand [`format_grouped_porcelain`] output back into line number -> prompt hash (AI) or
+/// author name (human).
+#[allow(dead_code)]
+pub fn parse_grouped_porcelain(output: &str) -> Result<HashMap<u32, String>, GitAiError> {
+    let invalid = |line: &str| GitAiError::Generic(format!("Invalid grouped porcelain: {}", line));
+
+    let mut line_authors = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in output.lines() {
+        if line.starts_with("file ") {
+            continue;
+        } else if let Some(rest) = line.strip_prefix("ai ") {
+            let hash = rest.split(' ').next().filter(|h| !h.is_empty());
+            current = Some(hash.ok_or_else(|| invalid(line))?.to_string());
+        } else if let Some(name) = line.strip_prefix("human ") {
+            current = Some(name.to_string());
+        } else if let Some(ranges) = line.strip_prefix("lines ") {
+            let author = current.take().ok_or_else(|| invalid(line))?;
+            for range in ranges.split(',') {
+                let (start, end) = match range.split_once('-') {
+                    Some((start, end)) => (start, end),
+                    None => (range, range),
+                };
+                let start: u32 = start.parse().map_err(|_| invalid(line))?;
+                let end: u32 = end.parse().map_err(|_| invalid(line))?;
+                for n in start..=end {
+                    line_authors.insert(n, author.clone());
+                }
+            }
+        } else {
+            return Err(invalid(line));
+        }
+    }
+    Ok(line_authors)
+}
+
+/// Find where AI-authored lines were deleted from `file_path` over its history and map each
+/// deletion site onto the blamed content (`target` commit, or the working tree when None).
+/// Returns tombstone lines keyed by the line they should be printed above; a key one past the
+/// last line marks deletions at the end of the file. Sites that were later rewritten are
+/// dropped since there is no longer a meaningful place to show them.
+fn collect_deleted_ai_tombstones(
+    repo: &Repository,
+    file_path: &str,
+    target: Option<&str>,
+) -> Result<HashMap<u32, Vec<String>>, GitAiError> {
+    let mut tombstones: HashMap<u32, Vec<String>> = HashMap::new();
+
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--format=%H".to_string());
+    args.push(target.unwrap_or("HEAD").to_string());
+    args.push("--".to_string());
+    args.push(file_path.to_string());
+    let output = exec_git(&args)?;
+    let commits: Vec<String> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    for commit in &commits {
+        // Root commits cannot delete anything
+        let Ok(parent) = repo.revparse_single(&format!("{}^", commit)) else {
+            continue;
+        };
+        let parent = parent.id();
+
+        let deletions: Vec<crate::commands::diff::DiffHunk> =
+            file_diff_hunks(repo, &parent, Some(commit), file_path)?
+                .into_iter()
+                .filter(|h| !h.deleted_lines.is_empty())
+                .collect();
+        if deletions.is_empty() {
+            continue;
+        }
+
+        let mut deleted_lines: Vec<u32> = deletions
+            .iter()
+            .flat_map(|h| h.deleted_lines.iter().copied())
+            .collect();
+        deleted_lines.sort_unstable();
+
+        let mut parent_options = GitAiBlameOptions {
+            newest_commit: Some(parent.clone()),
+            line_ranges: deleted_line_ranges(&deleted_lines),
+            no_output: true,
+            use_prompt_hashes_as_names: true,
+            ..Default::default()
+        };
+        parent_options.split_hunks_by_ai_author = false;
+        let (parent_authors, parent_prompts) = repo.blame(file_path, &parent_options)?;
+
+        let forward_hunks = file_diff_hunks(repo, commit, target, file_path)?;
+        let short_sha = &commit[..7.min(commit.len())];
+
+        for hunk in &deletions {
+            // Consecutive deleted lines from the same session become a single tombstone
+            let mut sessions: Vec<(String, u32)> = Vec::new();
+            for line in &hunk.deleted_lines {
+                let Some(hash) = parent_authors
+                    .get(line)
+                    .filter(|a| parent_prompts.contains_key(*a))
+                else {
+                    continue;
+                };
+                match sessions.last_mut() {
+                    Some((last, count)) if last == hash => *count += 1,
+                    _ => sessions.push((hash.clone(), 1)),
+                }
+            }
+            if sessions.is_empty() {
+                continue;
+            }
+
+            // A pure deletion's new_start is the line before the removed block
+            let site = if hunk.new_count == 0 {
+                hunk.new_start + 1
+            } else {
+                hunk.new_start
+            };
+            let Some(current_line) = map_line_through_hunks(site, &forward_hunks) else {
+                continue;
+            };
+
+            for (hash, count) in sessions {
+                let tool = parent_prompts[&hash].agent_id.tool_label();
+                tombstones.entry(current_line).or_default().push(format!(
+                    "~{} (deleted {} AI line(s) from {} session {})",
+                    short_sha, count, tool, hash
+                ));
+            }
+        }
+    }
+
+    Ok(tombstones)
+}
+
+/// `git diff -U0` hunks for one file between `from` and `to` (the working tree when None).
+fn file_diff_hunks(
+    repo: &Repository,
+    from: &str,
+    to: Option<&str>,
+    file_path: &str,
+) -> Result<Vec<crate::commands::diff::DiffHunk>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("diff".to_string());
+    args.push("-U0".to_string());
+    args.push("--no-color".to_string());
+    args.push("--no-ext-diff".to_string());
+    args.push(from.to_string());
+    if let Some(to) = to {
+        args.push(to.to_string());
+    }
+    args.push("--".to_string());
+    args.push(file_path.to_string());
+
+    let output = exec_git(&args)?;
+    let diff_text = String::from_utf8(output.stdout)?;
+    crate::commands::diff::parse_diff_hunks(&diff_text)
+}
+
+/// Follow an old-side line number through a diff. Returns None if the line itself changed.
+fn map_line_through_hunks(line: u32, hunks: &[crate::commands::diff::DiffHunk]) -> Option<u32> {
+    let mut offset: i64 = 0;
+    for hunk in hunks {
+        if hunk.old_count == 0 {
+            // Pure insertion after old line `old_start`
+            if line > hunk.old_start {
+                offset += hunk.new_count as i64;
+                continue;
+            }
+            break;
+        }
+        if line < hunk.old_start {
+            break;
+        }
+        if line < hunk.old_start + hunk.old_count {
+            return None;
+        }
+        offset += hunk.new_count as i64 - hunk.old_count as i64;
+    }
+    Some((line as i64 + offset) as u32)
+}
+
+/// Collapse sorted line numbers into inclusive (start, end) ranges for `-L`.
+fn deleted_line_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &line in lines {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+    ranges
+}
+
+/// Files with at least `min_lines` AI-attributed lines across the notes of the commits in
+/// `spec` (a commit or `<start>..<end>` range), with their AI line counts, sorted by path.
+pub fn ai_only_files(
+    repo: &Repository,
+    spec: &str,
+    min_lines: usize,
+) -> Result<Vec<(String, usize)>, GitAiError> {
+    let commits = crate::commands::show::resolve_commits(repo, spec)?;
+    let mut ai_lines: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    for commit in get_commits_with_notes_from_list(repo, &commits)? {
+        let CommitAuthorship::Log { authorship_log, .. } = commit else {
+            continue;
+        };
+        for attestation in &authorship_log.attestations {
+            let lines: std::collections::BTreeSet<u32> = attestation
+                .entries
+                .iter()
+                .flat_map(|e| e.line_ranges.iter().flat_map(|r| r.expand()))
+                .collect();
+            *ai_lines.entry(attestation.file_path.clone()).or_default() += lines.len();
+        }
+    }
+    Ok(ai_lines
+        .into_iter()
+        .filter(|(_, count)| *count > 0 && *count >= min_lines)
+        .collect())
+}
+
+/// Print AI-assisted vs total lines per team. Each line is credited to the human behind it:
+/// the prompt's `human_author` for AI lines, the commit author otherwise. Authors are matched
+/// by email first, then name; anyone missing from the map lands in "(unmapped)".
+fn output_team_summary(
+    hunks: &[BlameHunk],
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    team_map: &HashMap<String, String>,
+) {
+    let mut teams: std::collections::BTreeMap<String, (u32, u32)> =
+        std::collections::BTreeMap::new();
+
+    for hunk in hunks {
+        for line_num in hunk.range.0..=hunk.range.1 {
+            let is_ai = line_authors
+                .get(&line_num)
+                .is_some_and(|author| prompt_records.contains_key(author));
+
+            let (name, email) = match (is_ai, hunk.ai_human_author.as_deref()) {
+                (true, Some(human_author)) => split_name_email(human_author),
+                _ => (hunk.original_author.as_str(), hunk.author_email.as_str()),
+            };
+            let team = team_for(team_map, name, email);
+
+            let counts = teams.entry(team.to_string()).or_default();
+            if is_ai {
+                counts.0 += 1;
+            }
+            counts.1 += 1;
+        }
+    }
+
+    let width = teams.keys().map(|t| t.len()).max().unwrap_or(0).max(4);
+    println!(
+        "{:<width$}  {:>8}  {:>8}  {:>6}",
+        "team", "ai_lines", "total", "ai%"
+    );
+    for (team, (ai, total)) in &teams {
+        let pct = if *total > 0 {
+            *ai as f64 * 100.0 / *total as f64
+        } else {
+            0.0
+        };
+        println!("{:<width$}  {:>8}  {:>8}  {:>5.1}%", team, ai, total, pct);
+    }
+}
+
+/// Team of an author, matched by email first, then name; "(unmapped)" when neither is in
+/// `team_map`.
+pub fn team_for<'a>(team_map: &'a HashMap<String, String>, name: &str, email: &str) -> &'a str {
+    team_map
+        .get(&email.to_lowercase())
+        .or_else(|| team_map.get(&name.to_lowercase()))
+        .map(|t| t.as_str())
+        .unwrap_or("(unmapped)")
+}
+
+/// Split "Name <email>" into its parts; a bare string is treated as a name.
+pub fn split_name_email(author: &str) -> (&str, &str) {
+    match author.split_once('<') {
+        Some((name, rest)) => (name.trim(), rest.trim_end_matches('>').trim()),
+        None => (author.trim(), ""),
+    }
+}
+
+/// Load a JSON object mapping author emails or names to team names. Keys are lowercased so
+/// lookups are case-insensitive.
+pub fn load_team_map(path: &str) -> Result<HashMap<String, String>, GitAiError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| GitAiError::Generic(format!("Failed to read team map '{}': {}", path, e)))?;
+    let map: HashMap<String, String> = serde_json::from_str(&content)
+        .map_err(|e| GitAiError::Generic(format!("Invalid team map '{}': {}", path, e)))?;
+    Ok(map
+        .into_iter()
+        .map(|(author, team)| (author.to_lowercase(), team))
+        .collect())
+}
+
+fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOptions) -> String {
     let dt = DateTime::from_timestamp(author_time, 0)
         .unwrap_or_else(|| DateTime::from_timestamp(0, 0).unwrap());
 
@@ -1513,7 +2855,8 @@ fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOpti
 
 pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions), GitAiError> {
     let mut options = GitAiBlameOptions::default();
-    let mut file_path = None;
+    let mut positionals: Vec<String> = Vec::new();
+    let mut after_separator = false;
     let mut i = 0;
 
     while i < args.len() {
@@ -1534,12 +2877,30 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 }
                 i += 2;
             }
+            // -L<range> with the range attached, as git also accepts
+            arg if arg.starts_with("-L") && arg.len() > 2 => {
+                let range_str = &arg[2..];
+                match parse_line_range(range_str) {
+                    Some(range) => options.line_ranges.push(range),
+                    None => {
+                        return Err(GitAiError::Generic(format!(
+                            "Invalid line range: {}",
+                            range_str
+                        )));
+                    }
+                }
+                i += 1;
+            }
 
             // Output format options
             "--porcelain" => {
                 options.porcelain = true;
                 i += 1;
             }
+            "--porcelain-v2" => {
+                options.porcelain_v2 = true;
+                i += 1;
+            }
             "--line-porcelain" => {
                 options.line_porcelain = true;
                 options.porcelain = true; // Implies --porcelain
@@ -1667,6 +3028,18 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 options.color_by_age = true;
                 i += 1;
             }
+            "--color" | "--color=always" => {
+                options.color = ColorChoice::Always;
+                i += 1;
+            }
+            "--no-color" | "--color=never" => {
+                options.color = ColorChoice::Never;
+                i += 1;
+            }
+            "--color=auto" => {
+                options.color = ColorChoice::Auto;
+                i += 1;
+            }
 
             // Progress options
             "--progress" => {
@@ -1761,22 +3134,145 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 options.json = true;
                 i += 1;
             }
+            "--json-per-line" => {
+                options.json = true;
+                options.json_per_line = true;
+                i += 1;
+            }
 
-            // Mark unknown authorship
-            "--mark-unknown" => {
-                options.mark_unknown = true;
+            "--count-only" => {
+                options.count_only = true;
                 i += 1;
             }
 
-            // File path (non-option argument)
-            arg if !arg.starts_with('-') => {
-                if file_path.is_none() {
-                    file_path = Some(arg.to_string());
+            "--human-only" => {
+                options.human_only = true;
+                i += 1;
+            }
+
+            "--agent-legend" => {
+                options.agent_legend = Some(true);
+                i += 1;
+            }
+
+            "--no-agent-legend" => {
+                options.agent_legend = Some(false);
+                i += 1;
+            }
+
+            "--show-prompt" => {
+                options.show_prompt = true;
+                i += 1;
+            }
+
+            "--show-acceptance" => {
+                options.show_acceptance = true;
+                i += 1;
+            }
+
+            "--model-colors" => {
+                let Some(spec) = args.get(i + 1) else {
+                    return Err(GitAiError::Generic(
+                        "--model-colors requires <model>=<color>[,...]".to_string(),
+                    ));
+                };
+                options.model_colors = Some(parse_model_colors(spec)?);
+                i += 2;
+            }
+
+            "--map-session" => {
+                let mapping = args.get(i + 1).and_then(|m| m.split_once('='));
+                let Some((hash, label)) = mapping.filter(|(h, l)| !h.is_empty() && !l.is_empty())
+                else {
+                    return Err(GitAiError::Generic(
+                        "--map-session requires <hash>=<label>".to_string(),
+                    ));
+                };
+                options
+                    .session_labels
+                    .insert(hash.to_string(), label.to_string());
+                i += 2;
+            }
+
+            "--no-merge-prompts" => {
+                options.no_merge_prompts = true;
+                i += 1;
+            }
+
+            "--resolve-timeout" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --resolve-timeout".to_string(),
+                    ));
+                }
+                if let Ok(ms) = args[i + 1].parse::<u64>() {
+                    options.resolve_timeout = Some(ms);
                 } else {
                     return Err(GitAiError::Generic(
-                        "Multiple file paths specified".to_string(),
+                        "Invalid number for --resolve-timeout".to_string(),
+                    ));
+                }
+                i += 2;
+            }
+
+            "--limit-lines" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --limit-lines".to_string(),
                     ));
                 }
+                match args[i + 1].parse::<u32>() {
+                    Ok(n) if n > 0 => options.limit_lines = Some(n),
+                    _ => {
+                        return Err(GitAiError::Generic(
+                            "Invalid number for --limit-lines".to_string(),
+                        ));
+                    }
+                }
+                i += 2;
+            }
+
+            "--attribute-deleted" => {
+                options.attribute_deleted = true;
+                i += 1;
+            }
+
+            "--squash-view" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --squash-view".to_string(),
+                    ));
+                }
+                options.squash_view = Some(args[i + 1].clone());
+                i += 2;
+            }
+
+            "--team-map" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --team-map".to_string(),
+                    ));
+                }
+                options.team_map = Some(load_team_map(&args[i + 1])?);
+                i += 2;
+            }
+
+            // Mark unknown authorship
+            "--mark-unknown" => {
+                options.mark_unknown = true;
+                i += 1;
+            }
+
+            // Everything after `--` is the file path
+            "--" => {
+                positionals.extend(args[i + 1..].iter().cloned());
+                after_separator = true;
+                break;
+            }
+
+            // File path or revision (non-option argument)
+            arg if !arg.starts_with('-') => {
+                positionals.push(arg.to_string());
                 i += 1;
             }
 
@@ -1787,23 +3283,96 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
         }
     }
 
-    let file_path =
-        file_path.ok_or_else(|| GitAiError::Generic("No file path specified".to_string()))?;
+    let (file_path, rev) = match positionals.as_slice() {
+        [] => return Err(GitAiError::Generic("No file path specified".to_string())),
+        [file] if after_separator => (file.clone(), None),
+        [file] => split_file_at_rev(file),
+        // `<rev> -- <file>`
+        [rev, file] if after_separator => (file.clone(), Some(rev.clone())),
+        // git's `<rev> <file>`, or `<file> <rev>` when the first isn't a revision
+        [first, second] => {
+            if is_commit_rev(first) {
+                (second.clone(), Some(first.clone()))
+            } else {
+                (first.clone(), Some(second.clone()))
+            }
+        }
+        _ => {
+            return Err(GitAiError::Generic(
+                "Multiple file paths specified".to_string(),
+            ));
+        }
+    };
+    if let Some(rev) = rev {
+        options.newest_commit = Some(if rev.is_empty() {
+            "HEAD".to_string()
+        } else {
+            rev
+        });
+    }
 
     Ok((file_path, options))
 }
 
-fn parse_line_range(range_str: &str) -> Option<(u32, u32)> {
-    if let Some(dash_pos) = range_str.find(',') {
-        let start_str = &range_str[..dash_pos];
-        let end_str = &range_str[dash_pos + 1..];
+/// Whether `rev` resolves to a commit in the repository of the current directory.
+fn is_commit_rev(rev: &str) -> bool {
+    let args = vec![
+        "rev-parse".to_string(),
+        "--verify".to_string(),
+        "--quiet".to_string(),
+        format!("{}^{{commit}}", rev),
+    ];
+    exec_git(&args).is_ok()
+}
 
-        if let (Ok(start), Ok(end)) = (start_str.parse::<u32>(), end_str.parse::<u32>()) {
-            return Some((start, end));
-        }
-    } else if let Ok(line) = range_str.parse::<u32>() {
-        return Some((line, line));
+/// Split `<file>@<rev>` into its path and revision. An argument naming an existing path is
+/// left whole, as is a path whose only `@`s start reflog selectors like `@{1}`. When several
+/// `@`s could split it, the first leaving an existing path wins, else the first one.
+fn split_file_at_rev(arg: &str) -> (String, Option<String>) {
+    if Path::new(arg).exists() {
+        return (arg.to_string(), None);
+    }
+    let candidates: Vec<usize> = arg
+        .match_indices('@')
+        .map(|(i, _)| i)
+        .filter(|&i| i > 0 && !arg[i + 1..].starts_with('{'))
+        .collect();
+    let split = candidates
+        .iter()
+        .find(|&&i| Path::new(&arg[..i]).exists())
+        .or(candidates.first());
+    match split {
+        Some(&i) => (arg[..i].to_string(), Some(arg[i + 1..].to_string())),
+        None => (arg.to_string(), None),
     }
+}
+
+/// Parse a `-L` range: `<start>,<end>` (in either order), `<start>,+<count>`,
+/// `<start>,-<count>` (the lines ending at `<start>`), `<start>,` or `,<end>` for an open
+/// side, or `<start>` alone for a single line. An open end is `u32::MAX`, which
+/// `Repository::blame` clamps to the file's length.
+fn parse_line_range(range_str: &str) -> Option<(u32, u32)> {
+    let Some((start_str, end_str)) = range_str.split_once(',') else {
+        let line = range_str.parse::<u32>().ok()?;
+        return (line > 0).then_some((line, line));
+    };
 
-    None
+    let start = if start_str.is_empty() {
+        1
+    } else {
+        start_str.parse::<u32>().ok()?
+    };
+    let (start, end) = if end_str.is_empty() {
+        (start, u32::MAX)
+    } else if let Some(count) = end_str.strip_prefix('+') {
+        let count = count.parse::<u32>().ok()?.max(1);
+        (start, start.saturating_add(count - 1))
+    } else if let Some(count) = end_str.strip_prefix('-') {
+        let count = count.parse::<u32>().ok()?.max(1);
+        (start.saturating_sub(count - 1).max(1), start)
+    } else {
+        let end = end_str.parse::<u32>().ok()?;
+        (start.min(end), start.max(end))
+    };
+    (start > 0).then_some((start, end))
 }
@@ -2,19 +2,26 @@ use crate::authorship::attribution_tracker::{
     Attribution, AttributionTracker, INITIAL_ATTRIBUTION_TS, LineAttribution,
 };
 use crate::authorship::authorship_log::PromptRecord;
-use crate::authorship::authorship_log_serialization::generate_short_hash;
+use crate::authorship::authorship_log_serialization::{
+    MAX_SHORT_HASH_LENGTH, generate_short_hash, generate_short_hash_with_length,
+};
+use crate::authorship::gitai_ignore::GitAiIgnore;
 use crate::authorship::imara_diff_utils::{LineChangeTag, compute_line_changes};
+use crate::authorship::transcript::{MAX_TRANSCRIPT_BYTES_CONFIG_KEY, max_transcript_bytes};
 use crate::authorship::working_log::CheckpointKind;
 use crate::authorship::working_log::{Checkpoint, WorkingLogEntry};
 use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
 use crate::config::Config;
 use crate::error::GitAiError;
-use crate::git::repo_storage::{PersistedWorkingLog, RepoStorage};
-use crate::git::repository::Repository;
+use crate::git::repo_storage::{
+    InitialAttributions, PersistedWorkingLog, RepoStorage, encode_file_version, read_worktree_bytes,
+};
+use crate::git::repository::{Repository, exec_git};
 use crate::git::status::{EntryKind, StatusCode};
 use crate::utils::{debug_log, normalize_to_posix};
 use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -31,19 +38,98 @@ struct FileLineStats {
 
 use crate::authorship::working_log::AgentId;
 
+/// What a checkpoint run recorded; `checkpoint --json` prints it to stdout.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckpointSummary {
+    /// Files that got a new entry in this checkpoint
+    pub entries: usize,
+    /// Files with changes since the last commit
+    pub files: usize,
+    /// Checkpoints in the working log after this run
+    pub working_log_len: usize,
+    pub kind: String,
+    /// The agent the checkpoint was attributed to; None for human checkpoints
+    pub agent: Option<AgentId>,
+    pub file_attributions: Vec<FileAttributionSummary>,
+    /// Files processed at once, when `gitai.checkpointConcurrency` changed it from the default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+}
+
+/// Attribution of one file after a checkpoint: who owns its current lines, and what this
+/// checkpoint changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileAttributionSummary {
+    pub file: String,
+    pub ai_lines: u32,
+    pub human_lines: u32,
+    pub lines_added: u32,
+    pub lines_deleted: u32,
+}
+
+impl CheckpointSummary {
+    fn new(entries_len: usize, files_len: usize, working_log_len: usize) -> Self {
+        CheckpointSummary {
+            entries: entries_len,
+            files: files_len,
+            working_log_len,
+            ..Default::default()
+        }
+    }
+}
+
+/// Git config key for how many files a checkpoint processes at once.
+pub const CHECKPOINT_CONCURRENCY_CONFIG_KEY: &str = "gitai.checkpointConcurrency";
+pub const DEFAULT_CHECKPOINT_CONCURRENCY: usize = 30;
+/// Files are mostly waiting on git and disk, so allow several per core before clamping
+const CHECKPOINT_CONCURRENCY_PER_CORE: usize = 8;
+
+/// Parse a `gitai.checkpointConcurrency` value.
+pub fn parse_checkpoint_concurrency(value: &str) -> Result<usize, GitAiError> {
+    match value.trim().parse::<usize>() {
+        Ok(concurrency) if concurrency >= 1 => Ok(concurrency),
+        _ => Err(GitAiError::Generic(format!(
+            "{} must be a number of at least 1, got '{}'",
+            CHECKPOINT_CONCURRENCY_CONFIG_KEY,
+            value.trim()
+        ))),
+    }
+}
+
+/// `repo`'s `gitai.checkpointConcurrency`, clamped to [`CHECKPOINT_CONCURRENCY_PER_CORE`]
+/// times the available parallelism. Unset or invalid values fall back to
+/// [`DEFAULT_CHECKPOINT_CONCURRENCY`].
+fn checkpoint_concurrency(repo: &Repository) -> usize {
+    let configured = match repo.config_get_str(CHECKPOINT_CONCURRENCY_CONFIG_KEY) {
+        Ok(Some(value)) => match parse_checkpoint_concurrency(&value) {
+            Ok(concurrency) => concurrency,
+            Err(e) => {
+                debug_log(&format!("Ignoring {}", e));
+                return DEFAULT_CHECKPOINT_CONCURRENCY;
+            }
+        },
+        _ => return DEFAULT_CHECKPOINT_CONCURRENCY,
+    };
+    let max = std::thread::available_parallelism()
+        .map(|cores| cores.get() * CHECKPOINT_CONCURRENCY_PER_CORE)
+        .unwrap_or(DEFAULT_CHECKPOINT_CONCURRENCY);
+    configured.min(max)
+}
+
 /// Build EventAttributes with repo metadata.
 /// Reused for both AgentUsage and Checkpoint events.
 fn build_checkpoint_attrs(
     repo: &Repository,
     base_commit: &str,
-    agent_id: Option<&AgentId>,
+    checkpoint: &Checkpoint,
 ) -> crate::metrics::EventAttributes {
     let mut attrs = crate::metrics::EventAttributes::with_version(env!("CARGO_PKG_VERSION"))
         .base_commit_sha(base_commit);
 
     // Add AI-specific attributes
-    if let Some(agent_id) = agent_id {
-        let prompt_id = generate_short_hash(&agent_id.id, &agent_id.tool);
+    if let Some(agent_id) = &checkpoint.agent_id
+        && let Some(prompt_id) = checkpoint.session_hash()
+    {
         attrs = attrs
             .tool(&agent_id.tool)
             .model(&agent_id.model)
@@ -70,6 +156,243 @@ fn build_checkpoint_attrs(
     attrs
 }
 
+/// Describe any merge, rebase, cherry-pick or revert in progress, or unresolved conflicts.
+/// The index is only partially meaningful in those states, so `--verify-clean` refuses to
+/// checkpoint rather than attribute half-applied changes.
+pub fn in_progress_operation(repo: &Repository) -> Result<Option<String>, GitAiError> {
+    let git_dir = repo.path();
+    let markers = [
+        ("MERGE_HEAD", "merge"),
+        ("rebase-merge", "rebase"),
+        ("rebase-apply", "rebase"),
+        ("CHERRY_PICK_HEAD", "cherry-pick"),
+        ("REVERT_HEAD", "revert"),
+    ];
+    if let Some((_, operation)) = markers
+        .iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+    {
+        return Ok(Some(format!("a {} is in progress", operation)));
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("ls-files".to_string());
+    args.push("--unmerged".to_string());
+    let output = crate::git::repository::exec_git(&args)?;
+    if !output.stdout.is_empty() {
+        return Ok(Some("the index has unresolved conflicts".to_string()));
+    }
+
+    Ok(None)
+}
+
+/// Relabel every checkpoint and attribution of session `from_hash` in the current working log
+/// as session `into_hash`, so edits split across two session ids (e.g. after the agent
+/// restarted) commit as one prompt. Both sessions must already have checkpoints. Returns the
+/// number of checkpoints relabeled.
+pub fn merge_session_into(
+    repo: &Repository,
+    from_hash: &str,
+    into_hash: &str,
+) -> Result<usize, GitAiError> {
+    if from_hash == into_hash {
+        return Err(GitAiError::Generic(format!(
+            "Cannot merge session {} into itself",
+            from_hash
+        )));
+    }
+
+    let working_log = current_working_log(repo)?;
+    // Hold the lock across read-modify-write so a concurrent checkpoint isn't lost
+    let lock = working_log.lock()?;
+    let mut checkpoints = working_log.read_all_checkpoints()?;
+
+    let into_agent = session_agent_id(&checkpoints, into_hash)?;
+    session_agent_id(&checkpoints, from_hash)?;
+
+    let mut relabeled = 0;
+    for checkpoint in &mut checkpoints {
+        if checkpoint.session_hash().as_deref() == Some(from_hash) {
+            checkpoint.agent_id = Some(into_agent.clone());
+            checkpoint.session_hash = Some(into_hash.to_string());
+            relabeled += 1;
+        }
+        for entry in &mut checkpoint.entries {
+            for attribution in &mut entry.attributions {
+                if attribution.author_id == from_hash {
+                    attribution.author_id = into_hash.to_string();
+                }
+            }
+            for line_attribution in &mut entry.line_attributions {
+                if line_attribution.author_id == from_hash {
+                    line_attribution.author_id = into_hash.to_string();
+                }
+                if line_attribution.overrode.as_deref() == Some(from_hash) {
+                    line_attribution.overrode = Some(into_hash.to_string());
+                }
+            }
+        }
+    }
+
+    working_log.write_all_checkpoints(&checkpoints, &lock)?;
+    Ok(relabeled)
+}
+
+/// Error unless the current working log has a checkpoint for session `hash`.
+pub fn ensure_session_exists(repo: &Repository, hash: &str) -> Result<(), GitAiError> {
+    let checkpoints = current_working_log(repo)?.read_all_checkpoints()?;
+    session_agent_id(&checkpoints, hash).map(|_| ())
+}
+
+/// The current working log as written by `checkpoint --dump-working-log`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkingLogDump {
+    pub base_commit: String,
+    pub checkpoints: Vec<Checkpoint>,
+    /// Contents of the INITIAL file: attributions carried over from before the base commit
+    pub initial: InitialAttributions,
+    /// True when checkpoint transcripts and prompt messages were left out
+    pub transcripts_redacted: bool,
+}
+
+/// Write the current working log (checkpoints, entries, attributions and INITIAL) as JSON
+/// to `path`. Transcripts are dropped unless `include_transcripts`, so dumps can be attached
+/// to bug reports. Returns the number of checkpoints written.
+pub fn dump_working_log(
+    repo: &Repository,
+    path: &std::path::Path,
+    include_transcripts: bool,
+) -> Result<usize, GitAiError> {
+    let working_log = current_working_log(repo)?;
+    let mut dump = WorkingLogDump {
+        base_commit: working_log.base_commit.clone(),
+        checkpoints: working_log.read_all_checkpoints()?,
+        initial: working_log.read_initial_attributions(),
+        transcripts_redacted: !include_transcripts,
+    };
+    if !include_transcripts {
+        for checkpoint in &mut dump.checkpoints {
+            checkpoint.transcript = None;
+        }
+        for prompt in dump.initial.prompts.values_mut() {
+            prompt.messages.clear();
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&dump)?;
+    std::fs::write(path, json)?;
+    Ok(dump.checkpoints.len())
+}
+
+fn current_working_log(repo: &Repository) -> Result<PersistedWorkingLog, GitAiError> {
+    let base_commit = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target().ok())
+        .unwrap_or_else(|| "initial".to_string());
+    let repo_storage = RepoStorage::for_repo_path(repo.path(), &repo.workdir()?);
+    Ok(repo_storage.working_log_for_base_commit(&base_commit))
+}
+
+fn session_agent_id(checkpoints: &[Checkpoint], hash: &str) -> Result<AgentId, GitAiError> {
+    checkpoints
+        .iter()
+        .find(|c| c.session_hash().as_deref() == Some(hash))
+        .and_then(|c| c.agent_id.clone())
+        .ok_or_else(|| GitAiError::Generic(format!("No checkpoints found for session {}", hash)))
+}
+
+/// The session hash `agent_id`'s checkpoints are recorded under in the current working log.
+pub fn session_hash_of_agent(repo: &Repository, agent_id: &AgentId) -> Result<String, GitAiError> {
+    current_working_log(repo)?
+        .read_all_checkpoints()?
+        .iter()
+        .rev()
+        .find(|c| {
+            c.agent_id
+                .as_ref()
+                .is_some_and(|other| other.id == agent_id.id && other.tool == agent_id.tool)
+        })
+        .and_then(Checkpoint::session_hash)
+        .ok_or_else(|| {
+            GitAiError::Generic(format!(
+                "No checkpoints found for {} session {}",
+                agent_id.tool, agent_id.id
+            ))
+        })
+}
+
+/// The short hash to record `agent_id`'s session under. A session already in the working
+/// log, or carried over in INITIAL, keeps its hash whatever `gitai.hashLength` says now. A
+/// new session gets the configured length, lengthened until no other session shares it, so
+/// two sessions never end up attributed to one prompt.
+fn resolve_session_hash(
+    checkpoints: &[Checkpoint],
+    initial_prompts: &HashMap<String, PromptRecord>,
+    agent_id: &AgentId,
+) -> Result<String, GitAiError> {
+    let same_session = |other: &AgentId| other.id == agent_id.id && other.tool == agent_id.tool;
+    if let Some(hash) = checkpoints
+        .iter()
+        .rev()
+        .find(|c| c.agent_id.as_ref().is_some_and(same_session))
+        .and_then(Checkpoint::session_hash)
+    {
+        return Ok(hash);
+    }
+    if let Some(hash) = initial_prompts
+        .iter()
+        .find(|(_, prompt)| same_session(&prompt.agent_id))
+        .map(|(hash, _)| hash.clone())
+    {
+        return Ok(hash);
+    }
+
+    let taken: HashSet<String> = checkpoints
+        .iter()
+        .filter_map(Checkpoint::session_hash)
+        .chain(initial_prompts.keys().cloned())
+        .collect();
+    let configured = generate_short_hash(&agent_id.id, &agent_id.tool).len();
+    for length in configured..=MAX_SHORT_HASH_LENGTH {
+        let hash = generate_short_hash_with_length(&agent_id.id, &agent_id.tool, length);
+        if !taken.contains(&hash) {
+            if length > configured {
+                debug_log(&format!(
+                    "Session hash of {} session {} collides at {} characters; using {}",
+                    agent_id.tool, agent_id.id, configured, hash
+                ));
+            }
+            return Ok(hash);
+        }
+    }
+    Err(GitAiError::Generic(format!(
+        "Session hash of {} session {} collides with another session in the working log",
+        agent_id.tool, agent_id.id
+    )))
+}
+
+/// The commit checkpoints are recorded against: HEAD, or "initial" in a repo without commits
+pub fn checkpoint_base_commit(repo: &Repository) -> String {
+    match repo.head() {
+        Ok(head) => match head.target() {
+            Ok(oid) => oid,
+            Err(_) => "initial".to_string(),
+        },
+        Err(_) => "initial".to_string(),
+    }
+}
+
+/// Whether HEAD moved since the last checkpoint, so its working log may hold checkpoints
+/// from an earlier visit to this commit. Hooks that carry the working log over to a new
+/// HEAD (checkout, reset, stash pop, commit) record that HEAD too, so migrated checkpoints
+/// are not mistaken for stale ones. False before the first checkpoint.
+pub fn head_moved_since_last_checkpoint(repo: &Repository) -> bool {
+    repo.storage
+        .read_last_checkpoint_base()
+        .is_some_and(|base| base != checkpoint_base_commit(repo))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     repo: &Repository,
@@ -80,18 +403,21 @@ pub fn run(
     quiet: bool,
     agent_run_result: Option<AgentRunResult>,
     is_pre_commit: bool,
-) -> Result<(usize, usize, usize), GitAiError> {
+    ignore_unstaged: bool,
+) -> Result<CheckpointSummary, GitAiError> {
     let checkpoint_start = Instant::now();
     debug_log("[BENCHMARK] Starting checkpoint run");
 
-    // Robustly handle zero-commit repos
-    let base_commit = match repo.head() {
-        Ok(head) => match head.target() {
-            Ok(oid) => oid,
-            Err(_) => "initial".to_string(),
-        },
-        Err(_) => "initial".to_string(),
-    };
+    // Presets can hand us an empty tool or model; never let either reach the working log
+    let agent_run_result = agent_run_result.map(|mut result| {
+        result.agent_id.normalize_tool();
+        if result.checkpoint_kind != CheckpointKind::Human {
+            result.agent_id.normalize_model();
+        }
+        result
+    });
+
+    let base_commit = checkpoint_base_commit(repo);
 
     // Cannot run checkpoint on bare repositories
     if repo.workdir().is_err() {
@@ -109,6 +435,13 @@ pub fn run(
         "[BENCHMARK] Storage initialization took {:?}",
         storage_start.elapsed()
     ));
+    if let Err(e) = repo_storage.write_last_checkpoint_base(&base_commit) {
+        debug_log(&format!("Failed to record checkpoint base commit: {}", e));
+    }
+
+    // Held until the checkpoint is appended, so a concurrent checkpoint can't change the log
+    // between the read the new entries are diffed against and the write
+    let lock = working_log.lock()?;
 
     // Early exit for human only
     if is_pre_commit {
@@ -128,7 +461,7 @@ pub fn run(
             && !Config::get().get_feature_flags().inter_commit_move
         {
             debug_log("No AI edits,in pre-commit checkpoint, skipping");
-            return Ok((0, 0, 0));
+            return Ok(CheckpointSummary::new(0, 0, 0));
         }
     }
 
@@ -140,6 +473,13 @@ pub fn run(
         working_log.set_dirty_files(Some(dirty_files));
     }
 
+    // Checkpoint what's staged: files with unstaged edits are read from the index instead
+    if ignore_unstaged {
+        let mut dirty_files = working_log.dirty_files.clone().unwrap_or_default();
+        dirty_files.extend(index_contents_of_unstaged_files(repo)?);
+        working_log.set_dirty_files(Some(dirty_files));
+    }
+
     // Get the current timestamp in milliseconds since the Unix epoch
     let ts = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -217,7 +557,7 @@ pub fn run(
     ));
 
     let files_start = Instant::now();
-    let files = get_all_tracked_files(
+    let (files, renamed_from) = get_all_tracked_files(
         repo,
         &base_commit,
         &working_log,
@@ -233,7 +573,7 @@ pub fn run(
     let read_checkpoints_start = Instant::now();
     let mut checkpoints = if reset {
         // If reset flag is set, start with an empty working log
-        working_log.reset_working_log()?;
+        working_log.reset_working_log(&lock)?;
         Vec::new()
     } else {
         working_log.read_all_checkpoints()?
@@ -293,12 +633,25 @@ pub fn run(
                 debug_log("");
             }
         }
-        return Ok((0, files.len(), checkpoints.len()));
+        return Ok(CheckpointSummary::new(0, files.len(), checkpoints.len()));
     }
 
+    let session_hash = match agent_run_result.as_ref() {
+        Some(result) if kind != CheckpointKind::Human => Some(resolve_session_hash(
+            &checkpoints,
+            &working_log.read_initial_attributions().prompts,
+            &result.agent_id,
+        )?),
+        _ => None,
+    };
+
     // Save current file states and get content hashes
     let save_states_start = Instant::now();
-    let file_content_hashes = save_current_file_states(&working_log, &files)?;
+    let crlf_paths = repo.crlf_normalized_paths(&files).unwrap_or_else(|e| {
+        debug_log(&format!("Failed to read line ending attributes: {}", e));
+        HashSet::new()
+    });
+    let file_content_hashes = save_current_file_states(&working_log, &files, &crlf_paths)?;
     debug_log(&format!(
         "[BENCHMARK] save_current_file_states for {} files took {:?}",
         files.len(),
@@ -326,14 +679,18 @@ pub fn run(
 
     // Get checkpoint entries using unified function that handles both initial and subsequent checkpoints
     let entries_start = Instant::now();
+    let concurrency = checkpoint_concurrency(repo);
     let (entries, file_stats) = smol::block_on(get_checkpoint_entries(
         kind,
         repo,
         &working_log,
         &files,
         &file_content_hashes,
+        &crlf_paths,
+        &renamed_from,
         &checkpoints,
-        agent_run_result.as_ref(),
+        session_hash.as_deref(),
+        concurrency,
         ts,
     ))?;
     debug_log(&format!(
@@ -359,9 +716,20 @@ pub fn run(
         if kind != CheckpointKind::Human
             && let Some(agent_run) = &agent_run_result
         {
-            checkpoint.transcript = Some(agent_run.transcript.clone().unwrap_or_default());
+            let mut transcript = agent_run.transcript.clone().unwrap_or_default();
+            if let Some(max_bytes) = max_transcript_bytes(repo)
+                && transcript.truncate_to_bytes(max_bytes)
+            {
+                debug_log(&format!(
+                    "Truncated transcript to {} messages per {}",
+                    transcript.messages().len(),
+                    MAX_TRANSCRIPT_BYTES_CONFIG_KEY
+                ));
+            }
+            checkpoint.transcript = Some(transcript);
             checkpoint.agent_id = Some(agent_run.agent_id.clone());
             checkpoint.agent_metadata = agent_run.agent_metadata.clone();
+            checkpoint.session_hash = session_hash.clone();
         }
         debug_log(&format!(
             "[BENCHMARK] Checkpoint creation took {:?}",
@@ -393,7 +761,7 @@ pub fn run(
 
         // Append checkpoint to the working log
         let append_start = Instant::now();
-        working_log.append_checkpoint(&checkpoint)?;
+        working_log.append_checkpoint(&checkpoint, &lock)?;
         debug_log(&format!(
             "[BENCHMARK] Appending checkpoint to working log took {:?}",
             append_start.elapsed()
@@ -401,7 +769,7 @@ pub fn run(
         checkpoints.push(checkpoint.clone());
 
         // Build common attributes once (reused for all events)
-        let attrs = build_checkpoint_attrs(repo, &base_commit, checkpoint.agent_id.as_ref());
+        let attrs = build_checkpoint_attrs(repo, &base_commit, &checkpoint);
 
         // Record agent usage metric for AI checkpoints
         if kind != CheckpointKind::Human && checkpoint.agent_id.is_some() {
@@ -477,22 +845,58 @@ pub fn run(
         }
     }
 
-    // Return the requested values: (entries_len, files_len, working_log_len)
+    let human_id = CheckpointKind::Human.to_str();
+    let file_attributions = entries
+        .iter()
+        .zip(file_stats.iter())
+        .map(|(entry, file_stat)| {
+            let (mut ai_lines, mut human_lines) = (0, 0);
+            for attr in &entry.line_attributions {
+                let count = attr.end_line - attr.start_line + 1;
+                if attr.author_id == human_id {
+                    human_lines += count;
+                } else {
+                    ai_lines += count;
+                }
+            }
+            FileAttributionSummary {
+                file: entry.file.clone(),
+                ai_lines,
+                human_lines,
+                lines_added: file_stat.additions,
+                lines_deleted: file_stat.deletions,
+            }
+        })
+        .collect();
+
     debug_log(&format!(
         "[BENCHMARK] Total checkpoint run took {:?}",
         checkpoint_start.elapsed()
     ));
-    Ok((entries.len(), files.len(), checkpoints.len()))
+    Ok(CheckpointSummary {
+        entries: entries.len(),
+        files: files.len(),
+        working_log_len: checkpoints.len(),
+        kind: kind.to_str(),
+        agent: agent_run_result
+            .filter(|_| kind != CheckpointKind::Human)
+            .map(|r| r.agent_id),
+        file_attributions,
+        concurrency: Some(concurrency).filter(|c| *c != DEFAULT_CHECKPOINT_CONCURRENCY),
+    })
 }
 
-// Gets tracked changes AND
+/// Changed and untracked text files, plus the original path of each one git reports as a
+/// staged rename
 fn get_status_of_files(
     repo: &Repository,
     working_log: &PersistedWorkingLog,
     edited_filepaths: HashSet<String>,
     skip_untracked: bool,
-) -> Result<Vec<String>, GitAiError> {
+    gitai_ignore: &GitAiIgnore,
+) -> Result<(Vec<String>, HashMap<String, String>), GitAiError> {
     let mut files = Vec::new();
+    let mut renamed_from = HashMap::new();
 
     // Use porcelain v2 format to get status
 
@@ -509,51 +913,60 @@ fn get_status_of_files(
         status_start.elapsed()
     ));
 
-    for entry in statuses {
-        // Skip ignored files
-        if entry.kind == EntryKind::Ignored {
-            continue;
-        }
-
-        // Skip unmerged/conflicted files - we'll track them once the conflict is resolved
-        if entry.kind == EntryKind::Unmerged {
-            continue;
-        }
+    let changed: Vec<_> = statuses
+        .into_iter()
+        .filter(|entry| {
+            // Skip ignored files, and unmerged/conflicted files - we'll track them once the
+            // conflict is resolved
+            entry.kind != EntryKind::Ignored && entry.kind != EntryKind::Unmerged
+        })
+        .filter(|entry| {
+            // Include files that have any change (staged or unstaged) or are untracked
+            entry.staged != StatusCode::Unmodified
+                || entry.unstaged != StatusCode::Unmodified
+                || entry.kind == EntryKind::Untracked
+        })
+        // Skip files excluded by .gitai-ignore (lockfiles, build output, ...)
+        .filter(|entry| !gitai_ignore.is_ignored(&entry.path))
+        .collect();
 
-        // Include files that have any change (staged or unstaged) or are untracked
-        let has_change = entry.staged != StatusCode::Unmodified
-            || entry.unstaged != StatusCode::Unmodified
-            || entry.kind == EntryKind::Untracked;
+    let changed_paths: Vec<String> = changed.iter().map(|entry| entry.path.clone()).collect();
+    let text_attributes = text_attributes_or_default(repo, &changed_paths);
 
-        if has_change {
-            // For deleted files, check if they were text files in HEAD
-            let is_deleted =
-                entry.staged == StatusCode::Deleted || entry.unstaged == StatusCode::Deleted;
+    for entry in changed {
+        // For deleted files, check if they were text files in HEAD
+        let is_deleted =
+            entry.staged == StatusCode::Deleted || entry.unstaged == StatusCode::Deleted;
 
-            let is_text = if is_deleted {
-                is_text_file_in_head(repo, &entry.path)
-            } else {
-                is_text_file(working_log, &entry.path)
-            };
+        let is_text = if is_deleted {
+            is_text_file_in_head(repo, &entry.path, &text_attributes)
+        } else {
+            is_text_file(working_log, &entry.path, &text_attributes)
+        };
 
-            if is_text {
-                files.push(entry.path.clone());
+        if is_text {
+            if entry.kind == EntryKind::Rename
+                && let Some(orig_path) = entry.orig_path
+            {
+                renamed_from.insert(entry.path.clone(), orig_path);
             }
+            files.push(entry.path);
         }
     }
 
-    Ok(files)
+    Ok((files, renamed_from))
 }
 
 /// Get all files that should be tracked, including those from previous checkpoints and INITIAL attributions
 ///
+/// Also returns the original path of every tracked file git reports as a staged rename.
 fn get_all_tracked_files(
     repo: &Repository,
     _base_commit: &str,
     working_log: &PersistedWorkingLog,
     edited_filepaths: Option<&Vec<String>>,
     is_pre_commit: bool,
-) -> Result<Vec<String>, GitAiError> {
+) -> Result<(Vec<String>, HashMap<String, String>), GitAiError> {
     let mut files: HashSet<String> = edited_filepaths
         .map(|paths| paths.iter().cloned().collect())
         .unwrap_or_default();
@@ -575,6 +988,9 @@ fn get_all_tracked_files(
         repo.path_is_in_workdir(&path_buf)
     };
 
+    // Files from INITIAL attributions and previous checkpoints, text-checked together below
+    let mut previously_tracked: Vec<String> = Vec::new();
+
     let initial_read_start = Instant::now();
     for file in working_log.read_initial_attributions().files.keys() {
         // Normalize path separators to forward slashes
@@ -587,9 +1003,7 @@ fn get_all_tracked_files(
             ));
             continue;
         }
-        if is_text_file(working_log, &normalized_path) {
-            files.insert(normalized_path);
-        }
+        previously_tracked.push(normalized_path);
     }
     debug_log(&format!(
         "[BENCHMARK]   Reading INITIAL attributions in get_all_tracked_files took {:?}",
@@ -611,10 +1025,7 @@ fn get_all_tracked_files(
                     continue;
                 }
                 if !files.contains(&normalized_path) {
-                    // Check if it's a text file before adding
-                    if is_text_file(working_log, &normalized_path) {
-                        files.insert(normalized_path);
-                    }
+                    previously_tracked.push(normalized_path);
                 }
             }
         }
@@ -624,6 +1035,13 @@ fn get_all_tracked_files(
         checkpoints_read_start.elapsed()
     ));
 
+    let text_attributes = text_attributes_or_default(repo, &previously_tracked);
+    for path in previously_tracked {
+        if !files.contains(&path) && is_text_file(working_log, &path, &text_attributes) {
+            files.insert(path);
+        }
+    }
+
     let has_ai_checkpoints = if let Ok(working_log_data) = working_log.read_all_checkpoints() {
         working_log_data.iter().any(|checkpoint| {
             checkpoint.kind == CheckpointKind::AiAgent || checkpoint.kind == CheckpointKind::AiTab
@@ -632,11 +1050,16 @@ fn get_all_tracked_files(
         false
     };
 
+    let gitai_ignore = repo_workdir
+        .as_deref()
+        .map(GitAiIgnore::load)
+        .unwrap_or_default();
+
     let status_files_start = Instant::now();
-    let mut results_for_tracked_files = if is_pre_commit && !has_ai_checkpoints {
-        get_status_of_files(repo, working_log, files, true)?
+    let (mut results_for_tracked_files, renamed_from) = if is_pre_commit && !has_ai_checkpoints {
+        get_status_of_files(repo, working_log, files, true, &gitai_ignore)?
     } else {
-        get_status_of_files(repo, working_log, files, false)?
+        get_status_of_files(repo, working_log, files, false, &gitai_ignore)?
     };
     debug_log(&format!(
         "[BENCHMARK]   get_status_of_files in get_all_tracked_files took {:?}",
@@ -645,6 +1068,12 @@ fn get_all_tracked_files(
 
     // Ensure to always include all dirty files
     if let Some(ref dirty_files) = working_log.dirty_files {
+        let dirty_paths: Vec<String> = dirty_files
+            .keys()
+            .map(|path| normalize_to_posix(path))
+            .filter(|path| is_path_in_repo(path))
+            .collect();
+        let text_attributes = text_attributes_or_default(repo, &dirty_paths);
         for file_path in dirty_files.keys() {
             // Normalize path separators to forward slashes
             let normalized_path = normalize_to_posix(file_path);
@@ -656,22 +1085,62 @@ fn get_all_tracked_files(
                 ));
                 continue;
             }
+            let relative_path = repo_workdir
+                .as_ref()
+                .and_then(|workdir| {
+                    std::path::Path::new(&normalized_path)
+                        .strip_prefix(workdir)
+                        .ok()
+                })
+                .map(|p| normalize_to_posix(&p.to_string_lossy()))
+                .unwrap_or_else(|| normalized_path.clone());
+            if gitai_ignore.is_ignored(&relative_path) {
+                continue;
+            }
             // Only add if not already in the files list
             if !results_for_tracked_files.contains(&normalized_path) {
                 // Check if it's a text file before adding
-                if is_text_file(working_log, &normalized_path) {
+                if is_text_file(working_log, &normalized_path, &text_attributes) {
                     results_for_tracked_files.push(normalized_path);
                 }
             }
         }
     }
 
-    Ok(results_for_tracked_files)
+    Ok((results_for_tracked_files, renamed_from))
+}
+
+/// Staged content for every file whose working tree copy differs from the index. Untracked
+/// files have nothing staged, so they read as empty.
+fn index_contents_of_unstaged_files(
+    repo: &Repository,
+) -> Result<HashMap<String, String>, GitAiError> {
+    let list = |extra: &[&str]| -> Result<Vec<String>, GitAiError> {
+        let mut args = repo.global_args_for_exec();
+        args.extend(extra.iter().map(|a| a.to_string()));
+        let output = exec_git(&args)?;
+        Ok(String::from_utf8(output.stdout)?
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(|p| p.to_string())
+            .collect())
+    };
+
+    let modified = list(&["diff", "--name-only", "-z"])?;
+    let mut contents = repo.get_all_staged_files_content(&modified)?;
+    for path in list(&["ls-files", "--others", "--exclude-standard", "-z"])? {
+        contents.insert(path, String::new());
+    }
+    Ok(contents)
 }
 
+/// Store the current content of `files` as blobs, keyed by path. Paths in `crlf_paths` are
+/// stored with the LF line endings git would commit them with (see
+/// `Repository::crlf_normalized_paths`), so a CRLF checkout diffs cleanly against HEAD.
 fn save_current_file_states(
     working_log: &PersistedWorkingLog,
     files: &[String],
+    crlf_paths: &HashSet<String>,
 ) -> Result<HashMap<String, String>, GitAiError> {
     let _read_start = Instant::now();
 
@@ -688,6 +1157,7 @@ fn save_current_file_states(
         let dirty_files = Arc::new(dirty_files);
 
         let futures = files.iter().map(|file_path| {
+            let normalize_crlf = crlf_paths.contains(file_path);
             let file_path = file_path.clone();
             let blobs_dir = Arc::clone(&blobs_dir);
             let repo_workdir = Arc::clone(&repo_workdir);
@@ -711,9 +1181,16 @@ fn save_current_file_states(
                     } else {
                         repo_workdir.join(&file_path).to_string_lossy().to_string()
                     };
-                    // Read from filesystem
-                    std::fs::read_to_string(&abs_path).unwrap_or_default()
+                    // Read from filesystem, taking symlinks as their target text
+                    read_worktree_bytes(std::path::Path::new(&abs_path))
+                        .and_then(|bytes| String::from_utf8(bytes).ok())
+                        .unwrap_or_default()
                 });
+                let content = if normalize_crlf {
+                    normalize_line_endings(content)
+                } else {
+                    content
+                };
 
                 // Create SHA256 hash of the content
                 let mut hasher = Sha256::new();
@@ -725,7 +1202,7 @@ fn save_current_file_states(
 
                 // Write content to blob file
                 let blob_path = blobs_dir.join(&sha);
-                std::fs::write(blob_path, content)?;
+                std::fs::write(blob_path, encode_file_version(&content)?)?;
 
                 Ok::<(String, String), GitAiError>((file_path, sha))
             }
@@ -748,9 +1225,22 @@ fn save_current_file_states(
     Ok(file_content_hashes)
 }
 
+/// CRLF to LF, as git does on add. Content with null bytes is binary to git and left alone.
+fn normalize_line_endings(content: String) -> String {
+    if content.contains("\r\n") && !content.contains('\0') {
+        content.replace("\r\n", "\n")
+    } else {
+        content
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
+/// `previous_path` is the path a staged rename moved `file_path` from: its checkpoints, HEAD
+/// content and blame stand in for the new path's until the new path has its own, so
+/// attributions follow the file and only lines changed since the rename go to this author.
 fn get_checkpoint_entry_for_file(
     file_path: String,
+    previous_path: Option<String>,
     kind: CheckpointKind,
     repo: Repository,
     working_log: PersistedWorkingLog,
@@ -758,8 +1248,9 @@ fn get_checkpoint_entry_for_file(
     file_content_hash: String,
     author_id: Arc<String>,
     head_commit_sha: Arc<Option<String>>,
-    head_tree_id: Arc<Option<String>>,
+    head_blob_ids: Arc<HashMap<String, String>>,
     initial_attributions: Arc<HashMap<String, Vec<LineAttribution>>>,
+    normalize_crlf: bool,
     ts: u128,
 ) -> Result<Option<(WorkingLogEntry, FileLineStats)>, GitAiError> {
     let feature_flag_inter_commit_move = Config::get().get_feature_flags().inter_commit_move;
@@ -768,13 +1259,23 @@ fn get_checkpoint_entry_for_file(
     let current_content = working_log
         .read_current_file_content(&file_path)
         .unwrap_or_default();
+    let current_content = if normalize_crlf {
+        normalize_line_endings(current_content)
+    } else {
+        current_content
+    };
 
-    // Try to get previous state from checkpoints first
+    // Try to get previous state from checkpoints first (the renamed-from path's, for a file
+    // not checkpointed since it was renamed)
     let from_checkpoint = previous_checkpoints.iter().rev().find_map(|checkpoint| {
         checkpoint
             .entries
             .iter()
             .find(|e| e.file == file_path)
+            .or_else(|| {
+                let previous_path = previous_path.as_ref()?;
+                checkpoint.entries.iter().find(|e| &e.file == previous_path)
+            })
             .map(|entry| {
                 (
                     working_log
@@ -788,9 +1289,19 @@ fn get_checkpoint_entry_for_file(
     // Get INITIAL attributions for this file (needed early for the skip check)
     let initial_attrs_for_file = initial_attributions
         .get(&file_path)
+        .or_else(|| initial_attributions.get(previous_path.as_ref()?))
         .cloned()
         .unwrap_or_default();
 
+    // HEAD has the file under its old name until a rename is committed
+    let head_path = previous_path.as_deref().unwrap_or(&file_path);
+    // A renamed file needs an entry under its new path even when its content is unchanged,
+    // or its attributions stay behind with the old path
+    let carries_rename = previous_path.is_some()
+        && !previous_checkpoints
+            .iter()
+            .any(|checkpoint| checkpoint.entries.iter().any(|e| e.file == file_path));
+
     let is_from_checkpoint = from_checkpoint.is_some();
     let (previous_content, prev_attributions) = if let Some((content, attrs)) = from_checkpoint {
         // File exists in a previous checkpoint - use that
@@ -798,30 +1309,18 @@ fn get_checkpoint_entry_for_file(
     } else {
         // File doesn't exist in any previous checkpoint - need to initialize from git + INITIAL
         // Get previous content from HEAD tree
-        let previous_content = if let Some(tree_id) = head_tree_id.as_ref().as_ref() {
-            let head_tree = repo.find_tree(tree_id.clone()).ok();
-            if let Some(tree) = head_tree {
-                match tree.get_path(std::path::Path::new(&file_path)) {
-                    Ok(entry) => {
-                        if let Ok(blob) = repo.find_blob(entry.id()) {
-                            let blob_content = blob.content().unwrap_or_default();
-                            String::from_utf8_lossy(&blob_content).to_string()
-                        } else {
-                            String::new()
-                        }
-                    }
-                    Err(_) => String::new(),
-                }
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
+        let previous_content = head_blob_ids
+            .get(head_path)
+            .and_then(|oid| repo.blob_content(oid).ok())
+            .map(|content| String::from_utf8_lossy(&content).to_string())
+            .unwrap_or_default();
 
         // Skip if no changes, UNLESS we have INITIAL attributions for this file
         // (in which case we need to create an entry to record those attributions)
-        if current_content == previous_content && initial_attrs_for_file.is_empty() {
+        if current_content == previous_content
+            && initial_attrs_for_file.is_empty()
+            && !carries_rename
+        {
             return Ok(None);
         }
 
@@ -849,7 +1348,7 @@ fn get_checkpoint_entry_for_file(
             ai_blame_opts.oldest_date = Some(*OLDEST_AI_BLAME_DATE);
         }
         let ai_blame = if feature_flag_inter_commit_move {
-            repo.blame(&file_path, &ai_blame_opts).ok()
+            repo.blame(head_path, &ai_blame_opts).ok()
         } else {
             // When skipping blame, default all lines to "human"
             let total_lines = previous_content.lines().count() as u32;
@@ -935,7 +1434,7 @@ fn get_checkpoint_entry_for_file(
 
     // Skip if no changes (but we already checked this earlier, accounting for INITIAL attributions)
     // For files from previous checkpoints, check if content has changed
-    if is_from_checkpoint && current_content == previous_content {
+    if is_from_checkpoint && current_content == previous_content && !carries_rename {
         return Ok(None);
     }
 
@@ -963,8 +1462,11 @@ async fn get_checkpoint_entries(
     working_log: &PersistedWorkingLog,
     files: &[String],
     file_content_hashes: &HashMap<String, String>,
+    crlf_paths: &HashSet<String>,
+    renamed_from: &HashMap<String, String>,
     previous_checkpoints: &[Checkpoint],
-    agent_run_result: Option<&AgentRunResult>,
+    session_hash: Option<&str>,
+    concurrency: usize,
     ts: u128,
 ) -> Result<(Vec<WorkingLogEntry>, Vec<FileLineStats>), GitAiError> {
     let entries_fn_start = Instant::now();
@@ -981,13 +1483,8 @@ async fn get_checkpoint_entries(
     // Determine author_id based on checkpoint kind and agent_id
     let author_id = if kind != CheckpointKind::Human {
         // For AI checkpoints, use session hash
-        agent_run_result
-            .map(|result| {
-                crate::authorship::authorship_log_serialization::generate_short_hash(
-                    &result.agent_id.id,
-                    &result.agent_id.tool,
-                )
-            })
+        session_hash
+            .map(str::to_string)
             .unwrap_or_else(|| kind.to_str())
     } else {
         // For human checkpoints, use checkpoint kind string
@@ -1001,15 +1498,25 @@ async fn get_checkpoint_entries(
         .and_then(|h| h.target().ok())
         .and_then(|oid| repo.find_commit(oid).ok());
     let head_commit_sha = head_commit.as_ref().map(|c| c.id().to_string());
-    let head_tree_id = head_commit
+    // Look up every file's HEAD blob up front rather than opening the tree once per file
+    let head_blobs_start = Instant::now();
+    let head_paths: Vec<String> = files
+        .iter()
+        .map(|file| renamed_from.get(file).unwrap_or(file).clone())
+        .collect();
+    let head_blob_ids = head_commit
         .as_ref()
         .and_then(|c| c.tree().ok())
-        .map(|t| t.id().to_string());
-
-    const MAX_CONCURRENT: usize = 30;
+        .and_then(|tree| tree.blob_ids_for_paths(&head_paths).ok())
+        .unwrap_or_default();
+    debug_log(&format!(
+        "[BENCHMARK] Looking up HEAD blobs for {} files took {:?}",
+        head_paths.len(),
+        head_blobs_start.elapsed()
+    ));
 
     // Create a semaphore to limit concurrent tasks
-    let semaphore = Arc::new(smol::lock::Semaphore::new(MAX_CONCURRENT));
+    let semaphore = Arc::new(smol::lock::Semaphore::new(concurrency));
 
     // Move checkpoint data to Arc once, outside the loop to avoid repeated allocations
     let previous_checkpoints = Arc::new(previous_checkpoints.to_vec());
@@ -1017,7 +1524,7 @@ async fn get_checkpoint_entries(
     // Move other repeated allocations outside the loop
     let author_id = Arc::new(author_id);
     let head_commit_sha = Arc::new(head_commit_sha);
-    let head_tree_id = Arc::new(head_tree_id);
+    let head_blob_ids = Arc::new(head_blob_ids);
     let initial_attributions = Arc::new(initial_attributions);
 
     // Spawn tasks for each file
@@ -1031,12 +1538,14 @@ async fn get_checkpoint_entries(
         let previous_checkpoints = Arc::clone(&previous_checkpoints);
         let author_id = Arc::clone(&author_id);
         let head_commit_sha = Arc::clone(&head_commit_sha);
-        let head_tree_id = Arc::clone(&head_tree_id);
+        let head_blob_ids = Arc::clone(&head_blob_ids);
         let blob_sha = file_content_hashes
             .get(&file_path)
             .cloned()
             .unwrap_or_default();
         let initial_attributions = Arc::clone(&initial_attributions);
+        let normalize_crlf = crlf_paths.contains(&file_path);
+        let previous_path = renamed_from.get(&file_path).cloned();
         let semaphore = Arc::clone(&semaphore);
 
         let task = smol::spawn(async move {
@@ -1047,6 +1556,7 @@ async fn get_checkpoint_entries(
             smol::unblock(move || {
                 get_checkpoint_entry_for_file(
                     file_path,
+                    previous_path,
                     kind,
                     repo,
                     working_log,
@@ -1054,8 +1564,9 @@ async fn get_checkpoint_entries(
                     blob_sha,
                     author_id.clone(),
                     head_commit_sha.clone(),
-                    head_tree_id.clone(),
+                    head_blob_ids.clone(),
                     initial_attributions.clone(),
+                    normalize_crlf,
                     ts,
                 )
             })
@@ -1230,9 +1741,28 @@ fn compute_line_stats(
     Ok(stats)
 }
 
-fn is_text_file(working_log: &PersistedWorkingLog, path: &str) -> bool {
+/// Git attributes for `paths` (see `Repository::text_attributes`), or none when they can't be
+/// read so every file falls back to the null-byte check.
+fn text_attributes_or_default(repo: &Repository, paths: &[String]) -> HashMap<String, bool> {
+    repo.text_attributes(paths).unwrap_or_else(|e| {
+        debug_log(&format!("Failed to read text attributes: {}", e));
+        HashMap::new()
+    })
+}
+
+/// Whether `path` is a text file, going by its `text_attributes` verdict when it has one and
+/// otherwise by whether its content is free of null bytes.
+fn is_text_file(
+    working_log: &PersistedWorkingLog,
+    path: &str,
+    text_attributes: &HashMap<String, bool>,
+) -> bool {
     // Normalize path for dirty_files lookup
     let normalized_path = normalize_to_posix(path);
+    let attribute = text_attributes.get(&normalized_path).copied();
+    if attribute == Some(false) {
+        return false;
+    }
     let skip_metadata_check = working_log
         .dirty_files
         .as_ref()
@@ -1250,13 +1780,25 @@ fn is_text_file(working_log: &PersistedWorkingLog, path: &str) -> bool {
         }
     }
 
+    if attribute == Some(true) {
+        return true;
+    }
+
     working_log
         .read_current_file_content(&normalized_path)
         .map(|content| !content.chars().any(|c| c == '\0'))
         .unwrap_or(false)
 }
 
-fn is_text_file_in_head(repo: &Repository, path: &str) -> bool {
+fn is_text_file_in_head(
+    repo: &Repository,
+    path: &str,
+    text_attributes: &HashMap<String, bool>,
+) -> bool {
+    if let Some(&is_text) = text_attributes.get(path) {
+        return is_text;
+    }
+
     // For deleted files, check if they were text files in HEAD
     let head_commit = match repo
         .head()
@@ -1473,7 +2015,7 @@ mod tests {
                 model: "test_model".to_string(),
             },
             agent_metadata: None,
-            transcript: Some(AiTranscript { messages: vec![] }),
+            transcript: Some(AiTranscript::new()),
             checkpoint_kind: CheckpointKind::AiAgent,
             repo_working_dir: None,
             edited_filepaths: Some(vec![
@@ -1539,7 +2081,7 @@ mod tests {
 
         // Store the checkpoint with external path
         working_log
-            .append_checkpoint(&fake_checkpoint)
+            .append_checkpoint(&fake_checkpoint, &working_log.lock().unwrap())
             .expect("Should be able to append checkpoint");
 
         // Now make actual changes to a file in the repo
@@ -85,10 +85,8 @@ impl AgentCheckpointPreset for ClaudePreset {
                             "operation": "transcript_and_model_from_claude_code_jsonl"
                         })),
                     );
-                    (
-                        crate::authorship::transcript::AiTranscript::new(),
-                        Some("unknown".to_string()),
-                    )
+                    let (transcript, model) = generic_transcript_fallback(transcript_path);
+                    (transcript, model.or_else(|| Some("unknown".to_string())))
                 }
             };
 
@@ -292,10 +290,8 @@ impl AgentCheckpointPreset for GeminiPreset {
                             "operation": "transcript_and_model_from_gemini_json"
                         })),
                     );
-                    (
-                        crate::authorship::transcript::AiTranscript::new(),
-                        Some("unknown".to_string()),
-                    )
+                    let (transcript, model) = generic_transcript_fallback(transcript_path);
+                    (transcript, model.or_else(|| Some("unknown".to_string())))
                 }
             };
 
@@ -478,11 +474,29 @@ impl AgentCheckpointPreset for ContinueCliPreset {
             .and_then(|v| v.as_str())
             .ok_or_else(|| GitAiError::PresetError("cwd not found in hook_input".to_string()))?;
 
-        // Extract model from hook_input (required)
+        // Parse transcript from JSON file
+        let (transcript, transcript_model) =
+            match ContinueCliPreset::transcript_from_continue_json(transcript_path) {
+                Ok(transcript) => (transcript, None),
+                Err(e) => {
+                    eprintln!("[Warning] Failed to parse Continue CLI JSON: {e}");
+                    log_error(
+                        &e,
+                        Some(serde_json::json!({
+                            "agent_tool": "continue-cli",
+                            "operation": "transcript_from_continue_json"
+                        })),
+                    );
+                    generic_transcript_fallback(transcript_path)
+                }
+            };
+
+        // Extract model from hook_input, else whatever model the transcript recorded
         let model = hook_data
             .get("model")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string())
+            .or(transcript_model)
             .unwrap_or_else(|| {
                 eprintln!("[Warning] Continue CLI: 'model' field not found in hook_input, defaulting to 'unknown'");
                 eprintln!("[Debug] hook_data keys: {:?}", hook_data.as_object().map(|obj| obj.keys().collect::<Vec<_>>()));
@@ -491,22 +505,6 @@ impl AgentCheckpointPreset for ContinueCliPreset {
 
         eprintln!("[Debug] Continue CLI using model: {}", model);
 
-        // Parse transcript from JSON file
-        let transcript = match ContinueCliPreset::transcript_from_continue_json(transcript_path) {
-            Ok(transcript) => transcript,
-            Err(e) => {
-                eprintln!("[Warning] Failed to parse Continue CLI JSON: {e}");
-                log_error(
-                    &e,
-                    Some(serde_json::json!({
-                        "agent_tool": "continue-cli",
-                        "operation": "transcript_from_continue_json"
-                    })),
-                );
-                crate::authorship::transcript::AiTranscript::new()
-            }
-        };
-
         // The session_id is the unique identifier for this conversation
         let agent_id = AgentId {
             tool: "continue-cli".to_string(),
@@ -1442,9 +1440,9 @@ impl AgentCheckpointPreset for DroidPreset {
         };
 
         // Parse the Droid transcript JSONL file
-        let transcript =
+        let (transcript, transcript_model) =
             match DroidPreset::transcript_and_model_from_droid_jsonl(&resolved_transcript_path) {
-                Ok((transcript, _model)) => transcript,
+                Ok(parsed) => parsed,
                 Err(e) => {
                     eprintln!("[Warning] Failed to parse Droid JSONL: {e}");
                     log_error(
@@ -1454,15 +1452,16 @@ impl AgentCheckpointPreset for DroidPreset {
                             "operation": "transcript_and_model_from_droid_jsonl"
                         })),
                     );
-                    crate::authorship::transcript::AiTranscript::new()
+                    generic_transcript_fallback(&resolved_transcript_path)
                 }
             };
 
-        // Extract model from settings.json
-        let model = match DroidPreset::model_from_droid_settings_json(&resolved_settings_path) {
-            Ok(m) => m.unwrap_or_else(|| "unknown".to_string()),
-            Err(_) => "unknown".to_string(),
-        };
+        // Extract model from settings.json, else whatever model the transcript recorded
+        let model = DroidPreset::model_from_droid_settings_json(&resolved_settings_path)
+            .ok()
+            .flatten()
+            .or(transcript_model)
+            .unwrap_or_else(|| "unknown".to_string());
 
         let agent_id = AgentId {
             tool: "droid".to_string(),
@@ -1975,3 +1974,196 @@ impl AgentCheckpointPreset for AiTabPreset {
         })
     }
 }
+
+/// Transcript layouts recognized by [`transcript_and_model_from_generic_json`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptShape {
+    /// One `{"role", "content"}` object per line
+    RoleContentJsonl,
+    /// An object with a `messages` array (or a bare array) of `{"role", "content"}`
+    MessagesArray,
+    /// An OpenAI-style chat completion: `{"choices": [{"message": {...}}]}`
+    OpenAiChoices,
+}
+
+/// Parse a transcript in whichever generic shape it matches, for tools without a bespoke
+/// preset. Returns the transcript, the model if recorded, and the shape that matched.
+pub fn transcript_and_model_from_generic_json(
+    transcript_path: &str,
+) -> Result<(AiTranscript, Option<String>, TranscriptShape), GitAiError> {
+    let content = std::fs::read_to_string(transcript_path).map_err(GitAiError::IoError)?;
+    parse_generic_transcript(&content)
+}
+
+pub fn parse_generic_transcript(
+    content: &str,
+) -> Result<(AiTranscript, Option<String>, TranscriptShape), GitAiError> {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(content) {
+        let model = value
+            .get("model")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        if let Some(choices) = value.get("choices").and_then(|v| v.as_array()) {
+            let messages: Vec<&serde_json::Value> =
+                choices.iter().filter_map(|c| c.get("message")).collect();
+            if let Some(transcript) = transcript_from_role_messages(messages) {
+                return Ok((transcript, model, TranscriptShape::OpenAiChoices));
+            }
+        }
+
+        let messages = match &value {
+            serde_json::Value::Array(items) => Some(items),
+            _ => value.get("messages").and_then(|v| v.as_array()),
+        };
+        if let Some(messages) = messages
+            && let Some(transcript) = transcript_from_role_messages(messages.iter())
+        {
+            return Ok((transcript, model, TranscriptShape::MessagesArray));
+        }
+    }
+
+    let mut lines = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(value) => lines.push(value),
+            Err(_) => {
+                lines.clear();
+                break;
+            }
+        }
+    }
+    if !lines.is_empty() {
+        let model = lines
+            .iter()
+            .find_map(|l| l.get("model").and_then(|v| v.as_str()))
+            .map(|s| s.to_string());
+        if let Some(transcript) = transcript_from_role_messages(lines.iter()) {
+            return Ok((transcript, model, TranscriptShape::RoleContentJsonl));
+        }
+    }
+
+    Err(GitAiError::PresetError(
+        "Unrecognized transcript format: expected JSONL of {role, content}, an object with a \
+         messages array, or an OpenAI-style {choices} response"
+            .to_string(),
+    ))
+}
+
+/// Salvage a transcript with the generic parser when a preset's own parser rejects it (e.g.
+/// after the tool changes its format), rather than recording an empty transcript.
+fn generic_transcript_fallback(transcript_path: &str) -> (AiTranscript, Option<String>) {
+    match transcript_and_model_from_generic_json(transcript_path) {
+        Ok((transcript, model, _)) => (transcript, model),
+        Err(_) => (AiTranscript::new(), None),
+    }
+}
+
+/// Map `{"role", "content"}` objects to messages. None unless every entry has a string role.
+fn transcript_from_role_messages<'a>(
+    messages: impl IntoIterator<Item = &'a serde_json::Value>,
+) -> Option<AiTranscript> {
+    let mut transcript = AiTranscript::new();
+    let mut matched = false;
+
+    for message in messages {
+        let role = message.get("role")?.as_str()?;
+        matched = true;
+        let timestamp = message
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let is_user = match role {
+            "user" | "human" => true,
+            "assistant" | "model" | "ai" => false,
+            // system prompts, tool results, etc.
+            _ => continue,
+        };
+
+        let mut texts = Vec::new();
+        match message.get("content") {
+            Some(serde_json::Value::String(text)) => texts.push(text.clone()),
+            Some(serde_json::Value::Array(parts)) => {
+                for part in parts {
+                    match part.get("type").and_then(|v| v.as_str()) {
+                        Some("text") | None => {
+                            if let Some(text) = part
+                                .get("text")
+                                .and_then(|v| v.as_str())
+                                .or_else(|| part.as_str())
+                            {
+                                texts.push(text.to_string());
+                            }
+                        }
+                        Some("thinking") if !is_user => {
+                            if let Some(text) = part.get("thinking").and_then(|v| v.as_str())
+                                && !text.trim().is_empty()
+                            {
+                                transcript.add_message(Message::Thinking {
+                                    text: text.trim().to_string(),
+                                    timestamp: timestamp.clone(),
+                                });
+                            }
+                        }
+                        Some("tool_use") if !is_user => {
+                            if let Some(name) = part.get("name").and_then(|v| v.as_str()) {
+                                transcript.add_message(Message::ToolUse {
+                                    name: name.to_string(),
+                                    input: part
+                                        .get("input")
+                                        .cloned()
+                                        .unwrap_or(serde_json::Value::Null),
+                                    timestamp: timestamp.clone(),
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let text = texts.join("\n");
+        let text = text.trim();
+        if !text.is_empty() {
+            transcript.add_message(if is_user {
+                Message::User {
+                    text: text.to_string(),
+                    timestamp: timestamp.clone(),
+                }
+            } else {
+                Message::Assistant {
+                    text: text.to_string(),
+                    timestamp: timestamp.clone(),
+                }
+            });
+        }
+
+        // OpenAI-style function calls; arguments are a JSON-encoded string
+        if let Some(tool_calls) = message.get("tool_calls").and_then(|v| v.as_array()) {
+            for call in tool_calls {
+                let Some(function) = call.get("function") else {
+                    continue;
+                };
+                let Some(name) = function.get("name").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let input = match function.get("arguments") {
+                    Some(serde_json::Value::String(args)) => serde_json::from_str(args)
+                        .unwrap_or_else(|_| serde_json::Value::String(args.clone())),
+                    Some(other) => other.clone(),
+                    None => serde_json::Value::Null,
+                };
+                transcript.add_message(Message::ToolUse {
+                    name: name.to_string(),
+                    input,
+                    timestamp: timestamp.clone(),
+                });
+            }
+        }
+    }
+
+    matched.then_some(transcript)
+}
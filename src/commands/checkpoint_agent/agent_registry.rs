@@ -0,0 +1,212 @@
+use crate::authorship::transcript::AiTranscript;
+use crate::commands::checkpoint_agent::agent_presets::{
+    ClaudePreset, ContinueCliPreset, GeminiPreset, GithubCopilotPreset,
+};
+use crate::error::GitAiError;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Transcript parsers a user-defined agent can reuse, named after the preset they come from.
+pub const TRANSCRIPT_FORMATS: &[&str] = &["claude", "gemini", "continue-cli", "github-copilot"];
+
+/// How to recognise an agent that runs `git-ai checkpoint` without a preset, and where to
+/// find its session id, model and transcript.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentDefinition {
+    pub tool: String,
+    /// Set (non-empty, not "0"/"false") in processes the agent spawns
+    pub env_vars: Vec<String>,
+    /// Hold the agent's session id, when it exports one
+    pub session_env_vars: Vec<String>,
+    /// Executable names of the agent, matched against the parent process
+    pub process_names: Vec<String>,
+    /// Hold the model name, when the agent exports one
+    pub model_env_vars: Vec<String>,
+    /// Hold the path of the session transcript, when the agent exports one
+    pub transcript_env_vars: Vec<String>,
+    /// Parser for that transcript, one of [`TRANSCRIPT_FORMATS`]
+    pub transcript_format: Option<String>,
+}
+
+impl AgentDefinition {
+    fn builtin(
+        tool: &str,
+        env_vars: &[&str],
+        session_env_vars: &[&str],
+        process_names: &[&str],
+    ) -> Self {
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+        AgentDefinition {
+            tool: tool.to_string(),
+            env_vars: strings(env_vars),
+            session_env_vars: strings(session_env_vars),
+            process_names: strings(process_names),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AgentsFile {
+    #[serde(default)]
+    agents: Vec<AgentDefinition>,
+}
+
+/// Agents `git-ai checkpoint` can recognise, in the order they are tried: the user's
+/// `~/.git-ai/agents.json` entries first, then the built-in agents they don't replace.
+#[derive(Debug, Clone)]
+pub struct AgentRegistry {
+    agents: Vec<AgentDefinition>,
+}
+
+impl AgentRegistry {
+    pub fn builtin() -> Self {
+        AgentRegistry {
+            agents: vec![
+                AgentDefinition::builtin(
+                    "claude",
+                    &["CLAUDECODE"],
+                    &["CLAUDE_CODE_SESSION_ID"],
+                    &["claude"],
+                ),
+                AgentDefinition::builtin("gemini", &["GEMINI_CLI"], &[], &["gemini"]),
+                AgentDefinition::builtin("cursor", &["CURSOR_AGENT"], &[], &["cursor-agent"]),
+                AgentDefinition::builtin("opencode", &["OPENCODE"], &[], &["opencode"]),
+                AgentDefinition::builtin("droid", &[], &[], &["droid"]),
+                AgentDefinition::builtin("continue-cli", &[], &[], &["cn"]),
+            ],
+        }
+    }
+
+    /// Built-in agents overlaid with `~/.git-ai/agents.json`. A file that fails to parse is
+    /// reported and ignored so checkpoints keep working.
+    pub fn load() -> Self {
+        let mut registry = Self::builtin();
+        let Some(path) = agents_file_path() else {
+            return registry;
+        };
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            return registry;
+        };
+        match parse_agents(&data) {
+            Ok(agents) => registry.extend(agents),
+            Err(e) => eprintln!("Ignoring {}: {}", path.display(), e),
+        }
+        registry
+    }
+
+    /// Puts `agents` ahead of the registered ones, each replacing any agent with its tool name.
+    pub fn extend(&mut self, agents: Vec<AgentDefinition>) {
+        self.agents
+            .retain(|existing| !agents.iter().any(|agent| agent.tool == existing.tool));
+        let mut agents = agents;
+        agents.append(&mut self.agents);
+        self.agents = agents;
+    }
+
+    pub fn agents(&self) -> &[AgentDefinition] {
+        &self.agents
+    }
+
+    pub fn get(&self, tool: &str) -> Option<&AgentDefinition> {
+        self.agents.iter().find(|agent| agent.tool == tool)
+    }
+}
+
+/// `~/.git-ai/agents.json`
+pub fn agents_file_path() -> Option<PathBuf> {
+    crate::config::git_ai_dir_path().map(|dir| dir.join("agents.json"))
+}
+
+/// Parses an agents file: `{"agents": [{"tool": "...", "env_vars": [...], ...}]}`.
+pub fn parse_agents(json: &str) -> Result<Vec<AgentDefinition>, GitAiError> {
+    let file: AgentsFile = serde_json::from_str(json)
+        .map_err(|e| GitAiError::PresetError(format!("invalid agents file: {}", e)))?;
+    for agent in &file.agents {
+        if agent.tool.trim().is_empty() {
+            return Err(GitAiError::PresetError(
+                "every agent needs a non-empty tool".to_string(),
+            ));
+        }
+        if let Some(format) = &agent.transcript_format
+            && !TRANSCRIPT_FORMATS.contains(&format.as_str())
+        {
+            return Err(GitAiError::PresetError(format!(
+                "agent '{}': unknown transcript_format '{}' (expected one of {})",
+                agent.tool,
+                format,
+                TRANSCRIPT_FORMATS.join(", ")
+            )));
+        }
+    }
+    Ok(file.agents)
+}
+
+/// Reads a transcript with the parser of the preset named by `format`, returning it with
+/// the model it records, if any.
+pub fn load_transcript(
+    format: &str,
+    path: &str,
+) -> Result<(AiTranscript, Option<String>), GitAiError> {
+    match format {
+        "claude" => ClaudePreset::transcript_and_model_from_claude_code_jsonl(path),
+        "gemini" => GeminiPreset::transcript_and_model_from_gemini_json(path),
+        "continue-cli" => ContinueCliPreset::transcript_from_continue_json(path).map(|t| (t, None)),
+        "github-copilot" => {
+            GithubCopilotPreset::transcript_and_model_from_copilot_session_json(path)
+                .map(|(transcript, model, _)| (transcript, model))
+        }
+        _ => Err(GitAiError::PresetError(format!(
+            "unknown transcript format '{}'",
+            format
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_agents_override_and_extend_builtins() {
+        let agents = parse_agents(
+            r#"{"agents": [
+                {"tool": "acme", "env_vars": ["ACME_AGENT"], "model_env_vars": ["ACME_MODEL"]},
+                {"tool": "gemini", "process_names": ["gemini-next"]}
+            ]}"#,
+        )
+        .unwrap();
+        let mut registry = AgentRegistry::builtin();
+        registry.extend(agents);
+
+        let tools: Vec<&str> = registry.agents().iter().map(|a| a.tool.as_str()).collect();
+        assert_eq!(
+            tools,
+            vec![
+                "acme",
+                "gemini",
+                "claude",
+                "cursor",
+                "opencode",
+                "droid",
+                "continue-cli"
+            ]
+        );
+        assert_eq!(
+            registry.get("gemini").unwrap().process_names,
+            vec!["gemini-next".to_string()]
+        );
+        assert!(registry.get("gemini").unwrap().env_vars.is_empty());
+
+        let err = parse_agents(r#"{"agents": [{"tool": "acme", "transcript_format": "xml"}]}"#)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown transcript_format 'xml'"),
+            "{}",
+            err
+        );
+        assert!(parse_agents(r#"{"agents": [{"tool": ""}]}"#).is_err());
+    }
+}
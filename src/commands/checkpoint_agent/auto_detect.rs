@@ -0,0 +1,205 @@
+use crate::authorship::working_log::{AgentId, CheckpointKind};
+use crate::commands::checkpoint_agent::agent_presets::{AgentRunResult, ClaudePreset};
+use crate::commands::checkpoint_agent::agent_registry::{
+    AgentDefinition, AgentRegistry, load_transcript,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Transcripts older than this are assumed to belong to a finished session.
+const TRANSCRIPT_RECENCY: Duration = Duration::from_secs(5 * 60);
+
+/// Everything `--auto-detect-agent` looks at, captured up front so detection is deterministic.
+#[derive(Debug, Clone, Default)]
+pub struct AgentEnvironment {
+    pub env: HashMap<String, String>,
+    pub parent_pid: Option<u32>,
+    pub parent_process: Option<String>,
+    pub home_dir: Option<PathBuf>,
+    pub working_dir: String,
+}
+
+impl AgentEnvironment {
+    pub fn current(working_dir: &str) -> Self {
+        let parent_pid = parent_pid();
+        AgentEnvironment {
+            env: std::env::vars().collect(),
+            parent_pid,
+            parent_process: parent_pid.and_then(process_name),
+            home_dir: dirs::home_dir(),
+            working_dir: working_dir.to_string(),
+        }
+    }
+}
+
+/// Best-guess agent run for a checkpoint made without a preset: the registry's environment
+/// variables first, then the parent process name, then a recently written Claude Code
+/// transcript for the working directory. Returns None when nothing matches, in which case
+/// the caller records a human checkpoint.
+pub fn detect_agent(
+    environment: &AgentEnvironment,
+    registry: &AgentRegistry,
+) -> Option<AgentRunResult> {
+    let transcript = recent_claude_transcript(environment);
+
+    let agent = registry
+        .agents()
+        .iter()
+        .find(|agent| agent.env_vars.iter().any(|v| env_flag_set(environment, v)))
+        .or_else(|| {
+            let parent = environment.parent_process.as_deref()?;
+            registry
+                .agents()
+                .iter()
+                .find(|agent| agent.process_names.iter().any(|name| name == parent))
+        })
+        .or_else(|| {
+            transcript.as_ref()?;
+            registry.get("claude")
+        })?;
+
+    let transcript = transcript.filter(|_| agent.tool == "claude");
+    Some(agent_run(agent, environment, transcript))
+}
+
+/// Agent run for a checkpoint that names a registered agent without a built-in preset, as
+/// in `git-ai checkpoint <tool>`.
+pub fn registered_agent_run(
+    agent: &AgentDefinition,
+    environment: &AgentEnvironment,
+) -> AgentRunResult {
+    agent_run(agent, environment, None)
+}
+
+fn agent_run(
+    agent: &AgentDefinition,
+    environment: &AgentEnvironment,
+    claude_transcript: Option<PathBuf>,
+) -> AgentRunResult {
+    let env_value = |names: &[String]| {
+        names
+            .iter()
+            .find_map(|v| environment.env.get(v).filter(|s| !s.is_empty()).cloned())
+    };
+
+    let mut agent_id = AgentId {
+        tool: agent.tool.clone(),
+        id: env_value(&agent.session_env_vars).unwrap_or_else(|| match environment.parent_pid {
+            Some(pid) => format!("auto-{}", pid),
+            None => "auto".to_string(),
+        }),
+        model: env_value(&agent.model_env_vars).unwrap_or_else(|| "unknown".to_string()),
+    };
+
+    let mut agent_metadata = None;
+    let mut ai_transcript = None;
+    if let Some(path) = claude_transcript {
+        let path_str = path.to_string_lossy().to_string();
+        if let Ok((parsed, model)) =
+            ClaudePreset::transcript_and_model_from_claude_code_jsonl(&path_str)
+        {
+            if let Some(model) = model {
+                agent_id.model = model;
+            }
+            ai_transcript = Some(parsed);
+        }
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            agent_id.id = stem.to_string();
+        }
+        agent_metadata = Some(HashMap::from([("transcript_path".to_string(), path_str)]));
+    } else if let (Some(format), Some(path)) = (
+        agent.transcript_format.as_deref(),
+        env_value(&agent.transcript_env_vars),
+    ) {
+        match load_transcript(format, &path) {
+            Ok((parsed, model)) => {
+                if let Some(model) = model
+                    && agent_id.model == "unknown"
+                {
+                    agent_id.model = model;
+                }
+                ai_transcript = Some(parsed);
+            }
+            Err(e) => eprintln!("Could not read {} transcript {}: {}", agent.tool, path, e),
+        }
+        agent_metadata = Some(HashMap::from([("transcript_path".to_string(), path)]));
+    }
+
+    AgentRunResult {
+        agent_id,
+        agent_metadata,
+        checkpoint_kind: CheckpointKind::AiAgent,
+        transcript: ai_transcript,
+        repo_working_dir: None,
+        edited_filepaths: None,
+        will_edit_filepaths: None,
+        dirty_files: None,
+    }
+}
+
+fn env_flag_set(environment: &AgentEnvironment, name: &str) -> bool {
+    environment
+        .env
+        .get(name)
+        .is_some_and(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+}
+
+/// Most recently modified `.jsonl` in Claude Code's project directory for the working
+/// directory, if it was written within [`TRANSCRIPT_RECENCY`].
+fn recent_claude_transcript(environment: &AgentEnvironment) -> Option<PathBuf> {
+    let home = environment.home_dir.as_ref()?;
+    // Claude Code names project directories after the cwd with non-alphanumerics as '-'
+    let encoded: String = environment
+        .working_dir
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let project_dir = home.join(".claude").join("projects").join(encoded);
+
+    let now = SystemTime::now();
+    std::fs::read_dir(project_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|path| Some((modified(&path)?, path)))
+        .filter(|(mtime, _)| {
+            now.duration_since(*mtime)
+                .is_ok_and(|age| age <= TRANSCRIPT_RECENCY)
+        })
+        .max_by_key(|(mtime, _)| *mtime)
+        .map(|(_, path)| path)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+#[cfg(unix)]
+fn parent_pid() -> Option<u32> {
+    Some(std::os::unix::process::parent_id())
+}
+
+#[cfg(not(unix))]
+fn parent_pid() -> Option<u32> {
+    None
+}
+
+/// Executable name of `pid`, without any directory.
+fn process_name(pid: u32) -> Option<String> {
+    let name = if cfg!(target_os = "linux") {
+        std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?
+    } else if cfg!(unix) {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "comm=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        String::from_utf8(output.stdout).ok()?
+    } else {
+        return None;
+    };
+    let name = name.trim();
+    let name = Path::new(name).file_name()?.to_str()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
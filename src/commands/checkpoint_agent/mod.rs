@@ -1,3 +1,5 @@
 pub mod agent_presets;
+pub mod agent_registry;
 pub mod agent_v1_preset;
+pub mod auto_detect;
 pub mod opencode_preset;
@@ -1,7 +1,7 @@
 use dirs;
 use serde_json::Value;
 
-use crate::git::repository::find_repository_in_path;
+use crate::git::repository::{Repository, find_repository_in_path};
 
 /// Determines the type of pattern value provided
 #[derive(Debug, PartialEq)]
@@ -93,6 +93,7 @@ fn print_config_help() {
     eprintln!("  git-ai config set <key> <value> --add    Add to array (extends existing)");
     eprintln!("  git-ai config --add <key> <value>        Add to array or upsert into object");
     eprintln!("  git-ai config unset <key>    Remove config value (reverts to default)");
+    eprintln!("  git-ai config --validate     Check the config file and referenced paths");
     eprintln!();
     eprintln!("Configuration Keys:");
     eprintln!("  git_path                     Path to git binary");
@@ -110,6 +111,8 @@ fn print_config_help() {
     eprintln!("  include_prompts_in_repositories  Repos to include for prompt storage (array)");
     eprintln!("  default_prompt_storage       Fallback storage mode for non-included repos");
     eprintln!("  quiet                        Suppress chart output after commits (bool)");
+    eprintln!("  disable_prompt_merging       Skip cross-commit prompt lookup in blame (bool)");
+    eprintln!("  sign_authorship              GPG-sign authorship notes after commits (bool)");
     eprintln!();
     eprintln!("Repository Patterns:");
     eprintln!("  For exclude/allow/exclude_prompts_in_repositories, you can provide:");
@@ -146,6 +149,25 @@ pub fn handle_config(args: &[String]) {
         return;
     }
 
+    if args[0] == "--validate" {
+        let problems = match validate_config() {
+            Ok(problems) => problems,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        if problems.is_empty() {
+            println!("Configuration is valid");
+            return;
+        }
+        for problem in &problems {
+            eprintln!("error: {}", problem);
+        }
+        eprintln!("{} problem(s) found", problems.len());
+        std::process::exit(1);
+    }
+
     // Check for --add flag anywhere in args
     let is_add_mode = args.iter().any(|a| a == "--add");
     let filtered_args: Vec<&String> = args.iter().filter(|a| *a != "--add").collect();
@@ -208,6 +230,51 @@ pub fn handle_config(args: &[String]) {
     }
 }
 
+/// Problems in the config file plus the current repository's blame.ignoreRevsFile, which
+/// `git-ai blame` picks up on its own.
+fn validate_config() -> Result<Vec<String>, String> {
+    let path = crate::config::config_file_path_public()
+        .ok_or_else(|| "Could not determine config file path".to_string())?;
+
+    let mut problems = Vec::new();
+    if path.exists() {
+        let data =
+            std::fs::read(&path).map_err(|e| format!("Failed to read config file: {}", e))?;
+        match serde_json::from_slice::<Value>(&data) {
+            Ok(value) => problems.extend(crate::config::validate_file_config(&value)),
+            Err(e) => problems.push(format!("{}: invalid JSON: {}", path.display(), e)),
+        }
+    }
+
+    if let Ok(cwd) = std::env::current_dir()
+        && let Ok(repo) = find_repository_in_path(&cwd.to_string_lossy())
+    {
+        problems.extend(validate_repo_config(&repo));
+    }
+
+    Ok(problems)
+}
+
+fn validate_repo_config(repo: &Repository) -> Vec<String> {
+    let mut problems = Vec::new();
+    if let Ok(Some(config_path)) = repo.config_get_str("blame.ignoreRevsFile")
+        && !config_path.is_empty()
+        && let Ok(workdir) = repo.workdir()
+        && !workdir.join(&config_path).exists()
+    {
+        problems.push(format!(
+            "blame.ignoreRevsFile: '{}' does not exist",
+            config_path
+        ));
+    }
+    // The same check doctor runs, so the two can't disagree
+    match crate::commands::doctor::gitai_config_problems(repo) {
+        Ok((_, gitai_problems)) => problems.extend(gitai_problems),
+        Err(e) => problems.push(format!("cannot read gitai.* config: {}", e)),
+    }
+    problems
+}
+
 fn show_all_config() -> Result<(), String> {
     let file_config = crate::config::load_file_config_public()?;
 
@@ -303,6 +370,14 @@ fn show_all_config() -> Result<(), String> {
     }
 
     effective_config.insert("quiet".to_string(), Value::Bool(runtime_config.is_quiet()));
+    effective_config.insert(
+        "disable_prompt_merging".to_string(),
+        Value::Bool(runtime_config.prompt_merging_disabled()),
+    );
+    effective_config.insert(
+        "sign_authorship".to_string(),
+        Value::Bool(runtime_config.sign_authorship()),
+    );
 
     // Feature flags - show effective flags with defaults applied
     let flags_value = serde_json::to_value(runtime_config.get_feature_flags())
@@ -392,6 +467,8 @@ fn get_config_value(key: &str) -> Result<(), String> {
                 }
             }
             "quiet" => Value::Bool(runtime_config.is_quiet()),
+            "disable_prompt_merging" => Value::Bool(runtime_config.prompt_merging_disabled()),
+            "sign_authorship" => Value::Bool(runtime_config.sign_authorship()),
             _ => return Err(format!("Unknown config key: {}", key)),
         };
 
@@ -553,6 +630,18 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 eprintln!("[quiet]: {}", bool_value);
             }
+            "disable_prompt_merging" => {
+                let bool_value = parse_bool(value)?;
+                file_config.disable_prompt_merging = Some(bool_value);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[disable_prompt_merging]: {}", bool_value);
+            }
+            "sign_authorship" => {
+                let bool_value = parse_bool(value)?;
+                file_config.sign_authorship = Some(bool_value);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[sign_authorship]: {}", bool_value);
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -724,6 +813,20 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     eprintln!("- [quiet]: {}", v);
                 }
             }
+            "disable_prompt_merging" => {
+                let old_value = file_config.disable_prompt_merging.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [disable_prompt_merging]: {}", v);
+                }
+            }
+            "sign_authorship" => {
+                let old_value = file_config.sign_authorship.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [sign_authorship]: {}", v);
+                }
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
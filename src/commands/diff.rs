@@ -162,7 +162,7 @@ pub fn execute_diff(
         DiffFormat::Json => {
             let diff_json = build_diff_json(repo, &from_commit, &to_commit, &hunks, &attributions)?;
             serde_json::to_string(&diff_json)
-                .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON: {}", e)))?
+                .map_err(|e| GitAiError::Serialization(format!("JSON: {}", e)))?
         }
         DiffFormat::GitCompatibleTerminal => {
             format_annotated_diff(repo, &from_commit, &to_commit, &attributions)?
@@ -251,7 +251,7 @@ pub fn get_diff_with_line_numbers(
     parse_diff_hunks(&diff_text)
 }
 
-fn parse_diff_hunks(diff_text: &str) -> Result<Vec<DiffHunk>, GitAiError> {
+pub(crate) fn parse_diff_hunks(diff_text: &str) -> Result<Vec<DiffHunk>, GitAiError> {
     let mut hunks = Vec::new();
     let mut current_file = String::new();
 
@@ -405,7 +405,7 @@ pub fn overlay_diff_attributions(
                         // Check if this author is an AI tool by looking up in prompt_records
                         let attribution = if prompt_records
                             .values()
-                            .any(|pr| &pr.agent_id.tool == author)
+                            .any(|pr| pr.agent_id.tool_label() == author)
                         {
                             Attribution::Ai(author.clone())
                         } else {
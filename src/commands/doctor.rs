@@ -0,0 +1,342 @@
+use crate::authorship::authorship_log_serialization::{
+    HASH_LENGTH_CONFIG_KEY, MAX_SHORT_HASH_LENGTH, MIN_SHORT_HASH_LENGTH,
+};
+use crate::authorship::secrets::IGNORE_PROMPTS_CONFIG_KEY;
+use crate::authorship::transcript::{MAX_TRANSCRIPT_BYTES_CONFIG_KEY, parse_max_transcript_bytes};
+use crate::commands::checkpoint::{
+    CHECKPOINT_CONCURRENCY_CONFIG_KEY, parse_checkpoint_concurrency,
+};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{
+    AUTHORSHIP_REFSPEC_CONFIG_KEY, parse_authorship_refspec, ref_exists, tracking_ref_for_remote,
+};
+use crate::git::repo_storage::COMPRESS_BLOBS_CONFIG_KEY;
+use crate::git::repository::Repository;
+use crate::mdm::utils::{get_current_binary_path, git_shim_path};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Result of one `git-ai doctor` check. `Warn` is a failed check that doesn't stop git-ai
+/// from tracking authorship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    #[serde(rename = "ok")]
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "ok  "),
+            CheckStatus::Warn => write!(f, "warn"),
+            CheckStatus::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        DoctorCheck {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// `git-ai doctor --json` output
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    /// False when any check failed; warnings don't count
+    pub ok: bool,
+    pub checks: Vec<DoctorCheck>,
+}
+
+pub fn handle_doctor(args: &[String]) {
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ => {
+                eprintln!("Unknown doctor argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let mut checks = vec![check_git_proxy()];
+    match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => checks.extend(repository_checks(&repo)),
+        Err(_) => checks.push(DoctorCheck::new(
+            "repository",
+            CheckStatus::Warn,
+            "not inside a git repository; run again from one to check its setup",
+        )),
+    }
+    let report = DoctorReport {
+        ok: !checks.iter().any(|c| c.status == CheckStatus::Fail),
+        checks,
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize doctor report: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        // Failures go to stderr so scripts can tell them apart from the rest of the report
+        for check in &report.checks {
+            let line = format!("{} {}: {}", check.status, check.name, check.detail);
+            match check.status {
+                CheckStatus::Fail => eprintln!("{}", line),
+                _ => println!("{}", line),
+            }
+        }
+    }
+
+    if !report.ok {
+        std::process::exit(1);
+    }
+}
+
+/// Checks that need a repository: its `gitai.*` config, authorship notes, `user.name` and
+/// git-ai's storage directory.
+pub fn repository_checks(repo: &Repository) -> Vec<DoctorCheck> {
+    vec![
+        check_gitai_config(repo),
+        check_notes_ref(repo),
+        check_user_name(repo),
+        check_storage_writable(&repo.storage.working_logs),
+    ]
+}
+
+/// `git` on PATH must be git-ai's shim, or commands run through plain git and nothing is
+/// tracked.
+fn check_git_proxy() -> DoctorCheck {
+    let name = "git proxy";
+    let Ok(exe) = get_current_binary_path() else {
+        return DoctorCheck::new(name, CheckStatus::Fail, "cannot locate the git-ai binary");
+    };
+    let hint = match git_shim_path().parent() {
+        Some(dir) => format!("put {} first on PATH", dir.display()),
+        None => "reinstall git-ai".to_string(),
+    };
+
+    match find_on_path("git") {
+        Some(git) if git.canonicalize().is_ok_and(|path| path == exe) => DoctorCheck::new(
+            name,
+            CheckStatus::Pass,
+            format!("git resolves to git-ai ({})", git.display()),
+        ),
+        Some(git) => DoctorCheck::new(
+            name,
+            CheckStatus::Fail,
+            format!("git resolves to {}, not git-ai; {}", git.display(), hint),
+        ),
+        None => DoctorCheck::new(name, CheckStatus::Fail, format!("no git on PATH; {}", hint)),
+    }
+}
+
+fn find_on_path(program: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", program)
+    } else {
+        program.to_string()
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// Every `gitai.*` key must be one git-ai reads, with a value it accepts.
+fn check_gitai_config(repo: &Repository) -> DoctorCheck {
+    let name = "gitai config";
+    let (count, problems) = match gitai_config_problems(repo) {
+        Ok(result) => result,
+        Err(e) => {
+            return DoctorCheck::new(
+                name,
+                CheckStatus::Fail,
+                format!("cannot read config: {}", e),
+            );
+        }
+    };
+
+    match (count, problems.is_empty()) {
+        (0, _) => DoctorCheck::new(
+            name,
+            CheckStatus::Pass,
+            "no gitai.* keys set; using defaults",
+        ),
+        (n, true) => DoctorCheck::new(name, CheckStatus::Pass, format!("{} key(s) valid", n)),
+        (_, false) => DoctorCheck::new(name, CheckStatus::Fail, problems.join("; ")),
+    }
+}
+
+/// The number of `gitai.*` keys set and the sorted problems with them. Shared by doctor and
+/// `config --validate` so the two always agree.
+pub fn gitai_config_problems(repo: &Repository) -> Result<(usize, Vec<String>), GitAiError> {
+    let entries = repo.config_get_regexp(r"^gitai\.")?;
+    let mut problems: Vec<String> = entries
+        .iter()
+        .filter_map(|(key, value)| gitai_config_problem(key, value))
+        .collect();
+    problems.sort();
+    Ok((entries.len(), problems))
+}
+
+/// Why `value` is invalid for `key` (as listed by git, lowercased), if it is.
+pub fn gitai_config_problem(key: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    let problem = if key.eq_ignore_ascii_case(HASH_LENGTH_CONFIG_KEY) {
+        match value.parse::<usize>() {
+            Ok(length) if (MIN_SHORT_HASH_LENGTH..=MAX_SHORT_HASH_LENGTH).contains(&length) => {
+                return None;
+            }
+            _ => format!(
+                "expected a number from {} to {}",
+                MIN_SHORT_HASH_LENGTH, MAX_SHORT_HASH_LENGTH
+            ),
+        }
+    } else if key.eq_ignore_ascii_case(COMPRESS_BLOBS_CONFIG_KEY) {
+        match value.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" | "false" | "no" | "off" | "0" => return None,
+            _ => "expected true or false".to_string(),
+        }
+    } else if key.eq_ignore_ascii_case(AUTHORSHIP_REFSPEC_CONFIG_KEY) {
+        match parse_authorship_refspec(value) {
+            Ok(_) => return None,
+            Err(e) => e.to_string(),
+        }
+    } else if key.eq_ignore_ascii_case(CHECKPOINT_CONCURRENCY_CONFIG_KEY) {
+        match parse_checkpoint_concurrency(value) {
+            Ok(_) => return None,
+            Err(_) => "expected a number of at least 1".to_string(),
+        }
+    } else if key.eq_ignore_ascii_case(MAX_TRANSCRIPT_BYTES_CONFIG_KEY) {
+        match parse_max_transcript_bytes(value) {
+            Ok(_) => return None,
+            Err(_) => "expected a byte count such as 65536 or 64k".to_string(),
+        }
+    } else if key.eq_ignore_ascii_case(IGNORE_PROMPTS_CONFIG_KEY) {
+        return None;
+    } else {
+        "unknown key".to_string()
+    };
+    Some(format!("{}={}: {}", key, value, problem))
+}
+
+/// A missing notes ref is normal before the first AI commit, so this only warns.
+fn check_notes_ref(repo: &Repository) -> DoctorCheck {
+    let name = "authorship notes";
+    if !ref_exists(repo, "refs/notes/ai") {
+        return DoctorCheck::new(
+            name,
+            CheckStatus::Warn,
+            "refs/notes/ai not found; it is created by the first commit with AI edits, or by fetching notes from a remote",
+        );
+    }
+
+    match repo.get_default_remote().ok().flatten() {
+        Some(remote) if !ref_exists(repo, &tracking_ref_for_remote(&remote)) => DoctorCheck::new(
+            name,
+            CheckStatus::Warn,
+            format!(
+                "refs/notes/ai present, but notes were never fetched from {}; run git fetch {}",
+                remote, remote
+            ),
+        ),
+        _ => DoctorCheck::new(name, CheckStatus::Pass, "refs/notes/ai present"),
+    }
+}
+
+/// Checkpoints credit human edits to `user.name`, falling back to "unknown".
+fn check_user_name(repo: &Repository) -> DoctorCheck {
+    let name = "user.name";
+    match repo.config_get_str("user.name") {
+        Ok(Some(user)) if !user.trim().is_empty() => {
+            DoctorCheck::new(name, CheckStatus::Pass, user.trim().to_string())
+        }
+        _ => DoctorCheck::new(
+            name,
+            CheckStatus::Warn,
+            "not set; human edits will be credited to 'unknown' (git config user.name <name>)",
+        ),
+    }
+}
+
+fn check_storage_writable(dir: &Path) -> DoctorCheck {
+    let name = "storage";
+    let probe = dir.join(".doctor-probe");
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b""))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => DoctorCheck::new(
+            name,
+            CheckStatus::Pass,
+            format!("{} writable", dir.display()),
+        ),
+        Err(e) => DoctorCheck::new(
+            name,
+            CheckStatus::Fail,
+            format!("{} not writable: {}", dir.display(), e),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitai_config_problem() {
+        assert_eq!(gitai_config_problem("gitai.hashlength", "12"), None);
+        assert_eq!(gitai_config_problem("gitai.compressblobs", "off"), None);
+        assert_eq!(
+            gitai_config_problem("gitai.authorshiprefspec", "refs/notes/ai:refs/notes/ai"),
+            None
+        );
+        assert_eq!(gitai_config_problem("gitai.ignoreprompts", "cursor"), None);
+        assert_eq!(
+            gitai_config_problem("gitai.maxtranscriptbytes", "64k"),
+            None
+        );
+        assert_eq!(
+            gitai_config_problem("gitai.checkpointconcurrency", "4"),
+            None
+        );
+
+        assert_eq!(
+            gitai_config_problem("gitai.hashlength", "3"),
+            Some("gitai.hashlength=3: expected a number from 7 to 40".to_string())
+        );
+        assert_eq!(
+            gitai_config_problem("gitai.compressblobs", "maybe"),
+            Some("gitai.compressblobs=maybe: expected true or false".to_string())
+        );
+        assert_eq!(
+            gitai_config_problem("gitai.checkpointconcurrency", "0"),
+            Some("gitai.checkpointconcurrency=0: expected a number of at least 1".to_string())
+        );
+        assert_eq!(
+            gitai_config_problem("gitai.hashlenght", "12"),
+            Some("gitai.hashlenght=12: unknown key".to_string())
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::signing::SignatureStatus;
+use crate::commands::verify::{CommitVerification, verify_commit};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::get_authorship;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Version tag of the `--format json` document.
+pub const ATTRIBUTION_FORMAT: &str = "git-ai-attribution/1";
+
+/// One policy check rendered as a JUnit `<testcase>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JunitTestCase {
+    pub classname: String,
+    pub name: String,
+    /// Failure message; None when the check passed
+    pub failure: Option<String>,
+}
+
+/// A group of checks rendered as a JUnit `<testsuite>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JunitTestSuite {
+    pub name: String,
+    pub cases: Vec<JunitTestCase>,
+}
+
+pub fn handle_export(args: &[String]) {
+    let mut format: Option<String> = None;
+    let mut max_ai_percent: Option<f64> = None;
+    let mut require_signed = false;
+    let mut revs: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --format requires a value");
+                    std::process::exit(1);
+                }
+                format = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--max-ai-percent" => {
+                let Some(percent) = args.get(i + 1).and_then(|v| v.parse::<f64>().ok()) else {
+                    eprintln!("Error: --max-ai-percent requires a number");
+                    std::process::exit(1);
+                };
+                max_ai_percent = Some(percent);
+                i += 2;
+            }
+            "--require-signed" => {
+                require_signed = true;
+                i += 1;
+            }
+            arg if arg.starts_with('-') => {
+                eprintln!("Unknown export argument: {}", arg);
+                std::process::exit(1);
+            }
+            _ => {
+                revs.push(args[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    let format = match format.as_deref() {
+        Some(f @ ("junit" | "json")) => f.to_string(),
+        Some(other) => {
+            eprintln!("Error: unsupported export format: {}", other);
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Error: export requires --format <junit|json>");
+            std::process::exit(1);
+        }
+    };
+    if format != "junit" && (require_signed || max_ai_percent.is_some()) {
+        eprintln!("Error: --require-signed and --max-ai-percent only apply to --format junit");
+        std::process::exit(1);
+    }
+
+    if revs.is_empty() {
+        revs.push("HEAD".to_string());
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut commits = Vec::new();
+    for rev in &revs {
+        match repo.revparse_single(rev) {
+            Ok(obj) => commits.push(obj.id()),
+            Err(e) => {
+                eprintln!("Failed to resolve {}: {}", rev, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let rendered = if format == "json" {
+        attribution_document(&repo, &commits).and_then(|doc| {
+            serde_json::to_string_pretty(&doc)
+                .map(|json| json + "\n")
+                .map_err(GitAiError::from)
+        })
+    } else {
+        policy_suites(&repo, &commits, require_signed, max_ai_percent)
+            .map(|suites| render_junit(&suites))
+    };
+    match rendered {
+        Ok(output) => print!("{}", output),
+        Err(e) => {
+            eprintln!("Failed to export: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `export --format json`: line-level provenance for each exported commit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttributionDocument {
+    pub format: String,
+    pub commits: Vec<CommitAttribution>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommitAttribution {
+    pub commit: String,
+    /// False when the commit has no authorship note; every added line is then listed as human
+    pub has_authorship_log: bool,
+    pub files: Vec<FileAttribution>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileAttribution {
+    pub path: String,
+    /// AI and human ranges in line order
+    pub ranges: Vec<AttributedRange>,
+}
+
+/// Inclusive line range of a file at the commit. `author` is "ai" or "human"; the prompt
+/// fields are only set for "ai" ranges.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttributedRange {
+    pub start: u32,
+    pub end: u32,
+    pub author: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_hash: Option<String>,
+    /// Who ran the session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub human_author: Option<String>,
+    /// Lines of the session's output a human later rewrote (`overriden_lines`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overridden_lines: Option<u32>,
+    /// Whether a human rewrote any of the session's output (`overriden_lines > 0`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub human_edited: Option<bool>,
+}
+
+impl AttributedRange {
+    fn human(start: u32, end: u32) -> Self {
+        AttributedRange {
+            start,
+            end,
+            author: "human",
+            tool: None,
+            model: None,
+            prompt_hash: None,
+            human_author: None,
+            overridden_lines: None,
+            human_edited: None,
+        }
+    }
+}
+
+/// Build the attribution document for `commits`: the AI ranges of each commit's authorship
+/// note, plus the lines the commit added that no AI session accounts for as human ranges.
+pub fn attribution_document(
+    repo: &Repository,
+    commits: &[String],
+) -> Result<AttributionDocument, GitAiError> {
+    let mut exported = Vec::new();
+    for commit_sha in commits {
+        exported.push(commit_attribution(repo, commit_sha)?);
+    }
+    Ok(AttributionDocument {
+        format: ATTRIBUTION_FORMAT.to_string(),
+        commits: exported,
+    })
+}
+
+fn commit_attribution(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<CommitAttribution, GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let parent = if commit.parent_count()? == 0 {
+        EMPTY_TREE_HASH.to_string()
+    } else {
+        commit.parent(0)?.id()
+    };
+    let added_lines = repo.diff_added_lines(&parent, commit_sha, None)?;
+    let log = get_authorship(repo, commit_sha);
+
+    let mut files: BTreeMap<String, Vec<AttributedRange>> = BTreeMap::new();
+    let mut ai_lines: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    if let Some(log) = &log {
+        for attestation in &log.attestations {
+            let ranges = files.entry(attestation.file_path.clone()).or_default();
+            let covered = ai_lines.entry(attestation.file_path.clone()).or_default();
+            for entry in &attestation.entries {
+                let prompt = log.metadata.prompts.get(&entry.hash);
+                for range in &entry.line_ranges {
+                    let (start, end) = match range {
+                        LineRange::Single(line) => (*line, *line),
+                        LineRange::Range(start, end) => (*start, *end),
+                    };
+                    covered.extend(start..=end);
+                    ranges.push(AttributedRange {
+                        start,
+                        end,
+                        author: "ai",
+                        tool: prompt.map(|p| p.agent_id.tool_label().to_string()),
+                        model: prompt.map(|p| p.agent_id.model.clone()),
+                        prompt_hash: Some(entry.hash.clone()),
+                        human_author: prompt.and_then(|p| p.human_author.clone()),
+                        overridden_lines: prompt.map(|p| p.overriden_lines),
+                        human_edited: prompt.map(|p| p.overriden_lines > 0),
+                    });
+                }
+            }
+        }
+    }
+
+    for (path, lines) in added_lines {
+        let covered = ai_lines.get(&path);
+        let human: Vec<u32> = lines
+            .into_iter()
+            .filter(|line| !covered.is_some_and(|c| c.contains(line)))
+            .collect();
+        if human.is_empty() {
+            continue;
+        }
+        let ranges = files.entry(path).or_default();
+        for range in LineRange::compress_lines(&human) {
+            ranges.push(match range {
+                LineRange::Single(line) => AttributedRange::human(line, line),
+                LineRange::Range(start, end) => AttributedRange::human(start, end),
+            });
+        }
+    }
+
+    Ok(CommitAttribution {
+        commit: commit_sha.to_string(),
+        has_authorship_log: log.is_some(),
+        files: files
+            .into_iter()
+            .map(|(path, mut ranges)| {
+                ranges.sort_by_key(|r| (r.start, r.end));
+                FileAttribution { path, ranges }
+            })
+            .collect(),
+    })
+}
+
+/// Run the authorship policy checks for `commits`: one `verify` case per commit, plus one
+/// AI-ratio case per file changed in each commit when `max_ai_percent` is set.
+pub fn policy_suites(
+    repo: &Repository,
+    commits: &[String],
+    require_signed: bool,
+    max_ai_percent: Option<f64>,
+) -> Result<Vec<JunitTestSuite>, GitAiError> {
+    let mut verify_cases = Vec::new();
+    for commit_sha in commits {
+        let verification = verify_commit(repo, commit_sha)?;
+        verify_cases.push(verification_case(commit_sha, &verification, require_signed));
+    }
+    let mut suites = vec![JunitTestSuite {
+        name: "git-ai.verify".to_string(),
+        cases: verify_cases,
+    }];
+
+    if let Some(max_percent) = max_ai_percent {
+        let mut ratio_cases = Vec::new();
+        for commit_sha in commits {
+            ratio_cases.extend(ai_ratio_cases(repo, commit_sha, max_percent)?);
+        }
+        suites.push(JunitTestSuite {
+            name: "git-ai.ai-ratio".to_string(),
+            cases: ratio_cases,
+        });
+    }
+
+    Ok(suites)
+}
+
+/// Turn a `verify` result into a test case, failing the same results `git-ai verify` does.
+pub fn verification_case(
+    commit_sha: &str,
+    verification: &CommitVerification,
+    require_signed: bool,
+) -> JunitTestCase {
+    let failure = match verification {
+        CommitVerification::NoNote if require_signed => Some("no authorship note".to_string()),
+        CommitVerification::Signature(SignatureStatus::Unsigned) if require_signed => {
+            Some("authorship note is unsigned".to_string())
+        }
+        CommitVerification::Signature(SignatureStatus::Invalid) => {
+            Some("authorship note has an invalid signature".to_string())
+        }
+        _ => None,
+    };
+    JunitTestCase {
+        classname: "git-ai.verify".to_string(),
+        name: commit_sha.to_string(),
+        failure,
+    }
+}
+
+/// One case per file the commit added lines to, failing when the share of those lines
+/// attributed to AI in the commit's note exceeds `max_percent`.
+fn ai_ratio_cases(
+    repo: &Repository,
+    commit_sha: &str,
+    max_percent: f64,
+) -> Result<Vec<JunitTestCase>, GitAiError> {
+    let mut ai_lines: BTreeMap<String, usize> = BTreeMap::new();
+    if let Some(log) = get_authorship(repo, commit_sha) {
+        for attestation in &log.attestations {
+            let lines: BTreeSet<u32> = attestation
+                .entries
+                .iter()
+                .flat_map(|e| e.line_ranges.iter().flat_map(|r| r.expand()))
+                .collect();
+            ai_lines.insert(attestation.file_path.clone(), lines.len());
+        }
+    }
+
+    let mut cases = Vec::new();
+    for (file, added) in added_lines_per_file(repo, commit_sha)? {
+        if added == 0 {
+            continue;
+        }
+        let ai = ai_lines.get(&file).copied().unwrap_or(0).min(added);
+        cases.push(ai_ratio_case(commit_sha, &file, ai, added, max_percent));
+    }
+    Ok(cases)
+}
+
+pub fn ai_ratio_case(
+    commit_sha: &str,
+    file: &str,
+    ai_lines: usize,
+    added_lines: usize,
+    max_percent: f64,
+) -> JunitTestCase {
+    let percent = ai_lines as f64 * 100.0 / added_lines as f64;
+    let failure = (percent > max_percent).then(|| {
+        format!(
+            "{:.1}% of added lines are AI-authored ({}/{}), above the {}% limit",
+            percent, ai_lines, added_lines, max_percent
+        )
+    });
+    JunitTestCase {
+        classname: format!("git-ai.ai-ratio.{}", &commit_sha[..7.min(commit_sha.len())]),
+        name: file.to_string(),
+        failure,
+    }
+}
+
+/// Lines added per file in `commit_sha` relative to its first parent (binary files skipped).
+fn added_lines_per_file(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<Vec<(String, usize)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("show".to_string());
+    args.push("--numstat".to_string());
+    // NUL-terminated records keep paths with tabs, quotes or non-ASCII bytes unquoted
+    args.push("-z".to_string());
+    args.push("--format=".to_string());
+    args.push("--no-renames".to_string());
+    args.push(commit_sha.to_string());
+    let output = exec_git(&args)?;
+
+    Ok(String::from_utf8(output.stdout)?
+        .split('\0')
+        .filter_map(|record| {
+            let mut parts = record.trim_start_matches('\n').splitn(3, '\t');
+            let added = parts.next()?.parse::<usize>().ok()?;
+            let _deleted = parts.next()?;
+            Some((parts.next()?.to_string(), added))
+        })
+        .collect())
+}
+
+/// Render suites as a JUnit XML report.
+pub fn render_junit(suites: &[JunitTestSuite]) -> String {
+    let total: usize = suites.iter().map(|s| s.cases.len()).sum();
+    let failures: usize = suites.iter().map(suite_failures).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"git-ai\" tests=\"{}\" failures=\"{}\">\n",
+        total, failures
+    ));
+    for suite in suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            xml_escape(&suite.name),
+            suite.cases.len(),
+            suite_failures(suite)
+        ));
+        for case in &suite.cases {
+            let open = format!(
+                "    <testcase classname=\"{}\" name=\"{}\"",
+                xml_escape(&case.classname),
+                xml_escape(&case.name)
+            );
+            match &case.failure {
+                Some(message) => {
+                    xml.push_str(&open);
+                    xml.push_str(">\n");
+                    xml.push_str(&format!(
+                        "      <failure message=\"{}\"/>\n",
+                        xml_escape(message)
+                    ));
+                    xml.push_str("    </testcase>\n");
+                }
+                None => {
+                    xml.push_str(&open);
+                    xml.push_str("/>\n");
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn suite_failures(suite: &JunitTestSuite) -> usize {
+    suite.cases.iter().filter(|c| c.failure.is_some()).count()
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
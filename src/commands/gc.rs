@@ -0,0 +1,249 @@
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::utils::debug_log;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Unreferenced working log blobs younger than this are kept: a checkpoint writes its blobs
+/// before it records them, so a recent blob may belong to one still in progress.
+const BLOB_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Object store totals from `git count-objects -v`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObjectCounts {
+    pub loose_objects: u64,
+    /// Loose plus packed size, in KiB
+    pub size_kib: u64,
+}
+
+/// Working log blobs no checkpoint references, removed (or, in a dry run, removable) by gc.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlobPruneReport {
+    pub blobs: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GcReport {
+    pub before: ObjectCounts,
+    pub after: ObjectCounts,
+}
+
+pub fn handle_gc(args: &[String]) {
+    let mut aggressive = false;
+    let mut dry_run = false;
+    for arg in args {
+        match arg.as_str() {
+            "--aggressive" => aggressive = true,
+            "--dry-run" => dry_run = true,
+            _ => {
+                eprintln!("Unknown gc argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let blobs = match prune_working_log_blobs(&repo, dry_run) {
+        Ok(blobs) => blobs,
+        Err(e) => {
+            eprintln!("gc failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if dry_run {
+        println!(
+            "Unreferenced working log blobs: {} ({} bytes reclaimable)",
+            blobs.blobs, blobs.bytes
+        );
+        return;
+    }
+    println!(
+        "Removed unreferenced working log blobs: {} ({} bytes)",
+        blobs.blobs, blobs.bytes
+    );
+
+    match gc_notes(&repo, aggressive) {
+        Ok(report) => {
+            println!(
+                "Loose objects: {} -> {}",
+                report.before.loose_objects, report.after.loose_objects
+            );
+            println!(
+                "Object store size: {} KiB -> {} KiB",
+                report.before.size_kib, report.after.size_kib
+            );
+        }
+        Err(e) => {
+            eprintln!("gc failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Delete the blobs in each working log that none of its checkpoints reference, e.g. file
+/// versions left behind by checkpoints that were since replaced or reset. Each working log
+/// is locked while it is scanned, and blobs written within [`BLOB_GRACE_PERIOD`] are kept.
+/// With `dry_run`, nothing is deleted and the report counts what would be.
+pub fn prune_working_log_blobs(
+    repo: &Repository,
+    dry_run: bool,
+) -> Result<BlobPruneReport, GitAiError> {
+    let mut report = BlobPruneReport::default();
+    let Ok(entries) = std::fs::read_dir(&repo.storage.working_logs) else {
+        return Ok(report);
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let base_commit = entry.file_name().to_string_lossy().to_string();
+        let working_log = repo.storage.working_log_for_base_commit(&base_commit);
+        let blobs_dir = working_log.dir.join("blobs");
+        if !blobs_dir.is_dir() {
+            continue;
+        }
+
+        let _lock = working_log.lock()?;
+        // A working log that can't be read might still reference any of its blobs
+        let checkpoints = match working_log.read_all_checkpoints() {
+            Ok(checkpoints) => checkpoints,
+            Err(e) => {
+                debug_log(&format!("gc: skipping working log {}: {}", base_commit, e));
+                continue;
+            }
+        };
+        let referenced: HashSet<&str> = checkpoints
+            .iter()
+            .flat_map(|checkpoint| &checkpoint.entries)
+            .map(|entry| entry.blob_sha.as_str())
+            .collect();
+
+        for blob in std::fs::read_dir(&blobs_dir)? {
+            let blob = blob?;
+            let metadata = blob.metadata()?;
+            let name = blob.file_name().to_string_lossy().to_string();
+            let recent = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_none_or(|age| age < BLOB_GRACE_PERIOD);
+            if !metadata.is_file() || recent || referenced.contains(name.as_str()) {
+                continue;
+            }
+
+            if !dry_run {
+                std::fs::remove_file(blob.path())?;
+            }
+            report.blobs += 1;
+            report.bytes += metadata.len();
+        }
+    }
+
+    Ok(report)
+}
+
+/// Pack the objects reachable from the authorship notes refs and drop the loose copies.
+/// `aggressive` recomputes deltas with a wider window into a single fresh pack, then prunes
+/// unreachable loose objects (e.g. note blobs orphaned by notes merges) older than
+/// `gc.pruneExpire`.
+pub fn gc_notes(repo: &Repository, aggressive: bool) -> Result<GcReport, GitAiError> {
+    let before = count_objects(repo)?;
+
+    let notes_refs = notes_refs(repo)?;
+    if !notes_refs.is_empty() {
+        let mut args = repo.global_args_for_exec();
+        args.push("pack-objects".to_string());
+        args.push("--revs".to_string());
+        args.push("--quiet".to_string());
+        if aggressive {
+            args.push("--no-reuse-delta".to_string());
+            args.push("--window=250".to_string());
+            args.push("--depth=50".to_string());
+        } else {
+            // Only pick up objects that aren't packed yet
+            args.push("--incremental".to_string());
+        }
+        args.push(pack_base_path(repo)?);
+        let revs = notes_refs.join("\n") + "\n";
+        exec_git_stdin(&args, revs.as_bytes())?;
+
+        let mut args = repo.global_args_for_exec();
+        args.push("prune-packed".to_string());
+        args.push("--quiet".to_string());
+        exec_git(&args)?;
+    }
+
+    if aggressive {
+        let expire = repo
+            .config_get_str("gc.pruneExpire")?
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "2.weeks.ago".to_string());
+        let mut args = repo.global_args_for_exec();
+        args.push("prune".to_string());
+        args.push(format!("--expire={}", expire));
+        exec_git(&args)?;
+    }
+
+    Ok(GcReport {
+        before,
+        after: count_objects(repo)?,
+    })
+}
+
+/// refs/notes/ai plus the per-remote tracking refs under refs/notes/ai-remote/.
+fn notes_refs(repo: &Repository) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("for-each-ref".to_string());
+    args.push("--format=%(refname)".to_string());
+    args.push("refs/notes/ai".to_string());
+    args.push("refs/notes/ai-remote/".to_string());
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+/// Base name for new packs in the (possibly shared) object directory.
+fn pack_base_path(repo: &Repository) -> Result<String, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-parse".to_string());
+    args.push("--path-format=absolute".to_string());
+    args.push("--git-path".to_string());
+    args.push("objects/pack/pack".to_string());
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+fn count_objects(repo: &Repository) -> Result<ObjectCounts, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("count-objects".to_string());
+    args.push("-v".to_string());
+    let output = exec_git(&args)?;
+
+    let mut counts = ObjectCounts::default();
+    for line in String::from_utf8(output.stdout)?.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().parse::<u64>().unwrap_or(0);
+        match key {
+            "count" => counts.loose_objects = value,
+            "size" | "size-pack" => counts.size_kib += value,
+            _ => {}
+        }
+    }
+    Ok(counts)
+}
@@ -1,21 +1,28 @@
+use crate::authorship::history_stats;
 use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::range_authorship;
-use crate::authorship::stats::stats_command;
+use crate::authorship::signing::force_signing;
+use crate::authorship::stats::{diff_ai_share, stats_command};
 use crate::authorship::working_log::{AgentId, CheckpointKind};
 use crate::commands;
 use crate::commands::checkpoint_agent::agent_presets::{
     AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult, AiTabPreset, ClaudePreset,
     ContinueCliPreset, CursorPreset, DroidPreset, GeminiPreset, GithubCopilotPreset,
 };
+use crate::commands::checkpoint_agent::agent_registry::AgentRegistry;
 use crate::commands::checkpoint_agent::agent_v1_preset::AgentV1Preset;
+use crate::commands::checkpoint_agent::auto_detect::{
+    AgentEnvironment, detect_agent, registered_agent_run,
+};
 use crate::commands::checkpoint_agent::opencode_preset::OpenCodePreset;
+use crate::commands::hooks::commit_hooks::mailmap_user_name;
 use crate::config;
 use crate::git::find_repository;
 use crate::git::find_repository_in_path;
-use crate::git::repository::{CommitRange, group_files_by_repository};
+use crate::git::repository::{CommitRange, Repository, group_files_by_repository};
 use crate::observability::wrapper_performance_targets::log_performance_for_checkpoint;
 use crate::observability::{self, log_message};
-use crate::utils::is_interactive_terminal;
+use crate::utils::{info_log, is_interactive_terminal, is_quiet};
 use std::env;
 use std::io::IsTerminal;
 use std::io::Read;
@@ -93,6 +100,12 @@ pub fn handle_git_ai(args: &[String]) {
                 log_message("diff", "info", None)
             }
         }
+        "log" => {
+            commands::log::handle_log(&args[1..]);
+        }
+        "doctor" => {
+            commands::doctor::handle_doctor(&args[1..]);
+        }
         "git-path" => {
             let config = config::Config::get();
             println!("{}", config.git_cmd());
@@ -120,6 +133,24 @@ pub fn handle_git_ai(args: &[String]) {
                 std::process::exit(1);
             }
         },
+        "import-blame" => {
+            commands::import_blame::handle_import_blame(&args[1..]);
+        }
+        "gc" => {
+            commands::gc::handle_gc(&args[1..]);
+        }
+        "notes-merge" => {
+            commands::notes_merge::handle_notes_merge(&args[1..]);
+        }
+        "verify" => {
+            commands::verify::handle_verify(&args[1..]);
+        }
+        "commit" => {
+            handle_commit(&args[1..]);
+        }
+        "export" => {
+            commands::export::handle_export(&args[1..]);
+        }
         "squash-authorship" => {
             commands::squash_authorship::handle_squash_authorship(&args[1..]);
         }
@@ -176,23 +207,101 @@ pub fn handle_git_ai(args: &[String]) {
 fn print_help() {
     eprintln!("git-ai - git proxy with AI authorship tracking");
     eprintln!();
-    eprintln!("Usage: git-ai <command> [args...]");
+    eprintln!("Usage: git-ai [-q|--quiet] [--verbose] <command> [args...]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -q, --quiet        Only print errors and warnings");
+    eprintln!("  --verbose          Print debug output, including timings");
     eprintln!();
     eprintln!("Commands:");
     eprintln!("  checkpoint         Checkpoint working changes and attribute author");
     eprintln!("    Presets: claude, continue-cli, cursor, gemini, github-copilot, ai_tab, mock_ai");
+    eprintln!("    Agents defined in ~/.git-ai/agents.json can also be named as presets");
     eprintln!(
         "    --hook-input <json|stdin>   JSON payload required by presets, or 'stdin' to read from stdin"
     );
     eprintln!("    --show-working-log          Display current working log");
     eprintln!("    --reset                     Reset working log");
+    eprintln!(
+        "    --since-last-commit         Reset the working log only if HEAD moved since the last checkpoint"
+    );
+    eprintln!("    --ignore-unstaged           Attribute only staged content, not unstaged edits");
+    eprintln!("    --dump-working-log <path>   Write the working log as JSON after checkpointing");
+    eprintln!(
+        "    --include-transcripts       With --dump-working-log, keep transcripts (redacted by default)"
+    );
+    eprintln!(
+        "    --merge-into <hash>         Relabel this checkpoint's session as an earlier session <hash>"
+    );
+    eprintln!(
+        "    --auto-detect-agent         Infer the agent from its environment, else checkpoint as human"
+    );
+    eprintln!(
+        "    --verify-clean              Refuse to checkpoint during a merge/rebase/cherry-pick or with conflicts"
+    );
+    eprintln!("    --json                      Print what was recorded as JSON on stdout");
+    eprintln!(
+        "    --kind <tab|agent|human>    Record the preset's edit as a tab completion, agent edit, or human edit"
+    );
     eprintln!("    mock_ai [pathspecs...]      Test preset accepting optional file pathspecs");
     eprintln!("  blame <file>       Git blame with AI authorship overlay");
+    eprintln!("    <file>@<rev>, <file> <rev>, <rev> -- <file>  Blame the file as of <rev>");
+    eprintln!(
+        "    --porcelain           Git's porcelain, plus ai-agent/ai-model/ai-prompt-hash on AI lines"
+    );
+    eprintln!(
+        "    --porcelain-v2        Group lines by session: one header per author, then line ranges"
+    );
+    eprintln!("    --json-per-line       JSON with one object per line, of kind human or ai");
+    eprintln!("    --count-only          Print only AI-authored and total line counts");
+    eprintln!(
+        "    --human-only          Dim AI-authored lines; with --count-only, count human lines"
+    );
+    eprintln!(
+        "    --agent-legend        List each session shown with its tool, model and line count"
+    );
+    eprintln!("    --no-agent-legend     Omit the legend (shown by default on a terminal)");
+    eprintln!("    --show-prompt         List the first prompt of each AI session shown");
+    eprintln!(
+        "    --show-acceptance     Show the share of AI lines in the file kept, not overridden"
+    );
+    eprintln!("    --map-session <hash>=<label> Show <label> instead of the tool for a session");
+    eprintln!(
+        "    --model-colors <model>=<color>,... Color AI lines by model (others auto-assigned)"
+    );
+    eprintln!(
+        "    --color[=always|never|auto], --no-color  Color AI lines by agent (default: auto on a terminal)"
+    );
+    eprintln!("    --limit-lines <n>     Stop after n annotated lines");
+    eprintln!("    --ai-only-files <range> List files with AI-authored lines in a commit or range");
+    eprintln!("    --min-lines <n>       With --ai-only-files, skip files with fewer AI lines");
+    eprintln!("    --no-merge-prompts    Don't resolve prompts from other commits' notes");
+    eprintln!("    --resolve-timeout <ms> Stop resolving prompts from other notes after <ms>");
+    eprintln!("    --attribute-deleted   Mark where AI-authored lines were later deleted");
+    eprintln!("    --squash-view <commit> Break a squashed commit's attribution down by session");
+    eprintln!(
+        "    --team-map <file>     Summarize AI-assisted lines per team (JSON author -> team)"
+    );
     eprintln!("  diff <commit|range>  Show diff with AI authorship annotations");
     eprintln!("    <commit>              Diff from commit's parent to commit");
     eprintln!("    <commit1>..<commit2>  Diff between two commits");
+    eprintln!("  log [<range>...]   Commit history with a summary of each commit's AI authorship");
+    eprintln!("    --oneline             One line per commit: short SHA, subject, summary");
     eprintln!("  stats [commit]     Show AI authorship statistics for a commit");
     eprintln!("    --json                 Output in JSON format");
+    eprintln!("    --by-file              Rank files by AI-authored lines (commit or range)");
+    eprintln!("    --top <n>              Limit --by-file output to the top n files");
+    eprintln!("    --diff <base>..<head>  AI-authored share of the lines added in the range");
+    eprintln!("                           (<base>...<head> starts from their merge base)");
+    eprintln!("    --fail-under <pct>     With --diff, exit 1 if the AI share is below pct");
+    eprintln!("    --fail-over <pct>      With --diff, exit 1 if the AI share is above pct");
+    eprintln!("    --csv                  With --by-file or --diff, output CSV");
+    eprintln!("    --no-header            With --csv, omit the header row (for appending)");
+    eprintln!("    --history              Per-model and per-author totals across the branch");
+    eprintln!("    --since <rev>          With --history, only walk commits after <rev>");
+    eprintln!("    --model <name>         With --history, only count models matching <name>");
+    eprintln!("    --stream               With --history, one JSON line per commit as walked");
+    eprintln!("    --team-map <file>      With --history, also total sessions per team (JSON map)");
     eprintln!("  status             Show uncommitted AI authorship status (debug)");
     eprintln!("    --json                 Output in JSON format");
     eprintln!("  show <rev|range>   Display authorship logs for a revision or range");
@@ -215,15 +324,47 @@ fn print_help() {
     eprintln!("    set <key> <value>     Set a config value (arrays: single value = [value])");
     eprintln!("    --add <key> <value>   Add to array or upsert into object");
     eprintln!("    unset <key>           Remove config value (reverts to default)");
+    eprintln!("    --validate            Check the config file and referenced paths");
     eprintln!("  install-hooks      Install git hooks for AI authorship tracking");
     eprintln!("  uninstall-hooks    Remove git-ai hooks from all detected tools");
     eprintln!("  ci                 Continuous integration utilities");
     eprintln!("    github                 GitHub CI helpers");
+    eprintln!("  import-blame <file>  Seed a human-authored baseline from plain git blame");
+    eprintln!("    --all                 Seed every tracked file at HEAD");
+    eprintln!("  notes-merge <ref>  Union another authorship notes ref into refs/notes/ai");
+    eprintln!("    --force               Replace local notes that can't be parsed");
+    eprintln!("  gc                 Remove orphaned working log blobs and pack note objects");
+    eprintln!("    --aggressive          Recompress the notes ref and prune unreachable objects");
+    eprintln!("    --dry-run             Report reclaimable working log blobs without deleting");
+    eprintln!("  commit [<git commit args>...]  Run git commit with AI authorship tracking");
+    eprintln!("    --sign-authorship     Sign this commit's authorship note (GPG or SSH)");
+    eprintln!(
+        "  verify [<commit>|<range>...]  Check authorship note signatures and integrity (default: HEAD)"
+    );
+    eprintln!("    --require-signed      Fail on unsigned notes or commits without notes");
+    eprintln!(
+        "    --fix-base-commit     Rewrite base_commit_sha to the annotated commit (default: all notes)"
+    );
+    eprintln!("    --signatures          Only check signatures (GPG or SSH, per gpg.format)");
+    eprintln!("    --all                 Check every commit with an authorship note");
+    eprintln!(
+        "    --reachable-only      Skip notes on commits no ref reaches (default: all notes)"
+    );
+    eprintln!(
+        "  export --format junit [<commit>...]  Report authorship policy checks as JUnit XML"
+    );
+    eprintln!("    --require-signed      Fail commits whose notes are missing or unsigned");
+    eprintln!("    --max-ai-percent <n>  Fail files whose added lines are more than n% AI");
+    eprintln!("  export --format json [<commit>...]  Export AI and human line attribution as JSON");
     eprintln!("  squash-authorship  Generate authorship log for squashed commits");
     eprintln!(
         "    <base_branch> <new_sha> <old_sha>  Required: base branch, new commit SHA, old commit SHA"
     );
     eprintln!("    --dry-run             Show what would be done without making changes");
+    eprintln!("  doctor             Check the git proxy, gitai.* config, notes ref and storage");
+    eprintln!(
+        "    --json                Print each check as {{name, status, detail}} plus an overall ok"
+    );
     eprintln!("  git-path           Print the path to the underlying git executable");
     eprintln!("  upgrade            Check for updates and install if available");
     eprintln!("    --force               Reinstall latest version even if already up to date");
@@ -244,6 +385,18 @@ fn print_help() {
     std::process::exit(0);
 }
 
+/// With `--verify-clean`, explain why the repository is not in a state to checkpoint.
+fn checkpoint_blocked_reason(repo: &Repository) -> Option<String> {
+    match commands::checkpoint::in_progress_operation(repo) {
+        Ok(Some(operation)) => Some(format!(
+            "{} (finish or abort it before recording attribution)",
+            operation
+        )),
+        Ok(None) => None,
+        Err(e) => Some(format!("failed to check repository state: {}", e)),
+    }
+}
+
 fn handle_checkpoint(args: &[String]) {
     let mut repository_working_dir = std::env::current_dir()
         .unwrap()
@@ -253,7 +406,16 @@ fn handle_checkpoint(args: &[String]) {
     // Parse checkpoint-specific arguments
     let mut show_working_log = false;
     let mut reset = false;
+    let mut since_last_commit = false;
+    let mut verify_clean = false;
+    let mut auto_detect_agent = false;
+    let mut ignore_unstaged = false;
+    let mut json = false;
+    let mut merge_into: Option<String> = None;
+    let mut dump_working_log: Option<String> = None;
+    let mut include_transcripts = false;
     let mut hook_input = None;
+    let mut kind_override: Option<CheckpointKind> = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -266,6 +428,60 @@ fn handle_checkpoint(args: &[String]) {
                 reset = true;
                 i += 1;
             }
+            "--since-last-commit" => {
+                since_last_commit = true;
+                i += 1;
+            }
+            "--verify-clean" => {
+                verify_clean = true;
+                i += 1;
+            }
+            "--auto-detect-agent" => {
+                auto_detect_agent = true;
+                i += 1;
+            }
+            "--ignore-unstaged" => {
+                ignore_unstaged = true;
+                i += 1;
+            }
+            "--json" => {
+                json = true;
+                i += 1;
+            }
+            "--dump-working-log" => {
+                if i + 1 < args.len() && !args[i + 1].starts_with("--") {
+                    dump_working_log = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --dump-working-log requires a file path");
+                    std::process::exit(1);
+                }
+            }
+            "--include-transcripts" => {
+                include_transcripts = true;
+                i += 1;
+            }
+            "--merge-into" => {
+                if i + 1 < args.len() && !args[i + 1].starts_with("--") {
+                    merge_into = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --merge-into requires a session hash");
+                    std::process::exit(1);
+                }
+            }
+            "--kind" => {
+                kind_override = match args.get(i + 1).map(String::as_str) {
+                    Some("tab") => Some(CheckpointKind::AiTab),
+                    Some("agent") => Some(CheckpointKind::AiAgent),
+                    Some("human") => Some(CheckpointKind::Human),
+                    _ => {
+                        eprintln!("Error: --kind requires one of tab, agent, human");
+                        std::process::exit(1);
+                    }
+                };
+                i += 2;
+            }
             "--hook-input" => {
                 if i + 1 < args.len() {
                     hook_input = Some(args[i + 1].clone());
@@ -450,16 +666,9 @@ fn handle_checkpoint(args: &[String]) {
                         .unwrap_or_else(|_| 0)
                 );
 
-                // Collect all remaining args (after mock_ai and flags) as pathspecs
-                let edited_filepaths = if args.len() > 1 {
-                    let mut paths = Vec::new();
-                    for arg in &args[1..] {
-                        // Skip flags
-                        if !arg.starts_with("--") {
-                            paths.push(arg.clone());
-                        }
-                    }
-                    if paths.is_empty() { None } else { Some(paths) }
+                let paths = checkpoint_pathspecs(&args[1..]);
+                let edited_filepaths = if !paths.is_empty() {
+                    Some(paths)
                 } else {
                     let working_dir = agent_run_result
                         .as_ref()
@@ -484,7 +693,57 @@ fn handle_checkpoint(args: &[String]) {
                     dirty_files: None,
                 });
             }
-            _ => {}
+            name => {
+                // Agents defined in ~/.git-ai/agents.json can be named like presets
+                if let Some(agent) = AgentRegistry::load().get(name) {
+                    // Only the paths the agent names are credited to it
+                    let environment = AgentEnvironment::current(&repository_working_dir);
+                    let mut agent_run = registered_agent_run(agent, &environment);
+                    let paths = checkpoint_pathspecs(&args[1..]);
+                    if !paths.is_empty() {
+                        agent_run.edited_filepaths = Some(paths);
+                    }
+                    agent_run_result = Some(agent_run);
+                }
+            }
+        }
+    }
+
+    // An explicit preset always wins over auto-detection
+    if auto_detect_agent && agent_run_result.is_none() {
+        let environment = AgentEnvironment::current(&repository_working_dir);
+        if let Some(mut agent_run) = detect_agent(&environment, &AgentRegistry::load()) {
+            agent_run.edited_filepaths = Some(get_all_files_for_mock_ai(&repository_working_dir));
+            agent_run_result = Some(agent_run);
+        }
+    }
+
+    // --kind relabels what the preset recorded, e.g. an editor reporting an autocomplete
+    if let Some(kind) = kind_override {
+        match agent_run_result.as_mut() {
+            Some(run) if run.checkpoint_kind != kind => {
+                // Human checkpoints snapshot the files about to be edited, AI ones the edited files
+                if kind == CheckpointKind::Human {
+                    run.will_edit_filepaths = run
+                        .edited_filepaths
+                        .take()
+                        .or(run.will_edit_filepaths.take());
+                } else if run.checkpoint_kind == CheckpointKind::Human {
+                    run.edited_filepaths = run
+                        .will_edit_filepaths
+                        .take()
+                        .or(run.edited_filepaths.take());
+                }
+                run.checkpoint_kind = kind;
+            }
+            Some(_) => {}
+            None if kind == CheckpointKind::Human => {}
+            None => {
+                eprintln!(
+                    "Error: --kind tab|agent requires an agent preset or --auto-detect-agent"
+                );
+                std::process::exit(1);
+            }
         }
     }
 
@@ -555,13 +814,13 @@ fn handle_checkpoint(args: &[String]) {
             let is_multi_repo = repo_files.len() > 1;
 
             if is_multi_repo {
-                eprintln!(
+                info_log(&format!(
                     "Multi-repo workspace detected. Found {} repositories with edits.",
                     repo_files.len()
-                );
+                ));
             } else {
-                eprintln!(
-                    "Workspace root is not a git repository. Detected repository from edited files."
+                info_log(
+                    "Workspace root is not a git repository. Detected repository from edited files.",
                 );
             }
 
@@ -573,21 +832,35 @@ fn handle_checkpoint(args: &[String]) {
             let checkpoint_start = std::time::Instant::now();
             let mut total_files_edited = 0;
             let mut repos_processed = 0;
+            let mut repo_summaries = Vec::new();
             let total_repos = repo_files.len();
 
+            if merge_into.is_some() {
+                eprintln!("Warning: --merge-into is ignored in multi-repository workspaces");
+            }
+
             // Process each repository separately
             for (repo_workdir, (repo, repo_file_paths)) in repo_files {
                 repos_processed += 1;
-                eprintln!(
+                info_log(&format!(
                     "Processing repository {}/{}: {}",
                     repos_processed,
                     total_repos,
                     repo_workdir.display()
-                );
+                ));
+
+                if verify_clean && let Some(reason) = checkpoint_blocked_reason(&repo) {
+                    eprintln!(
+                        "  Skipping checkpoint for {}: {}",
+                        repo_workdir.display(),
+                        reason
+                    );
+                    continue;
+                }
 
                 // Get user name from this repo's config
                 let default_user_name = match repo.config_get_str("user.name") {
-                    Ok(Some(name)) if !name.trim().is_empty() => name,
+                    Ok(Some(name)) if !name.trim().is_empty() => mailmap_user_name(&repo, name),
                     _ => {
                         eprintln!(
                             "Warning: git user.name not configured for {}. Using 'unknown'.",
@@ -616,20 +889,27 @@ fn handle_checkpoint(args: &[String]) {
                     &default_user_name,
                     checkpoint_kind,
                     show_working_log,
-                    reset,
-                    false,
+                    reset
+                        || (since_last_commit
+                            && commands::checkpoint::head_moved_since_last_checkpoint(&repo)),
+                    is_quiet(),
                     repo_agent_result,
                     false,
+                    ignore_unstaged,
                 );
 
                 match checkpoint_result {
-                    Ok((_, files_edited, _)) => {
-                        total_files_edited += files_edited;
-                        eprintln!(
+                    Ok(summary) => {
+                        total_files_edited += summary.files;
+                        info_log(&format!(
                             "  Checkpoint for {} completed ({} files)",
                             repo_workdir.display(),
-                            files_edited
-                        );
+                            summary.files
+                        ));
+                        repo_summaries.push(serde_json::json!({
+                            "repo": repo_workdir.to_string_lossy(),
+                            "checkpoint": summary,
+                        }));
                     }
                     Err(e) => {
                         eprintln!("  Checkpoint for {} failed: {}", repo_workdir.display(), e);
@@ -647,12 +927,15 @@ fn handle_checkpoint(args: &[String]) {
             let elapsed = checkpoint_start.elapsed();
             log_performance_for_checkpoint(total_files_edited, elapsed, checkpoint_kind);
             if is_multi_repo {
-                eprintln!(
+                info_log(&format!(
                     "Checkpoint completed in {:?} ({} repositories, {} total files)",
                     elapsed, repos_processed, total_files_edited
-                );
+                ));
             } else {
-                eprintln!("Checkpoint completed in {:?}", elapsed);
+                info_log(&format!("Checkpoint completed in {:?}", elapsed));
+            }
+            if json {
+                println!("{}", serde_json::Value::Array(repo_summaries));
             }
             return;
         }
@@ -667,6 +950,11 @@ fn handle_checkpoint(args: &[String]) {
     // Standard single-repo mode
     let repo = repo_result.unwrap();
 
+    if verify_clean && let Some(reason) = checkpoint_blocked_reason(&repo) {
+        eprintln!("Refusing to checkpoint: {}", reason);
+        std::process::exit(1);
+    }
+
     // Get the effective working directory from the detected repository
     let effective_working_dir = repo
         .workdir()
@@ -715,13 +1003,39 @@ fn handle_checkpoint(args: &[String]) {
 
     // Get the current user name from git config
     let default_user_name = match repo.config_get_str("user.name") {
-        Ok(Some(name)) if !name.trim().is_empty() => name,
+        Ok(Some(name)) if !name.trim().is_empty() => mailmap_user_name(&repo, name),
         _ => {
             eprintln!("Warning: git user.name not configured. Using 'unknown' as author.");
             "unknown".to_string()
         }
     };
 
+    // The session this checkpoint is recorded under, relabeled afterwards by --merge-into
+    let merge_from = match (&merge_into, &agent_run_result) {
+        (None, _) => None,
+        (Some(_), Some(r)) if checkpoint_kind != CheckpointKind::Human => {
+            // The agent as checkpoint::run will record it
+            let mut agent_id = r.agent_id.clone();
+            agent_id.normalize_tool();
+            Some(agent_id)
+        }
+        (Some(_), _) => {
+            eprintln!("Error: --merge-into requires an AI agent checkpoint");
+            std::process::exit(1);
+        }
+    };
+    if let Some(into) = &merge_into
+        && let Err(e) = commands::checkpoint::ensure_session_exists(&repo, into)
+    {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    // A checkpoint after HEAD moved shouldn't build on checkpoints from an earlier visit
+    if since_last_commit && commands::checkpoint::head_moved_since_last_checkpoint(&repo) {
+        reset = true;
+    }
+
     let checkpoint_start = std::time::Instant::now();
     let agent_tool = agent_run_result.as_ref().map(|r| r.agent_id.tool.clone());
     let checkpoint_result = commands::checkpoint::run(
@@ -730,15 +1044,63 @@ fn handle_checkpoint(args: &[String]) {
         checkpoint_kind,
         show_working_log,
         reset,
-        false,
+        is_quiet(),
         agent_run_result,
         false,
+        ignore_unstaged,
     );
     match checkpoint_result {
-        Ok((_, files_edited, _)) => {
+        Ok(summary) => {
             let elapsed = checkpoint_start.elapsed();
-            log_performance_for_checkpoint(files_edited, elapsed, checkpoint_kind);
-            eprintln!("Checkpoint completed in {:?}", elapsed);
+            log_performance_for_checkpoint(summary.files, elapsed, checkpoint_kind);
+            match summary.concurrency {
+                Some(concurrency) => info_log(&format!(
+                    "Checkpoint completed in {:?} ({} files at a time)",
+                    elapsed, concurrency
+                )),
+                None => info_log(&format!("Checkpoint completed in {:?}", elapsed)),
+            }
+            if json {
+                match serde_json::to_string(&summary) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("Failed to serialize checkpoint summary: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            if let (Some(from_agent), Some(into)) = (&merge_from, &merge_into) {
+                let merged = commands::checkpoint::session_hash_of_agent(&repo, from_agent)
+                    .and_then(|from| {
+                        commands::checkpoint::merge_session_into(&repo, &from, into)
+                            .map(|relabeled| (from, relabeled))
+                    });
+                match merged {
+                    Ok((from, relabeled)) => info_log(&format!(
+                        "Merged session {} into {} ({} checkpoints)",
+                        from, into, relabeled
+                    )),
+                    Err(e) => {
+                        eprintln!("Failed to merge sessions: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            if let Some(path) = &dump_working_log {
+                match commands::checkpoint::dump_working_log(
+                    &repo,
+                    std::path::Path::new(path),
+                    include_transcripts,
+                ) {
+                    Ok(count) => info_log(&format!("Dumped {} checkpoint(s) to {}", count, path)),
+                    Err(e) => {
+                        eprintln!("Failed to dump working log: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
 
             // Flush logs and metrics after checkpoint (skip for human checkpoints)
             if checkpoint_kind != CheckpointKind::Human {
@@ -779,6 +1141,11 @@ fn handle_ai_blame(args: &[String]) {
         }
     };
 
+    if args.iter().any(|a| a == "--ai-only-files") {
+        handle_ai_only_files(&repo, args);
+        return;
+    }
+
     // Parse blame arguments
     let (file_path, mut options) = match commands::blame::parse_blame_args(args) {
         Ok(result) => result,
@@ -788,6 +1155,10 @@ fn handle_ai_blame(args: &[String]) {
         }
     };
 
+    if config::Config::get().prompt_merging_disabled() {
+        options.no_merge_prompts = true;
+    }
+
     // Auto-detect ignore-revs-file if not explicitly provided, not disabled via --no-ignore-revs-file,
     // and git version supports --ignore-revs-file (git >= 2.23)
     if options.ignore_revs_file.is_none()
@@ -838,6 +1209,67 @@ fn handle_ai_blame(args: &[String]) {
     }
 }
 
+/// `blame --ai-only-files <range> [--min-lines <n>]`: list files with AI-authored lines.
+fn handle_ai_only_files(repo: &Repository, args: &[String]) {
+    let mut range: Option<String> = None;
+    let mut min_lines = 1;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--ai-only-files" => {
+                if i + 1 >= args.len() || args[i + 1].starts_with('-') {
+                    eprintln!("Error: --ai-only-files requires a commit or range");
+                    std::process::exit(1);
+                }
+                range = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--min-lines" => {
+                let Some(n) = args.get(i + 1).and_then(|v| v.parse::<usize>().ok()) else {
+                    eprintln!("Error: --min-lines requires a number");
+                    std::process::exit(1);
+                };
+                min_lines = n;
+                i += 2;
+            }
+            arg => {
+                eprintln!("Unexpected argument with --ai-only-files: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let range = range.unwrap_or_default();
+    match commands::blame::ai_only_files(repo, &range, min_lines) {
+        Ok(files) => {
+            for (file, _) in files {
+                println!("{}", file);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to list AI files: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `git-ai commit [--sign-authorship] [<git commit args>...]`: run `git commit` through the
+/// proxy, signing the authorship note even when `sign_authorship` is off in the config.
+fn handle_commit(args: &[String]) {
+    let mut git_args = vec!["commit".to_string()];
+    let mut passthrough = false;
+    for arg in args {
+        if !passthrough && arg == "--sign-authorship" {
+            force_signing();
+            continue;
+        }
+        passthrough |= arg == "--";
+        git_args.push(arg.clone());
+    }
+    commands::git_handlers::handle_git(&git_args);
+}
+
 fn handle_ai_diff(args: &[String]) {
     let current_dir = env::current_dir()
         .unwrap_or_else(|_| std::path::PathBuf::from("."))
@@ -868,9 +1300,21 @@ fn handle_stats(args: &[String]) {
     };
     // Parse stats-specific arguments
     let mut json_output = false;
+    let mut csv_output = false;
+    let mut csv_header = true;
     let mut commit_sha = None;
     let mut commit_range: Option<CommitRange> = None;
     let mut ignore_patterns: Vec<String> = Vec::new();
+    let mut by_file = false;
+    let mut top: Option<usize> = None;
+    let mut diff_range: Option<(String, String, bool)> = None;
+    let mut fail_under: Option<f64> = None;
+    let mut fail_over: Option<f64> = None;
+    let mut history = false;
+    let mut stream = false;
+    let mut since: Option<String> = None;
+    let mut model: Option<String> = None;
+    let mut team_map = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -879,6 +1323,95 @@ fn handle_stats(args: &[String]) {
                 json_output = true;
                 i += 1;
             }
+            "--by-file" => {
+                by_file = true;
+                i += 1;
+            }
+            "--csv" => {
+                csv_output = true;
+                i += 1;
+            }
+            "--csv-header" => {
+                csv_header = true;
+                i += 1;
+            }
+            "--history" => {
+                history = true;
+                i += 1;
+            }
+            "--stream" => {
+                stream = true;
+                history = true;
+                i += 1;
+            }
+            "--since" | "--model" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("{} requires a value", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--since" {
+                    since = Some(value.clone());
+                } else {
+                    model = Some(value.clone());
+                }
+                history = true;
+                i += 2;
+            }
+            "--team-map" => {
+                let Some(path) = args.get(i + 1) else {
+                    eprintln!("--team-map requires a file");
+                    std::process::exit(1);
+                };
+                match crate::commands::blame::load_team_map(path) {
+                    Ok(map) => team_map = Some(map),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::process::exit(1);
+                    }
+                }
+                history = true;
+                i += 2;
+            }
+            "--no-header" => {
+                csv_header = false;
+                i += 1;
+            }
+            "--top" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--top requires a number");
+                    std::process::exit(1);
+                }
+                match args[i + 1].parse::<usize>() {
+                    Ok(n) => top = Some(n),
+                    Err(_) => {
+                        eprintln!("Invalid number for --top: {}", args[i + 1]);
+                        std::process::exit(1);
+                    }
+                }
+                i += 2;
+            }
+            "--diff" => {
+                let Some((base, head, symmetric)) =
+                    args.get(i + 1).and_then(|r| split_diff_range(r))
+                else {
+                    eprintln!("--diff requires a range: <base>..<head>");
+                    std::process::exit(1);
+                };
+                diff_range = Some((base.to_string(), head.to_string(), symmetric));
+                i += 2;
+            }
+            "--fail-under" | "--fail-over" => {
+                let Some(pct) = args.get(i + 1).and_then(|v| v.parse::<f64>().ok()) else {
+                    eprintln!("{} requires a percentage", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--fail-under" {
+                    fail_under = Some(pct);
+                } else {
+                    fail_over = Some(pct);
+                }
+                i += 2;
+            }
             "--ignore" => {
                 // Collect all arguments after --ignore until we hit another flag or commit SHA
                 // This supports shell glob expansion: `--ignore *.lock` expands to `--ignore Cargo.lock package.lock`
@@ -945,6 +1478,150 @@ fn handle_stats(args: &[String]) {
         }
     }
 
+    if csv_output && !by_file && diff_range.is_none() {
+        eprintln!("--csv requires --by-file or --diff <base>..<head>");
+        std::process::exit(1);
+    }
+
+    if (fail_under.is_some() || fail_over.is_some()) && diff_range.is_none() {
+        eprintln!("--fail-under/--fail-over require --diff <base>..<head>");
+        std::process::exit(1);
+    }
+
+    if stream {
+        let stdout = std::io::stdout();
+        if let Err(e) = history_stats::stream_history_stats(
+            &repo,
+            since.as_deref(),
+            model.as_deref(),
+            &mut stdout.lock(),
+        ) {
+            eprintln!("Stats failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if history {
+        match history_stats::history_stats(
+            &repo,
+            since.as_deref(),
+            model.as_deref(),
+            &ignore_patterns,
+            team_map.as_ref(),
+        ) {
+            Ok(stats) => {
+                if json_output {
+                    println!("{}", serde_json::to_string(&stats).unwrap());
+                } else {
+                    history_stats::print_history_stats(&stats);
+                }
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some((base, head, symmetric)) = diff_range {
+        let from = if symmetric {
+            match repo.merge_base(base.clone(), head.clone()) {
+                Ok(merge_base) => merge_base,
+                Err(e) => {
+                    eprintln!("Stats failed: no merge base for {}...{}: {}", base, head, e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            base.clone()
+        };
+        let share = match diff_ai_share(&repo, &from, &head, &ignore_patterns) {
+            Ok(share) => share,
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let percent = share.ai_percent();
+        if json_output {
+            println!("{}", serde_json::to_string(&share).unwrap());
+        } else if csv_output {
+            print!("{}", share.to_csv(csv_header));
+        } else {
+            println!(
+                "{}{}{}: {} of {} added lines AI-authored ({:.1}%)",
+                base,
+                if symmetric { "..." } else { ".." },
+                head,
+                share.ai_lines,
+                share.added_lines,
+                percent
+            );
+        }
+
+        // Nothing was added, so there is no share to gate on
+        if share.added_lines == 0 {
+            return;
+        }
+        if let Some(min) = fail_under
+            && percent < min
+        {
+            eprintln!(
+                "AI-authored share {:.1}% is below --fail-under {}%",
+                percent, min
+            );
+            std::process::exit(1);
+        }
+        if let Some(max) = fail_over
+            && percent > max
+        {
+            eprintln!(
+                "AI-authored share {:.1}% is above --fail-over {}%",
+                percent, max
+            );
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if by_file {
+        let commit_shas = match &commit_range {
+            Some(range) => range.all_commits(),
+            None => {
+                let rev = commit_sha.clone().unwrap_or_else(|| "HEAD".to_string());
+                match repo.revparse_single(&rev) {
+                    Ok(obj) => vec![obj.id()],
+                    Err(_) => {
+                        eprintln!("No commit found: {}", rev);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        };
+        match range_authorship::rank_files_by_ai_lines(&repo, &commit_shas, &ignore_patterns, top) {
+            Ok(ranks) => {
+                if json_output {
+                    let json_str = serde_json::to_string(&ranks).unwrap();
+                    println!("{}", json_str);
+                } else if csv_output {
+                    print!(
+                        "{}",
+                        range_authorship::file_authorship_ranks_csv(&ranks, csv_header)
+                    );
+                } else {
+                    range_authorship::print_file_authorship_ranks(&ranks);
+                }
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     // Handle commit range if detected
     if let Some(range) = commit_range {
         match range_authorship::range_authorship(range, false, &ignore_patterns) {
@@ -977,6 +1654,34 @@ fn handle_stats(args: &[String]) {
     }
 }
 
+/// The arguments after a checkpoint preset's name that are paths rather than flags
+fn checkpoint_pathspecs(args: &[String]) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        // Skip flags (and the values of --merge-into/--dump-working-log/--kind)
+        if arg == "--merge-into" || arg == "--dump-working-log" || arg == "--kind" {
+            rest.next();
+        } else if !arg.starts_with("--") {
+            paths.push(arg.clone());
+        }
+    }
+    paths
+}
+
+/// Split a `--diff` range into its ends, and whether it is a symmetric `A...B` range, which
+/// is compared from the merge base of its ends like `git diff A...B`.
+fn split_diff_range(range: &str) -> Option<(&str, &str, bool)> {
+    let (base, head, symmetric) = match range.split_once("...") {
+        Some((base, head)) => (base, head, true),
+        None => {
+            let (base, head) = range.split_once("..")?;
+            (base, head, false)
+        }
+    };
+    (!base.is_empty() && !head.is_empty()).then_some((base, head, symmetric))
+}
+
 fn get_all_files_for_mock_ai(working_dir: &str) -> Vec<String> {
     // Find the git repository
     let repo = match find_repository_in_path(working_dir) {
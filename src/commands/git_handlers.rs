@@ -89,6 +89,10 @@ pub struct CommandHooksContext {
     pub fetch_authorship_handle: Option<std::thread::JoinHandle<()>>,
     pub stash_sha: Option<String>,
     pub push_authorship_handle: Option<std::thread::JoinHandle<()>>,
+    /// Extra remote to copy authorship notes to after a push, from `--mirror-notes`
+    pub mirror_notes_remote: Option<String>,
+    /// Buffered `git reset --pathspec-from-file=-` input, removed after the reset
+    pub reset_pathspec_file: Option<std::path::PathBuf>,
     /// VirtualAttributions captured before a pull --rebase --autostash operation.
     /// Used to preserve uncommitted AI attributions that git's internal stash would lose.
     pub stashed_va: Option<VirtualAttributions>,
@@ -137,6 +141,8 @@ pub fn handle_git(args: &[String]) {
             fetch_authorship_handle: None,
             stash_sha: None,
             push_authorship_handle: None,
+            mirror_notes_remote: None,
+            reset_pathspec_file: None,
             stashed_va: None,
         };
 
@@ -320,8 +326,13 @@ fn run_pre_command_hooks(
                 rebase_hooks::pre_rebase_hook(parsed_args, repository, command_hooks_context);
             }
             Some("reset") => {
+                command_hooks_context.reset_pathspec_file =
+                    reset_hooks::buffer_stdin_pathspecs(&mut parsed_args.command_args, repository);
                 reset_hooks::pre_reset_hook(parsed_args, repository);
             }
+            Some("merge") => {
+                merge_hooks::pre_merge_hook(parsed_args, repository);
+            }
             Some("cherry-pick") => {
                 cherry_pick_hooks::pre_cherry_pick_hook(
                     parsed_args,
@@ -330,8 +341,14 @@ fn run_pre_command_hooks(
                 );
             }
             Some("push") => {
-                command_hooks_context.push_authorship_handle =
-                    push_hooks::push_pre_command_hook(parsed_args, repository);
+                command_hooks_context.mirror_notes_remote =
+                    push_hooks::take_mirror_notes_remote(&mut parsed_args.command_args);
+                if push_hooks::take_no_authorship_flag(&mut parsed_args.command_args) {
+                    push_hooks::warn_authorship_not_pushed(repository);
+                } else {
+                    command_hooks_context.push_authorship_handle =
+                        push_hooks::push_pre_command_hook(parsed_args, repository);
+                }
             }
             Some("fetch") => {
                 command_hooks_context.fetch_authorship_handle =
@@ -411,7 +428,12 @@ fn run_post_command_hooks(
                 exit_status,
                 command_hooks_context,
             ),
-            Some("reset") => reset_hooks::post_reset_hook(parsed_args, repository, exit_status),
+            Some("reset") => {
+                reset_hooks::post_reset_hook(parsed_args, repository, exit_status);
+                if let Some(path) = command_hooks_context.reset_pathspec_file.take() {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
             Some("merge") => merge_hooks::post_merge_hook(parsed_args, exit_status, repository),
             Some("rebase") => rebase_hooks::handle_rebase_post_command(
                 command_hooks_context,
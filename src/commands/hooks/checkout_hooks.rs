@@ -152,6 +152,10 @@ pub fn post_checkout_hook(
 /// Remove attributions for specific files from working log (pathspec checkout case).
 fn remove_attributions_for_pathspecs(repository: &Repository, head: &str, pathspecs: &[String]) {
     let working_log = repository.storage.working_log_for_base_commit(head);
+    let Ok(lock) = working_log.lock() else {
+        debug_log("Failed to lock working log to remove checked-out pathspecs");
+        return;
+    };
 
     // Filter INITIAL attributions
     let initial = working_log.read_initial_attributions();
@@ -161,7 +165,8 @@ fn remove_attributions_for_pathspecs(repository: &Repository, head: &str, pathsp
             .into_iter()
             .filter(|(file, _)| !matches_any_pathspec(file, pathspecs))
             .collect();
-        let _ = working_log.write_initial_attributions(filtered_files, initial.prompts);
+        let _ =
+            working_log.write_initial_attributions_locked(filtered_files, initial.prompts, &lock);
     }
 
     // Filter checkpoints
@@ -175,7 +180,7 @@ fn remove_attributions_for_pathspecs(repository: &Repository, head: &str, pathsp
             })
             .filter(|cp| !cp.entries.is_empty())
             .collect();
-        let _ = working_log.write_all_checkpoints(&filtered);
+        let _ = working_log.write_all_checkpoints(&filtered, &lock);
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::authorship::pre_commit;
 use crate::commands::git_handlers::CommandHooksContext;
-use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run};
+use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run, is_no_verify};
 use crate::git::repository::Repository;
 use crate::git::rewrite_log::RewriteLogEvent;
 use crate::utils::debug_log;
@@ -13,7 +13,8 @@ pub fn commit_pre_command_hook(
         return false;
     }
 
-    // store HEAD context for post-command hook
+    // store HEAD context for post-command hook. On a detached HEAD this is the commit
+    // itself, which is all post-commit needs to find the working log
     repository.require_pre_command_head();
 
     let default_author = get_commit_default_author(repository, &parsed_args.command_args);
@@ -28,6 +29,15 @@ pub fn commit_pre_command_hook(
             );
             return false;
         }
+        // The human checkpoint still runs under --no-verify so hand edits aren't credited to
+        // AI, but like git's own hooks, a failure there no longer blocks the commit
+        if is_no_verify(&parsed_args.command_args) {
+            eprintln!(
+                "Pre-commit failed: {} (committing anyway because of --no-verify)",
+                e
+            );
+            return false;
+        }
         eprintln!("Pre-commit failed: {}", e);
         std::process::exit(1);
     }
@@ -104,7 +114,7 @@ pub fn get_commit_default_author(repo: &Repository, args: &[String]) -> String {
         && let Ok(Some(resolved_author)) = repo.resolve_author_spec(&author_spec)
         && !resolved_author.trim().is_empty()
     {
-        return resolved_author.trim().to_string();
+        return repo.mailmap_identity(resolved_author.trim());
     }
 
     // Normal precedence when --author is not specified:
@@ -166,7 +176,7 @@ pub fn get_commit_default_author(repo: &Repository, args: &[String]) -> String {
 
     // Format the author string based on what we have
     match (author_name, author_email) {
-        (Some(name), Some(email)) => format!("{} <{}>", name, email),
+        (Some(name), Some(email)) => repo.mailmap_identity(&format!("{} <{}>", name, email)),
         (Some(name), None) => name,
         (None, Some(email)) => email,
         (None, None) => {
@@ -176,6 +186,20 @@ pub fn get_commit_default_author(repo: &Repository, args: &[String]) -> String {
     }
 }
 
+/// A checkpoint author's `user.name`, canonicalized through the repository's mailmap
+/// together with `user.email` so it matches `git log --use-mailmap`.
+pub fn mailmap_user_name(repo: &Repository, name: String) -> String {
+    let email = match repo.config_get_str("user.email") {
+        Ok(Some(email)) if !email.trim().is_empty() => email,
+        _ => return name,
+    };
+    let mapped = repo.mailmap_identity(&format!("{} <{}>", name, email.trim()));
+    match mapped.split_once(" <") {
+        Some((mapped_name, _)) => mapped_name.to_string(),
+        None => name,
+    }
+}
+
 fn extract_author_from_args(args: &[String]) -> Option<String> {
     let mut i = 0;
     while i < args.len() {
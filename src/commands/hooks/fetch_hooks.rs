@@ -1,8 +1,10 @@
 use crate::authorship::virtual_attribution::{VirtualAttributions, restore_stashed_va};
 use crate::commands::git_handlers::CommandHooksContext;
 use crate::commands::hooks::commit_hooks::get_commit_default_author;
+use crate::commands::hooks::merge_hooks::log_merge_commit_if_created;
 use crate::commands::hooks::rebase_hooks::build_rebase_commit_mappings;
 use crate::commands::upgrade;
+use crate::error::GitAiError;
 use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run};
 use crate::git::repository::{Repository, exec_git, find_repository};
 use crate::git::rewrite_log::RewriteLogEvent;
@@ -20,17 +22,30 @@ pub fn fetch_pull_pre_command_hook(
         return None;
     }
 
-    crate::observability::spawn_background_flush();
-
     // Extract the remote name
     let remote = match fetch_remote_from_args(repository, parsed_args) {
-        Ok(remote) => remote,
-        Err(_) => {
-            debug_log("failed to extract remote for authorship fetch; skipping");
+        Ok(Some(remote)) => remote,
+        Ok(None) => {
+            eprintln!("No git remotes found.");
+            std::process::exit(1);
+        }
+        Err(GitAiError::Generic(message)) => {
+            eprintln!("warning: {}", message);
+            return None;
+        }
+        Err(e) => {
+            debug_log(&format!(
+                "failed to extract remote for authorship fetch: {}; skipping",
+                e
+            ));
             return None;
         }
     };
 
+    warn_if_shallow(parsed_args, repository);
+
+    crate::observability::spawn_background_flush();
+
     // Clone what we need for the background thread
     let global_args = repository.global_args_for_exec();
 
@@ -51,6 +66,20 @@ pub fn fetch_pull_pre_command_hook(
     }))
 }
 
+/// Notes are fetched in full, but in a shallow clone `git blame` credits every line from
+/// the missing history to the boundary commit, so attribution for those lines is lost.
+fn warn_if_shallow(parsed_args: &ParsedGitInvocation, repository: &Repository) {
+    let unshallows = parsed_args
+        .command_args
+        .iter()
+        .any(|arg| arg == "--unshallow");
+    if !unshallows && repository.is_shallow() {
+        eprintln!(
+            "warning: this is a shallow clone, so AI attribution is unavailable for lines last changed before its oldest commit; run git fetch --unshallow to restore it"
+        );
+    }
+}
+
 /// Pre-command hook for git pull.
 /// In addition to the standard fetch operations, this captures VirtualAttributions
 /// when pull --rebase --autostash is detected to preserve AI authorship.
@@ -181,10 +210,13 @@ pub fn pull_post_command_hook(
         return;
     }
 
-    // Handle committed authorship rewriting for pull --rebase
+    // Handle committed authorship rewriting for pull --rebase, or the merge commit a
+    // merging pull created
     let config = get_pull_rebase_autostash_config(parsed_args, repository);
     if config.is_rebase {
         process_completed_pull_rebase(repository, &old_head, &new_head);
+    } else {
+        log_merge_commit_if_created(parsed_args, repository);
     }
 }
 
@@ -7,6 +7,12 @@ use crate::{
     },
 };
 
+pub fn pre_merge_hook(parsed_args: &ParsedGitInvocation, repository: &mut Repository) {
+    if !parsed_args.has_command_flag("--squash") {
+        repository.require_pre_command_head();
+    }
+}
+
 pub fn post_merge_hook(
     parsed_args: &ParsedGitInvocation,
     exit_status: std::process::ExitStatus,
@@ -46,4 +52,45 @@ pub fn post_merge_hook(
             true,
         );
     }
+
+    if !parsed_args.has_command_flag("--squash")
+        && exit_status.success()
+        && !is_dry_run(&parsed_args.command_args)
+    {
+        log_merge_commit_if_created(parsed_args, repository);
+    }
+}
+
+/// A merge that commits on its own (no conflicts, or several branches), including one made
+/// by `git pull`, never goes through `git commit`, so its authorship is combined from the
+/// rewrite log here.
+pub fn log_merge_commit_if_created(parsed_args: &ParsedGitInvocation, repository: &mut Repository) {
+    let Ok(head_sha) = repository.head().and_then(|head| head.target()) else {
+        return;
+    };
+    if repository.pre_command_base_commit.as_deref() == Some(head_sha.as_str()) {
+        return;
+    }
+    let is_merge_commit = repository
+        .find_commit(head_sha.clone())
+        .and_then(|commit| commit.parent_count())
+        .is_ok_and(|count| count > 1);
+    if !is_merge_commit {
+        return;
+    }
+
+    let target_branch = repository
+        .head()
+        .ok()
+        .and_then(|head| head.name().map(|name| name.to_string()))
+        .unwrap_or_else(|| "HEAD".to_string());
+    let source_branch = parsed_args.pos_command(0).unwrap_or_default();
+    let commit_author = get_commit_default_author(repository, &parsed_args.command_args);
+
+    repository.handle_rewrite_log_event(
+        RewriteLogEvent::merge(source_branch, target_branch, Some(head_sha), true, vec![]),
+        commit_author,
+        false,
+        true,
+    );
 }
@@ -1,6 +1,7 @@
 use crate::commands::git_handlers::CommandHooksContext;
 use crate::commands::upgrade;
 use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run};
+use crate::git::refs::ref_exists;
 use crate::git::repository::{Repository, find_repository};
 use crate::git::sync_authorship::push_authorship_notes;
 use crate::utils::debug_log;
@@ -81,9 +82,9 @@ pub fn push_pre_command_hook(
 }
 
 pub fn push_post_command_hook(
-    _repository: &Repository,
-    _parsed_args: &ParsedGitInvocation,
-    _exit_status: std::process::ExitStatus,
+    repository: &Repository,
+    parsed_args: &ParsedGitInvocation,
+    exit_status: std::process::ExitStatus,
     command_hooks_context: &mut CommandHooksContext,
 ) {
     // Always wait for the authorship push thread to complete if it was started,
@@ -92,6 +93,63 @@ pub fn push_post_command_hook(
     if let Some(handle) = command_hooks_context.push_authorship_handle.take() {
         let _ = handle.join();
     }
+
+    // Mirror notes only once the primary push went through; a failure here is reported but
+    // never changes the push's exit status.
+    if let Some(mirror_remote) = command_hooks_context.mirror_notes_remote.take()
+        && exit_status.success()
+        && !is_dry_run(&parsed_args.command_args)
+    {
+        debug_log(&format!(
+            "mirroring authorship notes to remote: {}",
+            mirror_remote
+        ));
+        if let Err(e) = push_authorship_notes(repository, &mirror_remote) {
+            eprintln!(
+                "warning: failed to mirror authorship notes to {}: {}",
+                mirror_remote, e
+            );
+        }
+    }
+}
+
+/// Remove git-ai's `--mirror-notes <remote>` / `--mirror-notes=<remote>` from the push
+/// arguments (git would reject it) and return the remote.
+pub fn take_mirror_notes_remote(args: &mut Vec<String>) -> Option<String> {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let index = args[..end]
+        .iter()
+        .position(|a| a == "--mirror-notes" || a.starts_with("--mirror-notes="))?;
+
+    let arg = args.remove(index);
+    match arg.strip_prefix("--mirror-notes=") {
+        Some(remote) => Some(remote.to_string()),
+        None if index < end - 1 => Some(args.remove(index)),
+        None => None,
+    }
+    .filter(|remote| !remote.is_empty())
+}
+
+/// Remove git-ai's `--no-authorship` flag from the push arguments and report whether it was
+/// given. Such pushes go to git verbatim, without authorship notes.
+pub fn take_no_authorship_flag(args: &mut Vec<String>) -> bool {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    match args[..end].iter().position(|a| a == "--no-authorship") {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Tell the user once per push that their local authorship notes are staying behind.
+pub fn warn_authorship_not_pushed(repository: &Repository) {
+    if ref_exists(repository, "refs/notes/ai") {
+        eprintln!(
+            "warning: pushing without authorship notes (--no-authorship); AI attribution stays local to this clone"
+        );
+    }
 }
 
 fn extract_remote_from_push_args(args: &[String], known_remotes: &[String]) -> Option<String> {
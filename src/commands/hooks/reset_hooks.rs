@@ -4,6 +4,8 @@ use crate::{
     git::{cli_parser::ParsedGitInvocation, repository::Repository, rewrite_log::ResetKind},
     utils::debug_log,
 };
+use std::io::Read;
+use std::path::PathBuf;
 
 pub fn pre_reset_hook(parsed_args: &ParsedGitInvocation, repository: &mut Repository) {
     // Get the human author for the checkpoint
@@ -20,6 +22,7 @@ pub fn pre_reset_hook(parsed_args: &ParsedGitInvocation, repository: &mut Reposi
         true,
         None,
         true,
+        false,
     );
 
     // Capture HEAD before reset happens
@@ -34,6 +37,49 @@ pub fn pre_reset_hook(parsed_args: &ParsedGitInvocation, repository: &mut Reposi
     }
 }
 
+/// With `--pathspec-from-file=-` git reads the pathspecs from stdin, leaving nothing for the
+/// post-reset hook to read. Buffer stdin into a file under .git/ai and point the arguments at
+/// it so git and the hook see the same pathspecs. Returns the file, to be removed once the
+/// reset is done. Exits if stdin can't be buffered, since git would then reset every path.
+pub fn buffer_stdin_pathspecs(args: &mut [String], repository: &Repository) -> Option<PathBuf> {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let index = (0..end).find_map(|i| {
+        if args[i] == "--pathspec-from-file=-" {
+            Some(i)
+        } else if args[i] == "--pathspec-from-file" && args.get(i + 1).is_some_and(|v| v == "-") {
+            Some(i + 1)
+        } else {
+            None
+        }
+    })?;
+
+    // Once stdin has been read git would see no pathspecs and reset the whole index, so a
+    // failure from here on aborts the reset instead of running it
+    let mut pathspecs = Vec::new();
+    if let Err(e) = std::io::stdin().read_to_end(&mut pathspecs) {
+        eprintln!("Failed to read pathspecs from stdin: {}", e);
+        std::process::exit(1);
+    }
+    let path = repository
+        .storage
+        .repo_path
+        .join("ai")
+        .join(format!("reset-pathspecs-{}", std::process::id()));
+    if let Err(e) = std::fs::write(&path, &pathspecs) {
+        let _ = std::fs::remove_file(&path);
+        eprintln!("Failed to buffer pathspecs from stdin: {}", e);
+        std::process::exit(1);
+    }
+
+    let file = path.to_string_lossy().to_string();
+    args[index] = if args[index] == "-" {
+        file
+    } else {
+        format!("--pathspec-from-file={}", file)
+    };
+    Some(path)
+}
+
 pub fn post_reset_hook(
     parsed_args: &ParsedGitInvocation,
     repository: &mut Repository,
@@ -325,9 +371,11 @@ fn handle_reset_pathspec_preserve_working_dir(
 
     // Save merged working log for HEAD (which hasn't moved)
     let head_working_log = repository.storage.working_log_for_base_commit(new_head_sha);
-    let _ = head_working_log.reset_working_log();
-    for checkpoint in merged_checkpoints {
-        let _ = head_working_log.append_checkpoint(&checkpoint);
+    if let Ok(lock) = head_working_log.lock() {
+        let _ = head_working_log.reset_working_log(&lock);
+        for checkpoint in merged_checkpoints {
+            let _ = head_working_log.append_checkpoint(&checkpoint, &lock);
+        }
     }
 
     // Clean up the temporary working log for target_commit_sha (unless it's the same as HEAD)
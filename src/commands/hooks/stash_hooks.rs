@@ -38,6 +38,7 @@ pub fn pre_stash_hook(
             true,
             None,
             true, // same optimizations as pre_commit.rs
+            false,
         ) {
             Ok(result) => result,
             Err(e) => {
@@ -154,7 +155,7 @@ fn save_stash_authorship_log(repo: &Repository, pathspecs: &[String]) -> Result<
     // Save as git note at refs/notes/ai-stash
     let json = authorship_log
         .serialize_to_string()
-        .map_err(|e| GitAiError::Generic(format!("Failed to serialize authorship log: {}", e)))?;
+        .map_err(|e| GitAiError::Serialization(format!("authorship log: {}", e)))?;
     save_stash_note(repo, &stash_sha, &json)?;
 
     debug_log(&format!(
@@ -245,6 +246,7 @@ fn restore_stash_attributions(
     if !initial_files.is_empty() || !initial_prompts.is_empty() {
         let working_log = repo.storage.working_log_for_base_commit(&head_sha);
         working_log.write_initial_attributions(initial_files.clone(), initial_prompts.clone())?;
+        repo.storage.write_last_checkpoint_base(&head_sha)?;
 
         debug_log(&format!(
             "✓ Wrote INITIAL attributions to working log for {}",
@@ -0,0 +1,155 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{
+    CommitAuthorship, get_commits_with_notes_from_list, notes_add_authorship_log,
+};
+use crate::git::repository::{Repository, exec_git};
+use std::collections::BTreeSet;
+
+/// Result of seeding a human baseline from plain git blame.
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportBlameSummary {
+    pub files: usize,
+    pub seeded_commits: Vec<String>,
+    pub skipped_commits: usize,
+}
+
+pub fn handle_import_blame(args: &[String]) {
+    let mut all = false;
+    let mut files: Vec<String> = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "--all" => all = true,
+            _ if arg.starts_with('-') => {
+                eprintln!("Unknown import-blame argument: {}", arg);
+                std::process::exit(1);
+            }
+            _ => files.push(arg.clone()),
+        }
+    }
+
+    if all != files.is_empty() {
+        eprintln!("Error: import-blame requires either <file>... or --all");
+        eprintln!("Usage: git-ai import-blame <file>... | --all");
+        std::process::exit(1);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let files = if all {
+        match tracked_files_at_head(&repo) {
+            Ok(files) => files,
+            Err(e) => {
+                eprintln!("Failed to list tracked files: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        files
+    };
+
+    match import_blame(&repo, &files) {
+        Ok(summary) => {
+            println!(
+                "Seeded human baseline for {} commit(s) across {} file(s) ({} already had authorship notes)",
+                summary.seeded_commits.len(),
+                summary.files,
+                summary.skipped_commits
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to import blame: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs plain git blame over each file at HEAD and writes an empty authorship
+/// log for every commit that owns lines but has no note yet. A note with no
+/// attestations marks all of that commit's lines as human-authored (by the
+/// commit author). Commits that already carry a note are left untouched.
+pub fn import_blame(repo: &Repository, files: &[String]) -> Result<ImportBlameSummary, GitAiError> {
+    let mut commits = BTreeSet::new();
+    for file in files {
+        commits.extend(blame_commits_at_head(repo, file)?);
+    }
+
+    let commits: Vec<String> = commits.into_iter().collect();
+    let mut summary = ImportBlameSummary {
+        files: files.len(),
+        ..Default::default()
+    };
+
+    for entry in get_commits_with_notes_from_list(repo, &commits)? {
+        match entry {
+            CommitAuthorship::Log { .. } => summary.skipped_commits += 1,
+            CommitAuthorship::NoLog { sha, .. } => {
+                let mut authorship_log = AuthorshipLog::new();
+                authorship_log.metadata.base_commit_sha = sha.clone();
+                notes_add_authorship_log(repo, &sha, &mut authorship_log)?;
+                summary.seeded_commits.push(sha);
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Distinct commits that own at least one line of `file` at HEAD.
+fn blame_commits_at_head(repo: &Repository, file: &str) -> Result<BTreeSet<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("blame".to_string());
+    args.push("--porcelain".to_string());
+    args.push("HEAD".to_string());
+    args.push("--".to_string());
+    args.push(file.to_string());
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let mut commits = BTreeSet::new();
+    for line in stdout.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        // Porcelain hunk headers are "<sha> <orig_line> <final_line>[ <num_lines>]"
+        let mut fields = line.split(' ');
+        if let (Some(sha), Some(orig), Some(_)) = (fields.next(), fields.next(), fields.next())
+            && sha.len() == 40
+            && sha.chars().all(|c| c.is_ascii_hexdigit())
+            && orig.parse::<u32>().is_ok()
+        {
+            commits.insert(sha.to_string());
+        }
+    }
+
+    Ok(commits)
+}
+
+fn tracked_files_at_head(repo: &Repository) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("ls-tree".to_string());
+    args.push("-r".to_string());
+    args.push("-z".to_string());
+    args.push("HEAD".to_string());
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| GitAiError::Generic("Failed to parse git ls-tree output".to_string()))?;
+
+    // Entries are "<mode> <type> <oid>\t<path>"; only blobs can be blamed (skip submodules)
+    Ok(stdout
+        .split('\0')
+        .filter_map(|entry| entry.split_once('\t'))
+        .filter(|(meta, _)| meta.split(' ').nth(1) == Some("blob"))
+        .map(|(_, path)| path.to_string())
+        .collect())
+}
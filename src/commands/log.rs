@@ -0,0 +1,170 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::get_reference_as_authorship_log_v3;
+use crate::git::repository::{Repository, exec_git};
+use std::collections::BTreeSet;
+use std::fmt;
+
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+
+/// One-line digest of a commit's authorship log, as shown by `git-ai log`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthorshipSummary {
+    pub ai_lines: u32,
+    /// `<tool>/<model>` of every session in the log
+    pub agents: Vec<String>,
+    /// AI-written lines a human then changed before committing
+    pub overridden_lines: u32,
+}
+
+impl AuthorshipSummary {
+    pub fn from_log(log: &AuthorshipLog) -> Self {
+        let ai_lines = log
+            .attestations
+            .iter()
+            .flat_map(|file| &file.entries)
+            .flat_map(|entry| &entry.line_ranges)
+            .map(|range| match range {
+                LineRange::Single(_) => 1,
+                LineRange::Range(start, end) => end.saturating_sub(*start) + 1,
+            })
+            .sum();
+        let agents: BTreeSet<String> = log
+            .metadata
+            .prompts
+            .values()
+            .map(|p| format!("{}/{}", p.agent_id.tool_label(), p.agent_id.model))
+            .collect();
+        AuthorshipSummary {
+            ai_lines,
+            agents: agents.into_iter().collect(),
+            overridden_lines: log
+                .metadata
+                .prompts
+                .values()
+                .map(|p| p.overriden_lines)
+                .sum(),
+        }
+    }
+}
+
+impl fmt::Display for AuthorshipSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} AI line{}",
+            self.ai_lines,
+            if self.ai_lines == 1 { "" } else { "s" }
+        )?;
+        if !self.agents.is_empty() {
+            write!(f, " by {}", self.agents.join(", "))?;
+        }
+        match self.overridden_lines {
+            0 => write!(f, "; no human overrides"),
+            n => write!(f, "; {} overridden by humans", n),
+        }
+    }
+}
+
+/// A commit from the walk, with its authorship summary when it has a note.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub sha: String,
+    pub short_sha: String,
+    pub author: String,
+    pub date: String,
+    pub message: String,
+    pub summary: Option<AuthorshipSummary>,
+}
+
+pub fn handle_log(args: &[String]) {
+    let mut oneline = false;
+    let mut walk_args = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--oneline" => oneline = true,
+            _ => walk_args.push(arg.clone()),
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let entries = match log_entries(&repo, &walk_args) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to read commit history: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    for (index, entry) in entries.iter().enumerate() {
+        if oneline {
+            let subject = entry.message.lines().next().unwrap_or("");
+            match &entry.summary {
+                Some(summary) => println!("{} {} [{}]", entry.short_sha, subject, summary),
+                None => println!("{} {}", entry.short_sha, subject),
+            }
+            continue;
+        }
+
+        if index > 0 {
+            println!();
+        }
+        println!("commit {}", entry.sha);
+        println!("Author: {}", entry.author);
+        println!("Date:   {}", entry.date);
+        if let Some(summary) = &entry.summary {
+            println!("AI:     {}", summary);
+        }
+        println!();
+        for line in entry.message.trim_end().lines() {
+            println!("    {}", line);
+        }
+    }
+}
+
+/// Walk commits with `git log <walk_args>` (revision ranges, paths and limiting options
+/// pass straight through) and summarize each commit's authorship log. Commits without
+/// a readable log get no summary.
+pub fn log_entries(repo: &Repository, walk_args: &[String]) -> Result<Vec<LogEntry>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push(format!(
+        "--format=%H{sep}%h{sep}%an <%ae>{sep}%ad{sep}%B{end}",
+        sep = "%x1f",
+        end = "%x1e"
+    ));
+    args.extend(walk_args.iter().cloned());
+    let output = exec_git(&args)?;
+
+    Ok(String::from_utf8(output.stdout)?
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(5, FIELD_SEPARATOR);
+            let sha = fields.next()?.to_string();
+            if sha.is_empty() {
+                return None;
+            }
+            let summary = get_reference_as_authorship_log_v3(repo, &sha)
+                .ok()
+                .map(|log| AuthorshipSummary::from_log(&log));
+            Some(LogEntry {
+                short_sha: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                message: fields.next()?.to_string(),
+                sha,
+                summary,
+            })
+        })
+        .collect())
+}
@@ -4,16 +4,22 @@ pub mod checkpoint_agent;
 pub mod ci_handlers;
 pub mod config;
 pub mod diff;
+pub mod doctor;
 pub mod exchange_nonce;
+pub mod export;
 pub mod flush_cas;
 pub mod flush_logs;
 pub mod flush_metrics_db;
+pub mod gc;
 pub mod git_ai_handlers;
 pub mod git_handlers;
 pub mod hooks;
+pub mod import_blame;
 pub mod install_hooks;
+pub mod log;
 pub mod login;
 pub mod logout;
+pub mod notes_merge;
 pub mod personal_dashboard;
 pub mod prompt_picker;
 pub mod prompts_db;
@@ -25,3 +31,4 @@ pub mod squash_authorship;
 pub mod status;
 pub mod sync_prompts;
 pub mod upgrade;
+pub mod verify;
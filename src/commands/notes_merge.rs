@@ -0,0 +1,153 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{
+    list_notes_in_ref, notes_add, ref_exists, show_authorship_note, show_note_in_ref,
+    strip_signature,
+};
+use crate::git::repository::Repository;
+
+/// Result of unioning another notes ref into refs/notes/ai.
+#[derive(Debug, Default, PartialEq)]
+pub struct NotesMergeSummary {
+    /// Commits whose note only existed in the source ref
+    pub copied: usize,
+    /// Commits whose local note was unioned with the source note
+    pub unioned: usize,
+    /// Commits whose notes were already identical
+    pub unchanged: usize,
+    /// Source notes that could not be parsed as authorship logs
+    pub skipped: usize,
+    /// Commits whose local note could not be parsed, left as they are (without `--force`)
+    pub unparseable_local: Vec<String>,
+    /// Unparseable local notes replaced with the source note (with `--force`)
+    pub overwritten: usize,
+    /// Lines attributed to different sessions on each side (local attribution kept)
+    pub conflicting_lines: usize,
+}
+
+pub fn handle_notes_merge(args: &[String]) {
+    let force = args.iter().any(|arg| arg == "--force");
+    let refs: Vec<&String> = args.iter().filter(|arg| *arg != "--force").collect();
+    let source = match refs.as_slice() {
+        [source] if !source.starts_with('-') => *source,
+        _ => {
+            eprintln!("Error: notes-merge requires exactly one notes ref");
+            eprintln!("Usage: git-ai notes-merge [--force] <ref>");
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let source_ref = if ref_exists(&repo, source) {
+        source.clone()
+    } else if ref_exists(&repo, &format!("refs/notes/{}", source)) {
+        format!("refs/notes/{}", source)
+    } else {
+        eprintln!("Error: notes ref not found: {}", source);
+        std::process::exit(1);
+    };
+
+    match merge_notes(&repo, &source_ref, force) {
+        Ok(summary) => {
+            println!(
+                "Merged authorship notes from {}: {} copied, {} unioned, {} unchanged",
+                source_ref, summary.copied, summary.unioned, summary.unchanged
+            );
+            if summary.conflicting_lines > 0 {
+                println!(
+                    "{} line(s) were attributed to different sessions; kept local attribution",
+                    summary.conflicting_lines
+                );
+            }
+            if summary.skipped > 0 {
+                eprintln!(
+                    "Warning: skipped {} unparseable note(s) in {}",
+                    summary.skipped, source_ref
+                );
+            }
+            if summary.overwritten > 0 {
+                eprintln!(
+                    "Replaced {} unparseable local note(s) with the note from {}",
+                    summary.overwritten, source_ref
+                );
+            }
+            if !summary.unparseable_local.is_empty() {
+                eprintln!(
+                    "Warning: kept {} unparseable local note(s); rerun with --force to replace them with the note from {}:",
+                    summary.unparseable_local.len(),
+                    source_ref
+                );
+                for commit_sha in &summary.unparseable_local {
+                    eprintln!("  {}", commit_sha);
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to merge notes: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Union every authorship log in `source_ref` into refs/notes/ai with [`AuthorshipLog::merge`].
+/// Unlike the `ours` strategy used after fetch, commits annotated on both sides keep the
+/// attributions from each. A local note that can't be parsed is only replaced when `force`
+/// is set, since it may be newer than anything this git-ai can read.
+pub fn merge_notes(
+    repo: &Repository,
+    source_ref: &str,
+    force: bool,
+) -> Result<NotesMergeSummary, GitAiError> {
+    let mut summary = NotesMergeSummary::default();
+
+    for commit_sha in list_notes_in_ref(repo, source_ref)? {
+        let Some(theirs_content) = show_note_in_ref(repo, source_ref, &commit_sha) else {
+            continue;
+        };
+        let Ok(theirs) = AuthorshipLog::deserialize_from_string(&theirs_content) else {
+            summary.skipped += 1;
+            continue;
+        };
+
+        let Some(ours_content) = show_authorship_note(repo, &commit_sha) else {
+            notes_add(repo, &commit_sha, &theirs_content)?;
+            summary.copied += 1;
+            continue;
+        };
+        let Ok(mut merged) = AuthorshipLog::deserialize_from_string(&ours_content) else {
+            if force {
+                notes_add(repo, &commit_sha, &theirs_content)?;
+                summary.overwritten += 1;
+            } else {
+                summary.unparseable_local.push(commit_sha);
+            }
+            continue;
+        };
+
+        let unmerged = merged.clone();
+        summary.conflicting_lines += merged.merge(&theirs);
+        if merged == unmerged {
+            summary.unchanged += 1;
+            continue;
+        }
+
+        // The union is neither side's signed content, and signing it with the local key would
+        // claim the other side's attributions, so it is written unsigned
+        strip_signature(&mut merged);
+        let serialized = merged
+            .serialize_to_string()
+            .map_err(|_| GitAiError::Serialization("authorship log".to_string()))?;
+        notes_add(repo, &commit_sha, &serialized)?;
+        summary.unioned += 1;
+    }
+
+    Ok(summary)
+}
@@ -857,6 +857,7 @@ fn fetch_from_git_notes(
 
                     let transcript = AiTranscript {
                         messages: prompt_record.messages.clone(),
+                        truncated: prompt_record.transcript_truncated,
                     };
                     let start_time = transcript.first_message_timestamp_unix();
                     let last_time = transcript.last_message_timestamp_unix();
@@ -1,5 +1,5 @@
 use crate::api::{ApiClient, ApiContext, ApiFileRecord};
-use crate::api::{BundleData, CreateBundleRequest};
+use crate::api::{BUNDLE_FORMAT_VERSION, BundleData, CreateBundleRequest};
 use crate::authorship::prompt_utils::find_prompt_with_db_fallback;
 use crate::authorship::secrets::redact_secrets_from_prompts;
 use crate::commands::diff::{DiffOptions, get_diff_json_filtered};
@@ -195,6 +195,7 @@ pub fn create_bundle(
 
     // Create bundle with prompts and optional files
     let bundle_request = CreateBundleRequest {
+        version: BUNDLE_FORMAT_VERSION,
         title,
         data: BundleData { prompts, files },
     };
@@ -142,6 +142,7 @@ enum ConfigKeyResult {
     Submit,
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_config_key_event(
     config: &mut ShareConfig,
     focused_field: &mut usize,
@@ -54,9 +54,9 @@ fn show_authorship(repo: &Repository, spec: &str) -> Result<(), GitAiError> {
                 if multiple_commits {
                     println!("{}", sha);
                 }
-                let serialized = authorship_log.serialize_to_string().map_err(|_| {
-                    GitAiError::Generic("Failed to serialize authorship log".to_string())
-                })?;
+                let serialized = authorship_log
+                    .serialize_to_string()
+                    .map_err(|_| GitAiError::Serialization("authorship log".to_string()))?;
                 println!("{}", serialized);
             }
             CommitAuthorship::NoLog { sha, .. } => {
@@ -71,7 +71,7 @@ fn show_authorship(repo: &Repository, spec: &str) -> Result<(), GitAiError> {
     Ok(())
 }
 
-fn resolve_commits(repo: &Repository, spec: &str) -> Result<Vec<String>, GitAiError> {
+pub(crate) fn resolve_commits(repo: &Repository, spec: &str) -> Result<Vec<String>, GitAiError> {
     if let Some((start, end)) = spec.split_once("..") {
         if start.is_empty() || end.is_empty() {
             return Err(GitAiError::Generic(
@@ -2,6 +2,7 @@ use crate::authorship::stats::{CommitStats, write_stats_to_terminal};
 use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::authorship::working_log::CheckpointKind;
 use crate::commands::checkpoint;
+use crate::commands::hooks::commit_hooks::mailmap_user_name;
 use crate::error::GitAiError;
 use crate::git::find_repository;
 use crate::git::repo_storage::InitialAttributions;
@@ -46,7 +47,7 @@ fn run_status(json: bool) -> Result<(), GitAiError> {
     let repo = find_repository(&[])?;
 
     let default_user_name = match repo.config_get_str("user.name") {
-        Ok(Some(name)) if !name.trim().is_empty() => name,
+        Ok(Some(name)) if !name.trim().is_empty() => mailmap_user_name(&repo, name),
         _ => "unknown".to_string(),
     };
 
@@ -59,6 +60,7 @@ fn run_status(json: bool) -> Result<(), GitAiError> {
         true,
         None,
         false,
+        false,
     );
 
     let head = repo.head()?;
@@ -312,6 +314,7 @@ fn stats_from_authorship_log_with_override(
             // Calculate time waiting for AI from transcript
             let transcript = crate::authorship::transcript::AiTranscript {
                 messages: prompt_record.messages.clone(),
+                truncated: prompt_record.transcript_truncated,
             };
             stats.time_waiting_for_ai += calculate_waiting_time(&transcript);
         }
@@ -0,0 +1,357 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::signing::{
+    SignatureStatus, sign_authorship_log, verify_authorship_log_signature,
+};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{
+    get_authorship, grep_ai_notes, list_notes_in_ref, notes_add, show_authorship_note,
+};
+use crate::git::repository::{Repository, exec_git};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Per-commit result of `git-ai verify`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitVerification {
+    NoNote,
+    Signature(SignatureStatus),
+}
+
+pub fn handle_verify(args: &[String]) {
+    let mut require_signed = false;
+    let mut fix_base_commit = false;
+    let mut all = false;
+    let mut reachable_only = false;
+    let mut signatures_only = false;
+    let mut revs: Vec<String> = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "--require-signed" => require_signed = true,
+            "--fix-base-commit" => fix_base_commit = true,
+            "--all" => all = true,
+            "--reachable-only" => reachable_only = true,
+            "--signatures" => signatures_only = true,
+            _ if arg.starts_with('-') => {
+                eprintln!("Unknown verify argument: {}", arg);
+                std::process::exit(1);
+            }
+            _ => revs.push(arg.clone()),
+        }
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // --fix-base-commit and --reachable-only default to every note rather than HEAD
+    if revs.is_empty() && !fix_base_commit && !all && !reachable_only {
+        revs.push("HEAD".to_string());
+    }
+    let commits = match target_commits(&repo, &revs, reachable_only) {
+        Ok(commits) => commits,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if fix_base_commit {
+        handle_fix_base_commit(&repo, &commits);
+        return;
+    }
+
+    let mut failures = 0;
+    for commit_sha in &commits {
+        // A note that can't be read fails its own commit; the rest are still checked
+        let result = match verify_commit(&repo, commit_sha) {
+            Ok(result) => result,
+            Err(e) => {
+                println!("{} unreadable note", commit_sha);
+                eprintln!("Error: {} {}", commit_sha, e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        let (label, failed) = match &result {
+            CommitVerification::NoNote => ("no authorship note".to_string(), require_signed),
+            CommitVerification::Signature(SignatureStatus::Unsigned) => {
+                ("unsigned".to_string(), require_signed)
+            }
+            CommitVerification::Signature(SignatureStatus::Valid { signer }) => {
+                (format!("signed ({})", signer), false)
+            }
+            CommitVerification::Signature(SignatureStatus::Invalid) => {
+                ("invalid signature".to_string(), true)
+            }
+        };
+        println!("{} {}", commit_sha, label);
+        if failed {
+            eprintln!("Error: {} {}", commit_sha, label);
+            failures += 1;
+        }
+        if signatures_only {
+            continue;
+        }
+
+        let problems = match integrity_problems(&repo, commit_sha) {
+            Ok(problems) => problems,
+            Err(e) => vec![e.to_string()],
+        };
+        for problem in &problems {
+            eprintln!("Error: {} {}", commit_sha, problem);
+        }
+        if !problems.is_empty() {
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Resolve `revs` (commits, or ranges such as `main..HEAD`), or every commit with an
+/// authorship note when none are given. With
+/// `reachable_only`, commits no ref reaches (notes left behind by rewritten or deleted
+/// branches) are dropped and counted on stderr.
+fn target_commits(
+    repo: &Repository,
+    revs: &[String],
+    reachable_only: bool,
+) -> Result<Vec<String>, String> {
+    let mut commits = Vec::new();
+    if revs.is_empty() {
+        commits = list_notes_in_ref(repo, "refs/notes/ai")
+            .map_err(|e| format!("Failed to list authorship notes: {}", e))?;
+    } else {
+        for rev in revs {
+            if rev.contains("..") {
+                let range = rev_list(repo, &[rev.as_str()])
+                    .map_err(|e| format!("Failed to resolve {}: {}", rev, e))?;
+                commits.extend(range);
+                continue;
+            }
+            let obj = repo
+                .revparse_single(rev)
+                .map_err(|e| format!("Failed to resolve {}: {}", rev, e))?;
+            commits.push(obj.id());
+        }
+    }
+
+    if reachable_only {
+        let reachable = reachable_commits(repo)
+            .map_err(|e| format!("Failed to list reachable commits: {}", e))?;
+        let total = commits.len();
+        commits.retain(|sha| reachable.contains(sha));
+        let skipped = total - commits.len();
+        if skipped > 0 {
+            eprintln!("Skipped {} note(s) on unreachable commits", skipped);
+        }
+    }
+    Ok(commits)
+}
+
+/// Commits reachable from any branch, tag, remote ref or HEAD (the notes refs themselves
+/// excluded).
+fn reachable_commits(repo: &Repository) -> Result<HashSet<String>, GitAiError> {
+    Ok(rev_list(repo, &["--exclude=refs/notes/*", "--all"])?
+        .into_iter()
+        .collect())
+}
+
+fn rev_list(repo: &Repository, rev_args: &[&str]) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.extend(rev_args.iter().map(|arg| arg.to_string()));
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+/// Repair `base_commit_sha` on the notes of `commits`. Notes that can't be repaired are
+/// reported and skipped, and the command fails once the rest are done.
+fn handle_fix_base_commit(repo: &Repository, commits: &[String]) {
+    let mut fixed = 0;
+    let mut failed = 0;
+    for commit_sha in commits {
+        match fix_base_commit_sha(repo, commit_sha) {
+            Ok(Some(old)) => {
+                let old = if old.is_empty() {
+                    "(empty)"
+                } else {
+                    old.as_str()
+                };
+                println!("{} base_commit_sha {} -> {}", commit_sha, old, commit_sha);
+                fixed += 1;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Failed to fix {}: {}", commit_sha, e);
+                failed += 1;
+            }
+        }
+    }
+    println!(
+        "Fixed base_commit_sha on {} of {} note(s)",
+        fixed,
+        commits.len()
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Point the note's `base_commit_sha` at the commit it annotates, keeping its attestations.
+/// Returns the previous value when the note was rewritten. Signed notes are re-signed, since
+/// the old signature no longer covers the metadata, but only when the local signing key made
+/// the old signature; anyone else's signed note is refused rather than re-signed as ours.
+pub fn fix_base_commit_sha(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<Option<String>, GitAiError> {
+    let Some(content) = show_authorship_note(repo, commit_sha) else {
+        return Ok(None);
+    };
+    let mut authorship_log = AuthorshipLog::deserialize_from_string(&content)
+        .map_err(|e| GitAiError::Generic(format!("Failed to parse authorship log: {}", e)))?;
+    if authorship_log.metadata.base_commit_sha == commit_sha {
+        return Ok(None);
+    }
+
+    let original_signer = match authorship_log.metadata.signature {
+        Some(_) => match verify_authorship_log_signature(repo, &authorship_log)? {
+            SignatureStatus::Valid { signer } => Some(signer),
+            _ => {
+                return Err(GitAiError::Generic(
+                    "its signature does not verify; not re-signing it".to_string(),
+                ));
+            }
+        },
+        None => None,
+    };
+
+    let old = std::mem::replace(
+        &mut authorship_log.metadata.base_commit_sha,
+        commit_sha.to_string(),
+    );
+    if let Some(original_signer) = original_signer {
+        sign_authorship_log(repo, &mut authorship_log)?;
+        if !authorship_log
+            .metadata
+            .signer
+            .as_ref()
+            .is_some_and(|signer| signer.eq_ignore_ascii_case(&original_signer))
+        {
+            return Err(GitAiError::Generic(format!(
+                "it is signed by {}, not the local signing key; not re-signing it",
+                original_signer
+            )));
+        }
+    }
+    let serialized = authorship_log
+        .serialize_to_string()
+        .map_err(|_| GitAiError::Serialization("authorship log".to_string()))?;
+    notes_add(repo, commit_sha, &serialized)?;
+    Ok(Some(old))
+}
+
+/// Check the signature on a commit's authorship note. Logs without a signature verify as
+/// unsigned rather than failing; callers decide whether that is acceptable.
+pub fn verify_commit(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<CommitVerification, GitAiError> {
+    let Some(content) = show_authorship_note(repo, commit_sha) else {
+        return Ok(CommitVerification::NoNote);
+    };
+    let authorship_log = AuthorshipLog::deserialize_from_string(&content)
+        .map_err(|e| GitAiError::Generic(format!("Failed to parse authorship log: {}", e)))?;
+    Ok(CommitVerification::Signature(
+        verify_authorship_log_signature(repo, &authorship_log)?,
+    ))
+}
+
+/// Ways a commit's authorship note disagrees with the commit: a `base_commit_sha` naming
+/// another commit, attestations for files the commit doesn't have, line ranges past the end
+/// of the file, and session hashes with no prompt record in any note of `refs/notes/ai` (a note
+/// may reference a prompt stored in another commit's note). Empty when the note is consistent
+/// or there is no note.
+pub fn integrity_problems(repo: &Repository, commit_sha: &str) -> Result<Vec<String>, GitAiError> {
+    let Some(content) = show_authorship_note(repo, commit_sha) else {
+        return Ok(Vec::new());
+    };
+    let authorship_log = AuthorshipLog::deserialize_from_string(&content)
+        .map_err(|e| GitAiError::Generic(format!("Failed to parse authorship log: {}", e)))?;
+
+    let mut problems = Vec::new();
+    let mut foreign_prompts: HashMap<String, bool> = HashMap::new();
+    if authorship_log.metadata.base_commit_sha != commit_sha {
+        problems.push(format!(
+            "base_commit_sha is {} (repair with --fix-base-commit)",
+            authorship_log.metadata.base_commit_sha
+        ));
+    }
+
+    let tree = repo.find_commit(commit_sha.to_string())?.tree()?;
+    for file in &authorship_log.attestations {
+        let line_count = match tree.get_path(Path::new(&file.file_path)) {
+            Ok(entry) => {
+                let content = repo.find_blob(entry.id())?.content()?;
+                Some(String::from_utf8_lossy(&content).lines().count() as u32)
+            }
+            Err(_) => None,
+        };
+        let Some(line_count) = line_count else {
+            problems.push(format!("{}: file not in commit", file.file_path));
+            continue;
+        };
+
+        for entry in &file.entries {
+            if !authorship_log.metadata.prompts.contains_key(&entry.hash)
+                && !*foreign_prompts
+                    .entry(entry.hash.clone())
+                    .or_insert_with(|| prompt_in_other_notes(repo, &entry.hash))
+            {
+                problems.push(format!(
+                    "{}: session {} missing from prompts",
+                    file.file_path, entry.hash
+                ));
+            }
+            for range in &entry.line_ranges {
+                let (start, end) = match *range {
+                    LineRange::Single(line) => (line, line),
+                    LineRange::Range(start, end) => (start, end),
+                };
+                if start == 0 || start > end || end > line_count {
+                    problems.push(format!(
+                        "{}: lines {}-{} out of range (file has {} lines)",
+                        file.file_path, start, end, line_count
+                    ));
+                }
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// Whether some note in `refs/notes/ai` has a prompt record for `hash`, found the same way
+/// blame resolves prompts from other commits' notes
+fn prompt_in_other_notes(repo: &Repository, hash: &str) -> bool {
+    grep_ai_notes(repo, &format!("\"{}\"", hash))
+        .unwrap_or_default()
+        .iter()
+        .any(|sha| {
+            get_authorship(repo, sha).is_some_and(|log| log.metadata.prompts.contains_key(hash))
+        })
+}
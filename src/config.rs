@@ -73,6 +73,8 @@ pub struct Config {
     default_prompt_storage: Option<String>,
     api_key: Option<String>,
     quiet: bool,
+    disable_prompt_merging: bool,
+    sign_authorship: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -139,6 +141,10 @@ pub struct FileConfig {
     pub api_key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quiet: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_prompt_merging: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sign_authorship: Option<bool>,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -161,6 +167,8 @@ pub struct ConfigPatch {
     pub disable_auto_updates: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt_storage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sign_authorship: Option<bool>,
 }
 
 impl Config {
@@ -375,6 +383,16 @@ impl Config {
         self.quiet
     }
 
+    /// Returns true if blame should not resolve prompts from other commits' notes
+    pub fn prompt_merging_disabled(&self) -> bool {
+        self.disable_prompt_merging
+    }
+
+    /// Returns true if authorship notes should be GPG-signed when written after a commit
+    pub fn sign_authorship(&self) -> bool {
+        self.sign_authorship
+    }
+
     /// Override feature flags for testing purposes.
     /// Only available when the `test-support` feature is enabled or in test mode.
     /// Must be `pub` to work with integration tests in the `tests/` directory.
@@ -572,6 +590,18 @@ fn build_config() -> Config {
     // Get quiet setting (defaults to false)
     let quiet = file_cfg.as_ref().and_then(|c| c.quiet).unwrap_or(false);
 
+    // Get disable_prompt_merging setting (defaults to false)
+    let disable_prompt_merging = file_cfg
+        .as_ref()
+        .and_then(|c| c.disable_prompt_merging)
+        .unwrap_or(false);
+
+    // Get sign_authorship setting (defaults to false)
+    let sign_authorship = file_cfg
+        .as_ref()
+        .and_then(|c| c.sign_authorship)
+        .unwrap_or(false);
+
     #[cfg(any(test, feature = "test-support"))]
     {
         let mut config = Config {
@@ -591,6 +621,8 @@ fn build_config() -> Config {
             default_prompt_storage,
             api_key,
             quiet,
+            disable_prompt_merging,
+            sign_authorship,
         };
         apply_test_config_patch(&mut config);
         config
@@ -614,6 +646,8 @@ fn build_config() -> Config {
         default_prompt_storage,
         api_key,
         quiet,
+        disable_prompt_merging,
+        sign_authorship,
     }
 }
 
@@ -783,6 +817,139 @@ pub fn load_file_config_public() -> Result<FileConfig, String> {
         .map_err(|e| format!("Failed to parse config file: {}", e))
 }
 
+/// Check the raw config file JSON, returning every problem found rather than stopping at
+/// the first. Covers value types, enum values, glob patterns, that `git_path` exists, and
+/// settings that have no effect on their own.
+pub fn validate_file_config(value: &serde_json::Value) -> Vec<String> {
+    use serde_json::Value;
+
+    let Some(object) = value.as_object() else {
+        return vec!["config must be a JSON object".to_string()];
+    };
+
+    let mut problems = Vec::new();
+    for (key, value) in object {
+        match key.as_str() {
+            "git_path" => match value.as_str() {
+                Some(path) if is_executable(Path::new(path.trim())) => {}
+                Some(path) => problems.push(format!("git_path: '{}' does not exist", path)),
+                None => problems.push("git_path: expected a string".to_string()),
+            },
+            "exclude_prompts_in_repositories"
+            | "include_prompts_in_repositories"
+            | "allow_repositories"
+            | "exclude_repositories" => match value.as_array() {
+                Some(patterns) => {
+                    for pattern in patterns {
+                        match pattern.as_str() {
+                            Some(p) => {
+                                if let Err(e) = Pattern::new(p) {
+                                    problems.push(format!(
+                                        "{}: invalid glob pattern '{}': {}",
+                                        key, p, e
+                                    ));
+                                }
+                            }
+                            None => problems.push(format!(
+                                "{}: expected an array of strings, found {}",
+                                key, pattern
+                            )),
+                        }
+                    }
+                }
+                None => problems.push(format!("{}: expected an array of strings", key)),
+            },
+            "telemetry_oss" => match value.as_str() {
+                Some("on") | Some("off") => {}
+                _ => problems.push(format!(
+                    "telemetry_oss: expected \"on\" or \"off\", found {}",
+                    value
+                )),
+            },
+            "telemetry_enterprise_dsn" | "api_key" => {
+                if !value.is_string() {
+                    problems.push(format!("{}: expected a string", key));
+                }
+            }
+            "api_base_url" => match value.as_str() {
+                Some(url) if url.starts_with("http://") || url.starts_with("https://") => {}
+                Some(url) => {
+                    problems.push(format!("api_base_url: '{}' is not an http(s) URL", url))
+                }
+                None => problems.push("api_base_url: expected a string".to_string()),
+            },
+            "disable_version_checks"
+            | "disable_auto_updates"
+            | "quiet"
+            | "disable_prompt_merging"
+            | "sign_authorship" => {
+                if !value.is_boolean() {
+                    problems.push(format!("{}: expected true or false, found {}", key, value));
+                }
+            }
+            "update_channel" => {
+                if value.as_str().and_then(UpdateChannel::from_str).is_none() {
+                    problems.push(format!(
+                        "update_channel: expected one of latest, next, enterprise-latest, enterprise-next, found {}",
+                        value
+                    ));
+                }
+            }
+            "prompt_storage" | "default_prompt_storage" => {
+                if value
+                    .as_str()
+                    .and_then(|s| s.parse::<PromptStorageMode>().ok())
+                    .is_none()
+                {
+                    problems.push(format!(
+                        "{}: expected one of default, notes, local, found {}",
+                        key, value
+                    ));
+                }
+            }
+            "feature_flags" => match value.as_object() {
+                Some(flags) => {
+                    for (flag, flag_value) in flags {
+                        if !crate::feature_flags::FEATURE_FLAG_NAMES.contains(&flag.as_str()) {
+                            problems.push(format!("feature_flags: unknown flag '{}'", flag));
+                        } else if !flag_value.is_boolean() {
+                            problems.push(format!(
+                                "feature_flags.{}: expected true or false, found {}",
+                                flag, flag_value
+                            ));
+                        }
+                    }
+                }
+                None => problems.push("feature_flags: expected an object".to_string()),
+            },
+            _ => problems.push(format!("unknown key '{}'", key)),
+        }
+    }
+
+    if object.contains_key("default_prompt_storage")
+        && !object.contains_key("include_prompts_in_repositories")
+    {
+        problems.push(
+            "default_prompt_storage: has no effect without include_prompts_in_repositories"
+                .to_string(),
+        );
+    }
+    if object
+        .get("disable_auto_updates")
+        .is_some_and(|v| v == &Value::Bool(false))
+        && object
+            .get("disable_version_checks")
+            .is_some_and(|v| v == &Value::Bool(true))
+    {
+        problems.push(
+            "disable_auto_updates: auto updates need version checks, which are disabled"
+                .to_string(),
+        );
+    }
+
+    problems
+}
+
 /// Save the file config
 pub fn save_file_config(config: &FileConfig) -> Result<(), String> {
     let path =
@@ -851,6 +1018,9 @@ fn apply_test_config_patch(config: &mut Config) {
                 );
             }
         }
+        if let Some(sign_authorship) = patch.sign_authorship {
+            config.sign_authorship = sign_authorship;
+        }
     }
 }
 
@@ -885,6 +1055,8 @@ mod tests {
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            disable_prompt_merging: false,
+            sign_authorship: false,
         }
     }
 
@@ -992,6 +1164,8 @@ mod tests {
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            disable_prompt_merging: false,
+            sign_authorship: false,
         }
     }
 
@@ -1108,6 +1282,8 @@ mod tests {
             default_prompt_storage: default_prompt_storage.map(|s| s.to_string()),
             api_key: None,
             quiet: false,
+            disable_prompt_merging: false,
+            sign_authorship: false,
         }
     }
 
@@ -1,3 +1,4 @@
+use crate::api::client::ApiRequestError;
 use std::fmt;
 
 #[derive(Debug)]
@@ -18,6 +19,26 @@ pub enum GitAiError {
     FromUtf8Error(std::string::FromUtf8Error),
     PresetError(String),
     SqliteError(rusqlite::Error),
+    /// Errors from requests to the git-ai API that retrying couldn't fix
+    ApiError(ApiRequestError),
+    /// A value (an authorship log, a transcript, JSON output) could not be serialized
+    Serialization(String),
+    /// `git notes` failed to write the authorship note for a commit
+    NotesWrite {
+        commit_sha: String,
+        reason: String,
+    },
+    /// No prompt record exists for a session hash
+    MissingPromptRecord {
+        hash: String,
+    },
+    /// A file's content could not be read from the commit it was looked up in
+    FileContentMissing {
+        path: String,
+        commit: String,
+    },
+    /// A line range in an authorship note could not be parsed
+    InvalidLineRange(String),
     Generic(String),
 }
 
@@ -42,6 +63,20 @@ impl fmt::Display for GitAiError {
             GitAiError::FromUtf8Error(e) => write!(f, "From UTF-8 error: {}", e),
             GitAiError::PresetError(e) => write!(f, "{}", e),
             GitAiError::SqliteError(e) => write!(f, "SQLite error: {}", e),
+            GitAiError::ApiError(e) => write!(f, "API error: {}", e),
+            GitAiError::Serialization(what) => write!(f, "Failed to serialize {}", what),
+            GitAiError::NotesWrite { commit_sha, reason } => write!(
+                f,
+                "Failed to write authorship note for {}: {}",
+                commit_sha, reason
+            ),
+            GitAiError::MissingPromptRecord { hash } => {
+                write!(f, "No prompt record found for session {}", hash)
+            }
+            GitAiError::FileContentMissing { path, commit } => {
+                write!(f, "no such path '{}' in {}", path, commit)
+            }
+            GitAiError::InvalidLineRange(range) => write!(f, "Invalid line range '{}'", range),
             GitAiError::Generic(e) => write!(f, "Generic error: {}", e),
             GitAiError::GixError(e) => write!(f, "Gix error: {}", e),
         }
@@ -81,6 +116,12 @@ impl From<std::string::FromUtf8Error> for GitAiError {
     }
 }
 
+impl From<ApiRequestError> for GitAiError {
+    fn from(err: ApiRequestError) -> Self {
+        GitAiError::ApiError(err)
+    }
+}
+
 impl From<rusqlite::Error> for GitAiError {
     fn from(err: rusqlite::Error) -> Self {
         GitAiError::SqliteError(err)
@@ -105,6 +146,20 @@ impl Clone for GitAiError {
             GitAiError::FromUtf8Error(e) => GitAiError::FromUtf8Error(e.clone()),
             GitAiError::PresetError(s) => GitAiError::PresetError(s.clone()),
             GitAiError::SqliteError(e) => GitAiError::Generic(format!("SQLite error: {}", e)),
+            GitAiError::ApiError(e) => GitAiError::ApiError(e.clone()),
+            GitAiError::Serialization(s) => GitAiError::Serialization(s.clone()),
+            GitAiError::NotesWrite { commit_sha, reason } => GitAiError::NotesWrite {
+                commit_sha: commit_sha.clone(),
+                reason: reason.clone(),
+            },
+            GitAiError::MissingPromptRecord { hash } => {
+                GitAiError::MissingPromptRecord { hash: hash.clone() }
+            }
+            GitAiError::FileContentMissing { path, commit } => GitAiError::FileContentMissing {
+                path: path.clone(),
+                commit: commit.clone(),
+            },
+            GitAiError::InvalidLineRange(s) => GitAiError::InvalidLineRange(s.clone()),
             GitAiError::Generic(s) => GitAiError::Generic(s.clone()),
             GitAiError::GixError(e) => GitAiError::Generic(format!("Gix error: {}", e)),
         }
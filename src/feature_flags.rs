@@ -38,6 +38,9 @@ macro_rules! define_feature_flags {
             )*
         }
 
+        /// Names accepted under `feature_flags` in the config file
+        pub(crate) const FEATURE_FLAG_NAMES: &[&str] = &[$(stringify!($file_name),)*];
+
         impl FeatureFlags {
             /// Merge flags with a base, applying any Some values as overrides
             fn merge_with(base: Self, overrides: DeserializableFeatureFlags) -> Self {
@@ -52,7 +55,7 @@ macro_rules! define_feature_flags {
 // Define all feature flags in one place
 // Format: struct_field: file_and_env_name, debug = <bool>, release = <bool>
 define_feature_flags!(
-    rewrite_stash: rewrite_stash, debug = true, release = false,
+    rewrite_stash: rewrite_stash, debug = true, release = true,
     inter_commit_move: checkpoint_inter_commit_move, debug = false, release = false,
     auth_keyring: auth_keyring, debug = false, release = false,
 );
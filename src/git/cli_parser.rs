@@ -481,6 +481,69 @@ pub fn is_dry_run(args: &[String]) -> bool {
     args.iter().any(|arg| arg == "--dry-run")
 }
 
+/// `git commit` short options that take a value, attached (`-mmsg`) or as the next argument
+const COMMIT_SHORT_OPTIONS_WITH_VALUE: &[char] = &['m', 'F', 'C', 'c', 't'];
+
+/// `git commit` short options whose value is optional and can only be attached (`-Skeyid`)
+const COMMIT_SHORT_OPTIONS_WITH_ATTACHED_VALUE: &[char] = &['S', 'u'];
+
+/// `git commit` long options that take the next argument as their value unless given with `=`
+const COMMIT_LONG_OPTIONS_WITH_VALUE: &[&str] = &[
+    "--message",
+    "--file",
+    "--reuse-message",
+    "--reedit-message",
+    "--template",
+    "--author",
+    "--date",
+    "--fixup",
+    "--squash",
+    "--cleanup",
+    "--trailer",
+    "--pathspec-from-file",
+];
+
+/// Whether `git commit` was asked to bypass hooks with `--no-verify`/`-n`, including `-n` inside
+/// combined short flags (`-anm msg`). A later `--verify` turns them back on, as in git. Option
+/// values such as the message in `-m -n` are skipped.
+pub fn is_no_verify(args: &[String]) -> bool {
+    let mut no_verify = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        i += 1;
+        if arg == "--" {
+            break;
+        }
+        if let Some(long) = arg.strip_prefix("--") {
+            match arg {
+                "--no-verify" => no_verify = true,
+                "--verify" => no_verify = false,
+                _ if !long.contains('=') && COMMIT_LONG_OPTIONS_WITH_VALUE.contains(&arg) => i += 1,
+                _ => {}
+            }
+            continue;
+        }
+        let Some(shorts) = arg.strip_prefix('-') else {
+            continue;
+        };
+        for (pos, flag) in shorts.char_indices() {
+            if flag == 'n' {
+                no_verify = true;
+            } else if COMMIT_SHORT_OPTIONS_WITH_VALUE.contains(&flag) {
+                // The rest of the cluster is the value; without one, the next argument is
+                if pos + flag.len_utf8() == shorts.len() {
+                    i += 1;
+                }
+                break;
+            } else if COMMIT_SHORT_OPTIONS_WITH_ATTACHED_VALUE.contains(&flag) {
+                break;
+            }
+        }
+    }
+    no_verify
+}
+
 /// Extract the target directory from git clone command arguments.
 /// Returns the directory where the repository was cloned to.
 ///
@@ -718,4 +781,21 @@ mod tests {
             Some("my-dir".to_string())
         );
     }
+
+    #[test]
+    fn test_is_no_verify() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(is_no_verify(&args(&["--no-verify", "-m", "msg"])));
+        assert!(is_no_verify(&args(&["-n", "-m", "msg"])));
+        assert!(!is_no_verify(&args(&["-m", "msg"])));
+        assert!(!is_no_verify(&args(&["--no-verify", "--verify"])));
+        assert!(is_no_verify(&args(&["-anm", "msg"])));
+        assert!(is_no_verify(&args(&["-a", "-n"])));
+        assert!(!is_no_verify(&args(&["-m", "-n"])));
+        assert!(!is_no_verify(&args(&["--message", "-n"])));
+        assert!(!is_no_verify(&args(&["-mn"])));
+        assert!(!is_no_verify(&args(&["-Sn"])));
+        assert!(is_no_verify(&args(&["--message=-n", "-n"])));
+        assert!(!is_no_verify(&args(&["-m", "msg", "--", "-n"])));
+    }
 }
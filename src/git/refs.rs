@@ -1,4 +1,5 @@
 use crate::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, AuthorshipLog};
+use crate::authorship::signing::{sign_authorship_log, signing_enabled};
 use crate::authorship::working_log::Checkpoint;
 use crate::error::GitAiError;
 use crate::git::repository::{Repository, exec_git, exec_git_stdin};
@@ -10,6 +11,52 @@ use std::collections::{HashMap, HashSet};
 pub const AI_AUTHORSHIP_REFNAME: &str = "ai";
 pub const AI_AUTHORSHIP_PUSH_REFSPEC: &str = "refs/notes/ai:refs/notes/ai";
 
+/// Git config key overriding [`AI_AUTHORSHIP_PUSH_REFSPEC`], e.g. for mirrors that keep
+/// authorship notes under a custom namespace on the remote.
+pub const AUTHORSHIP_REFSPEC_CONFIG_KEY: &str = "gitai.authorshipRefspec";
+
+/// Check an authorship refspec and return its remote (destination) ref. Notes are always
+/// written to refs/notes/ai locally, and the push stays unforced so remote notes get merged
+/// rather than overwritten.
+pub fn parse_authorship_refspec(refspec: &str) -> Result<String, GitAiError> {
+    let refspec = refspec.trim();
+    if refspec.is_empty() {
+        return Err(GitAiError::Generic(format!(
+            "{} must not be empty",
+            AUTHORSHIP_REFSPEC_CONFIG_KEY
+        )));
+    }
+    let invalid = |reason: &str| {
+        GitAiError::Generic(format!(
+            "{} '{}' {}",
+            AUTHORSHIP_REFSPEC_CONFIG_KEY, refspec, reason
+        ))
+    };
+    let Some((src, dst)) = refspec.split_once(':') else {
+        return Err(invalid("must be <src>:<dst>"));
+    };
+    if src.starts_with('+') {
+        return Err(invalid("must not force-push (drop the leading '+')"));
+    }
+    if src != "refs/notes/ai" {
+        return Err(invalid("must push refs/notes/ai"));
+    }
+    if !dst.starts_with("refs/") || dst.contains(':') || dst.contains('*') {
+        return Err(invalid("must name a single remote ref under refs/"));
+    }
+    Ok(dst.to_string())
+}
+
+/// The refspec authorship notes are pushed with, and the remote ref they are fetched from:
+/// `gitai.authorshipRefspec` when set, else [`AI_AUTHORSHIP_PUSH_REFSPEC`].
+pub fn authorship_refspec(repo: &Repository) -> Result<(String, String), GitAiError> {
+    let refspec = repo
+        .config_get_str(AUTHORSHIP_REFSPEC_CONFIG_KEY)?
+        .unwrap_or_else(|| AI_AUTHORSHIP_PUSH_REFSPEC.to_string());
+    let remote_ref = parse_authorship_refspec(&refspec)?;
+    Ok((refspec.trim().to_string(), remote_ref))
+}
+
 pub fn notes_add(
     repo: &Repository,
     commit_sha: &str,
@@ -25,10 +72,47 @@ pub fn notes_add(
     args.push(commit_sha.to_string());
 
     // Use stdin to provide the note content to avoid command line length limits
-    exec_git_stdin(&args, note_content.as_bytes())?;
+    exec_git_stdin(&args, note_content.as_bytes()).map_err(|e| GitAiError::NotesWrite {
+        commit_sha: commit_sha.to_string(),
+        reason: match e {
+            GitAiError::GitCliError { stderr, .. } => stderr.trim().to_string(),
+            e => e.to_string(),
+        },
+    })?;
     Ok(())
 }
 
+/// Write `authorship_log` as the note for `commit_sha`. A signature carried over from the note
+/// it was rewritten from no longer covers it, so it is dropped, and the log is re-signed when
+/// signing is enabled. Every authorship note write (commit, amend, rebase, cherry-pick, merge)
+/// goes through here so `verify --require-signed` holds after history rewrites.
+pub fn notes_add_authorship_log(
+    repo: &Repository,
+    commit_sha: &str,
+    authorship_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    strip_signature(authorship_log);
+    // Sign last so the signature covers exactly what lands in the note
+    if signing_enabled()
+        && let Err(e) = sign_authorship_log(repo, authorship_log)
+    {
+        eprintln!("Warning: Failed to sign authorship note: {}", e);
+    }
+
+    let authorship_json = authorship_log
+        .serialize_to_string()
+        .map_err(|_| GitAiError::Serialization("authorship log".to_string()))?;
+    notes_add(repo, commit_sha, &authorship_json)
+}
+
+/// Drop a signature that no longer covers `authorship_log` after it was changed.
+pub fn strip_signature(authorship_log: &mut AuthorshipLog) {
+    if authorship_log.metadata.signature.take().is_some() {
+        authorship_log.metadata.signer = None;
+        authorship_log.metadata.schema_version = AUTHORSHIP_LOG_VERSION.to_string();
+    }
+}
+
 // Check which commits from the given list have authorship notes.
 // Uses git cat-file --batch-check to efficiently check multiple commits in one invocation.
 // Returns a Vec of CommitAuthorship for each commit.
@@ -162,10 +246,11 @@ pub fn get_reference_as_authorship_log_v3(
     // Try to deserialize as AuthorshipLog
     let authorship_log = match AuthorshipLog::deserialize_from_string(&content) {
         Ok(log) => log,
-        Err(_) => {
-            return Err(GitAiError::Generic(
-                "Failed to parse authorship log".to_string(),
-            ));
+        Err(e) => {
+            return Err(GitAiError::Generic(format!(
+                "Failed to parse authorship log: {}",
+                e
+            )));
         }
     };
 
@@ -219,6 +304,19 @@ pub fn ref_exists(repo: &Repository, ref_name: &str) -> bool {
     exec_git(&args).is_ok()
 }
 
+/// Commit refs/notes/ai points at, or None when there are no authorship notes yet
+pub fn ai_notes_tip(repo: &Repository) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.push("show-ref".to_string());
+    args.push("--verify".to_string());
+    args.push("-s".to_string());
+    args.push(format!("refs/notes/{}", AI_AUTHORSHIP_REFNAME));
+
+    let output = exec_git(&args).ok()?;
+    let tip = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!tip.is_empty()).then_some(tip)
+}
+
 /// Merge notes from a source ref into refs/notes/ai
 /// Uses the 'ours' strategy to combine notes without data loss
 pub fn merge_notes_from_ref(repo: &Repository, source_ref: &str) -> Result<(), GitAiError> {
@@ -239,6 +337,40 @@ pub fn merge_notes_from_ref(repo: &Repository, source_ref: &str) -> Result<(), G
     Ok(())
 }
 
+/// List the commits annotated in a notes ref (e.g. "refs/notes/ai-remote/origin")
+pub fn list_notes_in_ref(repo: &Repository, notes_ref: &str) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("notes".to_string());
+    args.push(format!("--ref={}", notes_ref));
+    args.push("list".to_string());
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| GitAiError::Generic("Failed to parse git notes list output".to_string()))?;
+
+    // Each line is "<note blob> <annotated commit>"
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(_, commit)| commit.to_string())
+        .collect())
+}
+
+/// Show a commit's note from an arbitrary notes ref, or None if it has none
+pub fn show_note_in_ref(repo: &Repository, notes_ref: &str, commit_sha: &str) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.push("notes".to_string());
+    args.push(format!("--ref={}", notes_ref));
+    args.push("show".to_string());
+    args.push(commit_sha.to_string());
+
+    exec_git(&args)
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
 /// Copy a ref to another location (used for initial setup of local notes from tracking ref)
 pub fn copy_ref(repo: &Repository, source_ref: &str, dest_ref: &str) -> Result<(), GitAiError> {
     let mut args = repo.global_args_for_exec();
@@ -306,6 +438,29 @@ mod tests {
     use super::*;
     use crate::git::test_utils::TmpRepo;
 
+    #[test]
+    fn test_parse_authorship_refspec() {
+        assert_eq!(
+            parse_authorship_refspec(AI_AUTHORSHIP_PUSH_REFSPEC).unwrap(),
+            "refs/notes/ai"
+        );
+        assert_eq!(
+            parse_authorship_refspec(" refs/notes/ai:refs/notes/mirror/ai ").unwrap(),
+            "refs/notes/mirror/ai"
+        );
+        for invalid in [
+            "",
+            "   ",
+            "refs/notes/ai",
+            "+refs/notes/ai:refs/notes/ai",
+            "refs/notes/other:refs/notes/ai",
+            "refs/notes/ai:notes/ai",
+            "refs/notes/ai:refs/notes/*",
+        ] {
+            assert!(parse_authorship_refspec(invalid).is_err(), "{}", invalid);
+        }
+    }
+
     #[test]
     fn test_notes_add_and_show_authorship_note() {
         // Create a temporary repository
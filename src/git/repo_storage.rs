@@ -1,15 +1,71 @@
 use crate::authorship::attribution_tracker::LineAttribution;
 use crate::authorship::authorship_log::PromptRecord;
-use crate::authorship::authorship_log_serialization::generate_short_hash;
+use crate::authorship::authorship_log_serialization::{
+    generate_short_hash, generate_short_hash_with_length,
+};
 use crate::authorship::working_log::{CHECKPOINT_API_VERSION, Checkpoint, CheckpointKind};
 use crate::error::GitAiError;
+use crate::git::repository::Repository;
 use crate::git::rewrite_log::{RewriteLogEvent, append_event_to_file};
 use crate::utils::{debug_log, normalize_to_posix};
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How long to wait for another process to release a working log lock
+const WORKING_LOG_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+const WORKING_LOG_LOCK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const WORKING_LOG_LOCK_FILE: &str = "checkpoints.lock";
+
+pub const COMPRESS_BLOBS_CONFIG_KEY: &str = "gitai.compressBlobs";
+/// Marks a zlib-compressed file version. Starts with a NUL so it can't collide with the
+/// text files checkpoints store; blobs without it are read back as written.
+const COMPRESSED_BLOB_MAGIC: &[u8] = b"\0gitai-zlib\0";
+
+static COMPRESS_BLOBS: AtomicBool = AtomicBool::new(true);
+
+/// Compress file versions written from now on unless `repo` sets `gitai.compressBlobs` to
+/// false. Existing blobs stay readable either way.
+pub fn configure_blob_compression(repo: &Repository) {
+    let enabled = match repo.config_get_str(COMPRESS_BLOBS_CONFIG_KEY) {
+        Ok(Some(value)) => !matches!(
+            value.trim().to_lowercase().as_str(),
+            "false" | "no" | "off" | "0"
+        ),
+        _ => true,
+    };
+    COMPRESS_BLOBS.store(enabled, Ordering::Relaxed);
+}
+
+/// Bytes to store for a file version, compressed when enabled
+pub fn encode_file_version(content: &str) -> Result<Vec<u8>, GitAiError> {
+    if !COMPRESS_BLOBS.load(Ordering::Relaxed) {
+        return Ok(content.as_bytes().to_vec());
+    }
+    let mut encoder = ZlibEncoder::new(COMPRESSED_BLOB_MAGIC.to_vec(), Compression::fast());
+    encoder.write_all(content.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+/// Content of a stored file version, compressed or not
+pub fn decode_file_version(bytes: Vec<u8>) -> Result<String, GitAiError> {
+    match bytes.strip_prefix(COMPRESSED_BLOB_MAGIC) {
+        Some(compressed) => {
+            let mut content = String::new();
+            ZlibDecoder::new(compressed).read_to_string(&mut content)?;
+            Ok(content)
+        }
+        None => Ok(String::from_utf8(bytes)?),
+    }
+}
 
 /// Initial attributions data structure stored in the INITIAL file
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -20,6 +76,15 @@ pub struct InitialAttributions {
     pub prompts: HashMap<String, PromptRecord>,
 }
 
+/// Prompt records `git-ai blame` resolved from other commits' notes, valid for as long as
+/// refs/notes/ai still points at `notes_tip`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BlamePromptCache {
+    pub notes_tip: String,
+    /// None when no note has the prompt
+    pub prompts: HashMap<String, Option<PromptRecord>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RepoStorage {
     pub repo_path: PathBuf,
@@ -27,6 +92,9 @@ pub struct RepoStorage {
     pub working_logs: PathBuf,
     pub rewrite_log: PathBuf,
     pub logs: PathBuf,
+    pub blame_prompt_cache: PathBuf,
+    /// HEAD when the last checkpoint ran, for `checkpoint --since-last-commit`
+    pub last_checkpoint_base: PathBuf,
 }
 
 impl RepoStorage {
@@ -35,6 +103,8 @@ impl RepoStorage {
         let working_logs_dir = ai_dir.join("working_logs");
         let rewrite_log_file = ai_dir.join("rewrite_log");
         let logs_dir = ai_dir.join("logs");
+        let blame_prompt_cache_file = ai_dir.join("blame_prompt_cache.json");
+        let last_checkpoint_base_file = ai_dir.join("last_checkpoint_base");
 
         let config = RepoStorage {
             repo_path: repo_path.to_path_buf(),
@@ -42,6 +112,8 @@ impl RepoStorage {
             working_logs: working_logs_dir,
             rewrite_log: rewrite_log_file,
             logs: logs_dir,
+            blame_prompt_cache: blame_prompt_cache_file,
+            last_checkpoint_base: last_checkpoint_base_file,
         };
 
         config.ensure_config_directory().unwrap();
@@ -86,6 +158,23 @@ impl RepoStorage {
 
     #[allow(dead_code)]
     pub fn delete_working_log_for_base_commit(&self, sha: &str) -> Result<(), GitAiError> {
+        let working_log_dir = self.working_logs.join(sha);
+        if !working_log_dir.exists() {
+            return Ok(());
+        }
+        let lock = WorkingLogLock::acquire(
+            working_log_dir.join(WORKING_LOG_LOCK_FILE),
+            WORKING_LOG_LOCK_TIMEOUT,
+        )?;
+        self.delete_working_log_for_base_commit_locked(sha, &lock)
+    }
+
+    /// [`Self::delete_working_log_for_base_commit`] for a caller already holding that log's lock.
+    pub fn delete_working_log_for_base_commit_locked(
+        &self,
+        sha: &str,
+        _lock: &WorkingLogLock,
+    ) -> Result<(), GitAiError> {
         let working_log_dir = self.working_logs.join(sha);
         if working_log_dir.exists() {
             if cfg!(debug_assertions) {
@@ -115,7 +204,13 @@ impl RepoStorage {
         let old_dir = self.working_logs.join(old_sha);
         let new_dir = self.working_logs.join(new_sha);
         if old_dir.exists() && !new_dir.exists() {
+            let _lock = WorkingLogLock::acquire(
+                old_dir.join(WORKING_LOG_LOCK_FILE),
+                WORKING_LOG_LOCK_TIMEOUT,
+            )?;
             fs::rename(&old_dir, &new_dir)?;
+            // The moved checkpoints are current, not left over from an earlier visit
+            self.write_last_checkpoint_base(new_sha)?;
             debug_log(&format!(
                 "Renamed working log from {} to {}",
                 old_sha, new_sha
@@ -124,6 +219,21 @@ impl RepoStorage {
         Ok(())
     }
 
+    /// Base commit the last checkpoint was recorded against, if any checkpoint has run
+    pub fn read_last_checkpoint_base(&self) -> Option<String> {
+        let base = fs::read_to_string(&self.last_checkpoint_base).ok()?;
+        let base = base.trim();
+        (!base.is_empty()).then(|| base.to_string())
+    }
+
+    /// Record `sha` as the base of the current working log. Checkpoints and hooks that
+    /// migrate uncommitted attributions onto a new HEAD call this so `--since-last-commit`
+    /// doesn't take the migrated log for a stale one.
+    pub fn write_last_checkpoint_base(&self, sha: &str) -> Result<(), GitAiError> {
+        fs::write(&self.last_checkpoint_base, format!("{}\n", sha))?;
+        Ok(())
+    }
+
     /* Rewrite Log Persistance */
 
     /// Append a rewrite event to the rewrite log file and return the full log
@@ -144,6 +254,49 @@ impl RepoStorage {
         let content = fs::read_to_string(&self.rewrite_log)?;
         crate::git::rewrite_log::deserialize_events_from_jsonl(&content)
     }
+
+    /* Blame Prompt Cache */
+
+    /// Prompts cached by earlier blames, or none when the cache is missing, unreadable, or
+    /// was written for a different refs/notes/ai tip.
+    pub fn read_blame_prompt_cache(
+        &self,
+        notes_tip: &str,
+    ) -> HashMap<String, Option<PromptRecord>> {
+        let Ok(content) = fs::read_to_string(&self.blame_prompt_cache) else {
+            return HashMap::new();
+        };
+        match serde_json::from_str::<BlamePromptCache>(&content) {
+            Ok(cache) if cache.notes_tip == notes_tip => cache.prompts,
+            Ok(_) => {
+                debug_log("Ignoring blame prompt cache written for an older refs/notes/ai");
+                HashMap::new()
+            }
+            Err(e) => {
+                debug_log(&format!("Ignoring unreadable blame prompt cache: {}", e));
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Replace the blame prompt cache. Written to a temp file and renamed so concurrent
+    /// blames never read a partial cache.
+    pub fn write_blame_prompt_cache(
+        &self,
+        notes_tip: &str,
+        prompts: &HashMap<String, Option<PromptRecord>>,
+    ) -> Result<(), GitAiError> {
+        let cache = BlamePromptCache {
+            notes_tip: notes_tip.to_string(),
+            prompts: prompts.clone(),
+        };
+        let tmp = self
+            .blame_prompt_cache
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(&cache)?)?;
+        fs::rename(&tmp, &self.blame_prompt_cache)?;
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -193,7 +346,17 @@ impl PersistedWorkingLog {
         self.dirty_files = normalized_dirty_files;
     }
 
-    pub fn reset_working_log(&self) -> Result<(), GitAiError> {
+    /// Acquire the per-base-commit lock guarding read-modify-write of the working log.
+    /// The lock is released when the returned guard is dropped.
+    pub fn lock(&self) -> Result<WorkingLogLock, GitAiError> {
+        WorkingLogLock::acquire(
+            self.dir.join(WORKING_LOG_LOCK_FILE),
+            WORKING_LOG_LOCK_TIMEOUT,
+        )
+    }
+
+    /// Clear the checkpoints and blobs. Callers hold this log's lock.
+    pub fn reset_working_log(&self, _lock: &WorkingLogLock) -> Result<(), GitAiError> {
         // Clear all blobs by removing the blobs directory
         let blobs_dir = self.dir.join("blobs");
         if blobs_dir.exists() {
@@ -210,7 +373,7 @@ impl PersistedWorkingLog {
     /* blob storage */
     pub fn get_file_version(&self, sha: &str) -> Result<String, GitAiError> {
         let blob_path = self.dir.join("blobs").join(sha);
-        Ok(fs::read_to_string(blob_path)?)
+        decode_file_version(fs::read(blob_path)?)
     }
 
     #[allow(dead_code)]
@@ -226,7 +389,7 @@ impl PersistedWorkingLog {
 
         // Write content to blob file
         let blob_path = blobs_dir.join(&sha);
-        fs::write(blob_path, content)?;
+        fs::write(blob_path, encode_file_version(content)?)?;
 
         Ok(sha)
     }
@@ -304,14 +467,20 @@ impl PersistedWorkingLog {
         let file_path = self.to_repo_absolute_path(file_path);
 
         // Fall back to reading from filesystem
-        match fs::read(&file_path) {
-            Ok(bytes) => Ok(String::from_utf8_lossy(&bytes).to_string()),
-            Err(_) => Ok(String::new()),
+        match read_worktree_bytes(Path::new(&file_path)) {
+            Some(bytes) => Ok(String::from_utf8_lossy(&bytes).to_string()),
+            None => Ok(String::new()),
         }
     }
 
     /* append checkpoint */
-    pub fn append_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), GitAiError> {
+    /// Callers hold this log's lock, so concurrent checkpoints don't drop each other's entries
+    /// in the read-modify-write.
+    pub fn append_checkpoint(
+        &self,
+        checkpoint: &Checkpoint,
+        lock: &WorkingLogLock,
+    ) -> Result<(), GitAiError> {
         // Read existing checkpoints
         let mut checkpoints = self.read_all_checkpoints().unwrap_or_default();
 
@@ -365,7 +534,7 @@ impl PersistedWorkingLog {
         self.prune_old_char_attributions(&mut checkpoints);
 
         // Write all checkpoints back
-        self.write_all_checkpoints(&checkpoints)
+        self.write_all_checkpoints(&checkpoints, lock)
     }
 
     pub fn read_all_checkpoints(&self) -> Result<Vec<Checkpoint>, GitAiError> {
@@ -399,20 +568,34 @@ impl PersistedWorkingLog {
         }
 
         // Migrate 7-char prompt hashes to 16-char hashes
-        // Step 1: Build mapping from old 7-char hash to new 16-char hash
+        // Step 1: Build mapping from old 7-char hash to new 16-char hash. A session that already
+        // recorded its hash keeps it, even when that hash is 7 chars long (gitai.hashLength=7)
         let mut old_to_new_hash: HashMap<String, String> = HashMap::new();
 
-        for checkpoint in &checkpoints {
+        for checkpoint in checkpoints.iter().filter(|c| c.session_hash.is_none()) {
             if let Some(agent_id) = &checkpoint.agent_id {
                 let new_hash = generate_short_hash(&agent_id.id, &agent_id.tool);
                 let old_hash = new_hash[..7].to_string();
                 old_to_new_hash.insert(old_hash, new_hash);
             }
         }
+        for checkpoint in &checkpoints {
+            if let (Some(agent_id), Some(recorded)) =
+                (&checkpoint.agent_id, &checkpoint.session_hash)
+            {
+                let old_hash = generate_short_hash_with_length(&agent_id.id, &agent_id.tool, 7);
+                old_to_new_hash.insert(old_hash, recorded.clone());
+            }
+        }
 
-        // Step 2: Replace 7-char author_ids in all checkpoints' attributions and line_attributions
+        // Step 2: Replace 7-char author_ids in legacy checkpoints' attributions and
+        // line_attributions. Checkpoints with a recorded session hash already use it
         let mut migrated_checkpoints = Vec::new();
         for mut checkpoint in checkpoints {
+            if checkpoint.session_hash.is_some() {
+                migrated_checkpoints.push(checkpoint);
+                continue;
+            }
             for entry in &mut checkpoint.entries {
                 // Replace author_ids in attributions
                 for attr in &mut entry.attributions {
@@ -476,8 +659,13 @@ impl PersistedWorkingLog {
     /// Write all checkpoints to the JSONL file, replacing any existing content
     /// Note: Unlike append_checkpoint(), this preserves transcripts because it's used
     /// by post-commit after transcripts have been refetched and need to be preserved
-    /// for from_just_working_log() to read them.
-    pub fn write_all_checkpoints(&self, checkpoints: &[Checkpoint]) -> Result<(), GitAiError> {
+    /// for from_just_working_log() to read them. Callers hold this log's lock across the
+    /// read that produced `checkpoints`.
+    pub fn write_all_checkpoints(
+        &self,
+        checkpoints: &[Checkpoint],
+        _lock: &WorkingLogLock,
+    ) -> Result<(), GitAiError> {
         let checkpoints_file = self.dir.join("checkpoints.jsonl");
 
         // Serialize all checkpoints to JSONL
@@ -538,6 +726,17 @@ impl PersistedWorkingLog {
         &self,
         attributions: HashMap<String, Vec<LineAttribution>>,
         prompts: HashMap<String, PromptRecord>,
+    ) -> Result<(), GitAiError> {
+        let lock = self.lock()?;
+        self.write_initial_attributions_locked(attributions, prompts, &lock)
+    }
+
+    /// [`Self::write_initial_attributions`] for a caller already holding this log's lock.
+    pub fn write_initial_attributions_locked(
+        &self,
+        attributions: HashMap<String, Vec<LineAttribution>>,
+        prompts: HashMap<String, PromptRecord>,
+        _lock: &WorkingLogLock,
     ) -> Result<(), GitAiError> {
         // Filter out empty attributions
         let filtered: HashMap<String, Vec<LineAttribution>> = attributions
@@ -590,6 +789,64 @@ impl PersistedWorkingLog {
     }
 }
 
+/// Exclusive OS advisory lock (flock on Unix, LockFileEx on Windows) on a single working log's
+/// lock file. The OS releases it when the guard is dropped or the process dies, so a crashed
+/// holder never leaves a lock behind and a slow one never has it taken away.
+#[derive(Debug)]
+pub struct WorkingLogLock {
+    _file: fs::File,
+}
+
+impl WorkingLogLock {
+    fn acquire(path: PathBuf, timeout: Duration) -> Result<Self, GitAiError> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        let started = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(fs::TryLockError::WouldBlock) => {
+                    if started.elapsed() >= timeout {
+                        return Err(GitAiError::Generic(format!(
+                            "Timed out after {:?} waiting for working log lock {} (another checkpoint may be running)",
+                            timeout,
+                            path.display()
+                        )));
+                    }
+                    std::thread::sleep(WORKING_LOG_LOCK_POLL_INTERVAL);
+                }
+                Err(fs::TryLockError::Error(e)) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+/// Content of a working tree path as git stores it. A symlink is its target text and is never
+/// followed, since it may point outside the repository or at a device that never finishes
+/// reading. Anything other than a regular file or symlink has no content.
+pub fn read_worktree_bytes(path: &Path) -> Option<Vec<u8>> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(path).ok()?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Some(target.as_os_str().as_bytes().to_vec())
+        }
+        #[cfg(not(unix))]
+        {
+            Some(normalize_to_posix(&target.to_string_lossy()).into_bytes())
+        }
+    } else if metadata.is_file() {
+        fs::read(path).ok()
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -667,6 +924,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_large_file_version_round_trips_compressed() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+
+        let content: String = (0..50_000)
+            .map(|i| format!("let value_{} = compute({});\n", i, i % 97))
+            .collect();
+        let sha = working_log
+            .persist_file_version(&content)
+            .expect("Failed to persist file version");
+
+        let stored = fs::read(working_log.dir.join("blobs").join(&sha)).unwrap();
+        assert!(stored.starts_with(COMPRESSED_BLOB_MAGIC));
+        assert!(
+            stored.len() * 4 < content.len(),
+            "Stored {} bytes for {} bytes of content",
+            stored.len(),
+            content.len()
+        );
+        assert_eq!(working_log.get_file_version(&sha).unwrap(), content);
+
+        // Blobs written before compression existed are read back as they are
+        let legacy = "written uncompressed\n";
+        fs::write(working_log.dir.join("blobs").join("legacy"), legacy).unwrap();
+        assert_eq!(working_log.get_file_version("legacy").unwrap(), legacy);
+    }
+
     #[test]
     fn test_persisted_working_log_blob_storage() {
         // Create a temporary repository
@@ -731,7 +1018,7 @@ mod tests {
 
         // Test appending checkpoint
         working_log
-            .append_checkpoint(&checkpoint)
+            .append_checkpoint(&checkpoint, &working_log.lock().unwrap())
             .expect("Failed to append checkpoint");
 
         // Test reading all checkpoints
@@ -755,7 +1042,7 @@ mod tests {
         );
 
         working_log
-            .append_checkpoint(&checkpoint2)
+            .append_checkpoint(&checkpoint2, &working_log.lock().unwrap())
             .expect("Failed to append second checkpoint");
 
         let checkpoints = working_log
@@ -766,6 +1053,80 @@ mod tests {
         assert_eq!(checkpoints[1].author, "test-author-2");
     }
 
+    #[test]
+    fn test_concurrent_append_checkpoint_keeps_all_entries() {
+        use crate::authorship::working_log::CheckpointKind;
+
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+
+        let per_writer = 20;
+        std::thread::scope(|scope| {
+            for writer in ["editor", "agent"] {
+                let working_log = &working_log;
+                scope.spawn(move || {
+                    for i in 0..per_writer {
+                        let checkpoint = Checkpoint::new(
+                            CheckpointKind::Human,
+                            format!("diff-{}", i),
+                            format!("{}-{}", writer, i),
+                            vec![],
+                        );
+                        working_log
+                            .append_checkpoint(&checkpoint, &working_log.lock().unwrap())
+                            .expect("Failed to append checkpoint");
+                    }
+                });
+            }
+        });
+
+        let checkpoints = working_log
+            .read_all_checkpoints()
+            .expect("Failed to read checkpoints");
+        assert_eq!(checkpoints.len(), per_writer * 2);
+        for writer in ["editor", "agent"] {
+            for i in 0..per_writer {
+                let author = format!("{}-{}", writer, i);
+                assert!(
+                    checkpoints.iter().any(|c| c.author == author),
+                    "Missing checkpoint {}",
+                    author
+                );
+            }
+        }
+        // Every writer released the lock
+        WorkingLogLock::acquire(
+            working_log.dir.join(WORKING_LOG_LOCK_FILE),
+            Duration::from_millis(50),
+        )
+        .expect("Lock should be free once writers finish");
+    }
+
+    #[test]
+    fn test_working_log_lock_times_out_on_contention() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+
+        let _held = working_log.lock().expect("Failed to acquire lock");
+        // However old the lock file looks, a live holder keeps it
+        fs::File::options()
+            .write(true)
+            .open(working_log.dir.join(WORKING_LOG_LOCK_FILE))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+        let err = WorkingLogLock::acquire(
+            working_log.dir.join(WORKING_LOG_LOCK_FILE),
+            Duration::from_millis(50),
+        )
+        .expect_err("Second acquire should time out");
+        assert!(err.to_string().contains("waiting for working log lock"));
+    }
+
     #[test]
     fn test_read_all_checkpoints_filters_incompatible_versions() {
         use crate::authorship::working_log::CheckpointKind;
@@ -845,7 +1206,7 @@ mod tests {
             vec![],
         );
         working_log
-            .append_checkpoint(&checkpoint)
+            .append_checkpoint(&checkpoint, &working_log.lock().unwrap())
             .expect("Failed to append checkpoint");
 
         // Verify they exist
@@ -857,7 +1218,7 @@ mod tests {
 
         // Reset the working log
         working_log
-            .reset_working_log()
+            .reset_working_log(&working_log.lock().unwrap())
             .expect("Failed to reset working log");
 
         // Verify blobs are cleared
@@ -890,6 +1251,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blame_prompt_cache_is_keyed_to_notes_tip() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+
+        assert!(repo_storage.read_blame_prompt_cache("tip-a").is_empty());
+
+        let mut prompts = HashMap::new();
+        prompts.insert("abc1234".to_string(), None);
+        repo_storage
+            .write_blame_prompt_cache("tip-a", &prompts)
+            .expect("Failed to write blame prompt cache");
+
+        let cached = repo_storage.read_blame_prompt_cache("tip-a");
+        assert_eq!(cached.len(), 1);
+        assert!(cached["abc1234"].is_none());
+        assert!(
+            repo_storage.read_blame_prompt_cache("tip-b").is_empty(),
+            "Cache written for another notes tip should be ignored"
+        );
+    }
+
     #[test]
     fn test_working_log_for_base_commit_creates_directory() {
         // Create a temporary repository
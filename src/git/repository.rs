@@ -714,6 +714,42 @@ impl<'a> Tree<'a> {
             ))),
         }
     }
+
+    // Blob ids of `paths` in this tree and its subtrees, keyed by path. One `git ls-tree` per
+    // batch of paths rather than a `get_path` per file; paths that aren't blobs are left out.
+    pub fn blob_ids_for_paths(
+        &self,
+        paths: &[String],
+    ) -> Result<HashMap<String, String>, GitAiError> {
+        let mut blob_ids = HashMap::new();
+        for batch in paths.chunks(256) {
+            let mut args = self.repo.global_args_for_exec();
+            args.push("ls-tree".to_string());
+            args.push("-z".to_string());
+            args.push("-r".to_string());
+            args.push(self.oid.clone());
+            args.push("--".to_string());
+            args.extend(batch.iter().cloned());
+            let output = exec_git(&args)?;
+
+            // Each record: "<mode> <type> <object>\t<file>\0"
+            for record in output.stdout.split(|b| *b == 0u8) {
+                let mut parts = record.splitn(2, |b| *b == b'\t');
+                let meta = String::from_utf8_lossy(parts.next().unwrap_or(&[])).to_string();
+                let Some(file_bytes) = parts.next() else {
+                    continue;
+                };
+                let mut meta = meta.split_whitespace().skip(1);
+                if let (Some("blob"), Some(oid)) = (meta.next(), meta.next()) {
+                    blob_ids.insert(
+                        String::from_utf8_lossy(file_bytes).to_string(),
+                        oid.to_string(),
+                    );
+                }
+            }
+        }
+        Ok(blob_ids)
+    }
 }
 
 pub struct Blob<'a> {
@@ -1000,6 +1036,33 @@ impl Repository {
         normalized.starts_with(&self.workdir)
     }
 
+    // Commits at the boundary of a shallow clone, which git treats as having no parents.
+    // Empty for a complete clone.
+    pub fn shallow_commits(&self) -> Result<HashSet<String>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("rev-parse".to_string());
+        args.push("--path-format=absolute".to_string());
+        args.push("--git-path".to_string());
+        args.push("shallow".to_string());
+        let output = exec_git(&args)?;
+        let shallow_file = PathBuf::from(String::from_utf8(output.stdout)?.trim());
+
+        match std::fs::read_to_string(shallow_file) {
+            Ok(contents) => Ok(contents
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashSet::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn is_shallow(&self) -> bool {
+        self.shallow_commits()
+            .is_ok_and(|commits| !commits.is_empty())
+    }
+
     // List all remotes for a given repository
     pub fn remotes(&self) -> Result<Vec<String>, GitAiError> {
         let mut args = self.global_args_for_exec();
@@ -1007,7 +1070,12 @@ impl Repository {
 
         let output = exec_git(&args)?;
         let remotes = String::from_utf8(output.stdout)?;
-        Ok(remotes.trim().split("\n").map(|s| s.to_string()).collect())
+        Ok(remotes
+            .trim()
+            .split("\n")
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect())
     }
 
     // List all remotes with their URLs as tuples (name, url)
@@ -1101,6 +1169,107 @@ impl Repository {
         }
     }
 
+    /// Whether git's attributes say each path is text: `text` or a `working-tree-encoding`
+    /// (e.g. UTF-16) mark it as text, `-text` or `binary` as binary. Paths the attributes
+    /// leave unspecified (or `text=auto`) are left out, so callers fall back to sniffing
+    /// the content.
+    pub fn text_attributes(&self, paths: &[String]) -> Result<HashMap<String, bool>, GitAiError> {
+        let mut verdicts = HashMap::new();
+        if paths.is_empty() {
+            return Ok(verdicts);
+        }
+
+        let mut args = self.global_args_for_exec();
+        args.push("check-attr".to_string());
+        args.push("-z".to_string());
+        args.push("--stdin".to_string());
+        args.push("text".to_string());
+        args.push("working-tree-encoding".to_string());
+        let mut stdin = Vec::new();
+        for path in paths {
+            stdin.extend_from_slice(path.as_bytes());
+            stdin.push(0);
+        }
+        let output = exec_git_stdin(&args, &stdin)?;
+
+        // NUL-separated <path> <attribute> <value> triples
+        let stdout = String::from_utf8(output.stdout)?;
+        let mut fields = stdout.split('\0');
+        while let (Some(path), Some(attr), Some(value)) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            let verdict = match (attr, value) {
+                (_, "unspecified") => continue,
+                ("text", "set") => true,
+                ("text", "unset") => false,
+                ("working-tree-encoding", "unset") => continue,
+                ("working-tree-encoding", _) => true,
+                _ => continue,
+            };
+            // `-text` wins over an encoding, like git skipping conversion for binary files
+            let entry = verdicts.entry(path.to_string()).or_insert(verdict);
+            *entry = *entry && verdict;
+        }
+        Ok(verdicts)
+    }
+
+    /// Paths whose CRLF line endings git turns into LF when adding them to the index: those
+    /// marked `text`, `text=auto` or with an `eol` attribute, plus (when `core.autocrlf` is
+    /// `true` or `input`) every path the attributes don't mark `-text`.
+    pub fn crlf_normalized_paths(&self, paths: &[String]) -> Result<HashSet<String>, GitAiError> {
+        let mut normalized = HashSet::new();
+        if paths.is_empty() {
+            return Ok(normalized);
+        }
+
+        let autocrlf = match self.config_get_str("core.autocrlf")? {
+            Some(value) => matches!(
+                value.to_ascii_lowercase().as_str(),
+                "true" | "yes" | "on" | "1" | "input"
+            ),
+            None => false,
+        };
+
+        let mut args = self.global_args_for_exec();
+        args.push("check-attr".to_string());
+        args.push("-z".to_string());
+        args.push("--stdin".to_string());
+        args.push("text".to_string());
+        args.push("eol".to_string());
+        let mut stdin = Vec::new();
+        for path in paths {
+            stdin.extend_from_slice(path.as_bytes());
+            stdin.push(0);
+        }
+        let output = exec_git_stdin(&args, &stdin)?;
+
+        // NUL-separated <path> <attribute> <value> triples, `text` before `eol` for each path
+        let stdout = String::from_utf8(output.stdout)?;
+        let mut fields = stdout.split('\0');
+        let mut text: Option<(String, String)> = None;
+        while let (Some(path), Some(attr), Some(value)) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            if attr == "text" {
+                text = Some((path.to_string(), value.to_string()));
+                continue;
+            }
+            let text_value = match &text {
+                Some((text_path, value)) if text_path == path => value.as_str(),
+                _ => "unspecified",
+            };
+            let converts = match text_value {
+                "set" | "auto" => true,
+                "unset" => false,
+                _ => value != "unspecified" || autocrlf,
+            };
+            if converts {
+                normalized.insert(path.to_string());
+            }
+        }
+        Ok(normalized)
+    }
+
     /// Get the git version as a tuple (major, minor, patch).
     /// Returns None if the version cannot be parsed.
     pub fn git_version(&self) -> Option<(u32, u32, u32)> {
@@ -1484,16 +1653,29 @@ impl Repository {
         push_authorship_notes(self, remote_name)
     }
 
-    pub fn upstream_remote(&self) -> Result<Option<String>, GitAiError> {
-        // Get current branch name using exec_git
+    /// Short name of the branch HEAD points at, or None when HEAD is detached.
+    /// Unborn branches (no commits yet) still report their name.
+    pub fn current_branch_name(&self) -> Result<Option<String>, GitAiError> {
         let mut args = self.global_args_for_exec();
-        args.push("branch".to_string());
-        args.push("--show-current".to_string());
-        let output = exec_git(&args)?;
-        let branch = String::from_utf8(output.stdout)?.trim().to_string();
-        if branch.is_empty() {
-            return Ok(None);
+        args.push("symbolic-ref".to_string());
+        args.push("--quiet".to_string());
+        args.push("--short".to_string());
+        args.push("HEAD".to_string());
+        match exec_git(&args) {
+            Ok(output) => {
+                let branch = String::from_utf8(output.stdout)?.trim().to_string();
+                Ok((!branch.is_empty()).then_some(branch))
+            }
+            // Exit code 1: HEAD is not a symbolic ref (detached)
+            Err(GitAiError::GitCliError { code: Some(1), .. }) => Ok(None),
+            Err(e) => Err(e),
         }
+    }
+
+    pub fn upstream_remote(&self) -> Result<Option<String>, GitAiError> {
+        let Some(branch) = self.current_branch_name()? else {
+            return Ok(None);
+        };
         let config_key = format!("branch.{}.remote", branch);
         self.config_get_str(&config_key)
     }
@@ -1534,6 +1716,33 @@ impl Repository {
         }
     }
 
+    /// Map a `Name <email>` identity through the repository's mailmap (`.mailmap`,
+    /// `mailmap.file` or `mailmap.blob`), as `git log --use-mailmap` shows it. Identities
+    /// without an email, or with no mailmap to consult, are returned unchanged.
+    pub fn mailmap_identity(&self, identity: &str) -> String {
+        if !identity.contains('<') || !self.has_mailmap() {
+            return identity.to_string();
+        }
+        let mut args = self.global_args_for_exec();
+        args.push("check-mailmap".to_string());
+        args.push(identity.to_string());
+        match exec_git(&args).map(|output| String::from_utf8(output.stdout)) {
+            Ok(Ok(mapped)) if !mapped.trim().is_empty() => mapped.trim().to_string(),
+            _ => identity.to_string(),
+        }
+    }
+
+    fn has_mailmap(&self) -> bool {
+        self.workdir()
+            .is_ok_and(|dir| dir.join(".mailmap").is_file())
+            || ["mailmap.file", "mailmap.blob"].iter().any(|key| {
+                self.config_get_str(key)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|v| !v.is_empty())
+            })
+    }
+
     // Create an iterator for the repo's references (git2-style)
     #[allow(dead_code)]
     pub fn references<'a>(&'a self) -> Result<References<'a>, GitAiError> {
@@ -1585,16 +1794,14 @@ impl Repository {
         Ok(Blob { repo: self, oid })
     }
 
-    // Lookup a reference to one of the objects in a repository.
-    pub fn find_tree(&self, oid: String) -> Result<Tree<'_>, GitAiError> {
-        let typ = self.object_type(&oid)?;
-        if typ != "tree" {
-            return Err(GitAiError::Generic(format!(
-                "Object is not a tree: {} (type: {})",
-                oid, typ
-            )));
+    /// Content of the blob `oid`, for ids already known to be blobs (e.g. from
+    /// [`Tree::blob_ids_for_paths`]), skipping the type check `find_blob` makes.
+    pub fn blob_content(&self, oid: &str) -> Result<Vec<u8>, GitAiError> {
+        Blob {
+            repo: self,
+            oid: oid.to_string(),
         }
-        Ok(Tree { repo: self, oid })
+        .content()
     }
 
     /// Get the content of a file at a specific commit
@@ -1882,9 +2089,10 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
     let git_dir_str = lines[0];
     let workdir_str = lines[1];
     let workdir = PathBuf::from(workdir_str);
-    // --git-dir may return a relative path (e.g. ".git"); resolve it against the toplevel
+    // --git-dir may return a relative path (e.g. ".git"). It is relative to the directory git
+    // ran in, which is not the toplevel when core.worktree points somewhere else.
     let git_dir = if Path::new(git_dir_str).is_relative() {
-        workdir.join(git_dir_str)
+        invocation_dir(global_args)?.join(git_dir_str)
     } else {
         PathBuf::from(git_dir_str)
     };
@@ -1914,6 +2122,15 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         global_args[1] = workdir_str;
     }
 
+    // With core.worktree the toplevel has no .git of its own, so running git from it (via -C)
+    // would not find the repository. Pin the git dir and work tree explicitly.
+    if !workdir.join(".git").exists() {
+        global_args.push("--git-dir".to_string());
+        global_args.push(git_dir.display().to_string());
+        global_args.push("--work-tree".to_string());
+        global_args.push(workdir.display().to_string());
+    }
+
     // Canonicalize workdir for reliable path comparisons (especially on Windows)
     // On Windows, canonical paths use the \\?\ UNC prefix, which makes path.starts_with()
     // comparisons work correctly. We store both regular and canonical versions.
@@ -1925,7 +2142,7 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         ))
     })?;
 
-    Ok(Repository {
+    let repo = Repository {
         global_args,
         storage: RepoStorage::for_repo_path(&git_dir, &workdir),
         git_dir,
@@ -1934,7 +2151,29 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         pre_reset_target_commit: None,
         workdir,
         canonical_workdir,
-    })
+    };
+
+    // Session hashes and working log blobs are written far from any repository handle, so
+    // the repo's gitai.hashLength and gitai.compressBlobs are applied process-wide
+    crate::authorship::authorship_log_serialization::configure_short_hash_length(&repo);
+    crate::git::repo_storage::configure_blob_compression(&repo);
+
+    Ok(repo)
+}
+
+/// Directory a git invocation with these global args runs in: the last `-C` (each resolved
+/// against the previous one, as git does), or the process's current directory.
+fn invocation_dir(global_args: &[String]) -> Result<PathBuf, GitAiError> {
+    let mut dir = std::env::current_dir()?;
+    let mut args = global_args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "-C"
+            && let Some(path) = args.next()
+        {
+            dir = dir.join(path);
+        }
+    }
+    Ok(dir)
 }
 
 #[allow(dead_code)]
@@ -2121,6 +2360,24 @@ pub fn exec_git(args: &[String]) -> Result<Output, GitAiError> {
     Ok(output)
 }
 
+/// Helper to start a git command whose stdout is read as it is produced. The caller must
+/// wait on the child and check its status.
+pub fn spawn_git(args: &[String]) -> Result<std::process::Child, GitAiError> {
+    let mut cmd = Command::new(config::Config::get().git_cmd());
+    cmd.args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    #[cfg(windows)]
+    {
+        if !is_interactive_terminal() {
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+    }
+
+    cmd.spawn().map_err(GitAiError::IoError)
+}
+
 /// Helper to execute a git command with data provided on stdin
 pub fn exec_git_stdin(args: &[String], stdin_data: &[u8]) -> Result<Output, GitAiError> {
     // TODO Make sure to handle process signals, etc.
@@ -2442,6 +2699,46 @@ fn parse_hunk_header(line: &str) -> Option<(Vec<u32>, bool)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_utils::TmpRepo;
+
+    #[test]
+    fn test_current_branch_name_on_branch() {
+        let (tmp_repo, _, _) = TmpRepo::new_with_base_commit().unwrap();
+        tmp_repo.create_branch("feature/x").unwrap();
+        assert_eq!(
+            tmp_repo.gitai_repo().current_branch_name().unwrap(),
+            Some("feature/x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_current_branch_name_detached_head() {
+        let (tmp_repo, _, _) = TmpRepo::new_with_base_commit().unwrap();
+        let head = tmp_repo.head_commit_sha().unwrap();
+        let repo = tmp_repo.gitai_repo();
+        let mut args = repo.global_args_for_exec();
+        args.extend(["checkout".to_string(), "--detach".to_string(), head]);
+        exec_git(&args).unwrap();
+        assert_eq!(repo.current_branch_name().unwrap(), None);
+        assert_eq!(repo.upstream_remote().unwrap(), None);
+    }
+
+    #[test]
+    fn test_current_branch_name_unborn_branch() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        let repo = tmp_repo.gitai_repo();
+        let mut args = repo.global_args_for_exec();
+        args.extend([
+            "symbolic-ref".to_string(),
+            "HEAD".to_string(),
+            "refs/heads/fresh".to_string(),
+        ]);
+        exec_git(&args).unwrap();
+        assert_eq!(
+            repo.current_branch_name().unwrap(),
+            Some("fresh".to_string())
+        );
+    }
 
     #[test]
     fn test_parse_git_version_standard() {
@@ -2490,6 +2787,39 @@ mod tests {
         assert_eq!(parse_git_version("git version x.y.z"), None);
     }
 
+    #[test]
+    fn test_tree_blob_ids_for_paths() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        tmp_repo.write_file("a.txt", "a\n", false).unwrap();
+        tmp_repo.write_file("dir/b.txt", "b\n", false).unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_author("test_user")
+            .unwrap();
+        tmp_repo.commit_with_message("Add files").unwrap();
+
+        let repo = tmp_repo.gitai_repo();
+        let head = repo
+            .find_commit(repo.head().unwrap().target().unwrap())
+            .unwrap();
+        let tree = head.tree().unwrap();
+        let paths = vec![
+            "a.txt".to_string(),
+            "dir/b.txt".to_string(),
+            "missing.txt".to_string(),
+        ];
+        let blob_ids = tree.blob_ids_for_paths(&paths).unwrap();
+
+        assert_eq!(blob_ids.len(), 2);
+        assert_eq!(
+            repo.blob_content(&blob_ids["dir/b.txt"]).unwrap(),
+            b"b\n".to_vec()
+        );
+        assert_eq!(
+            blob_ids["a.txt"],
+            tree.get_path(Path::new("a.txt")).unwrap().id()
+        );
+    }
+
     #[test]
     fn test_list_commit_files_with_utf8_filename() {
         use crate::git::test_utils::TmpRepo;
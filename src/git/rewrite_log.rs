@@ -50,7 +50,6 @@ pub enum RewriteLogEvent {
 }
 
 impl RewriteLogEvent {
-    #[allow(dead_code)]
     pub fn merge(
         source_branch: String,
         target_branch: String,
@@ -159,7 +158,6 @@ pub struct MergeEvent {
 }
 
 impl MergeEvent {
-    #[allow(dead_code)]
     pub fn new(
         source_branch: String,
         target_branch: String,
@@ -60,6 +60,9 @@ impl Repository {
         args.push("diff".to_string());
         args.push("--cached".to_string());
         args.push("--name-only".to_string());
+        // List both sides of a staged rename; `status` only pairs a rename up when both paths
+        // are in its pathspec
+        args.push("--no-renames".to_string());
         args.push("-z".to_string()); // NUL-separated output for proper UTF-8 handling
 
         let output = exec_git(&args)?;
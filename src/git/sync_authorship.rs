@@ -1,5 +1,5 @@
 use crate::git::refs::{
-    AI_AUTHORSHIP_PUSH_REFSPEC, copy_ref, merge_notes_from_ref, ref_exists, tracking_ref_for_remote,
+    authorship_refspec, copy_ref, merge_notes_from_ref, ref_exists, tracking_ref_for_remote,
 };
 use crate::{
     error::GitAiError,
@@ -18,10 +18,13 @@ pub enum NotesExistence {
     NotFound,
 }
 
+/// Resolve the remote a fetch or pull reads from: the one named in the arguments, else the
+/// upstream remote, else the default remote. `Ok(None)` means the repository has no remotes;
+/// naming a remote that isn't configured is an error.
 pub fn fetch_remote_from_args(
     repository: &Repository,
     parsed_args: &ParsedGitInvocation,
-) -> Result<String, GitAiError> {
+) -> Result<Option<String>, GitAiError> {
     let remotes = repository.remotes().ok();
     let remote_names: Vec<String> = remotes
         .as_ref()
@@ -43,11 +46,25 @@ pub fn fetch_remote_from_args(
             .cloned()
     });
 
-    let remote = specified_remote
-        .or_else(|| repository.upstream_remote().ok().flatten())
-        .or_else(|| repository.get_default_remote().ok().flatten());
+    // A bare name in the repository slot that isn't a configured remote would otherwise fall
+    // back to the upstream remote and fetch notes from somewhere the user didn't ask for
+    if specified_remote.is_none()
+        && !parsed_args
+            .command_args
+            .iter()
+            .any(|a| a == "--all" || a == "--multiple")
+        && let Some(name) = parsed_args.pos_command(0)
+    {
+        let (refspec, _) = authorship_refspec(repository)?;
+        return Err(GitAiError::Generic(format!(
+            "remote '{}' not found; skipping authorship notes fetch. To fetch notes from a repository that isn't a configured remote, pass the refspec explicitly: git fetch <repository> +{}",
+            name, refspec
+        )));
+    }
 
-    Ok(remote.unwrap().to_string())
+    Ok(specified_remote
+        .or_else(|| repository.upstream_remote().ok().flatten())
+        .or_else(|| repository.get_default_remote().ok().flatten()))
 }
 
 // for use with post-fetch and post-pull and post-clone hooks
@@ -60,18 +77,19 @@ pub fn fetch_authorship_notes(
 ) -> Result<NotesExistence, GitAiError> {
     // Generate tracking ref for this remote
     let tracking_ref = tracking_ref_for_remote(remote_name);
+    let (_, remote_ref) = authorship_refspec(repository)?;
 
     debug_log(&format!(
         "fetching authorship notes for remote '{}' to tracking ref '{}'",
         remote_name, tracking_ref
     ));
 
-    // First, check if the remote has refs/notes/ai using ls-remote
+    // First, check if the remote has the notes ref using ls-remote
     // This is important for bare repos where the refmap might not be configured
     let mut ls_remote_args = repository.global_args_for_exec();
     ls_remote_args.push("ls-remote".to_string());
     ls_remote_args.push(remote_name.to_string());
-    ls_remote_args.push("refs/notes/ai".to_string());
+    ls_remote_args.push(remote_ref.clone());
 
     debug_log(&format!("ls-remote command: {:?}", ls_remote_args));
 
@@ -107,7 +125,7 @@ pub fn fetch_authorship_notes(
     }
 
     // Now fetch the notes to the tracking ref with explicit refspec
-    let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
+    let fetch_refspec = format!("+{}:{}", remote_ref, tracking_ref);
 
     // Build the internal authorship fetch with explicit flags and disabled hooks
     // IMPORTANT: use repository.global_args_for_exec() to ensure -C flag is present for bare repos
@@ -181,7 +199,8 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
     // STEP 1: Fetch remote notes into tracking ref and merge before pushing
     // This ensures we don't lose notes from other branches/clones
     let tracking_ref = tracking_ref_for_remote(remote_name);
-    let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
+    let (push_refspec, remote_ref) = authorship_refspec(repository)?;
+    let fetch_refspec = format!("+{}:{}", remote_ref, tracking_ref);
 
     let mut fetch_before_push: Vec<String> = repository.global_args_for_exec();
     fetch_before_push.push("-c".to_string());
@@ -238,7 +257,7 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
     push_authorship.push("--no-verify".to_string());
     push_authorship.push("--no-signed".to_string());
     push_authorship.push(remote_name.to_string());
-    push_authorship.push(AI_AUTHORSHIP_PUSH_REFSPEC.to_string());
+    push_authorship.push(push_refspec);
 
     debug_log(&format!(
         "pushing authorship refs (no force): {:?}",
@@ -367,7 +367,9 @@ impl TmpRepo {
             true,
             None, // agent_run_result
             false,
+            false,
         )
+        .map(|summary| (summary.entries, summary.files, summary.working_log_len))
     }
 
     /// Triggers a checkpoint with AI content, creating proper prompts and agent data
@@ -401,6 +403,7 @@ impl TmpRepo {
         // Create a minimal transcript with empty messages (as requested)
         let transcript = AiTranscript {
             messages: vec![], // Default to empty as requested
+            truncated: false,
         };
 
         // Create agent run result
@@ -424,7 +427,9 @@ impl TmpRepo {
             true,
             Some(agent_run_result),
             false,
+            false,
         )
+        .map(|summary| (summary.entries, summary.files, summary.working_log_len))
     }
 
     /// Triggers a checkpoint with a custom agent run result
@@ -446,7 +451,9 @@ impl TmpRepo {
             true,  // quiet
             agent_run_result,
             false,
+            false,
         )
+        .map(|summary| (summary.entries, summary.files, summary.working_log_len))
     }
 
     /// Commits all changes with the given message and runs post-commit hook
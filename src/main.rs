@@ -20,11 +20,35 @@ use clap::Parser;
 #[command(about = "git proxy with AI authorship tracking", long_about = None)]
 #[command(disable_help_flag = true, disable_version_flag = true)]
 struct Cli {
+    /// Only print errors and warnings
+    #[arg(short = 'q', long)]
+    quiet: bool,
+
+    /// Print debug output, including timings
+    #[arg(long)]
+    verbose: bool,
+
     /// Git command and arguments
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     args: Vec<String>,
 }
 
+impl Cli {
+    /// Arguments for the git proxy. The verbosity flags are git-ai's own, so hand them back to
+    /// git rather than swallowing them.
+    fn git_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.quiet {
+            args.push("--quiet".to_string());
+        }
+        if self.verbose {
+            args.push("--verbose".to_string());
+        }
+        args.extend(self.args.iter().cloned());
+        args
+    }
+}
+
 fn main() {
     // Get the binary name that was called
     let binary_name = std::env::args_os()
@@ -43,15 +67,20 @@ fn main() {
     #[cfg(debug_assertions)]
     {
         if std::env::var("GIT_AI").as_deref() == Ok("git") {
-            commands::git_handlers::handle_git(&cli.args);
+            commands::git_handlers::handle_git(&cli.git_args());
             return;
         }
     }
 
     if binary_name == "git-ai" || binary_name == "git-ai.exe" {
+        if cli.quiet {
+            utils::set_verbosity(utils::Verbosity::Quiet);
+        } else if cli.verbose {
+            utils::set_verbosity(utils::Verbosity::Verbose);
+        }
         commands::git_ai_handlers::handle_git_ai(&cli.args);
         std::process::exit(0);
     }
 
-    commands::git_handlers::handle_git(&cli.args);
+    commands::git_handlers::handle_git(&cli.git_args());
 }
@@ -155,7 +155,7 @@ pub fn install_skills(dry_run: bool, _verbose: bool) -> Result<SkillsInstallResu
 
     let marketplace = generate_marketplace();
     let marketplace_content = serde_json::to_string_pretty(&marketplace)
-        .map_err(|e| GitAiError::Generic(format!("Failed to serialize marketplace.json: {}", e)))?;
+        .map_err(|e| GitAiError::Serialization(format!("marketplace.json: {}", e)))?;
     let marketplace_path = marketplace_plugin_dir.join("marketplace.json");
     write_atomic(&marketplace_path, marketplace_content.as_bytes())?;
 
@@ -169,7 +169,7 @@ pub fn install_skills(dry_run: bool, _verbose: bool) -> Result<SkillsInstallResu
 
     let plugin_json = generate_plugin_json();
     let plugin_json_content = serde_json::to_string_pretty(&plugin_json)
-        .map_err(|e| GitAiError::Generic(format!("Failed to serialize plugin.json: {}", e)))?;
+        .map_err(|e| GitAiError::Serialization(format!("plugin.json: {}", e)))?;
     let plugin_json_path = plugin_claude_dir.join("plugin.json");
     write_atomic(&plugin_json_path, plugin_json_content.as_bytes())?;
 
@@ -9,6 +9,39 @@ use std::path::PathBuf;
 static DEBUG_ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
 static DEBUG_PERFORMANCE_LEVEL: std::sync::OnceLock<u8> = std::sync::OnceLock::new();
 static IS_TERMINAL: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+static VERBOSITY: std::sync::OnceLock<Verbosity> = std::sync::OnceLock::new();
+
+/// How much git-ai prints, set once from the global `-q/--quiet` and `--verbose` flags
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Errors and warnings only
+    Quiet,
+    #[default]
+    Normal,
+    /// Also debug output, including `[BENCHMARK]` timings, even in release builds
+    Verbose,
+}
+
+/// Set the verbosity for the rest of the process. Only the first call takes effect.
+pub fn set_verbosity(verbosity: Verbosity) {
+    let _ = VERBOSITY.set(verbosity);
+}
+
+pub fn verbosity() -> Verbosity {
+    VERBOSITY.get().copied().unwrap_or_default()
+}
+
+/// True when `-q/--quiet` was passed or `quiet` is set in the config file
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet || crate::config::Config::get().is_quiet()
+}
+
+/// Print informational output (progress, summaries) to stderr unless quiet (see [`is_quiet`])
+pub fn info_log(msg: &str) {
+    if !is_quiet() {
+        eprintln!("{}", msg);
+    }
+}
 
 fn is_debug_enabled() -> bool {
     *DEBUG_ENABLED.get_or_init(|| {
@@ -47,13 +80,19 @@ pub fn debug_performance_log_structured(json: serde_json::Value) {
 /// Debug logging utility function
 ///
 /// Prints debug messages with a colored prefix when debug assertions are enabled or when
-/// the `GIT_AI_DEBUG` environment variable is set to "1".
+/// the `GIT_AI_DEBUG` environment variable is set to "1". `--verbose` always enables them and
+/// `--quiet` always silences them.
 ///
 /// # Arguments
 ///
 /// * `msg` - The debug message to print
 pub fn debug_log(msg: &str) {
-    if is_debug_enabled() {
+    let enabled = match verbosity() {
+        Verbosity::Quiet => false,
+        Verbosity::Normal => is_debug_enabled(),
+        Verbosity::Verbose => true,
+    };
+    if enabled {
         eprintln!("\x1b[1;33m[git-ai]\x1b[0m {}", msg);
     }
 }
@@ -162,7 +201,7 @@ pub const CREATE_NO_WINDOW: u32 = 0x08000000;
 /// ```
 pub fn unescape_git_path(path: &str) -> String {
     // If not quoted, return as-is
-    if !path.starts_with('"') || !path.ends_with('"') {
+    if path.len() < 2 || !path.starts_with('"') || !path.ends_with('"') {
         return path.to_string();
     }
 
@@ -234,10 +273,54 @@ pub fn unescape_git_path(path: &str) -> String {
     })
 }
 
+/// Quote `path` the way git quotes unusual paths: wrapped in double quotes, with `"`, `\`
+/// and control characters backslash-escaped. Non-ASCII characters are kept as UTF-8 rather
+/// than octal-escaped. [`unescape_git_path`] reverses it.
+///
+/// ```
+/// use git_ai::utils::{escape_git_path, unescape_git_path};
+///
+/// assert_eq!(escape_git_path("a\tb \"c\".txt"), "\"a\\tb \\\"c\\\".txt\"");
+/// assert_eq!(unescape_git_path(&escape_git_path("  two spaces")), "  two spaces");
+/// ```
+pub fn escape_git_path(path: &str) -> String {
+    let mut quoted = String::with_capacity(path.len() + 2);
+    quoted.push('"');
+    for c in path.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_ascii_control() => quoted.push_str(&format!("\\{:03o}", c as u8)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quote a CSV field (RFC 4180) when it contains a comma, quote or line break.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("src/main.rs"), "src/main.rs");
+        assert_eq!(csv_field("a,b.txt"), "\"a,b.txt\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
     #[test]
     fn test_unescape_git_path_simple() {
         // Unquoted path - no change
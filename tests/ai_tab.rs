@@ -366,3 +366,38 @@ fn test_ai_tab_e2e_handles_dirty_files_map() {
         "}".ai(),
     ]);
 }
+
+#[test]
+fn test_kind_flag_records_tab_checkpoint() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("lib.rs"), "fn a() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial human commit").unwrap();
+
+    fs::write(repo.path().join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "--kind", "tab", "lib.rs"])
+        .unwrap();
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let kinds: Vec<CheckpointKind> = checkpoints.iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, vec![CheckpointKind::AiTab]);
+    assert_eq!(checkpoints[0].entries.len(), 1);
+    assert_eq!(checkpoints[0].entries[0].file, "lib.rs");
+
+    repo.stage_all_and_commit("Accept completion").unwrap();
+    let mut file = repo.filename("lib.rs");
+    file.assert_lines_and_blame(lines!["fn a() {}".human(), "fn b() {}".ai()]);
+}
+
+#[test]
+fn test_kind_flag_requires_agent_for_ai_kinds() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("lib.rs"), "fn a() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial human commit").unwrap();
+
+    let err = repo.git_ai(&["checkpoint", "--kind", "tab"]).unwrap_err();
+    assert!(err.contains("requires an agent preset"), "{}", err);
+    let err = repo
+        .git_ai(&["checkpoint", "mock_ai", "--kind", "autocomplete"])
+        .unwrap_err();
+    assert!(err.contains("--kind requires one of"), "{}", err);
+}
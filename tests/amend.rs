@@ -441,3 +441,51 @@ fn test_amend_with_unstaged_middle_section() {
         "// AI section 3 line 2".ai()
     ]);
 }
+
+#[test]
+fn test_amend_moves_ai_authorship_to_amended_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.txt");
+    file.set_contents(lines!["human line", "ai line 1".ai(), "ai line 2".ai()]);
+    let original = repo.stage_all_and_commit("Agent commit").unwrap();
+    assert!(!original.authorship_log.attestations.is_empty());
+
+    // Rewording alone keeps the note's attestations and prompts
+    repo.git(&["commit", "--amend", "-m", "Agent commit (reworded)"])
+        .unwrap();
+    let reworded = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    assert_ne!(reworded, original.commit_sha);
+    let note = repo.git(&["notes", "--ref=ai", "show", &reworded]).unwrap();
+    let original_note = repo
+        .git(&["notes", "--ref=ai", "show", &original.commit_sha])
+        .unwrap();
+    assert_eq!(
+        note.split("---").next(),
+        original_note.split("---").next(),
+        "{}",
+        note
+    );
+
+    // Amending in more AI lines extends the same note, and the replaced commit's working log
+    // is cleaned up
+    file.insert_at(3, lines!["ai line 3".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "--amend", "--no-edit"]).unwrap();
+    let amended = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    assert!(
+        !repo
+            .path()
+            .join(".git/ai/working_logs")
+            .join(&reworded)
+            .exists()
+    );
+
+    file.assert_lines_and_blame(lines![
+        "human line".human(),
+        "ai line 1".ai(),
+        "ai line 2".ai(),
+        "ai line 3".ai()
+    ]);
+    let note = repo.git(&["notes", "--ref=ai", "show", &amended]).unwrap();
+    assert!(note.contains("lib.txt"), "{}", note);
+}
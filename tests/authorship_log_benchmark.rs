@@ -0,0 +1,114 @@
+//! Benchmarks for building and reading large authorship logs.
+//!
+//! Compares `AuthorshipLog::deserialize_from_string` against the streaming
+//! `deserialize_from_reader` on a log whose attestation section is ~50k lines, as seen
+//! in monorepo commits that touch thousands of files, and times attributing a file one
+//! line at a time into a single `AttestationEntry`.
+//!
+//! Run with: cargo test --test authorship_log_benchmark --release -- --nocapture --ignored
+
+use git_ai::authorship::authorship_log::LineRange;
+use git_ai::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, FileAttestation,
+};
+use std::io::BufReader;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 10;
+
+/// 10k files with 4 sessions each: 50k attestation lines.
+fn large_log() -> AuthorshipLog {
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = "0".repeat(40);
+    for file_index in 0..10_000u32 {
+        let mut file = FileAttestation::new(format!(
+            "packages/pkg{}/src/file{}.rs",
+            file_index % 97,
+            file_index
+        ));
+        for session in 0..4u32 {
+            let start = session * 50 + 1;
+            file.add_entry(AttestationEntry::new(
+                format!("{:016x}", session),
+                vec![
+                    LineRange::Range(start, start + 20),
+                    LineRange::Single(start + 30),
+                ],
+            ));
+        }
+        log.attestations.push(file);
+    }
+    log
+}
+
+fn average(durations: &[Duration]) -> Duration {
+    durations.iter().sum::<Duration>() / durations.len() as u32
+}
+
+#[test]
+#[ignore]
+fn test_authorship_log_reader_benchmark() {
+    let log = large_log();
+    let serialized = log.serialize_to_string().unwrap();
+    let lines = serialized.lines().count();
+    println!(
+        "\nAuthorship log: {} lines, {:.1} MiB",
+        lines,
+        serialized.len() as f64 / (1024.0 * 1024.0)
+    );
+    assert!(lines >= 50_000);
+
+    let path = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(path.path(), &serialized).unwrap();
+
+    let mut from_string = Vec::new();
+    let mut from_reader = Vec::new();
+    for _ in 0..RUNS {
+        // Both start from the file on disk, as a reader of a stored note would
+        let start = Instant::now();
+        let content = std::fs::read_to_string(path.path()).unwrap();
+        let parsed = AuthorshipLog::deserialize_from_string(&content).unwrap();
+        from_string.push(start.elapsed());
+        assert_eq!(parsed.attestations.len(), log.attestations.len());
+
+        let start = Instant::now();
+        let file = std::fs::File::open(path.path()).unwrap();
+        let parsed = AuthorshipLog::deserialize_from_reader(BufReader::new(file)).unwrap();
+        from_reader.push(start.elapsed());
+        assert_eq!(parsed, log);
+    }
+
+    println!(
+        "  deserialize_from_string: {:.2}ms avg over {} runs",
+        average(&from_string).as_secs_f64() * 1000.0,
+        RUNS
+    );
+    println!(
+        "  deserialize_from_reader: {:.2}ms avg over {} runs",
+        average(&from_reader).as_secs_f64() * 1000.0,
+        RUNS
+    );
+}
+
+/// A file attributed one line at a time, in the scattered order checkpoints arrive in.
+#[test]
+#[ignore]
+fn test_attestation_entry_single_line_benchmark() {
+    const LINES: u32 = 10_000;
+
+    let start = Instant::now();
+    let mut entry = AttestationEntry::new("0".repeat(16), Vec::new());
+    // Odd lines first, then even lines fill the gaps between them
+    for line in (1..=LINES).step_by(2).chain((2..=LINES).step_by(2)) {
+        entry.merge_line_ranges(&[LineRange::Single(line)]);
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "\n{} single-line attributions: {:.2}ms, {} stored ranges",
+        LINES,
+        elapsed.as_secs_f64() * 1000.0,
+        entry.line_ranges.len()
+    );
+    assert_eq!(entry.line_ranges, vec![LineRange::Range(1, LINES)]);
+}
@@ -0,0 +1,222 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::working_log::CheckpointKind;
+use git_ai::commands::checkpoint_agent::agent_registry::{AgentRegistry, parse_agents};
+use git_ai::commands::checkpoint_agent::auto_detect::{AgentEnvironment, detect_agent};
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::collections::HashMap;
+use std::fs;
+use test_utils::fixture_path;
+
+fn environment(env: &[(&str, &str)], parent_process: Option<&str>) -> AgentEnvironment {
+    AgentEnvironment {
+        env: env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+        parent_pid: Some(4242),
+        parent_process: parent_process.map(|p| p.to_string()),
+        home_dir: None,
+        working_dir: "/work/project".to_string(),
+    }
+}
+
+#[test]
+fn test_auto_detect_agent_from_env_vars() {
+    let claude = detect_agent(
+        &environment(
+            &[
+                ("CLAUDECODE", "1"),
+                ("CLAUDE_CODE_SESSION_ID", "session-123"),
+            ],
+            Some("zsh"),
+        ),
+        &AgentRegistry::builtin(),
+    )
+    .unwrap();
+    assert_eq!(claude.agent_id.tool, "claude");
+    assert_eq!(claude.agent_id.id, "session-123");
+    assert_eq!(claude.checkpoint_kind, CheckpointKind::AiAgent);
+
+    let gemini = detect_agent(
+        &environment(&[("GEMINI_CLI", "1")], None),
+        &AgentRegistry::builtin(),
+    )
+    .unwrap();
+    assert_eq!(gemini.agent_id.tool, "gemini");
+    assert_eq!(gemini.agent_id.id, "auto-4242");
+
+    // Explicitly disabled flags don't count
+    assert!(
+        detect_agent(
+            &environment(&[("CLAUDECODE", "0")], None),
+            &AgentRegistry::builtin()
+        )
+        .is_none()
+    );
+}
+
+#[test]
+fn test_auto_detect_agent_from_parent_process() {
+    let droid = detect_agent(&environment(&[], Some("droid")), &AgentRegistry::builtin()).unwrap();
+    assert_eq!(droid.agent_id.tool, "droid");
+
+    let cursor = detect_agent(
+        &environment(&[], Some("cursor-agent")),
+        &AgentRegistry::builtin(),
+    )
+    .unwrap();
+    assert_eq!(cursor.agent_id.tool, "cursor");
+
+    assert!(
+        detect_agent(
+            &environment(&[("TERM", "xterm")], Some("bash")),
+            &AgentRegistry::builtin()
+        )
+        .is_none()
+    );
+}
+
+#[test]
+fn test_auto_detect_agent_from_recent_claude_transcript() {
+    let home = tempfile::tempdir().unwrap();
+    let project_dir = home
+        .path()
+        .join(".claude")
+        .join("projects")
+        .join("-work-project");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::copy(
+        fixture_path("example-claude-code.jsonl"),
+        project_dir.join("cb947e5b-246e-4253-a953-631f7e464c6b.jsonl"),
+    )
+    .unwrap();
+
+    let mut env = environment(&[], Some("bash"));
+    env.home_dir = Some(home.path().to_path_buf());
+    let result = detect_agent(&env, &AgentRegistry::builtin()).unwrap();
+    assert_eq!(result.agent_id.tool, "claude");
+    assert_eq!(result.agent_id.id, "cb947e5b-246e-4253-a953-631f7e464c6b");
+    assert_eq!(result.agent_id.model, "claude-sonnet-4-20250514");
+    assert!(result.transcript.is_some_and(|t| !t.messages().is_empty()));
+}
+
+#[test]
+fn test_checkpoint_auto_detect_agent_attributes_to_detected_tool() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("test.txt"), "Line 1\nAgent line\nLine 2").unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "--auto-detect-agent"],
+        &[
+            ("CLAUDECODE", "1"),
+            ("CLAUDE_CODE_SESSION_ID", "auto-detect-test"),
+        ],
+    )
+    .unwrap();
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let agent_id = checkpoints.last().and_then(|c| c.agent_id.clone()).unwrap();
+    assert_eq!(agent_id.tool, "claude");
+    assert_eq!(agent_id.id, "auto-detect-test");
+
+    repo.stage_all_and_commit("Agent edit").unwrap();
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "Agent line".ai(),
+        "Line 2".human()
+    ]);
+}
+
+#[test]
+fn test_auto_detect_agent_from_user_defined_agent() {
+    let mut registry = AgentRegistry::builtin();
+    registry.extend(
+        parse_agents(
+            r#"{"agents": [{
+                "tool": "acme",
+                "env_vars": ["ACME_AGENT"],
+                "session_env_vars": ["ACME_SESSION"],
+                "model_env_vars": ["ACME_MODEL"],
+                "process_names": ["acme-cli"]
+            }]}"#,
+        )
+        .unwrap(),
+    );
+
+    let acme = detect_agent(
+        &environment(
+            &[
+                ("ACME_AGENT", "1"),
+                ("ACME_SESSION", "s-1"),
+                ("ACME_MODEL", "acme-large"),
+            ],
+            None,
+        ),
+        &registry,
+    )
+    .unwrap();
+    assert_eq!(acme.agent_id.tool, "acme");
+    assert_eq!(acme.agent_id.id, "s-1");
+    assert_eq!(acme.agent_id.model, "acme-large");
+
+    let acme = detect_agent(&environment(&[], Some("acme-cli")), &registry).unwrap();
+    assert_eq!(acme.agent_id.tool, "acme");
+    assert_eq!(acme.agent_id.model, "unknown");
+
+    // Unknown to the built-in registry
+    assert!(
+        detect_agent(
+            &environment(&[], Some("acme-cli")),
+            &AgentRegistry::builtin()
+        )
+        .is_none()
+    );
+}
+
+#[test]
+fn test_checkpoint_with_agent_from_agents_file() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(home.path().join(".git-ai")).unwrap();
+    fs::write(
+        home.path().join(".git-ai").join("agents.json"),
+        r#"{"agents": [{"tool": "acme", "session_env_vars": ["ACME_SESSION"]}]}"#,
+    )
+    .unwrap();
+
+    fs::write(repo.path().join("test.txt"), "Line 1\nAgent line\nLine 2").unwrap();
+    // Edited alongside, but not reported by the agent
+    fs::write(repo.path().join("notes.txt"), "Human line\n").unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "acme", "test.txt"],
+        &[
+            ("HOME", home.path().to_str().unwrap()),
+            ("ACME_SESSION", "acme-session"),
+        ],
+    )
+    .unwrap();
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let agent_id = checkpoints.last().and_then(|c| c.agent_id.clone()).unwrap();
+    assert_eq!(agent_id.tool, "acme");
+    assert_eq!(agent_id.id, "acme-session");
+
+    repo.stage_all_and_commit("Agent edit").unwrap();
+    let blame = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    let agent_line = blame.lines().find(|l| l.ends_with("Agent line")).unwrap();
+    assert!(agent_line.contains("(acme "), "{}", blame);
+
+    let blame = repo.git_ai(&["blame", "notes.txt"]).unwrap();
+    assert!(!blame.contains("(acme "), "{}", blame);
+}
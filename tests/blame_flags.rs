@@ -6,7 +6,7 @@ use git_ai::authorship::authorship_log_serialization::{
 };
 use git_ai::authorship::transcript::Message;
 use git_ai::authorship::working_log::AgentId;
-use git_ai::commands::blame::GitAiBlameOptions;
+use git_ai::commands::blame::{ColorChoice, GitAiBlameOptions, parse_grouped_porcelain};
 use git_ai::git::refs::notes_add;
 use git_ai::git::repository as GitAiRepository;
 use repos::test_file::ExpectedLineExt;
@@ -35,6 +35,8 @@ fn extract_authors(output: &str) -> Vec<String> {
 fn normalize_for_snapshot(output: &str) -> String {
     output
         .lines()
+        // git-ai's porcelain adds AI attribution headers that git doesn't have
+        .filter(|line| !line.starts_with("ai-"))
         .map(|line| {
             // Handle porcelain format lines
             if line.starts_with("author-mail") || line.starts_with("committer-mail") {
@@ -160,6 +162,46 @@ fn test_blame_line_range() {
     );
 }
 
+#[test]
+fn test_blame_line_range_forms_match_git() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines![
+        "Line 1",
+        "Line 2",
+        "Line 3".ai(),
+        "Line 4".ai(),
+        "Line 5",
+        "Line 6".ai()
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Relative, reversed, open-ended, and past-the-end ranges
+    for range in ["2,+3", "5,-3", "4,2", "4,", ",2", "5,100"] {
+        let git_output = repo.git(&["blame", "-L", range, "test.txt"]).unwrap();
+        let git_ai_output = repo.git_ai(&["blame", "-L", range, "test.txt"]).unwrap();
+        assert_eq!(
+            normalize_for_snapshot(&git_output),
+            normalize_for_snapshot(&git_ai_output),
+            "-L {} should match git",
+            range
+        );
+    }
+
+    let attached = repo.git_ai(&["blame", "-L3,4", "test.txt"]).unwrap();
+    let separate = repo.git_ai(&["blame", "-L", "3,4", "test.txt"]).unwrap();
+    assert_eq!(attached, separate);
+
+    // A single line
+    let output = repo.git_ai(&["blame", "-L", "3", "test.txt"]).unwrap();
+    assert_eq!(extract_authors(&output).len(), 1);
+
+    let err = repo
+        .git_ai(&["blame", "-L", "10,12", "test.txt"])
+        .unwrap_err();
+    assert!(err.contains("has only 6 lines"), "{}", err);
+}
+
 #[test]
 fn test_blame_porcelain_format() {
     let repo = TestRepo::new();
@@ -1124,6 +1166,7 @@ fn test_blame_ai_human_author() {
             accepted_lines: 1,
             overriden_lines: 0,
             messages_url: None,
+            transcript_truncated: false,
         },
     );
 
@@ -1145,6 +1188,7 @@ fn test_blame_ai_human_author() {
             accepted_lines: 1,
             overriden_lines: 0,
             messages_url: None,
+            transcript_truncated: false,
         },
     );
 
@@ -1185,3 +1229,1273 @@ fn test_blame_ai_human_author() {
         ]
     );
 }
+
+#[test]
+fn test_blame_porcelain_emits_single_header_for_same_hash_region() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines![
+        "Line 1".ai(),
+        "Line 2".ai(),
+        "Line 3".ai(),
+        "Line 4".ai(),
+        "Line 5".ai(),
+        "Line 6".ai(),
+        "Line 7".ai(),
+        "Line 8".ai(),
+        "Line 9".ai(),
+        "Line 10".ai(),
+        "Line 11".ai(),
+        "Line 12".ai()
+    ]);
+
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let git_ai_output = repo.git_ai(&["blame", "--porcelain", "test.txt"]).unwrap();
+
+    let header_blocks = git_ai_output
+        .lines()
+        .filter(|line| line.starts_with("author "))
+        .count();
+    assert_eq!(header_blocks, 1, "Expected a single metadata block");
+
+    let four_field_headers = git_ai_output
+        .lines()
+        .filter(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            parts.len() == 4 && parts[0].len() == 40
+        })
+        .count();
+    assert_eq!(four_field_headers, 1, "Expected a single hunk header");
+
+    let content_lines = git_ai_output
+        .lines()
+        .filter(|line| line.starts_with('\t'))
+        .count();
+    assert_eq!(content_lines, 12);
+
+    let git_output = repo.git(&["blame", "--porcelain", "test.txt"]).unwrap();
+    assert_eq!(
+        normalize_for_snapshot(&git_output),
+        normalize_for_snapshot(&git_ai_output)
+    );
+}
+
+#[test]
+fn test_blame_count_only() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines![
+        "Line 1",
+        "Line 2",
+        "AI Line 3".ai(),
+        "Line 4",
+        "AI Line 5".ai(),
+        "AI Line 6".ai()
+    ]);
+
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let output = repo.git_ai(&["blame", "--count-only", "test.txt"]).unwrap();
+    assert_eq!(output.trim(), "3 6");
+
+    let ranged = repo
+        .git_ai(&["blame", "--count-only", "-L", "1,3", "test.txt"])
+        .unwrap();
+    assert_eq!(ranged.trim(), "1 3");
+}
+
+#[test]
+fn test_blame_human_only_keeps_human_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines![
+        "Line 1",
+        "AI Line 2".ai(),
+        "Line 3",
+        "AI Line 4".ai()
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Not a terminal, so AI lines are left out rather than dimmed
+    let output = repo.git_ai(&["blame", "--human-only", "test.txt"]).unwrap();
+    let contents: Vec<&str> = output
+        .lines()
+        .filter_map(|l| l.split_once(") ").map(|(_, code)| code))
+        .collect();
+    assert_eq!(contents, vec!["Line 1", "Line 3"], "{}", output);
+    assert!(!output.contains("mock_ai"), "{}", output);
+    assert!(output.contains("Test User"), "{}", output);
+
+    let summary = repo
+        .git_ai(&["blame", "--human-only", "--count-only", "test.txt"])
+        .unwrap();
+    assert_eq!(summary.trim(), "2 4 50.0%");
+}
+
+/// Two commits on test.txt whose notes attribute line 1 and line 2 to the same session, with
+/// the prompt record only in the first commit's note. Returns the session's prompt hash.
+fn commit_foreign_prompt_history(repo: &TestRepo) -> String {
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["first line", "second line"]);
+    let first_sha = repo
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+
+    file.set_contents(lines!["first line", "second line changed"]);
+    let second_sha = repo
+        .stage_all_and_commit("Change second line")
+        .unwrap()
+        .commit_sha;
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+
+    // The prompt record only lives in the first commit's note
+    let prompt_hash = "f0reign1".to_string();
+    let mut first_log = AuthorshipLog::new();
+    first_log.metadata.base_commit_sha = first_sha.clone();
+    first_log.metadata.prompts.insert(
+        prompt_hash.clone(),
+        PromptRecord {
+            agent_id: AgentId {
+                tool: "cursor".to_string(),
+                id: "session_foreign".to_string(),
+                model: "claude-3-sonnet".to_string(),
+            },
+            human_author: None,
+            messages: vec![],
+            total_additions: 1,
+            total_deletions: 0,
+            accepted_lines: 1,
+            overriden_lines: 0,
+            messages_url: None,
+            transcript_truncated: false,
+        },
+    );
+    let mut first_attestation = FileAttestation::new("test.txt".to_string());
+    first_attestation.add_entry(AttestationEntry::new(
+        prompt_hash.clone(),
+        vec![LineRange::Single(1)],
+    ));
+    first_log.attestations.push(first_attestation);
+    notes_add(
+        &gitai_repo,
+        &first_sha,
+        &first_log.serialize_to_string().unwrap(),
+    )
+    .unwrap();
+
+    // The second commit's note references the hash without carrying its metadata
+    let mut second_log = AuthorshipLog::new();
+    second_log.metadata.base_commit_sha = second_sha.clone();
+    let mut second_attestation = FileAttestation::new("test.txt".to_string());
+    second_attestation.add_entry(AttestationEntry::new(
+        prompt_hash.clone(),
+        vec![LineRange::Single(2)],
+    ));
+    second_log.attestations.push(second_attestation);
+    notes_add(
+        &gitai_repo,
+        &second_sha,
+        &second_log.serialize_to_string().unwrap(),
+    )
+    .unwrap();
+
+    prompt_hash
+}
+
+#[test]
+fn test_blame_no_merge_prompts_shows_raw_foreign_hash() {
+    let repo = TestRepo::new();
+    let prompt_hash = commit_foreign_prompt_history(&repo);
+
+    let merged = repo.git_ai(&["blame", "-L", "2,2", "test.txt"]).unwrap();
+    assert_eq!(extract_authors(&merged), vec!["cursor".to_string()]);
+
+    let local = repo
+        .git_ai(&["blame", "--no-merge-prompts", "-L", "2,2", "test.txt"])
+        .unwrap();
+    assert_eq!(extract_authors(&local), vec![prompt_hash]);
+}
+
+#[test]
+fn test_blame_warns_about_sessions_without_any_prompt() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["first line", "second line"]);
+    let sha = repo
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+
+    // A note left behind by a bad rewrite: it attests a session no note has a prompt for
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = sha.clone();
+    let mut attestation = FileAttestation::new("test.txt".to_string());
+    attestation.add_entry(AttestationEntry::new(
+        "d4ngl1ngd4ngl1ng".to_string(),
+        vec![LineRange::Single(2)],
+    ));
+    log.attestations.push(attestation);
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    notes_add(&gitai_repo, &sha, &log.serialize_to_string().unwrap()).unwrap();
+
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(
+        output.contains(&format!(
+            "Warning: authorship note for {} attests session d4ngl1ngd4ngl1ng, but no note has its prompt",
+            sha
+        )),
+        "{}",
+        output
+    );
+
+    // Sessions resolved from another commit's note are not reported
+    let repo = TestRepo::new();
+    commit_foreign_prompt_history(&repo);
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(!output.contains("Warning"), "{}", output);
+}
+
+#[test]
+fn test_blame_warns_once_per_commit_and_not_for_internal_blames() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["base line"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.set_contents(lines!["base line", "first line", "second line"]);
+    let sha = repo.stage_all_and_commit("Add lines").unwrap().commit_sha;
+
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = sha.clone();
+    let mut attestation = FileAttestation::new("test.txt".to_string());
+    attestation.add_entry(AttestationEntry::new(
+        "d4ngl1ngd4ngl1ng".to_string(),
+        vec![LineRange::Single(2)],
+    ));
+    attestation.add_entry(AttestationEntry::new(
+        "0rph4n0rph4n0rph".to_string(),
+        vec![LineRange::Single(3)],
+    ));
+    log.attestations.push(attestation);
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    notes_add(&gitai_repo, &sha, &log.serialize_to_string().unwrap()).unwrap();
+
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert_eq!(
+        output.matches("Warning: authorship note").count(),
+        1,
+        "{}",
+        output
+    );
+    assert!(
+        output.contains(&format!(
+            "Warning: authorship note for {} attests sessions 0rph4n0rph4n0rph, d4ngl1ngd4ngl1ng, but no note has their prompts",
+            sha
+        )),
+        "{}",
+        output
+    );
+
+    // Blames run internally without output (here by diff) stay quiet
+    let output = repo.git_ai(&["diff", &sha]).unwrap();
+    assert!(!output.contains("Warning"), "{}", output);
+}
+
+#[test]
+fn test_blame_persists_foreign_prompts_until_notes_change() {
+    let repo = TestRepo::new();
+    let prompt_hash = commit_foreign_prompt_history(&repo);
+    let cache_path = repo
+        .path()
+        .join(".git")
+        .join("ai")
+        .join("blame_prompt_cache.json");
+
+    let output = repo.git_ai(&["blame", "-L", "2,2", "test.txt"]).unwrap();
+    assert_eq!(extract_authors(&output), vec!["cursor".to_string()]);
+    let cache: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&cache_path).unwrap()).unwrap();
+    let notes_tip = repo.git(&["rev-parse", "refs/notes/ai"]).unwrap();
+    assert_eq!(cache["notes_tip"], notes_tip.trim());
+    assert_eq!(cache["prompts"][&prompt_hash]["agent_id"]["tool"], "cursor");
+
+    // Later runs answer from the cache instead of searching the notes again
+    let tampered = std::fs::read_to_string(&cache_path)
+        .unwrap()
+        .replace("\"cursor\"", "\"from-cache\"");
+    std::fs::write(&cache_path, tampered).unwrap();
+    let output = repo.git_ai(&["blame", "-L", "2,2", "test.txt"]).unwrap();
+    assert_eq!(extract_authors(&output), vec!["from-cache".to_string()]);
+
+    // Any new note moves refs/notes/ai and invalidates the cache
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["AI line".ai()]);
+    repo.stage_all_and_commit("Another AI commit").unwrap();
+    let output = repo.git_ai(&["blame", "-L", "2,2", "test.txt"]).unwrap();
+    assert_eq!(extract_authors(&output), vec!["cursor".to_string()]);
+}
+
+#[test]
+fn test_blame_skips_prompt_cache_without_foreign_prompts() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human line", "AI line".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    // Every session is in its own commit's note, so nothing goes to the stored cache
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert_eq!(
+        extract_authors(&output),
+        vec!["Test".to_string(), "mock_ai".to_string()]
+    );
+    assert!(
+        !repo
+            .path()
+            .join(".git")
+            .join("ai")
+            .join("blame_prompt_cache.json")
+            .exists()
+    );
+}
+
+#[test]
+fn test_blame_team_map_collapses_authors_into_teams() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    repo.git_og(&["config", "user.name", "Alice"]).unwrap();
+    repo.git_og(&["config", "user.email", "alice@example.com"])
+        .unwrap();
+    file.set_contents(lines!["Alice AI line".ai(), "Alice line"]);
+    repo.stage_all_and_commit("Alice commit").unwrap();
+
+    repo.git_og(&["config", "user.name", "Bob"]).unwrap();
+    repo.git_og(&["config", "user.email", "bob@example.com"])
+        .unwrap();
+    file.insert_at(0, lines!["Bob AI line".ai(), "Bob line"]);
+    repo.stage_all_and_commit("Bob commit").unwrap();
+
+    repo.git_og(&["config", "user.name", "Carol"]).unwrap();
+    repo.git_og(&["config", "user.email", "carol@example.com"])
+        .unwrap();
+    file.insert_at(0, lines!["Carol line"]);
+    repo.stage_all_and_commit("Carol commit").unwrap();
+
+    // Alice matches by email, Bob by (case-insensitive) name; Carol is not mapped
+    let map_path = repo.path().join("teams.json");
+    std::fs::write(
+        &map_path,
+        r#"{"ALICE@example.com": "platform", "bob": "platform"}"#,
+    )
+    .unwrap();
+
+    let output = repo
+        .git_ai(&[
+            "blame",
+            "--team-map",
+            map_path.to_str().unwrap(),
+            "test.txt",
+        ])
+        .unwrap();
+    let rows: Vec<Vec<&str>> = output
+        .lines()
+        .skip(1)
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec!["(unmapped)", "0", "1", "0.0%"],
+            vec!["platform", "2", "4", "50.0%"],
+        ],
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_blame_attribute_deleted_marks_removed_ai_block() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines![
+        "Line 1",
+        "AI Line 2".ai(),
+        "AI Line 3".ai(),
+        "Line 4"
+    ]);
+    repo.stage_all_and_commit("Add AI block").unwrap();
+
+    file.delete_range(1, 3);
+    let deletion_sha = repo
+        .stage_all_and_commit("Remove AI block")
+        .unwrap()
+        .commit_sha;
+
+    let plain = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(!plain.contains("deleted"), "{}", plain);
+
+    let output = repo
+        .git_ai(&["blame", "--attribute-deleted", "test.txt"])
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", output);
+    assert!(lines[0].contains("Line 1"), "{}", output);
+    assert!(
+        lines[1].starts_with(&format!(
+            "~{} (deleted 2 AI line(s) from ",
+            &deletion_sha[..7]
+        )),
+        "{}",
+        output
+    );
+    assert!(lines[2].contains("Line 4"), "{}", output);
+}
+
+#[test]
+fn test_blame_resolve_timeout_renders_unresolved_hashes_raw() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    let contents: Vec<String> = (1..=20).map(|n| format!("line {}", n)).collect();
+    file.set_contents(contents.iter().map(|l| l.as_str()).collect::<Vec<_>>());
+    let sha = repo
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+
+    // Every line points at a different session that no note describes
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = sha.clone();
+    let mut attestation = FileAttestation::new("test.txt".to_string());
+    for n in 1..=20u32 {
+        attestation.add_entry(AttestationEntry::new(
+            format!("gone{:04}", n),
+            vec![LineRange::Single(n)],
+        ));
+    }
+    log.attestations.push(attestation);
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    notes_add(&gitai_repo, &sha, &log.serialize_to_string().unwrap()).unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--resolve-timeout", "0", "test.txt"])
+        .unwrap();
+    let timed_out = output.lines().filter(|l| l.contains("[timeout]")).count();
+    assert_eq!(timed_out, 20, "{}", output);
+    assert!(output.contains("gone0007 [timeout]"), "{}", output);
+
+    let output = repo
+        .git_ai(&["blame", "--resolve-timeout", "60000", "test.txt"])
+        .unwrap();
+    assert!(!output.contains("[timeout]"), "{}", output);
+}
+
+#[test]
+fn test_blame_porcelain_v2_round_trips_to_per_line_attribution() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines![
+        "Human 1",
+        "AI 2".ai(),
+        "AI 3".ai(),
+        "Human 4",
+        "AI 5".ai(),
+        "Human 6"
+    ]);
+    repo.stage_all_and_commit("Mixed commit").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--porcelain-v2", "test.txt"])
+        .unwrap();
+    assert_eq!(output.lines().next(), Some("file test.txt"));
+    // One header per author even though each owns several separate regions
+    assert_eq!(output.lines().filter(|l| l.starts_with("ai ")).count(), 1);
+    assert_eq!(
+        output.lines().filter(|l| l.starts_with("human ")).count(),
+        1
+    );
+    assert!(output.contains("lines 2-3,5\n"), "{}", output);
+    assert!(output.contains("lines 1,4,6\n"), "{}", output);
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    let options = GitAiBlameOptions {
+        no_output: true,
+        use_prompt_hashes_as_names: true,
+        ..Default::default()
+    };
+    let (per_line, _) = gitai_repo.blame("test.txt", &options).unwrap();
+
+    assert_eq!(parse_grouped_porcelain(&output).unwrap(), per_line);
+}
+
+#[test]
+fn test_blame_ai_only_files_lists_files_with_ai_lines_in_range() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["Readme"]);
+    let base = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut mostly_ai = repo.filename("src/mostly_ai.rs");
+    mostly_ai.set_contents(lines!["AI 1".ai(), "AI 2".ai(), "AI 3".ai()]);
+    let mut human = repo.filename("src/human.rs");
+    human.set_contents(lines!["Human 1", "Human 2"]);
+    repo.stage_all_and_commit("Add files").unwrap();
+
+    let mut touched = repo.filename("src/touched.rs");
+    touched.set_contents(lines!["Human", "One AI line".ai()]);
+    let head = repo.stage_all_and_commit("Touch").unwrap();
+
+    let range = format!("{}..{}", base.commit_sha, head.commit_sha);
+    let output = repo.git_ai(&["blame", "--ai-only-files", &range]).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec!["src/mostly_ai.rs", "src/touched.rs"]
+    );
+
+    let output = repo
+        .git_ai(&["blame", "--ai-only-files", &range, "--min-lines", "2"])
+        .unwrap();
+    assert_eq!(output.lines().collect::<Vec<_>>(), vec!["src/mostly_ai.rs"]);
+
+    // The base commit alone has no AI lines
+    let output = repo
+        .git_ai(&["blame", "--ai-only-files", &base.commit_sha])
+        .unwrap();
+    assert!(output.trim().is_empty(), "{}", output);
+}
+
+#[test]
+fn test_blame_prompt_with_empty_tool_falls_back_to_model_or_ai() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["first line", "second line"]);
+    let sha = repo
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+
+    // Malformed records from an older or third-party writer: no tool, with and without a model
+    let record = |model: &str| PromptRecord {
+        agent_id: AgentId {
+            tool: "".to_string(),
+            id: format!("session-{}", model),
+            model: model.to_string(),
+        },
+        human_author: None,
+        messages: vec![],
+        total_additions: 1,
+        total_deletions: 0,
+        accepted_lines: 1,
+        overriden_lines: 0,
+        messages_url: None,
+        transcript_truncated: false,
+    };
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = sha.clone();
+    log.metadata
+        .prompts
+        .insert("emptytl1".to_string(), record("gpt-4o"));
+    log.metadata
+        .prompts
+        .insert("emptytl2".to_string(), record("unknown"));
+    let mut attestation = FileAttestation::new("test.txt".to_string());
+    attestation.add_entry(AttestationEntry::new(
+        "emptytl1".to_string(),
+        vec![LineRange::Single(1)],
+    ));
+    attestation.add_entry(AttestationEntry::new(
+        "emptytl2".to_string(),
+        vec![LineRange::Single(2)],
+    ));
+    log.attestations.push(attestation);
+    notes_add(&gitai_repo, &sha, &log.serialize_to_string().unwrap()).unwrap();
+
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert_eq!(
+        extract_authors(&output),
+        vec!["gpt-4o".to_string(), "ai".to_string()]
+    );
+}
+
+#[test]
+fn test_blame_limit_lines_truncates_output() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("generated.txt");
+    file.set_contents(lines![
+        "AI 1".ai(),
+        "Line 2",
+        "Line 3",
+        "AI 4".ai(),
+        "Line 5",
+        "Line 6",
+        "Line 7",
+        "Line 8"
+    ]);
+    repo.stage_all_and_commit("Generated file").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--limit-lines", "3", "generated.txt"])
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 4, "{}", output);
+    assert!(lines[2].contains("Line 3"), "{}", output);
+    assert_eq!(lines[3], "\u{2026} (truncated, 5 more lines)");
+
+    // Applies after -L ranges
+    let output = repo
+        .git_ai(&["blame", "-L", "4,8", "--limit-lines", "2", "generated.txt"])
+        .unwrap();
+    assert!(
+        output.contains("AI 4") && output.contains("Line 5"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("Line 6"), "{}", output);
+    assert!(output.contains("(truncated, 3 more lines)"), "{}", output);
+
+    let json = repo
+        .git_ai(&["blame", "--json", "--limit-lines", "3", "generated.txt"])
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["truncated"], serde_json::Value::Bool(true));
+    let ranges: Vec<&String> = value["lines"].as_object().unwrap().keys().collect();
+    assert_eq!(ranges, vec!["1"]);
+
+    // Not truncated: no flag
+    let json = repo
+        .git_ai(&["blame", "--json", "--limit-lines", "100", "generated.txt"])
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value.get("truncated").is_none(), "{}", json);
+}
+
+#[test]
+fn test_blame_oldest_commit_bounds_walk() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Base 1", "Base 2", "Base 3"]);
+    repo.stage_all_and_commit("Base").unwrap();
+    file.insert_at(1, lines!["Old AI".ai()]);
+    let bound = repo.stage_all_and_commit("Old AI").unwrap();
+    file.insert_at(3, lines!["New AI".ai()]);
+    repo.stage_all_and_commit("New AI").unwrap();
+    file.insert_at(4, lines!["New human"]);
+    repo.stage_all_and_commit("New human").unwrap();
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    let unbounded = GitAiBlameOptions {
+        no_output: true,
+        return_human_authors_as_human: true,
+        use_prompt_hashes_as_names: true,
+        ..Default::default()
+    };
+    let bounded = GitAiBlameOptions {
+        oldest_commit: Some(bound.commit_sha.clone()),
+        ..unbounded.clone()
+    };
+
+    // Everything at or before the bound collapses into boundary hunks of the bound commit
+    let all_hunks = gitai_repo
+        .blame_hunks("test.txt", 1, 6, &unbounded)
+        .unwrap();
+    let bounded_hunks = gitai_repo.blame_hunks("test.txt", 1, 6, &bounded).unwrap();
+    let commits = |hunks: &[git_ai::commands::blame::BlameHunk]| {
+        hunks
+            .iter()
+            .map(|h| h.commit_sha.clone())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    };
+    assert_eq!(commits(&all_hunks), 4);
+    assert_eq!(commits(&bounded_hunks), 3);
+    assert!(
+        bounded_hunks
+            .iter()
+            .filter(|h| h.is_boundary)
+            .all(|h| h.commit_sha == bound.commit_sha)
+    );
+
+    // Lines introduced inside the range keep the same attribution
+    let (all_authors, _) = gitai_repo.blame("test.txt", &unbounded).unwrap();
+    let (bounded_authors, _) = gitai_repo.blame("test.txt", &bounded).unwrap();
+    for line in [4, 5] {
+        assert_eq!(
+            all_authors.get(&line),
+            bounded_authors.get(&line),
+            "line {}",
+            line
+        );
+    }
+    assert_ne!(bounded_authors.get(&4).unwrap(), "human");
+    assert_eq!(bounded_authors.get(&5).unwrap(), "human");
+}
+
+#[test]
+fn test_blame_agent_legend_lists_sessions_shown() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Human 1", "Session A".ai(), "Human 3"]);
+    repo.stage_all_and_commit("First session").unwrap();
+    file.insert_at(3, lines!["Session B 1".ai(), "Session B 2".ai()]);
+    repo.stage_all_and_commit("Second session").unwrap();
+
+    let legend_rows = |output: &str| -> Vec<String> {
+        output
+            .lines()
+            .skip_while(|l| *l != "Agents:")
+            .skip(1)
+            .map(|l| l.to_string())
+            .collect()
+    };
+
+    let output = repo
+        .git_ai(&["blame", "--agent-legend", "test.txt"])
+        .unwrap();
+    let rows = legend_rows(&output);
+    assert_eq!(rows.len(), 2, "{}", output);
+    assert!(
+        rows[0].ends_with("mock_ai unknown  1 line(s)"),
+        "{}",
+        output
+    );
+    assert!(
+        rows[1].ends_with("mock_ai unknown  2 line(s)"),
+        "{}",
+        output
+    );
+    let hash_a = rows[0].split_whitespace().next().unwrap();
+    let hash_b = rows[1].split_whitespace().next().unwrap();
+    assert_ne!(hash_a, hash_b);
+
+    // Only sessions on the shown lines are listed
+    let output = repo
+        .git_ai(&["blame", "--agent-legend", "-L", "1,3", "test.txt"])
+        .unwrap();
+    let rows = legend_rows(&output);
+    assert_eq!(rows.len(), 1, "{}", output);
+    assert!(rows[0].trim_start().starts_with(hash_a), "{}", output);
+
+    // Off by default when not on a terminal, and never in machine-readable formats
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(!output.contains("Agents:"), "{}", output);
+    let output = repo
+        .git_ai(&["blame", "--agent-legend", "--porcelain", "test.txt"])
+        .unwrap();
+    assert!(!output.contains("Agents:"), "{}", output);
+}
+
+#[test]
+fn test_blame_agent_legend_keeps_tool_names_for_no_output() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    let options = GitAiBlameOptions {
+        no_output: true,
+        agent_legend: Some(true),
+        ..Default::default()
+    };
+    let (line_authors, _) = gitai_repo.blame("test.txt", &options).unwrap();
+    assert_eq!(line_authors.get(&2).map(String::as_str), Some("mock_ai"));
+}
+
+#[test]
+fn test_blame_show_prompt_lists_first_user_message() {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.exclude_prompts_in_repositories = Some(vec![]);
+        patch.prompt_storage = Some("notes".to_string());
+    });
+    std::fs::write(repo.path().join("README.md"), "# Test\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let checkpoint = |agent: &str, file: &str, content: &str| {
+        std::fs::write(repo.path().join(file), content).unwrap();
+        let hook_input = serde_json::json!({
+            "type": "ai_agent",
+            "repo_working_dir": repo.path().to_str().unwrap(),
+            "edited_filepaths": [file],
+            "transcript": {"messages": [
+                Message::user("\nAdd a greeting helper\nthat says hello".to_string(), None),
+                Message::assistant("Done.".to_string(), None),
+            ]},
+            "agent_name": agent,
+            "model": "test-model",
+            "conversation_id": format!("{}-session", agent),
+        });
+        repo.git_ai(&[
+            "checkpoint",
+            "agent-v1",
+            "--hook-input",
+            &hook_input.to_string(),
+        ])
+        .unwrap();
+    };
+
+    checkpoint("test-agent", "greet.txt", "fn greet() {}\n");
+    repo.stage_all_and_commit("Greeting").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--show-prompt", "greet.txt"])
+        .unwrap();
+    let rows: Vec<&str> = output
+        .lines()
+        .skip_while(|l| *l != "Prompts:")
+        .skip(1)
+        .collect();
+    assert_eq!(rows.len(), 1, "{}", output);
+    assert!(rows[0].ends_with("  Add a greeting helper"), "{}", output);
+
+    // Transcripts stripped by gitai.ignorePrompts are reported as unavailable
+    repo.git_og(&["config", "gitai.ignorePrompts", "secret-agent"])
+        .unwrap();
+    checkpoint("secret-agent", "secret.txt", "hidden\n");
+    repo.stage_all_and_commit("Secret").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--show-prompt", "secret.txt"])
+        .unwrap();
+    assert!(output.contains("(prompt unavailable)"), "{}", output);
+    assert!(!output.contains("Add a greeting helper"), "{}", output);
+}
+
+#[test]
+fn test_max_transcript_bytes_truncates_stored_messages() {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.exclude_prompts_in_repositories = Some(vec![]);
+        patch.prompt_storage = Some("notes".to_string());
+    });
+    std::fs::write(repo.path().join("README.md"), "# Test\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.git_og(&["config", "gitai.maxTranscriptBytes", "2k"])
+        .unwrap();
+
+    let mut messages = vec![Message::user("Write the parser".to_string(), None)];
+    for i in 0..50 {
+        messages.push(Message::assistant(
+            format!("step {} {}", i, "x".repeat(100)),
+            None,
+        ));
+    }
+    std::fs::write(repo.path().join("parser.txt"), "parse\n").unwrap();
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": ["parser.txt"],
+        "transcript": {"messages": messages},
+        "agent_name": "test-agent",
+        "model": "test-model",
+        "conversation_id": "long-session",
+    });
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+    ])
+    .unwrap();
+    let commit = repo.stage_all_and_commit("Parser").unwrap();
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("session should be recorded");
+    let bytes: usize = prompt
+        .messages
+        .iter()
+        .map(|m| serde_json::to_string(m).unwrap().len())
+        .sum();
+    assert!(bytes <= 2048, "{} bytes kept", bytes);
+    assert_eq!(prompt.messages.first(), messages.first());
+    assert_eq!(prompt.messages.last(), messages.last());
+    assert!(prompt.transcript_truncated);
+    // Only the agent's own messages are kept, with nothing standing in for the dropped ones
+    assert!(
+        prompt.messages.iter().all(|m| messages.contains(m)),
+        "{:?}",
+        prompt.messages
+    );
+
+    let output = repo
+        .git_ai(&["blame", "--show-prompt", "parser.txt"])
+        .unwrap();
+    assert!(
+        output.contains("Write the parser  (transcript truncated)"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_blame_map_session_overrides_displayed_author() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Human 1", "AI 2".ai(), "Human 3"]);
+    let commit = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let note_before = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let log = AuthorshipLog::deserialize_from_string(note_before.trim()).unwrap();
+    let hash = log.metadata.prompts.keys().next().unwrap().clone();
+
+    let mapping = format!("{}=Refactor Bot", hash);
+    let output = repo
+        .git_ai(&["blame", "--map-session", &mapping, "test.txt"])
+        .unwrap();
+    let authors = extract_authors(&output);
+    assert_eq!(authors[1], "Refactor", "{}", output);
+    assert!(
+        output.lines().nth(1).unwrap().contains("(Refactor Bot "),
+        "{}",
+        output
+    );
+    assert!(!output.contains("mock_ai"), "{}", output);
+    assert_eq!(authors[0], authors[2]);
+
+    // Unmapped hashes keep the tool name, and the stored note is untouched
+    let output = repo
+        .git_ai(&[
+            "blame",
+            "--map-session",
+            "0000000000000000=Other",
+            "test.txt",
+        ])
+        .unwrap();
+    assert!(output.contains("mock_ai"), "{}", output);
+    let note_after = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    assert_eq!(note_before, note_after);
+
+    let err = repo
+        .git_ai(&["blame", "--map-session", "no-label", "test.txt"])
+        .unwrap_err();
+    assert!(
+        err.contains("--map-session requires <hash>=<label>"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_blame_model_colors_uses_mapped_color() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "Human 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    std::fs::write(
+        repo.path().join("test.txt"),
+        "Human 1\nClaude line\nHuman 2\n",
+    )
+    .unwrap();
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": ["test.txt"],
+        "transcript": {"messages": []},
+        "agent_name": "test-agent",
+        "model": "claude-sonnet-4",
+        "conversation_id": "colors-claude",
+    });
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+    ])
+    .unwrap();
+
+    std::fs::write(
+        repo.path().join("test.txt"),
+        "Human 1\nClaude line\nHuman 2\nMock line\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+    repo.stage_all_and_commit("AI edits").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--model-colors", "claude=blue", "test.txt"])
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(!lines[0].contains('\x1b'), "{}", output);
+    assert!(lines[1].starts_with("\x1b[34m"), "{}", output);
+    assert!(lines[1].ends_with("Claude line\x1b[0m"), "{}", output);
+    // Unmapped models get an auto-assigned color other than the mapped ones
+    assert!(lines[3].starts_with("\x1b["), "{}", output);
+    assert!(!lines[3].starts_with("\x1b[34m"), "{}", output);
+    assert!(!lines[2].contains('\x1b'), "{}", output);
+
+    // No colors without the flag
+    let output = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(!output.contains('\x1b'), "{}", output);
+
+    let err = repo
+        .git_ai(&["blame", "--model-colors", "claude=chartreuse", "test.txt"])
+        .unwrap_err();
+    assert!(err.contains("Unknown color 'chartreuse'"), "{}", err);
+}
+
+#[test]
+fn test_blame_porcelain_adds_ai_attribution_headers() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai(), "Human 3"]);
+    let commit = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    let hash = log.metadata.prompts.keys().next().unwrap().clone();
+
+    for flag in ["--porcelain", "--line-porcelain"] {
+        let output = repo.git_ai(&["blame", flag, "test.txt"]).unwrap();
+
+        // Split into per-line records: header, key/value lines, then the tab-prefixed content
+        let mut records: Vec<Vec<&str>> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        for line in output.lines() {
+            current.push(line);
+            if line.starts_with('\t') {
+                records.push(std::mem::take(&mut current));
+            }
+        }
+        assert!(current.is_empty(), "{}", output);
+        assert_eq!(records.len(), 3, "{}", output);
+        for record in &records {
+            let header: Vec<&str> = record[0].split(' ').collect();
+            assert!(header.len() == 3 || header.len() == 4, "{}", output);
+            assert_eq!(header[0].len(), 40, "{}", output);
+        }
+
+        let ai_fields = |record: &Vec<&str>| -> Vec<String> {
+            record
+                .iter()
+                .filter(|l| l.starts_with("ai-"))
+                .map(|l| l.to_string())
+                .collect()
+        };
+        assert_eq!(
+            ai_fields(&records[1]),
+            vec![
+                "ai-agent mock_ai".to_string(),
+                "ai-model unknown".to_string(),
+                format!("ai-prompt-hash {}", hash),
+            ],
+            "{}",
+            output
+        );
+        assert_eq!(records[1].last(), Some(&"\tAI 2"));
+        // Human lines keep plain porcelain
+        assert!(ai_fields(&records[0]).is_empty(), "{}", output);
+        assert!(ai_fields(&records[2]).is_empty(), "{}", output);
+    }
+}
+
+#[test]
+fn test_blame_show_acceptance_reports_kept_ai_lines() {
+    let repo = TestRepo::new();
+    std::fs::write(repo.path().join("lib.txt"), "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    std::fs::write(
+        repo.path().join("lib.txt"),
+        "base\nai 1\nai 2\nai 3\nai 4\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "lib.txt"]).unwrap();
+    // A human rewrites one of the agent's lines before committing
+    std::fs::write(
+        repo.path().join("lib.txt"),
+        "base\nai 1\nhuman 2\nai 3\nai 4\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("Agent commit").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--show-acceptance", "lib.txt"])
+        .unwrap();
+    assert!(
+        output.contains("acceptance: 75% (3 AI lines kept, 1 overridden)"),
+        "{}",
+        output
+    );
+
+    // Without the flag there is no footer
+    let output = repo.git_ai(&["blame", "lib.txt"]).unwrap();
+    assert!(!output.contains("acceptance:"), "{}", output);
+}
+
+#[test]
+fn test_blame_json_per_line_tags_human_and_ai_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai(), "Human 3"]);
+    let commit = repo.stage_all_and_commit("Initial commit").unwrap();
+    let hash = commit.authorship_log.attestations[0].entries[0]
+        .hash
+        .clone();
+
+    let json = repo
+        .git_ai(&["blame", "--json-per-line", "test.txt"])
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let lines = value["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 3, "{}", json);
+
+    assert_eq!(lines[0]["kind"], "human");
+    assert_eq!(lines[0]["line"], 1);
+    assert_eq!(lines[0]["content"], "Human 1");
+    assert_eq!(lines[0]["author"]["username"], "Test User");
+    assert_eq!(lines[0]["commit"], commit.commit_sha.as_str());
+
+    assert_eq!(lines[1]["kind"], "ai");
+    assert_eq!(lines[1]["content"], "AI 2");
+    assert_eq!(lines[1]["author"]["username"], "mock_ai");
+    assert_eq!(lines[1]["prompt_hash"], hash.as_str());
+    assert_eq!(value["prompts"][&hash]["agent_id"]["tool"], "mock_ai");
+    assert!(
+        value["prompts"][&hash]["overriden_lines"].is_number(),
+        "{}",
+        json
+    );
+
+    assert_eq!(lines[2]["kind"], "human");
+    assert_eq!(value["prompts"].as_object().unwrap().len(), 1);
+}
+
+#[test]
+fn test_blame_file_at_past_revision() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    // Rewrite the file by hand and add a new one, so HEAD no longer has the AI line
+    std::fs::write(repo.path().join("test.txt"), "Rewritten\n").unwrap();
+    std::fs::write(repo.path().join("new.txt"), "New\n").unwrap();
+    repo.stage_all_and_commit("Human rewrite").unwrap();
+
+    let head = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(!head.contains("mock_ai"), "{}", head);
+
+    let at_rev = repo.git_ai(&["blame", "test.txt@HEAD~1"]).unwrap();
+    let lines: Vec<&str> = at_rev.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", at_rev);
+    assert!(
+        lines[1].contains("mock_ai") && lines[1].ends_with("AI Line 2"),
+        "{}",
+        at_rev
+    );
+    assert!(!lines[0].contains("mock_ai"), "{}", at_rev);
+
+    // A trailing revision, git's `<rev> <file>` and `<rev> -- <file>` blame the same version
+    assert_eq!(
+        repo.git_ai(&["blame", "test.txt", "HEAD~1"]).unwrap(),
+        at_rev
+    );
+    assert_eq!(
+        repo.git_ai(&["blame", "HEAD~1", "--", "test.txt"]).unwrap(),
+        at_rev
+    );
+    assert_eq!(
+        repo.git_ai(&["blame", "HEAD~1", "test.txt"]).unwrap(),
+        at_rev
+    );
+
+    // A file deleted from the worktree is still read in git's `<rev> <file>` order
+    std::fs::remove_file(repo.path().join("test.txt")).unwrap();
+    assert_eq!(
+        repo.git_ai(&["blame", "HEAD~1", "test.txt"]).unwrap(),
+        at_rev
+    );
+
+    let err = repo.git_ai(&["blame", "new.txt@HEAD~1"]).unwrap_err();
+    assert!(err.contains("no such path 'new.txt' in HEAD~1"), "{}", err);
+}
+
+#[test]
+fn test_blame_color_marks_ai_lines_by_agent() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai(), "AI Line 3".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    let output = repo.git_ai(&["blame", "--color", "test.txt"]).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(!lines[0].contains('\x1b'), "{:?}", lines[0]);
+    for line in &lines[1..3] {
+        assert!(
+            line.starts_with("\x1b[") && line.ends_with("\x1b[0m"),
+            "{:?}",
+            line
+        );
+        assert!(line.contains("mock_ai"), "{:?}", line);
+    }
+    // Same agent, same color, on every run
+    let color = |line: &str| line[..line.find('m').unwrap()].to_string();
+    assert_eq!(color(lines[1]), color(lines[2]));
+    assert_eq!(
+        repo.git_ai(&["blame", "--color=always", "test.txt"])
+            .unwrap(),
+        output
+    );
+
+    // Auto stays plain when stdout isn't a terminal
+    for args in [
+        vec!["blame", "test.txt"],
+        vec!["blame", "--no-color", "test.txt"],
+        vec!["blame", "--color=auto", "test.txt"],
+    ] {
+        let plain = repo.git_ai(&args).unwrap();
+        assert!(!plain.contains('\x1b'), "{:?}: {}", args, plain);
+    }
+}
+
+#[test]
+fn test_blame_color_keeps_tool_names_for_no_output() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    let options = GitAiBlameOptions {
+        no_output: true,
+        color: ColorChoice::Always,
+        ..Default::default()
+    };
+    let (line_authors, _) = gitai_repo.blame("test.txt", &options).unwrap();
+    assert_eq!(line_authors.get(&2).map(String::as_str), Some("mock_ai"));
+}
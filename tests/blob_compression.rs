@@ -0,0 +1,65 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log::LineRange;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// Contents of every file version stored by the current working log
+fn stored_blobs(repo: &TestRepo) -> Vec<Vec<u8>> {
+    let blobs_dir = repo.current_working_logs().dir.join("blobs");
+    fs::read_dir(blobs_dir)
+        .unwrap()
+        .map(|entry| fs::read(entry.unwrap().path()).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_file_versions_are_compressed_by_default() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("big.txt");
+    file.set_contents(lines!["header"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let content: String = (0..5_000)
+        .map(|i| format!("generated line {}\n", i))
+        .collect();
+    fs::write(repo.path().join("big.txt"), format!("header\n{}", content)).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "big.txt"]).unwrap();
+
+    let blobs = stored_blobs(&repo);
+    assert!(!blobs.is_empty());
+    for blob in &blobs {
+        assert!(blob.starts_with(b"\0gitai-zlib\0"));
+        assert!(blob.len() < content.len() / 2);
+    }
+
+    // Attribution still sees every line the agent wrote
+    let commit = repo.stage_all_and_commit("Agent edit").unwrap();
+    let attestation = &commit.authorship_log.attestations[0];
+    assert_eq!(attestation.file_path, "big.txt");
+    assert_eq!(
+        attestation.entries[0].line_ranges,
+        vec![LineRange::Range(2, 5001)]
+    );
+}
+
+#[test]
+fn test_compress_blobs_config_disables_compression() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "gitai.compressBlobs", "false"])
+        .unwrap();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(lines!["first"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "first\nsecond\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "notes.txt"])
+        .unwrap();
+
+    assert!(
+        stored_blobs(&repo)
+            .iter()
+            .any(|blob| blob.as_slice() == b"first\nsecond\n")
+    );
+}
@@ -1,7 +1,9 @@
 #[macro_use]
 mod repos;
+use git_ai::authorship::authorship_log::LineRange;
 use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
+use std::collections::BTreeMap;
 
 /// Test cherry-picking a single AI-authored commit
 #[test]
@@ -425,3 +427,149 @@ fn test_cherry_pick_empty_commits() {
         "File content should be preserved after cherry-pick/abort"
     );
 }
+
+/// Each commit of a cherry-picked range keeps its own source commit's authorship, even when
+/// a later commit in the range rewrote the same lines
+#[test]
+fn test_cherry_pick_range_maps_authorship_per_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("README.md");
+    file.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut source_commits = Vec::new();
+    for session in ["session-one", "session-two", "session-three"] {
+        std::fs::write(
+            repo.path().join("shared.txt"),
+            format!("header\nvalue from {}\nfooter\n", session),
+        )
+        .unwrap();
+        repo.git_ai_with_env(
+            &["checkpoint", "--auto-detect-agent"],
+            &[("CLAUDECODE", "1"), ("CLAUDE_CODE_SESSION_ID", session)],
+        )
+        .unwrap();
+        let commit = repo.stage_all_and_commit(session).unwrap();
+        source_commits.push(commit.commit_sha);
+    }
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    let range = format!("{}..feature", main_branch);
+    repo.git(&["cherry-pick", &range]).unwrap();
+    let new_commits: Vec<String> = repo
+        .git_og(&["rev-list", "--reverse", "-n", "3", "HEAD"])
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+
+    let gitai_repo = git_ai::git::find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    // (file, line) -> session id of every AI-attributed line in a commit's note
+    let attributed_lines = |commit: &str| {
+        let log =
+            git_ai::git::refs::get_reference_as_authorship_log_v3(&gitai_repo, commit).unwrap();
+        let mut lines = BTreeMap::new();
+        for file in &log.attestations {
+            for entry in &file.entries {
+                let session = &log.metadata.prompts[&entry.hash].agent_id.id;
+                for range in &entry.line_ranges {
+                    let (start, end) = match range {
+                        LineRange::Single(line) => (*line, *line),
+                        LineRange::Range(start, end) => (*start, *end),
+                    };
+                    for line in start..=end {
+                        lines.insert((file.file_path.clone(), line), session.clone());
+                    }
+                }
+            }
+        }
+        lines
+    };
+
+    let sessions = ["session-one", "session-two", "session-three"];
+    for (idx, (source, new)) in source_commits.iter().zip(&new_commits).enumerate() {
+        let expected = attributed_lines(source);
+        let actual = attributed_lines(new);
+        assert!(!expected.is_empty());
+        // The source's lines keep their session, and nothing comes from a later commit's
+        // session (earlier sessions may still own unchanged lines)
+        for (line, session) in &expected {
+            assert_eq!(actual.get(line), Some(session), "{:?} of {}", line, source);
+        }
+        for session in actual.values() {
+            assert!(
+                sessions[..=idx].contains(&session.as_str()),
+                "{} attributed to {} in copy of {}",
+                session,
+                new,
+                source
+            );
+        }
+    }
+}
+
+/// Picks dropped as empty don't shift the pairing: the commits that did land keep their own
+/// source commit's authorship
+#[test]
+fn test_cherry_pick_range_with_skipped_empty_pick_maps_authorship_per_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("README.md");
+    file.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let commit_as = |session: &str| {
+        std::fs::write(
+            repo.path().join("shared.txt"),
+            format!("header\nvalue from {}\nfooter\n", session),
+        )
+        .unwrap();
+        repo.git_ai_with_env(
+            &["checkpoint", "--auto-detect-agent"],
+            &[("CLAUDECODE", "1"), ("CLAUDE_CODE_SESSION_ID", session)],
+        )
+        .unwrap();
+        repo.stage_all_and_commit(session).unwrap().commit_sha
+    };
+    let first = commit_as("session-one");
+    std::fs::write(repo.path().join("other.txt"), "already on main\n").unwrap();
+    repo.stage_all_and_commit("Shared change").unwrap();
+    let last = commit_as("session-three");
+
+    // Main already has the middle commit's change, so its pick comes up empty
+    repo.git(&["checkout", &main_branch]).unwrap();
+    std::fs::write(repo.path().join("other.txt"), "already on main\n").unwrap();
+    repo.stage_all_and_commit("Same change on main").unwrap();
+
+    let range = format!("{}..feature", main_branch);
+    assert!(repo.git(&["cherry-pick", &range]).is_err());
+    repo.git(&["cherry-pick", "--skip"]).unwrap();
+    let new_commits: Vec<String> = repo
+        .git_og(&["rev-list", "--reverse", "-n", "2", "HEAD"])
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(new_commits.len(), 2);
+
+    let gitai_repo = git_ai::git::find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    let sessions = |commit: &str| {
+        let log =
+            git_ai::git::refs::get_reference_as_authorship_log_v3(&gitai_repo, commit).unwrap();
+        log.attestations
+            .iter()
+            .flat_map(|file| &file.entries)
+            .map(|entry| log.metadata.prompts[&entry.hash].agent_id.id.clone())
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    assert_eq!(
+        sessions(&new_commits[0]),
+        ["session-one".to_string()].into()
+    );
+    assert_eq!(sessions(&new_commits[0]), sessions(&first));
+    // Unchanged lines the earlier pick brought in may keep their session
+    assert!(sessions(&new_commits[1]).is_superset(&sessions(&last)));
+}
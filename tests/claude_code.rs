@@ -172,6 +172,56 @@ fn test_claude_e2e_prefers_latest_checkpoint_for_prompts() {
     );
 }
 
+#[test]
+fn test_claude_max_transcript_bytes_survives_commit_refresh() {
+    use repos::test_repo::TestRepo;
+
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.exclude_prompts_in_repositories = Some(vec![]);
+        patch.prompt_storage = Some("notes".to_string());
+    });
+    let repo_root = repo.canonical_path();
+    let file_path = repo_root.join("main.rs");
+    fs::write(&file_path, "fn main() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.git_og(&["config", "gitai.maxTranscriptBytes", "2k"])
+        .unwrap();
+
+    // The commit reloads this transcript from disk, uncapped
+    let transcript_path = repo_root.join("claude-session.jsonl");
+    fs::copy(fixture_path("example-claude-code.jsonl"), &transcript_path).unwrap();
+    let hook_input = json!({
+        "cwd": repo_root.to_string_lossy().to_string(),
+        "hook_event_name": "PostToolUse",
+        "transcript_path": transcript_path.to_string_lossy().to_string(),
+        "tool_input": {
+            "file_path": file_path.to_string_lossy().to_string()
+        }
+    })
+    .to_string();
+    fs::write(&file_path, "fn main() {}\n// ai line\n").unwrap();
+    repo.git_ai(&["checkpoint", "claude", "--hook-input", &hook_input])
+        .unwrap();
+    let commit = repo.stage_all_and_commit("Add AI line").unwrap();
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("session should be recorded");
+    assert!(!prompt.messages.is_empty());
+    let bytes: usize = prompt
+        .messages
+        .iter()
+        .map(|m| serde_json::to_string(m).unwrap().len())
+        .sum();
+    assert!(bytes <= 2048, "{} bytes kept", bytes);
+    assert!(prompt.transcript_truncated);
+}
+
 #[test]
 fn test_parse_claude_code_jsonl_with_thinking() {
     let fixture = fixture_path("claude-code-with-thinking.jsonl");
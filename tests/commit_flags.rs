@@ -0,0 +1,80 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_commit_on_detached_head_records_authorship() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 4"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.git(&["checkout", "--detach"]).unwrap();
+    file.insert_at(1, lines!["AI Line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Detached AI commit").unwrap();
+
+    assert_eq!(
+        repo.git_og(&["rev-parse", "HEAD"]).unwrap().trim(),
+        commit.commit_sha
+    );
+    assert_eq!(commit.authorship_log.attestations.len(), 1);
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI Line 2".ai(), "Line 4".human()]);
+
+    // A second detached commit builds on the first one's attribution
+    file.insert_at(2, lines!["AI Line 3".ai()]);
+    repo.stage_all_and_commit("Another detached commit")
+        .unwrap();
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI Line 2".ai(),
+        "AI Line 3".ai(),
+        "Line 4".human()
+    ]);
+}
+
+#[test]
+fn test_commit_no_verify_keeps_authorship() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 5"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // --no-verify skips git's pre-commit hook, but the commit still gets its note
+    file.insert_at(1, lines!["AI Line 2".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "--no-verify", "-m", "Unverified"])
+        .unwrap();
+    let head = repo.git_og(&["rev-parse", "HEAD"]).unwrap();
+    repo.git_og(&["notes", "--ref=ai", "show", head.trim()])
+        .expect("--no-verify commits should still get an authorship note");
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI Line 2".ai(), "Line 5".human()]);
+
+    // Later commits keep the attribution, including -n inside combined short flags
+    file.insert_at(2, lines!["AI Line 3".ai()]);
+    repo.git(&["commit", "-anm", "Unverified again"]).unwrap();
+    file.insert_at(3, lines!["Line 4"]);
+    repo.stage_all_and_commit("Verified").unwrap();
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI Line 2".ai(),
+        "AI Line 3".ai(),
+        "Line 4".human(),
+        "Line 5".human()
+    ]);
+}
+
+#[test]
+fn test_commit_no_verify_credits_hand_edits_to_the_human() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["l1", "ai2".ai(), "ai3".ai()]);
+
+    // Edit an AI line by hand, with no checkpoint before the commit
+    std::fs::write(repo.path().join("test.txt"), "l1\nHUMAN2\nai3").unwrap();
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "--no-verify", "-m", "Unverified"])
+        .unwrap();
+
+    file.assert_lines_and_blame(lines!["l1".human(), "HUMAN2".human(), "ai3".ai()]);
+}
@@ -0,0 +1,167 @@
+#[macro_use]
+mod repos;
+
+use git_ai::config::validate_file_config;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+
+#[test]
+fn test_validate_accepts_good_config() {
+    let problems = validate_file_config(&json!({
+        "exclude_repositories": ["https://github.com/org/*"],
+        "telemetry_oss": "off",
+        "update_channel": "next",
+        "prompt_storage": "notes",
+        "include_prompts_in_repositories": ["*"],
+        "default_prompt_storage": "local",
+        "quiet": true,
+        "feature_flags": { "rewrite_stash": true }
+    }));
+    assert!(problems.is_empty(), "{:?}", problems);
+}
+
+#[test]
+fn test_validate_reports_every_problem() {
+    let problems = validate_file_config(&json!({
+        "git_path": "/nonexistent/bin/git",
+        "allow_repositories": ["[unclosed", 7],
+        "exclude_repositories": "private/*",
+        "telemetry_oss": "maybe",
+        "api_base_url": "usegitai.com",
+        "quiet": "yes",
+        "update_channel": "nightly",
+        "prompt_storage": "cloud",
+        "default_prompt_storage": "notes",
+        "feature_flags": { "rewrite_stash": "on", "no_such_flag": true },
+        "disable_version_checks": true,
+        "disable_auto_updates": false,
+        "colour": "blue"
+    }));
+
+    let expected = [
+        "git_path: '/nonexistent/bin/git' does not exist",
+        "allow_repositories: invalid glob pattern '[unclosed'",
+        "allow_repositories: expected an array of strings, found 7",
+        "exclude_repositories: expected an array of strings",
+        "telemetry_oss: expected \"on\" or \"off\", found \"maybe\"",
+        "api_base_url: 'usegitai.com' is not an http(s) URL",
+        "quiet: expected true or false, found \"yes\"",
+        "update_channel: expected one of",
+        "prompt_storage: expected one of default, notes, local, found \"cloud\"",
+        "feature_flags.rewrite_stash: expected true or false",
+        "feature_flags: unknown flag 'no_such_flag'",
+        "default_prompt_storage: has no effect without include_prompts_in_repositories",
+        "disable_auto_updates: auto updates need version checks",
+        "unknown key 'colour'",
+    ];
+    for message in expected {
+        assert!(
+            problems.iter().any(|p| p.starts_with(message)),
+            "missing {:?} in {:#?}",
+            message,
+            problems
+        );
+    }
+    assert_eq!(problems.len(), expected.len(), "{:#?}", problems);
+}
+
+#[test]
+fn test_config_validate_command_checks_file_and_repo() {
+    let repo = TestRepo::new();
+    let home = tempfile::tempdir().unwrap();
+    let home_str = home.path().to_str().unwrap();
+
+    let output = repo
+        .git_ai_with_env(&["config", "--validate"], &[("HOME", home_str)])
+        .unwrap();
+    assert!(output.contains("Configuration is valid"), "{}", output);
+
+    fs::create_dir_all(home.path().join(".git-ai")).unwrap();
+    fs::write(
+        home.path().join(".git-ai").join("config.json"),
+        r#"{"quiet": 1, "prompt_storage": "cloud"}"#,
+    )
+    .unwrap();
+    repo.git_og(&["config", "blame.ignoreRevsFile", "missing-revs"])
+        .unwrap();
+    repo.git_og(&["config", "gitai.hashLength", "64"]).unwrap();
+    repo.git_og(&["config", "gitai.checkpointConcurrency", "0"])
+        .unwrap();
+    repo.git_og(&["config", "gitai.maxTranscriptBytes", "lots"])
+        .unwrap();
+    repo.git_og(&["config", "gitai.compressBlobs", "maybe"])
+        .unwrap();
+    repo.git_og(&["config", "gitai.hashLenght", "12"]).unwrap();
+
+    let err = repo
+        .git_ai_with_env(&["config", "--validate"], &[("HOME", home_str)])
+        .unwrap_err();
+    assert!(
+        err.contains("error: quiet: expected true or false"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("error: prompt_storage: expected one of"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("error: blame.ignoreRevsFile: 'missing-revs' does not exist"),
+        "{}",
+        err
+    );
+    for problem in [
+        "error: gitai.hashlength=64: expected a number from 7 to 40",
+        "error: gitai.checkpointconcurrency=0: expected a number of at least 1",
+        "error: gitai.maxtranscriptbytes=lots: expected a byte count",
+        "error: gitai.compressblobs=maybe: expected true or false",
+        "error: gitai.hashlenght=12: unknown key",
+    ] {
+        assert!(err.contains(problem), "{}: {}", problem, err);
+    }
+    assert!(err.contains("8 problem(s) found"), "{}", err);
+
+    fs::write(
+        home.path().join(".git-ai").join("config.json"),
+        "{ not json",
+    )
+    .unwrap();
+    let err = repo
+        .git_ai_with_env(&["config", "--validate"], &[("HOME", home_str)])
+        .unwrap_err();
+    assert!(err.contains("invalid JSON"), "{}", err);
+}
+
+#[test]
+fn test_config_validate_agrees_with_doctor_on_gitai_keys() {
+    let repo = TestRepo::new();
+    let home = tempfile::tempdir().unwrap();
+    let home_str = home.path().to_str().unwrap();
+    repo.git_og(&["config", "gitai.hashLength", "6"]).unwrap();
+    repo.git_og(&["config", "gitai.maxTranscriptBytes", "-1"])
+        .unwrap();
+    repo.git_og(&["config", "gitai.noSuchKey", "1"]).unwrap();
+
+    let doctor = repo
+        .git_ai_with_env(&["doctor"], &[("HOME", home_str)])
+        .unwrap_err();
+    let validate = repo
+        .git_ai_with_env(&["config", "--validate"], &[("HOME", home_str)])
+        .unwrap_err();
+
+    let doctor_problems: Vec<&str> = doctor
+        .lines()
+        .find_map(|line| line.strip_prefix("FAIL gitai config: "))
+        .unwrap_or_else(|| panic!("missing gitai config check: {}", doctor))
+        .split("; ")
+        .collect();
+    let validate_problems: Vec<&str> = validate
+        .lines()
+        .filter_map(|line| line.strip_prefix("error: "))
+        .filter(|problem| problem.starts_with("gitai."))
+        .collect();
+    assert_eq!(doctor_problems.len(), 3, "{}", doctor);
+    assert_eq!(validate_problems, doctor_problems, "{}", validate);
+}
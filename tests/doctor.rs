@@ -0,0 +1,109 @@
+#[macro_use]
+mod repos;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::{TestRepo, get_binary_path};
+
+#[cfg(unix)]
+#[test]
+fn test_doctor_passes_with_git_shim_on_path() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["AI line".ai()]);
+    repo.stage_all_and_commit("Agent commit").unwrap();
+
+    // Put a `git` symlink to the binary first on PATH, as the installer does
+    let shim_dir = repo.path().join(".git").join("doctor-shim");
+    std::fs::create_dir_all(&shim_dir).unwrap();
+    std::os::unix::fs::symlink(get_binary_path(), shim_dir.join("git")).unwrap();
+    let path = format!(
+        "{}:{}",
+        shim_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let output = repo
+        .git_ai_with_env(&["doctor"], &[("PATH", &path)])
+        .unwrap();
+    assert!(
+        output.contains("ok   git proxy: git resolves to git-ai"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("ok   gitai config: no gitai.* keys set"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("ok   authorship notes: refs/notes/ai present"),
+        "{}",
+        output
+    );
+    assert!(output.contains("ok   user.name: "), "{}", output);
+    assert!(output.contains("ok   storage: "), "{}", output);
+}
+
+#[test]
+fn test_doctor_fails_on_missing_proxy_and_invalid_config() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "gitai.hashLength", "99"]).unwrap();
+    repo.git_og(&["config", "gitai.compresBlobs", "false"])
+        .unwrap();
+
+    // Tests run the binary directly, so git on PATH is the real git
+    let output = repo
+        .git_ai(&["doctor"])
+        .expect_err("doctor should exit non-zero when a critical check fails");
+    assert!(
+        output.contains("FAIL git proxy: git resolves to "),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains(
+            "FAIL gitai config: gitai.compresblobs=false: unknown key; gitai.hashlength=99: expected a number from 7 to 40"
+        ),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_doctor_json_reports_checks_and_overall_status() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "gitai.hashLength", "99"]).unwrap();
+
+    // Run the binary directly: the helpers drop stdout when the command fails
+    let output = std::process::Command::new(get_binary_path())
+        .args(["doctor", "--json"])
+        .current_dir(repo.path())
+        .env("GIT_AI_TEST_DB_PATH", repo.test_db_path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    // Tests run the binary directly, so git on PATH is the real git
+    assert_eq!(report["ok"], false, "{}", stdout);
+    let checks = report["checks"].as_array().unwrap();
+    let status = |name: &str| {
+        checks
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap_or_else(|| panic!("missing check {}: {}", name, stdout))["status"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(status("git proxy"), "fail");
+    assert_eq!(status("gitai config"), "fail");
+    assert_eq!(status("storage"), "ok");
+    assert!(
+        checks.iter().all(|c| c["detail"].is_string()
+            && ["ok", "warn", "fail"].contains(&c["status"].as_str().unwrap())),
+        "{}",
+        stdout
+    );
+}
@@ -203,6 +203,43 @@ fn test_droid_preset_stores_metadata_paths() {
     assert_eq!(metadata["transcript_path"], jsonl_path.to_str().unwrap());
 }
 
+#[test]
+fn test_droid_preset_takes_model_from_fallback_transcript() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let jsonl_path = temp_dir.path().join("session.jsonl");
+    let settings_path = temp_dir.path().join("session.settings.json");
+
+    // Not Droid JSONL, so the generic parser reads it, model included
+    fs::copy(fixture_path("generic-messages.json"), &jsonl_path).unwrap();
+    fs::write(&settings_path, "{}").unwrap();
+
+    let hook_input = json!({
+        "cwd": "/Users/testuser/projects/testing-git",
+        "hookEventName": "PostToolUse",
+        "sessionId": "052cb8d0-4616-488a-99fe-bfbbbe9429b3",
+        "toolName": "Read",
+        "transcriptPath": jsonl_path.to_str().unwrap()
+    })
+    .to_string();
+
+    let result = DroidPreset
+        .run(AgentCheckpointFlags {
+            hook_input: Some(hook_input.clone()),
+        })
+        .expect("Failed to run DroidPreset");
+    assert_eq!(result.agent_id.model, "acme-agent-1");
+    assert_eq!(result.transcript.unwrap().messages().len(), 3);
+
+    // The model in settings.json still wins
+    fs::write(&settings_path, r#"{"model":"test-model"}"#).unwrap();
+    let result = DroidPreset
+        .run(AgentCheckpointFlags {
+            hook_input: Some(hook_input),
+        })
+        .expect("Failed to run DroidPreset");
+    assert_eq!(result.agent_id.model, "test-model");
+}
+
 #[test]
 fn test_droid_preset_uses_raw_session_id() {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,171 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::signing::SignatureStatus;
+use git_ai::commands::export::{JunitTestSuite, ai_ratio_case, render_junit, verification_case};
+use git_ai::commands::verify::CommitVerification;
+use insta::assert_snapshot;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_render_junit_passing_and_failing_checks() {
+    let signed = "1111111111111111111111111111111111111111";
+    let unsigned = "2222222222222222222222222222222222222222";
+    let suites = vec![
+        JunitTestSuite {
+            name: "git-ai.verify".to_string(),
+            cases: vec![
+                verification_case(
+                    signed,
+                    &CommitVerification::Signature(SignatureStatus::Valid {
+                        signer: "ABCDEF".to_string(),
+                    }),
+                    true,
+                ),
+                verification_case(
+                    unsigned,
+                    &CommitVerification::Signature(SignatureStatus::Unsigned),
+                    true,
+                ),
+            ],
+        },
+        JunitTestSuite {
+            name: "git-ai.ai-ratio".to_string(),
+            cases: vec![
+                ai_ratio_case(signed, "src/lib.rs", 1, 4, 50.0),
+                ai_ratio_case(signed, "src/<generated>.rs", 3, 4, 50.0),
+            ],
+        },
+    ];
+
+    assert_snapshot!(render_junit(&suites));
+}
+
+#[test]
+fn test_export_junit_flags_files_over_ai_threshold() {
+    let repo = TestRepo::new();
+    let mut mostly_ai = repo.filename("mostly_ai.txt");
+    mostly_ai.set_contents(lines!["AI 1".ai(), "AI 2".ai(), "AI 3".ai(), "Human 4"]);
+    let mut mostly_human = repo.filename("mostly_human.txt");
+    mostly_human.set_contents(lines!["AI 1".ai(), "Human 2", "Human 3", "Human 4"]);
+    let commit = repo.stage_all_and_commit("Mixed commit").unwrap();
+
+    let xml = repo
+        .git_ai(&["export", "--format", "junit", "--max-ai-percent", "50"])
+        .unwrap();
+    assert!(
+        xml.contains(r#"<testsuites name="git-ai" tests="3" failures="1">"#),
+        "{}",
+        xml
+    );
+    assert!(
+        xml.contains(&format!(
+            r#"<testcase classname="git-ai.verify" name="{}"/>"#,
+            commit.commit_sha
+        )),
+        "{}",
+        xml
+    );
+    assert!(
+        xml.contains(r#"name="mostly_ai.txt">"#)
+            && xml.contains("75.0% of added lines are AI-authored (3/4)"),
+        "{}",
+        xml
+    );
+    assert!(xml.contains(r#"name="mostly_human.txt"/>"#), "{}", xml);
+
+    let xml = repo
+        .git_ai(&["export", "--format", "junit", "--require-signed"])
+        .unwrap();
+    assert!(
+        xml.contains(r#"<failure message="authorship note is unsigned"/>"#),
+        "{}",
+        xml
+    );
+    assert!(!xml.contains("git-ai.ai-ratio"), "{}", xml);
+}
+
+#[test]
+fn test_export_junit_checks_files_with_non_ascii_names() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("naïve file.txt");
+    file.set_contents(lines!["Human 1", "Human 2"]);
+    repo.stage_all_and_commit("Unicode path").unwrap();
+
+    let xml = repo
+        .git_ai(&["export", "--format", "junit", "--max-ai-percent", "50"])
+        .unwrap();
+    assert!(xml.contains(r#"name="naïve file.txt"/>"#), "{}", xml);
+}
+
+#[test]
+fn test_export_json_lists_ai_and_human_ranges() {
+    let repo = TestRepo::new();
+    let mut mixed = repo.filename("mixed.txt");
+    mixed.set_contents(lines![
+        "Human 1",
+        "AI 2".ai(),
+        "AI 3".ai(),
+        "Human 4",
+        "AI 5".ai()
+    ]);
+    let mut human = repo.filename("human.txt");
+    human.set_contents(lines!["Human only"]);
+    let commit = repo.stage_all_and_commit("Mixed commit").unwrap();
+
+    let output = repo.git_ai(&["export", "--format", "json"]).unwrap();
+    let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(doc["format"], "git-ai-attribution/1");
+    let exported = &doc["commits"][0];
+    assert_eq!(exported["commit"], commit.commit_sha.as_str());
+    assert_eq!(exported["has_authorship_log"], true);
+
+    let files = exported["files"].as_array().unwrap();
+    let ranges_of = |path: &str| -> Vec<(u64, u64, String)> {
+        files
+            .iter()
+            .find(|f| f["path"] == path)
+            .unwrap_or_else(|| panic!("{} missing from {}", path, output))["ranges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                (
+                    r["start"].as_u64().unwrap(),
+                    r["end"].as_u64().unwrap(),
+                    r["author"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(
+        ranges_of("mixed.txt"),
+        vec![
+            (1, 1, "human".to_string()),
+            (2, 3, "ai".to_string()),
+            (4, 4, "human".to_string()),
+            (5, 5, "ai".to_string()),
+        ]
+    );
+    assert_eq!(ranges_of("human.txt"), vec![(1, 1, "human".to_string())]);
+
+    let ai_range = files.iter().find(|f| f["path"] == "mixed.txt").unwrap()["ranges"][1].clone();
+    assert_eq!(ai_range["tool"], "mock_ai");
+    assert!(
+        commit
+            .authorship_log
+            .metadata
+            .prompts
+            .contains_key(ai_range["prompt_hash"].as_str().unwrap())
+    );
+    assert_eq!(ai_range["human_edited"], false);
+    // Human ranges carry no prompt fields
+    let human_range = &files.iter().find(|f| f["path"] == "human.txt").unwrap()["ranges"][0];
+    assert!(human_range.get("prompt_hash").is_none());
+
+    let err = repo
+        .git_ai(&["export", "--format", "json", "--max-ai-percent", "50"])
+        .unwrap_err();
+    assert!(err.contains("only apply to --format junit"), "{}", err);
+}
@@ -0,0 +1,37 @@
+mod repos;
+
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_fetch_unknown_remote_explains_skipped_notes() {
+    let (local, _upstream) = TestRepo::new_with_remote();
+
+    let err = local
+        .git(&["fetch", "no-such-remote"])
+        .expect_err("fetching an unknown remote should fail");
+    assert!(
+        err.contains("remote 'no-such-remote' not found"),
+        "missing git-ai message: {}",
+        err
+    );
+    assert!(
+        err.contains("+refs/notes/ai:refs/notes/ai"),
+        "missing refspec hint: {}",
+        err
+    );
+}
+
+#[test]
+fn test_fetch_without_remotes_exits_non_zero() {
+    let repo = TestRepo::new();
+
+    let err = repo
+        .git(&["fetch"])
+        .expect_err("fetch with no remotes should fail");
+    assert!(err.contains("No git remotes found."), "{}", err);
+
+    // Known remotes still fetch without any warning
+    let (local, _upstream) = TestRepo::new_with_remote();
+    let output = local.git(&["fetch", "origin"]).unwrap();
+    assert!(!output.contains("not found"), "{}", output);
+}
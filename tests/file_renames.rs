@@ -0,0 +1,85 @@
+mod repos;
+
+use git_ai::authorship::authorship_log::LineRange;
+use repos::test_repo::{NewCommit, TestRepo};
+use std::fs;
+
+/// Line ranges the commit's note attributes to AI in `file`
+fn ai_ranges(commit: &NewCommit, file: &str) -> Vec<LineRange> {
+    commit
+        .authorship_log
+        .attestations
+        .iter()
+        .filter(|a| a.file_path == file)
+        .flat_map(|a| a.entries.iter().flat_map(|e| e.line_ranges.clone()))
+        .collect()
+}
+
+#[test]
+fn test_uncommitted_ai_lines_follow_staged_rename() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join("old.txt"),
+        "one\ntwo\nthree\nfour\nfive\nsix\n",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(
+        repo.path().join("old.txt"),
+        "one\ntwo\nthree\nfour\nfive\nsix\nai seven\nai eight\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "old.txt"]).unwrap();
+
+    repo.git(&["mv", "old.txt", "new.txt"]).unwrap();
+    let commit = repo.stage_all_and_commit("Rename").unwrap();
+
+    assert_eq!(ai_ranges(&commit, "new.txt"), vec![LineRange::Range(7, 8)]);
+    assert!(ai_ranges(&commit, "old.txt").is_empty());
+}
+
+#[test]
+fn test_rename_with_edit_keeps_unchanged_ai_lines() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("old.txt"), "one\ntwo\nthree\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(
+        repo.path().join("old.txt"),
+        "one\ntwo\nthree\nai four\nai five\nai six\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "old.txt"]).unwrap();
+
+    // A human renames the file and rewrites one of the agent's lines before committing
+    repo.git(&["mv", "old.txt", "new.txt"]).unwrap();
+    fs::write(
+        repo.path().join("new.txt"),
+        "one\ntwo\nthree\nai four\nhuman five\nai six\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let entry = checkpoints
+        .last()
+        .unwrap()
+        .entries
+        .iter()
+        .find(|e| e.file == "new.txt")
+        .expect("the human checkpoint should track the renamed file");
+    let ai_lines: Vec<(u32, u32)> = entry
+        .line_attributions
+        .iter()
+        .filter(|a| a.author_id != "human")
+        .map(|a| (a.start_line, a.end_line))
+        .collect();
+    assert_eq!(ai_lines, vec![(4, 4), (6, 6)]);
+
+    let commit = repo.stage_all_and_commit("Rename and edit").unwrap();
+    assert_eq!(
+        ai_ranges(&commit, "new.txt"),
+        vec![LineRange::Single(4), LineRange::Single(6)]
+    );
+}
@@ -0,0 +1,122 @@
+mod repos;
+
+use repos::test_repo::TestRepo;
+
+fn loose_object_count(repo: &TestRepo) -> u64 {
+    repo.git_og(&["count-objects", "-v"])
+        .unwrap()
+        .lines()
+        .find_map(|l| l.strip_prefix("count: "))
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap()
+}
+
+#[test]
+fn test_gc_aggressive_packs_notes_and_keeps_them_readable() {
+    let repo = TestRepo::new();
+    std::fs::write(repo.path().join("file.txt"), "base\n").unwrap();
+    repo.git_og(&["add", "."]).unwrap();
+    repo.git_og(&["commit", "-m", "base"]).unwrap();
+    let head = repo
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+
+    // Each rewrite of the note leaves a new blob, tree and notes commit behind
+    for i in 0..40 {
+        repo.git_og(&[
+            "notes",
+            "--ref=ai",
+            "add",
+            "-f",
+            "-m",
+            &format!("note revision {}", i),
+            &head,
+        ])
+        .unwrap();
+    }
+    let before = loose_object_count(&repo);
+
+    let output = repo.git_ai(&["gc", "--aggressive"]).unwrap();
+    let after = loose_object_count(&repo);
+    assert!(
+        after + 100 <= before,
+        "expected loose objects to drop: {} -> {}\n{}",
+        before,
+        after,
+        output
+    );
+    assert!(
+        output.contains(&format!("Loose objects: {} -> {}", before, after)),
+        "{}",
+        output
+    );
+    assert!(output.contains("Object store size: "), "{}", output);
+
+    let note = repo.git_og(&["notes", "--ref=ai", "show", &head]).unwrap();
+    assert_eq!(note.trim(), "note revision 39");
+    let history = repo
+        .git_og(&["rev-list", "--count", "refs/notes/ai"])
+        .unwrap();
+    assert_eq!(history.trim(), "40");
+}
+
+fn backdate(path: &std::path::Path) {
+    std::fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(86_400))
+        .unwrap();
+}
+
+#[test]
+fn test_gc_removes_only_old_unreferenced_working_log_blobs() {
+    let repo = TestRepo::new();
+    std::fs::write(repo.path().join("file.txt"), "base\n").unwrap();
+    repo.stage_all_and_commit("base").unwrap();
+
+    std::fs::write(repo.path().join("file.txt"), "base\nai line\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "file.txt"]).unwrap();
+
+    let working_log = repo.current_working_logs();
+    let blobs_dir = working_log.dir.join("blobs");
+    let referenced: Vec<String> = working_log
+        .read_all_checkpoints()
+        .unwrap()
+        .iter()
+        .flat_map(|c| c.entries.iter().map(|e| e.blob_sha.clone()))
+        .collect();
+    assert!(!referenced.is_empty());
+
+    // One orphan from a day ago, and one that a checkpoint in progress may have just written
+    let stale = blobs_dir.join("stale-orphan");
+    std::fs::write(&stale, "left behind\n").unwrap();
+    for sha in &referenced {
+        backdate(&blobs_dir.join(sha));
+    }
+    backdate(&stale);
+    let fresh = blobs_dir.join("fresh-orphan");
+    std::fs::write(&fresh, "in flight\n").unwrap();
+
+    let output = repo.git_ai(&["gc", "--dry-run"]).unwrap();
+    assert!(
+        output.contains("Unreferenced working log blobs: 1 (12 bytes reclaimable)"),
+        "{}",
+        output
+    );
+    assert!(stale.exists());
+
+    let output = repo.git_ai(&["gc"]).unwrap();
+    assert!(
+        output.contains("Removed unreferenced working log blobs: 1 (12 bytes)"),
+        "{}",
+        output
+    );
+    assert!(!stale.exists());
+    assert!(fresh.exists());
+    for sha in referenced {
+        assert!(blobs_dir.join(sha).exists());
+    }
+}
@@ -0,0 +1,98 @@
+mod test_utils;
+
+use git_ai::authorship::transcript::Message;
+use git_ai::commands::checkpoint_agent::agent_presets::{
+    TranscriptShape, parse_generic_transcript, transcript_and_model_from_generic_json,
+};
+use serde_json::json;
+use test_utils::fixture_path;
+
+fn parse_fixture(
+    name: &str,
+) -> (
+    git_ai::authorship::transcript::AiTranscript,
+    Option<String>,
+    TranscriptShape,
+) {
+    transcript_and_model_from_generic_json(fixture_path(name).to_str().unwrap())
+        .expect("fixture should match a generic transcript shape")
+}
+
+#[test]
+fn test_generic_transcript_role_content_jsonl() {
+    let (transcript, model, shape) = parse_fixture("generic-role-content.jsonl");
+    assert_eq!(shape, TranscriptShape::RoleContentJsonl);
+    assert_eq!(model.as_deref(), Some("local-coder-7b"));
+    assert_eq!(
+        transcript.messages(),
+        &[
+            Message::user(
+                "Add a greeting function".to_string(),
+                Some("2025-10-01T12:00:00Z".to_string())
+            ),
+            Message::Thinking {
+                text: "A simple fn will do.".to_string(),
+                timestamp: Some("2025-10-01T12:00:05Z".to_string()),
+            },
+            Message::ToolUse {
+                name: "write_file".to_string(),
+                input: json!({"path": "src/lib.rs"}),
+                timestamp: Some("2025-10-01T12:00:05Z".to_string()),
+            },
+            Message::assistant(
+                "I'll add it to lib.rs.".to_string(),
+                Some("2025-10-01T12:00:05Z".to_string())
+            ),
+            Message::assistant("Done.".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn test_generic_transcript_messages_array() {
+    let (transcript, model, shape) = parse_fixture("generic-messages.json");
+    assert_eq!(shape, TranscriptShape::MessagesArray);
+    assert_eq!(model.as_deref(), Some("acme-agent-1"));
+    assert_eq!(
+        transcript.messages(),
+        &[
+            Message::user("Rename foo to bar".to_string(), None),
+            Message::assistant("Renaming now.".to_string(), None),
+            Message::assistant("Renamed 3 occurrences.".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn test_generic_transcript_openai_choices() {
+    let (transcript, model, shape) = parse_fixture("generic-openai-choices.json");
+    assert_eq!(shape, TranscriptShape::OpenAiChoices);
+    assert_eq!(model.as_deref(), Some("gpt-4o-mini"));
+    assert_eq!(
+        transcript.messages(),
+        &[
+            Message::assistant("Updating the config.".to_string(), None),
+            Message::tool_use("edit_file".to_string(), json!({"path": "config.toml"})),
+        ]
+    );
+}
+
+#[test]
+fn test_generic_transcript_rejects_unknown_shapes() {
+    for content in [
+        "",
+        "not json at all",
+        "{\"type\": \"user\", \"text\": \"no role\"}",
+        "{\"messages\": [{\"type\": \"gemini\", \"content\": \"hi\"}]}",
+        "{\"role\": \"user\", \"content\": \"ok\"}\n{truncated",
+        "[1, 2, 3]",
+    ] {
+        let err = parse_generic_transcript(content).unwrap_err();
+        assert!(
+            err.to_string().contains("Unrecognized transcript format"),
+            "{:?}: {}",
+            content,
+            err
+        );
+    }
+}
@@ -0,0 +1,69 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::collections::BTreeSet;
+use std::fs;
+
+#[test]
+fn test_gitai_ignore_excludes_files_from_checkpoints() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join(".gitai-ignore"),
+        "*.lock\nbuild/\ngenerated/**/*.ts\n!generated/keep.ts\n",
+    )
+    .unwrap();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::create_dir_all(repo.path().join("build")).unwrap();
+    fs::create_dir_all(repo.path().join("generated/api")).unwrap();
+    fs::create_dir_all(repo.path().join("src")).unwrap();
+    let files = [
+        ("Cargo.lock", "lock contents\n"),
+        ("build/out.txt", "build output\n"),
+        ("generated/api/client.ts", "export const client = 1;\n"),
+        ("generated/keep.ts", "export const keep = 1;\n"),
+        ("src/main.rs", "fn main() {}\n"),
+    ];
+    for (path, contents) in files {
+        fs::write(repo.path().join(path), contents).unwrap();
+    }
+    // Ignored paths are skipped even when the agent reports editing them
+    let mut args = vec!["checkpoint", "mock_ai"];
+    args.extend(files.iter().map(|(path, _)| *path));
+    repo.git_ai(&args).unwrap();
+
+    let checkpointed: BTreeSet<String> = repo
+        .current_working_logs()
+        .read_all_checkpoints()
+        .unwrap()
+        .iter()
+        .flat_map(|c| c.entries.iter().map(|e| e.file.clone()))
+        .collect();
+    assert_eq!(
+        checkpointed,
+        BTreeSet::from(["generated/keep.ts".to_string(), "src/main.rs".to_string()])
+    );
+
+    let commit = repo.stage_all_and_commit("Agent edit").unwrap();
+    let note = repo
+        .git(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    let attested: BTreeSet<&str> = log
+        .attestations
+        .iter()
+        .map(|a| a.file_path.as_str())
+        .collect();
+    assert_eq!(
+        attested,
+        BTreeSet::from(["generated/keep.ts", "src/main.rs"])
+    );
+
+    let mut main_rs = repo.filename("src/main.rs");
+    main_rs.assert_lines_and_blame(lines!["fn main() {}".ai()]);
+}
@@ -0,0 +1,156 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log_serialization::generate_short_hash_with_length;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+#[test]
+fn test_hash_length_config_sets_prompt_hash_length() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "gitai.hashLength", "24"]).unwrap();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human line", "AI line".ai()]);
+    let commit = repo.stage_all_and_commit("AI edit").unwrap();
+
+    let prompts = &commit.authorship_log.metadata.prompts;
+    assert_eq!(prompts.len(), 1);
+    let (hash, prompt) = prompts.iter().next().unwrap();
+    assert_eq!(hash.len(), 24);
+    assert_eq!(
+        hash,
+        &generate_short_hash_with_length(&prompt.agent_id.id, &prompt.agent_id.tool, 24)
+    );
+    file.assert_lines_and_blame(lines!["Human line".human(), "AI line".ai()]);
+}
+
+#[test]
+fn test_colliding_session_hash_is_lengthened() {
+    // "claude:session-671" and "claude:session-7729" share their first 7 hex characters
+    let first = "session-671";
+    let second = "session-7729";
+    assert_eq!(
+        generate_short_hash_with_length(first, "claude", 7),
+        generate_short_hash_with_length(second, "claude", 7)
+    );
+    assert_ne!(
+        generate_short_hash_with_length(first, "claude", 16),
+        generate_short_hash_with_length(second, "claude", 16)
+    );
+
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "gitai.hashLength", "7"]).unwrap();
+    let mut first_file = repo.filename("first.txt");
+    first_file.set_contents(lines!["First base"]);
+    let mut second_file = repo.filename("second.txt");
+    second_file.set_contents(lines!["Second base"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("first.txt"), "First base\nFirst agent\n").unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "--auto-detect-agent"],
+        &[("CLAUDECODE", "1"), ("CLAUDE_CODE_SESSION_ID", first)],
+    )
+    .unwrap();
+
+    fs::write(
+        repo.path().join("second.txt"),
+        "Second base\nSecond agent\n",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "--auto-detect-agent"],
+        &[("CLAUDECODE", "1"), ("CLAUDE_CODE_SESSION_ID", second)],
+    )
+    .unwrap();
+
+    // The second session is recorded under a longer hash rather than dropped
+    let commit = repo.stage_all_and_commit("Agent edits").unwrap();
+    let prompts = &commit.authorship_log.metadata.prompts;
+    assert_eq!(prompts.len(), 2, "{:?}", prompts.keys());
+    let (second_hash, _) = prompts
+        .iter()
+        .find(|(_, prompt)| prompt.agent_id.id == second)
+        .unwrap();
+    assert_eq!(
+        second_hash,
+        &generate_short_hash_with_length(second, "claude", 8)
+    );
+    first_file.assert_lines_and_blame(lines!["First base".human(), "First agent".ai()]);
+    second_file.assert_lines_and_blame(lines!["Second base".human(), "Second agent".ai()]);
+}
+
+#[test]
+fn test_changing_hash_length_keeps_existing_session_hash() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human line"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let session = [
+        ("CLAUDECODE", "1"),
+        ("CLAUDE_CODE_SESSION_ID", "long-session"),
+    ];
+    fs::write(repo.path().join("test.txt"), "Human line\nAI line 1\n").unwrap();
+    repo.git_ai_with_env(&["checkpoint", "--auto-detect-agent"], &session)
+        .unwrap();
+
+    // The session started at the default length and keeps it
+    repo.git_og(&["config", "gitai.hashLength", "24"]).unwrap();
+    fs::write(
+        repo.path().join("test.txt"),
+        "Human line\nAI line 1\nAI line 2\n",
+    )
+    .unwrap();
+    repo.git_ai_with_env(&["checkpoint", "--auto-detect-agent"], &session)
+        .unwrap();
+
+    let commit = repo.stage_all_and_commit("AI edit").unwrap();
+    let prompts = &commit.authorship_log.metadata.prompts;
+    assert_eq!(prompts.len(), 1, "{:?}", prompts.keys());
+    assert_eq!(prompts.keys().next().unwrap().len(), 16);
+    file.assert_lines_and_blame(lines![
+        "Human line".human(),
+        "AI line 1".ai(),
+        "AI line 2".ai()
+    ]);
+}
+
+#[test]
+fn test_seven_char_session_hash_survives_hash_length_change() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "gitai.hashLength", "7"]).unwrap();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human line"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let session = [
+        ("CLAUDECODE", "1"),
+        ("CLAUDE_CODE_SESSION_ID", "short-session"),
+    ];
+    fs::write(repo.path().join("test.txt"), "Human line\nAI line 1\n").unwrap();
+    repo.git_ai_with_env(&["checkpoint", "--auto-detect-agent"], &session)
+        .unwrap();
+
+    // Loading the working log at the new length must not mistake the recorded 7-char hash
+    // for a legacy one
+    repo.git_og(&["config", "gitai.hashLength", "16"]).unwrap();
+    fs::write(
+        repo.path().join("test.txt"),
+        "Human line\nAI line 1\nAI line 2\n",
+    )
+    .unwrap();
+    repo.git_ai_with_env(&["checkpoint", "--auto-detect-agent"], &session)
+        .unwrap();
+
+    let commit = repo.stage_all_and_commit("AI edit").unwrap();
+    let prompts = &commit.authorship_log.metadata.prompts;
+    assert_eq!(prompts.len(), 1, "{:?}", prompts.keys());
+    assert_eq!(prompts.keys().next().unwrap().len(), 7);
+    file.assert_lines_and_blame(lines![
+        "Human line".human(),
+        "AI line 1".ai(),
+        "AI line 2".ai()
+    ]);
+}
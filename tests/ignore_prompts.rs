@@ -307,3 +307,101 @@ fn test_prompt_sharing_disabled_with_empty_transcript() {
     // The key thing is the checkpoint should succeed
     assert!(!commit.commit_sha.is_empty());
 }
+
+/// Checkpoint `edited_files` as a session of `agent_name` with a one-message transcript
+fn checkpoint_as_agent(repo: &TestRepo, agent_name: &str, edited_files: Vec<String>) {
+    let mut transcript = AiTranscript::new();
+    transcript.add_message(Message::user(format!("Request for {}", agent_name), None));
+
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": edited_files,
+        "transcript": transcript,
+        "agent_name": agent_name,
+        "model": "test-model",
+        "conversation_id": format!("{}-conversation", agent_name),
+    });
+
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &serde_json::to_string(&hook_input).unwrap(),
+    ])
+    .expect("checkpoint should succeed");
+}
+
+/// Commit one file from an in-house agent and one from a third-party agent with
+/// `gitai.ignorePrompts` set to `ignore_prompts`, returning (tool, messages, additions,
+/// accepted lines) per prompt, sorted by tool.
+fn commit_two_agents(ignore_prompts: &str) -> Vec<(String, usize, u32, u32)> {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.exclude_prompts_in_repositories = Some(vec![]);
+        patch.prompt_storage = Some("notes".to_string());
+    });
+    fs::write(repo.path().join("README.md"), "# Test Repo\n").unwrap();
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "-m", "initial commit"]).unwrap();
+    repo.git(&["config", "gitai.ignorePrompts", ignore_prompts])
+        .unwrap();
+
+    fs::write(repo.path().join("ours.txt"), "Ours 1\nOurs 2\n").unwrap();
+    checkpoint_as_agent(&repo, "in-house-agent", vec!["ours.txt".to_string()]);
+    fs::write(
+        repo.path().join("theirs.txt"),
+        "Theirs 1\nTheirs 2\nTheirs 3\n",
+    )
+    .unwrap();
+    checkpoint_as_agent(&repo, "vendor-agent", vec!["theirs.txt".to_string()]);
+
+    repo.git(&["add", "-A"]).unwrap();
+    let commit = repo.commit("Add files").expect("commit should succeed");
+    let mut prompts: Vec<_> = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .map(|p| {
+            (
+                p.agent_id.tool.clone(),
+                p.messages.len(),
+                p.total_additions,
+                p.accepted_lines,
+            )
+        })
+        .collect();
+    prompts.sort();
+    prompts
+}
+
+#[test]
+fn test_ignore_prompts_strips_only_listed_agents() {
+    assert_eq!(
+        commit_two_agents("Vendor-Agent, some-other-tool"),
+        vec![
+            ("in-house-agent".to_string(), 1, 2, 2),
+            // Transcript dropped, line counts kept
+            ("vendor-agent".to_string(), 0, 3, 3),
+        ]
+    );
+}
+
+#[test]
+fn test_ignore_prompts_all_strips_every_agent() {
+    assert_eq!(
+        commit_two_agents("all"),
+        vec![
+            ("in-house-agent".to_string(), 0, 2, 2),
+            ("vendor-agent".to_string(), 0, 3, 3),
+        ]
+    );
+    assert_eq!(
+        commit_two_agents("none"),
+        vec![
+            ("in-house-agent".to_string(), 1, 2, 2),
+            ("vendor-agent".to_string(), 1, 3, 3),
+        ]
+    );
+}
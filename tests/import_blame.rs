@@ -0,0 +1,89 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// Simulate history that predates git-ai by dropping every authorship note
+fn drop_authorship_notes(repo: &TestRepo) {
+    repo.git_og(&["update-ref", "-d", "refs/notes/ai"]).unwrap();
+    assert!(
+        repo.git_og(&["notes", "--ref=ai", "list"])
+            .unwrap()
+            .trim()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_import_blame_seeds_human_baseline() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("legacy.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(2, lines!["Line 3"]);
+    let head = repo.stage_all_and_commit("Second").unwrap();
+
+    drop_authorship_notes(&repo);
+
+    let output = repo.git_ai(&["import-blame", "legacy.txt"]).unwrap();
+    assert!(
+        output.contains("Seeded human baseline for 2 commit(s)"),
+        "{}",
+        output
+    );
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &head.commit_sha])
+        .unwrap();
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    assert!(log.attestations.is_empty());
+    assert_eq!(log.metadata.base_commit_sha, head.commit_sha);
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "Line 2".human(), "Line 3".human()]);
+
+    // Future AI edits contrast against the seeded baseline
+    file.insert_at(3, lines!["AI line".ai()]);
+    repo.stage_all_and_commit("AI edit").unwrap();
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "Line 2".human(),
+        "Line 3".human(),
+        "AI line".ai()
+    ]);
+}
+
+#[test]
+fn test_import_blame_all_skips_commits_with_notes() {
+    let repo = TestRepo::new();
+    let mut a = repo.filename("a.txt");
+    let mut b = repo.filename("b.txt");
+
+    a.set_contents(lines!["A"]);
+    b.set_contents(lines!["B"]);
+    repo.stage_all_and_commit("Legacy").unwrap();
+    drop_authorship_notes(&repo);
+
+    b.insert_at(1, lines!["AI B".ai()]);
+    let ai_commit = repo.stage_all_and_commit("Tracked").unwrap();
+    let before = repo
+        .git_og(&["notes", "--ref=ai", "show", &ai_commit.commit_sha])
+        .unwrap();
+
+    let output = repo.git_ai(&["import-blame", "--all"]).unwrap();
+    assert!(
+        output.contains("Seeded human baseline for 1 commit(s) across 2 file(s) (1 already had authorship notes)"),
+        "{}",
+        output
+    );
+
+    let after = repo
+        .git_og(&["notes", "--ref=ai", "show", &ai_commit.commit_sha])
+        .unwrap();
+    assert_eq!(before, after);
+
+    a.assert_lines_and_blame(lines!["A".human()]);
+    b.assert_lines_and_blame(lines!["B".human(), "AI B".ai()]);
+}
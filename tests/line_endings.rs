@@ -0,0 +1,55 @@
+mod repos;
+
+use git_ai::authorship::authorship_log::LineRange;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// Commit `file` with LF endings, then rewrite it with CRLF endings plus one new line and
+/// checkpoint that as an AI edit. Returns the checkpoint's (additions, deletions) and the
+/// line ranges the committed note attributes to AI.
+fn crlf_edit_over_lf_blob(repo: &TestRepo, file: &str) -> ((u32, u32), Vec<LineRange>) {
+    fs::write(repo.path().join(file), "one\ntwo\nthree\n").unwrap();
+    repo.stage_all_and_commit("LF blob").unwrap();
+
+    fs::write(repo.path().join(file), "one\r\ntwo\r\nthree\r\nfour\r\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", file]).unwrap();
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let stats = &checkpoints.last().unwrap().line_stats;
+    let line_stats = (stats.additions, stats.deletions);
+
+    let commit = repo.stage_all_and_commit("CRLF edit").unwrap();
+    let ranges = commit
+        .authorship_log
+        .attestations
+        .iter()
+        .find(|a| a.file_path == file)
+        .map(|a| {
+            a.entries
+                .iter()
+                .flat_map(|e| e.line_ranges.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    (line_stats, ranges)
+}
+
+#[test]
+fn test_autocrlf_checkout_only_attributes_changed_lines() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "core.autocrlf", "true"]).unwrap();
+
+    let (line_stats, ranges) = crlf_edit_over_lf_blob(&repo, "notes.txt");
+    assert_eq!(line_stats, (1, 0));
+    assert_eq!(ranges, vec![LineRange::Single(4)]);
+}
+
+#[test]
+fn test_text_attribute_normalizes_without_autocrlf() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".gitattributes"), "*.txt text\n").unwrap();
+
+    let (line_stats, ranges) = crlf_edit_over_lf_blob(&repo, "notes.txt");
+    assert_eq!(line_stats, (1, 0));
+    assert_eq!(ranges, vec![LineRange::Single(4)]);
+}
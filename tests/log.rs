@@ -0,0 +1,85 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_log_oneline_summarizes_authorship() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Human line 1", "Human line 2"]);
+    let first = repo.stage_all_and_commit("Human commit").unwrap();
+    file.insert_at(
+        2,
+        lines!["AI line 1".ai(), "AI line 2".ai(), "AI line 3".ai()],
+    );
+    let second = repo.stage_all_and_commit("Agent commit").unwrap();
+    // A commit that never got a note is listed without a summary
+    file.insert_at(5, lines!["Untracked line"]);
+    repo.git_og(&["add", "-A"]).unwrap();
+    repo.git_og(&["commit", "-m", "Plain git commit"]).unwrap();
+
+    let output = repo.git_ai(&["log", "--oneline"]).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", output);
+    assert!(lines[0].ends_with(" Plain git commit"), "{}", output);
+    assert!(
+        lines[1].ends_with(" Agent commit [3 AI lines by mock_ai/unknown; no human overrides]"),
+        "{}",
+        output
+    );
+    assert!(lines[1].starts_with(&second.commit_sha[..7]), "{}", output);
+    assert!(
+        lines[2].ends_with(" Human commit [0 AI lines; no human overrides]"),
+        "{}",
+        output
+    );
+
+    // Revision ranges pass through to the walk
+    let range = format!("{}..HEAD~1", first.commit_sha);
+    let output = repo.git_ai(&["log", "--oneline", &range]).unwrap();
+    assert_eq!(output.lines().count(), 1, "{}", output);
+    assert!(output.contains("Agent commit"), "{}", output);
+}
+
+#[test]
+fn test_log_reports_human_overrides() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["AI line 1".ai(), "AI line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Agent commit").unwrap();
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let mut log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    for prompt in log.metadata.prompts.values_mut() {
+        prompt.overriden_lines = 4;
+    }
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    let output = repo.git_ai(&["log", "-1"]).unwrap();
+    assert!(
+        output.starts_with(&format!("commit {}\n", commit.commit_sha)),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("\nAI:     2 AI lines by mock_ai/unknown; 4 overridden by humans\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("\n    Agent commit"), "{}", output);
+}
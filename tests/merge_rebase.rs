@@ -1,5 +1,6 @@
 #[macro_use]
 mod repos;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
 use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
 
@@ -265,3 +266,247 @@ fn test_blame_after_merge_conflict_resolution() {
         "Line 10".human(),
     ]);
 }
+
+#[test]
+fn test_checkpoint_verify_clean_refuses_during_conflicted_merge() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.replace_at(1, "FEATURE VERSION");
+    repo.stage_all_and_commit("feature change").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    file = repo.filename("test.txt");
+    file.replace_at(1, "MAIN VERSION");
+    repo.stage_all_and_commit("main change").unwrap();
+
+    assert!(repo.git(&["merge", "feature"]).is_err());
+
+    // An agent edits the conflicted file mid-resolution
+    std::fs::write(
+        repo.path().join("test.txt"),
+        "Line 1\nAI RESOLUTION\nLine 3\n",
+    )
+    .unwrap();
+
+    let err = repo
+        .git_ai(&["checkpoint", "mock_ai", "--verify-clean", "test.txt"])
+        .unwrap_err();
+    assert!(
+        err.contains("Refusing to checkpoint: a merge is in progress"),
+        "{}",
+        err
+    );
+    assert!(
+        repo.current_working_logs()
+            .read_all_checkpoints()
+            .unwrap()
+            .is_empty(),
+        "no checkpoint should be recorded"
+    );
+
+    repo.git(&["merge", "--abort"]).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "--verify-clean"])
+        .unwrap();
+}
+
+/// AI checkpoint for `file` attributed to the agent-v1 tool `agent_name`
+fn checkpoint_as_agent(repo: &TestRepo, agent_name: &str, file: &str) {
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": [file],
+        "transcript": {"messages": []},
+        "agent_name": agent_name,
+        "model": "test-model",
+        "conversation_id": format!("{}-session", agent_name),
+    });
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+    ])
+    .unwrap();
+}
+
+#[test]
+fn test_octopus_merge_combines_authorship_from_every_parent() {
+    let repo = TestRepo::new();
+    std::fs::write(repo.path().join("README.md"), "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    for agent in ["agent-one", "agent-two", "agent-three"] {
+        repo.git(&["checkout", "-b", agent, &default_branch])
+            .unwrap();
+        let file = format!("{}.txt", agent);
+        std::fs::write(repo.path().join(&file), format!("written by {}\n", agent)).unwrap();
+        checkpoint_as_agent(&repo, agent, &file);
+        repo.stage_all_and_commit(&format!("{} changes", agent))
+            .unwrap();
+    }
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.git(&[
+        "merge",
+        "--no-edit",
+        "agent-one",
+        "agent-two",
+        "agent-three",
+    ])
+    .unwrap();
+    let parents = repo.git_og(&["show", "-s", "--format=%P", "HEAD"]).unwrap();
+    assert_eq!(parents.split_whitespace().count(), 3, "{}", parents);
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", "HEAD"])
+        .expect("octopus merge should get an authorship note");
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    let mut tools: Vec<&str> = log
+        .metadata
+        .prompts
+        .values()
+        .map(|p| p.agent_id.tool.as_str())
+        .collect();
+    tools.sort();
+    assert_eq!(tools, vec!["agent-one", "agent-three", "agent-two"]);
+
+    let mut files: Vec<&str> = log
+        .attestations
+        .iter()
+        .map(|a| a.file_path.as_str())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec!["agent-one.txt", "agent-three.txt", "agent-two.txt"]
+    );
+}
+
+#[test]
+fn test_merge_commit_keeps_parent_ai_lines_and_resolved_conflict_is_human() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines![
+        "Line 1", "Line 2", "Line 3", "Line 4", "Line 5", "Line 6"
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.replace_at(1, "AI HELPER".ai());
+    file.replace_at(4, "CONFLICT FEATURE VERSION".ai());
+    repo.stage_all_and_commit("feature changes").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    file = repo.filename("test.txt");
+    file.replace_at(4, "CONFLICT MAIN VERSION");
+    repo.stage_all_and_commit("main changes").unwrap();
+
+    assert!(
+        repo.git(&["merge", "feature", "-m", "merge feature"])
+            .is_err(),
+        "merge should conflict"
+    );
+    file = repo.filename("test.txt");
+    file.set_contents(lines![
+        "Line 1",
+        "AI HELPER",
+        "Line 3",
+        "Line 4",
+        "RESOLVED BY HAND",
+        "Line 6",
+    ]);
+    let merge = repo.stage_all_and_commit("merge feature").unwrap();
+
+    let parents = repo.git_og(&["show", "-s", "--format=%P", "HEAD"]).unwrap();
+    assert_eq!(parents.split_whitespace().count(), 2, "{}", parents);
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &merge.commit_sha])
+        .expect("merge commit should get an authorship note");
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    let attestation = log
+        .attestations
+        .iter()
+        .find(|a| a.file_path == "test.txt")
+        .expect("the feature branch's AI line should be attested on the merge");
+    let covers = |line: u32| {
+        attestation
+            .entries
+            .iter()
+            .any(|entry| entry.line_ranges.iter().any(|range| range.contains(line)))
+    };
+    assert!(covers(2), "AI line from feature should stay AI: {:?}", log);
+    assert!(
+        !covers(5),
+        "hand-resolved conflict should be human: {:?}",
+        log
+    );
+
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI HELPER".ai(),
+        "Line 3".human(),
+        "Line 4".human(),
+        "RESOLVED BY HAND".human(),
+        "Line 6".human(),
+    ]);
+}
+
+#[test]
+fn test_clean_merge_commit_note_carries_parent_ai_lines_without_counts() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.txt");
+    feature.set_contents(lines!["AI 1".ai(), "AI 2".ai()]);
+    repo.stage_all_and_commit("feature changes").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    file = repo.filename("test.txt");
+    file.insert_at(0, lines!["MAIN LINE"]);
+    repo.stage_all_and_commit("main changes").unwrap();
+
+    // Merges without conflicts commit on their own, so this goes through the
+    // rewrite log's Merge event rather than post-commit
+    repo.git(&["merge", "--no-ff", "feature", "-m", "merge feature"])
+        .unwrap();
+    let merge_sha = repo.git_og(&["rev-parse", "HEAD"]).unwrap();
+    let merge_sha = merge_sha.trim();
+    let parents = repo
+        .git_og(&["show", "-s", "--format=%P", merge_sha])
+        .unwrap();
+    assert_eq!(parents.split_whitespace().count(), 2, "{}", parents);
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", merge_sha])
+        .expect("merge commit should get an authorship note");
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    let attestation = log
+        .attestations
+        .iter()
+        .find(|a| a.file_path == "feature.txt")
+        .expect("the feature branch's AI lines should be attested on the merge");
+    assert_eq!(attestation.entries.len(), 1, "{:?}", log);
+    let prompt = &log.metadata.prompts[&attestation.entries[0].hash];
+    assert_eq!(prompt.total_additions, 0, "{:?}", prompt);
+    assert_eq!(prompt.accepted_lines, 0, "{:?}", prompt);
+
+    feature.assert_lines_and_blame(lines!["AI 1".ai(), "AI 2".ai()]);
+    file.assert_lines_and_blame(lines![
+        "MAIN LINE".human(),
+        "Line 1".human(),
+        "Line 2".human()
+    ]);
+}
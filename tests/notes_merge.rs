@@ -0,0 +1,157 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log::{LineRange, PromptRecord};
+use git_ai::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, FileAttestation,
+};
+use git_ai::authorship::working_log::AgentId;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn prompt(tool: &str, id: &str) -> PromptRecord {
+    PromptRecord {
+        agent_id: AgentId {
+            tool: tool.to_string(),
+            id: id.to_string(),
+            model: "test-model".to_string(),
+        },
+        human_author: None,
+        messages: vec![],
+        total_additions: 1,
+        total_deletions: 0,
+        accepted_lines: 1,
+        overriden_lines: 0,
+        messages_url: None,
+        transcript_truncated: false,
+    }
+}
+
+fn add_note(repo: &TestRepo, notes_ref: &str, commit_sha: &str, log: &AuthorshipLog) {
+    repo.git_og(&[
+        "notes",
+        &format!("--ref={}", notes_ref),
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        commit_sha,
+    ])
+    .unwrap();
+}
+
+fn read_note(repo: &TestRepo, commit_sha: &str) -> AuthorshipLog {
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", commit_sha])
+        .unwrap();
+    AuthorshipLog::deserialize_from_string(note.trim()).unwrap()
+}
+
+#[test]
+fn test_notes_merge_unions_divergent_refs() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["AI line 1".ai(), "Line 2", "Line 3"]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(3, lines!["Line 4"]);
+    let second = repo.stage_all_and_commit("Second").unwrap();
+
+    let local = read_note(&repo, &first.commit_sha);
+    let (local_hash, _) = local.metadata.prompts.iter().next().unwrap();
+    let local_hash = local_hash.clone();
+
+    // Another clone attributed line 3 of the first commit to a different session
+    let mut theirs = AuthorshipLog::new();
+    theirs.metadata.base_commit_sha = first.commit_sha.clone();
+    theirs
+        .metadata
+        .prompts
+        .insert("remote01".to_string(), prompt("cursor", "remote-session"));
+    let mut attestation = FileAttestation::new("test.txt".to_string());
+    attestation.add_entry(AttestationEntry::new(
+        "remote01".to_string(),
+        vec![LineRange::Single(3)],
+    ));
+    theirs.attestations.push(attestation);
+    add_note(&repo, "refs/notes/other", &first.commit_sha, &theirs);
+
+    // ...and annotated a commit we have no local note for
+    repo.git_og(&["notes", "--ref=ai", "remove", &second.commit_sha])
+        .unwrap();
+    let mut second_log = AuthorshipLog::new();
+    second_log.metadata.base_commit_sha = second.commit_sha.clone();
+    add_note(&repo, "refs/notes/other", &second.commit_sha, &second_log);
+
+    let output = repo.git_ai(&["notes-merge", "other"]).unwrap();
+    assert!(
+        output.contains(
+            "Merged authorship notes from refs/notes/other: 1 copied, 1 unioned, 0 unchanged"
+        ),
+        "{}",
+        output
+    );
+
+    let merged = read_note(&repo, &first.commit_sha);
+    assert!(merged.metadata.prompts.contains_key(&local_hash));
+    assert!(merged.metadata.prompts.contains_key("remote01"));
+    let entries = &merged.attestations[0].entries;
+    assert_eq!(
+        entries,
+        &vec![
+            AttestationEntry::new(local_hash, vec![LineRange::Single(1)]),
+            AttestationEntry::new("remote01".to_string(), vec![LineRange::Single(3)]),
+        ]
+    );
+
+    assert_eq!(read_note(&repo, &second.commit_sha), second_log);
+
+    // Merging again is a no-op
+    let output = repo.git_ai(&["notes-merge", "refs/notes/other"]).unwrap();
+    assert!(
+        output.contains("0 copied, 0 unioned, 2 unchanged"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_notes_merge_keeps_unparseable_local_note_without_force() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["AI line 1".ai(), "Line 2"]);
+    let commit = repo.stage_all_and_commit("First").unwrap();
+
+    let theirs = read_note(&repo, &commit.commit_sha);
+    add_note(&repo, "refs/notes/other", &commit.commit_sha, &theirs);
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        "not an authorship log",
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    let output = repo.git_ai(&["notes-merge", "other"]).unwrap();
+    assert!(
+        output.contains("kept 1 unparseable local note(s); rerun with --force"),
+        "{}",
+        output
+    );
+    assert!(output.contains(&commit.commit_sha), "{}", output);
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    assert_eq!(note.trim(), "not an authorship log");
+
+    let output = repo.git_ai(&["notes-merge", "--force", "other"]).unwrap();
+    assert!(
+        output.contains("Replaced 1 unparseable local note(s)"),
+        "{}",
+        output
+    );
+    assert_eq!(read_note(&repo, &commit.commit_sha), theirs);
+}
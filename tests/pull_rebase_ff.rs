@@ -425,3 +425,37 @@ fn test_pull_rebase_committed_and_autostash_preserves_all_authorship() {
     // Verify uncommitted AI authorship survived the autostash cycle
     uncommitted_ai.assert_lines_and_blame(vec!["Uncommitted AI line".ai()]);
 }
+
+// =============================================================================
+// Pull that merges
+// =============================================================================
+
+#[test]
+fn test_pull_merge_commit_carries_parent_ai_authorship() {
+    let setup = setup_divergent_pull_test();
+    let local = setup.local;
+
+    local
+        .git(&["pull", "--no-rebase", "--no-edit"])
+        .expect("pull --no-rebase should succeed");
+
+    let merge_sha = local.git_og(&["rev-parse", "HEAD"]).unwrap();
+    let merge_sha = merge_sha.trim();
+    let parents = local
+        .git_og(&["show", "-s", "--format=%P", merge_sha])
+        .unwrap();
+    assert_eq!(parents.split_whitespace().count(), 2, "{}", parents);
+
+    // The merge commit gets a note carrying the local side's AI lines
+    let note = local
+        .git_og(&["notes", "--ref=ai", "show", merge_sha])
+        .expect("the pull's merge commit should get an authorship note");
+    assert!(note.contains("ai_feature.txt"), "{}", note);
+    assert!(!note.contains("upstream_change.txt"), "{}", note);
+
+    let mut ai_file = local.filename("ai_feature.txt");
+    ai_file.assert_lines_and_blame(vec![
+        "AI generated feature line 1".ai(),
+        "AI generated feature line 2".ai(),
+    ]);
+}
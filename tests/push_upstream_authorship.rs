@@ -83,3 +83,139 @@ fn push_after_branch_set_upstream_pushes_authorship_notes() {
         "expected authorship notes to be pushed after setting upstream with git branch -u"
     );
 }
+
+#[test]
+fn push_with_mirror_notes_pushes_authorship_notes_to_both_remotes() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let backup = TestRepo::new_bare();
+    local
+        .git_og(&["remote", "add", "backup", backup.path().to_str().unwrap()])
+        .expect("adding backup remote should succeed");
+
+    let mut file = local.filename("mirrored.rs");
+    file.set_contents(vec!["fn mirrored() {}".ai()]);
+    let commit = local
+        .stage_all_and_commit("mirrored commit")
+        .expect("commit should succeed");
+
+    local
+        .git(&["push", "--mirror-notes", "backup", "origin", "HEAD"])
+        .expect("push with --mirror-notes should succeed");
+
+    let primary = read_remote_authorship_note(&upstream, &commit.commit_sha);
+    let mirrored = read_remote_authorship_note(&backup, &commit.commit_sha);
+    assert!(primary.is_some(), "expected notes on the primary remote");
+    assert_eq!(mirrored, primary, "expected identical notes on the mirror");
+
+    // Only the notes are mirrored, not the pushed branch
+    let branches = Command::new("git")
+        .args(["--git-dir", backup.path().to_str().unwrap(), "branch"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+}
+
+#[test]
+fn push_with_unreachable_mirror_notes_remote_still_succeeds() {
+    let (local, upstream) = TestRepo::new_with_remote();
+
+    let mut file = local.filename("primary_only.rs");
+    file.set_contents(vec!["fn primary_only() {}".ai()]);
+    let commit = local
+        .stage_all_and_commit("primary only")
+        .expect("commit should succeed");
+
+    let output = local
+        .git(&["push", "--mirror-notes=missing-remote", "origin", "HEAD"])
+        .expect("primary push should succeed even if mirroring fails");
+    assert!(
+        output.contains("failed to mirror authorship notes to missing-remote"),
+        "{}",
+        output
+    );
+    assert!(read_remote_authorship_note(&upstream, &commit.commit_sha).is_some());
+}
+
+#[test]
+fn authorship_refspec_config_redirects_push_and_fetch() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    local
+        .git_og(&[
+            "config",
+            "gitai.authorshipRefspec",
+            "refs/notes/ai:refs/notes/mirror/ai",
+        ])
+        .unwrap();
+
+    let mut file = local.filename("mirrored.rs");
+    file.set_contents(vec!["fn mirrored() {}".ai()]);
+    let commit = local.stage_all_and_commit("mirrored commit").unwrap();
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+
+    // Notes land under the configured namespace on the remote, not refs/notes/ai
+    assert!(read_remote_authorship_note(&upstream, &commit.commit_sha).is_none());
+    let git_dir = upstream.path().to_str().unwrap();
+    let output = Command::new("git")
+        .args(["--git-dir", git_dir, "notes", "--ref=mirror/ai", "show"])
+        .arg(&commit.commit_sha)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // ...and fetching reads them back from there
+    local
+        .git_og(&["update-ref", "-d", "refs/notes/ai"])
+        .unwrap();
+    local
+        .git_og(&["update-ref", "-d", "refs/notes/ai-remote/origin"])
+        .unwrap();
+    local.git(&["fetch", "origin"]).unwrap();
+    local
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .expect("notes should be fetched from the configured remote ref");
+
+    // An empty refspec is rejected instead of silently falling back
+    local
+        .git_og(&["config", "gitai.authorshipRefspec", ""])
+        .unwrap();
+    let output = local.git_ai(&["config", "--validate"]).unwrap_err();
+    assert!(
+        output.contains("gitai.authorshipRefspec must not be empty"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn push_with_no_authorship_skips_notes_and_warns() {
+    let (local, upstream) = TestRepo::new_with_remote();
+
+    let mut file = local.filename("local_only.rs");
+    file.set_contents(vec!["fn local_only() {}".ai()]);
+    let commit = local
+        .stage_all_and_commit("local only")
+        .expect("commit should succeed");
+
+    let output = local
+        .git(&["push", "--no-authorship", "origin", "HEAD"])
+        .expect("push with --no-authorship should succeed");
+    assert!(
+        output.contains("pushing without authorship notes"),
+        "expected a warning that notes stay local: {}",
+        output
+    );
+    assert_eq!(
+        output.matches("pushing without authorship notes").count(),
+        1,
+        "{}",
+        output
+    );
+
+    // The branch is pushed but its note is not
+    let branches = Command::new("git")
+        .args(["--git-dir", upstream.path().to_str().unwrap(), "branch"])
+        .output()
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+    assert!(read_remote_authorship_note(&upstream, &commit.commit_sha).is_none());
+}
@@ -1,7 +1,11 @@
 #[macro_use]
 mod repos;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use git_ai::authorship::rebase_authorship::rewrite_authorship_after_rebase_v2;
+use git_ai::git::repository as GitAiRepository;
 use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
+use std::collections::HashMap;
 
 /// Test simple rebase with no conflicts where trees are identical - multiple commits
 #[test]
@@ -849,6 +853,83 @@ fn test_rebase_preserve_merges() {
     side_file.assert_lines_and_blame(lines!["// AI side".ai()]);
 }
 
+/// Test that a merge commit rewritten by `rebase --rebase-merges` keeps the attributions
+/// brought in by both of its parents, when each parent adds a file the other doesn't have
+#[test]
+fn test_rebase_preserve_merges_attributes_both_parents() {
+    let repo = TestRepo::new();
+
+    let mut base_file = repo.filename("base.txt");
+    base_file.set_contents(lines!["base"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature_file = repo.filename("feature.txt");
+    feature_file.set_contents(lines!["// AI feature".ai()]);
+    repo.stage_all_and_commit("AI feature").unwrap();
+
+    repo.git(&["checkout", "-b", "side"]).unwrap();
+    let mut side_file = repo.filename("side.txt");
+    side_file.set_contents(lines!["// AI side".ai()]);
+    repo.stage_all_and_commit("AI side").unwrap();
+
+    // Advance feature after side branched off
+    repo.git(&["checkout", "feature"]).unwrap();
+    let mut feature_more_file = repo.filename("feature_more.txt");
+    feature_more_file.set_contents(lines!["// AI feature more".ai()]);
+    repo.stage_all_and_commit("AI feature more").unwrap();
+
+    repo.git(&["merge", "--no-ff", "side", "-m", "Merge side into feature"])
+        .unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut main_file = repo.filename("main.txt");
+    main_file.set_contents(lines!["main"]);
+    repo.stage_all_and_commit("Main work").unwrap();
+    let base = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git(&["rebase", "--rebase-merges", &base])
+        .expect("Rebase with --rebase-merges should succeed");
+
+    // The rebased merge commit carries attributions from both of its parents' sides,
+    // not just the files that differ from parent(0)
+    let merge_sha = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let merge_parents = repo
+        .git(&["rev-list", "--parents", "-n", "1", &merge_sha])
+        .unwrap();
+    assert_eq!(
+        merge_parents.split_whitespace().count(),
+        3,
+        "HEAD should be the rebased merge commit"
+    );
+    let merge_note = repo
+        .git(&["notes", "--ref=ai", "show", &merge_sha])
+        .expect("Rebased merge commit should have authorship note");
+    let merge_log = AuthorshipLog::deserialize_from_string(merge_note.trim()).unwrap();
+    for file in ["feature_more.txt", "side.txt"] {
+        let attestation = merge_log
+            .attestations
+            .iter()
+            .find(|a| a.file_path == file)
+            .unwrap_or_else(|| panic!("Merge note should attest {}. Got: {}", file, merge_note));
+        assert!(
+            attestation
+                .entries
+                .iter()
+                .any(|e| merge_log.metadata.prompts.contains_key(&e.hash)),
+            "{} should be attributed to an AI session in the merge note",
+            file
+        );
+    }
+
+    feature_file.assert_lines_and_blame(lines!["// AI feature".ai()]);
+    feature_more_file.assert_lines_and_blame(lines!["// AI feature more".ai()]);
+    side_file.assert_lines_and_blame(lines!["// AI side".ai()]);
+}
+
 /// Test rebase with commit splitting (fewer original commits than new commits)
 /// This tests that rebase handles AI authorship correctly even with complex commit histories
 #[test]
@@ -1118,3 +1199,99 @@ cat {} > "$1"
         "function feature3() {}".ai()
     ]);
 }
+
+/// Test that an author map credits each rewritten commit's sessions to its real author
+#[test]
+fn test_rebase_author_map_sets_human_author_per_commit() {
+    let repo = TestRepo::new();
+    let mut main_file = repo.filename("main.txt");
+    main_file.set_contents(lines!["main content"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    let default_branch = repo.current_branch();
+
+    // Alice and Bob both commit work from the shared session; Carol uses her own
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut original_commits = Vec::new();
+    for (file, session) in [
+        ("alice.txt", "shared-session"),
+        ("bob.txt", "shared-session"),
+        ("carol.txt", "carol-session"),
+    ] {
+        std::fs::write(
+            repo.path().join(file),
+            format!("// written in {}\n", session),
+        )
+        .unwrap();
+        repo.git_ai_with_env(
+            &["checkpoint", "--auto-detect-agent"],
+            &[("CLAUDECODE", "1"), ("CLAUDE_CODE_SESSION_ID", session)],
+        )
+        .unwrap();
+        let commit = repo.stage_all_and_commit(&format!("Add {}", file)).unwrap();
+        original_commits.push(commit.commit_sha);
+    }
+    let original_head = original_commits.last().unwrap().clone();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut main2_file = repo.filename("main2.txt");
+    main2_file.set_contents(lines!["more main content"]);
+    repo.stage_all_and_commit("Main advances").unwrap();
+
+    // Rebase with plain git so the rewrite below is the only one
+    repo.git_og(&["checkout", "feature"]).unwrap();
+    repo.git_og(&["rebase", &default_branch]).unwrap();
+    let new_commits: Vec<String> = repo
+        .git_og(&[
+            "rev-list",
+            "--reverse",
+            &format!("{}..HEAD", default_branch),
+        ])
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(new_commits.len(), 3);
+
+    let alice = "Alice <alice@example.com>".to_string();
+    let bob = "Bob <bob@example.com>".to_string();
+    let carol = "Carol <carol@example.com>".to_string();
+    let author_map = HashMap::from([
+        (original_commits[0].clone(), alice.clone()),
+        (original_commits[1].clone(), bob.clone()),
+        (original_commits[2].clone(), carol.clone()),
+    ]);
+    let gitai_repo =
+        GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    rewrite_authorship_after_rebase_v2(
+        &gitai_repo,
+        &original_head,
+        &original_commits,
+        &new_commits,
+        "",
+        Some(&author_map),
+    )
+    .unwrap();
+
+    let authors_of = |commit: &str| -> HashMap<String, Option<String>> {
+        let note = repo.git(&["notes", "--ref=ai", "show", commit]).unwrap();
+        let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+        log.metadata
+            .prompts
+            .into_values()
+            .map(|p| (p.agent_id.id, p.human_author))
+            .collect()
+    };
+    // Each note only carries the sessions it attests, credited to its own commit's author
+    assert_eq!(
+        authors_of(&new_commits[0]),
+        HashMap::from([("shared-session".to_string(), Some(alice))])
+    );
+    assert_eq!(
+        authors_of(&new_commits[1]),
+        HashMap::from([("shared-session".to_string(), Some(bob))])
+    );
+    assert_eq!(
+        authors_of(&new_commits[2]),
+        HashMap::from([("carol-session".to_string(), Some(carol))])
+    );
+}
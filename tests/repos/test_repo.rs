@@ -438,6 +438,50 @@ impl TestRepo {
         }
     }
 
+    /// Run a git command through git-ai with data provided on stdin
+    pub fn git_with_stdin(&self, args: &[&str], stdin_data: &[u8]) -> Result<String, String> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let mut full_args = vec!["-C", self.path.to_str().unwrap()];
+        full_args.extend(args);
+
+        let mut command = Command::new(get_binary_path());
+        command
+            .args(&full_args)
+            .env("GIT_AI", "git")
+            .env("GIT_AI_TEST_DB_PATH", self.test_db_path.to_str().unwrap())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        if let Some(patch) = &self.config_patch
+            && let Ok(patch_json) = serde_json::to_string(patch)
+        {
+            command.env("GIT_AI_TEST_CONFIG_PATCH", patch_json);
+        }
+
+        let mut child = command
+            .spawn()
+            .unwrap_or_else(|_| panic!("Failed to spawn git command: {:?}", args));
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(stdin_data)
+                .expect("Failed to write to stdin");
+        }
+        let output = child
+            .wait_with_output()
+            .unwrap_or_else(|_| panic!("Failed to wait for git command: {:?}", args));
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if output.status.success() {
+            Ok(format!("{}{}", stdout, stderr))
+        } else {
+            Err(stderr)
+        }
+    }
+
     /// Run a git-ai command with data provided on stdin
     pub fn git_ai_with_stdin(&self, args: &[&str], stdin_data: &[u8]) -> Result<String, String> {
         use std::io::Write;
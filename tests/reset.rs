@@ -542,3 +542,43 @@ fn test_reset_mixed_pathspec_multiple_commits() {
         "// More lib".ai(),
     ]);
 }
+
+/// Pathspecs piped to `git reset --pathspec-from-file=-` reach both git and the reset hook
+#[test]
+fn test_reset_pathspec_from_stdin_preserves_other_files() {
+    let repo = TestRepo::new();
+    let mut file1 = repo.filename("file1.txt");
+    let mut file2 = repo.filename("file2.txt");
+
+    file1.set_contents(lines!["base content 1", ""]);
+    file2.set_contents(lines!["base content 2", ""]);
+    let base_commit = repo.stage_all_and_commit("Base commit").unwrap();
+
+    file1.insert_at(1, lines!["// AI change to file1".ai()]);
+    file2.insert_at(1, lines!["// AI change to file2".ai()]);
+    repo.stage_all_and_commit("AI modifies both files").unwrap();
+    file2.insert_at(2, lines!["// More AI changes".ai()]);
+
+    repo.git_with_stdin(
+        &["reset", &base_commit.commit_sha, "--pathspec-from-file=-"],
+        b"file1.txt\n",
+    )
+    .expect("reset with pathspecs from stdin should succeed");
+
+    // Only file1.txt was reset in the index
+    let staged = repo.git_og(&["diff", "--cached", "--name-only"]).unwrap();
+    assert_eq!(staged.trim(), "file1.txt");
+
+    repo.stage_all_and_commit("After pathspec reset").unwrap();
+    file1 = repo.filename("file1.txt");
+    file1.assert_lines_and_blame(lines![
+        "base content 1".human(),
+        "// AI change to file1".ai(),
+    ]);
+    file2 = repo.filename("file2.txt");
+    file2.assert_lines_and_blame(lines![
+        "base content 2".human(),
+        "// AI change to file2".ai(),
+        "// More AI changes".ai(),
+    ]);
+}
@@ -0,0 +1,103 @@
+#[macro_use]
+mod repos;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::process::Command;
+
+/// A `--depth 1` clone of `upstream`, opened as a test repo
+fn shallow_clone(upstream: &TestRepo) -> TestRepo {
+    let path = std::env::temp_dir().join(format!("shallow-{}", rand::random::<u64>()));
+    let output = Command::new("git")
+        .args([
+            "clone",
+            "--depth",
+            "1",
+            &format!("file://{}", upstream.path().display()),
+            path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("failed to run git clone");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    TestRepo::new_at_path(&path)
+}
+
+#[test]
+fn test_blame_marks_lines_from_missing_history_in_shallow_clone() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let mut file = local.filename("feature.rs");
+    file.set_contents(lines!["fn first() {}".ai(), "fn second() {}".ai()]);
+    local.stage_all_and_commit("Older AI commit").unwrap();
+    file.insert_at(2, lines!["fn third() {}".ai()]);
+    local.stage_all_and_commit("Newest AI commit").unwrap();
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+
+    let shallow = shallow_clone(&upstream);
+    let output = shallow.git(&["fetch", "origin"]).unwrap();
+    assert!(
+        output.contains("warning: this is a shallow clone"),
+        "{}",
+        output
+    );
+
+    let blame = shallow.git_ai(&["blame", "feature.rs"]).unwrap();
+    let lines: Vec<&str> = blame.lines().collect();
+    assert!(
+        lines[0].contains("(attribution unavailable in shallow clone)"),
+        "{}",
+        blame
+    );
+    assert!(
+        lines[1].contains("(attribution unavailable in shallow clone)"),
+        "{}",
+        blame
+    );
+    // The boundary commit's own AI lines are still attributed from its note
+    assert!(lines[2].contains("mock_ai"), "{}", blame);
+
+    // Machine-readable output reports real authors only
+    let json = shallow.git_ai(&["blame", "--json", "feature.rs"]).unwrap();
+    assert!(!json.contains("shallow clone"), "{}", json);
+}
+
+#[test]
+fn test_cherry_pick_in_shallow_clone_keeps_ai_attribution() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let mut file = local.filename("feature.rs");
+    file.set_contents(lines!["fn first() {}", "fn second() {}"]);
+    local.stage_all_and_commit("Human base").unwrap();
+    let main = local.current_branch();
+
+    local.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(2, lines!["fn third() {}".ai(), "fn fourth() {}".ai()]);
+    let ai_commit = local.stage_all_and_commit("AI commit").unwrap();
+    local.git(&["push", "origin", "feature"]).unwrap();
+
+    local.git(&["checkout", &main]).unwrap();
+    let mut other = local.filename("other.rs");
+    other.set_contents(lines!["fn other() {}"]);
+    local.stage_all_and_commit("Human change on main").unwrap();
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+
+    let shallow = shallow_clone(&upstream);
+    shallow
+        .git(&["fetch", "--depth", "2", "origin", "feature"])
+        .unwrap();
+    shallow.git(&["fetch", "origin"]).unwrap();
+
+    shallow
+        .git(&["cherry-pick", &ai_commit.commit_sha])
+        .unwrap();
+
+    let note = shallow.git(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    assert!(!note.contains("shallow clone"), "{}", note);
+
+    let blame = shallow.git_ai(&["blame", "feature.rs"]).unwrap();
+    let lines: Vec<&str> = blame.lines().collect();
+    assert!(lines[2].contains("mock_ai"), "{}", blame);
+    assert!(lines[3].contains("mock_ai"), "{}", blame);
+}
@@ -0,0 +1,741 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log::LineRange;
+use git_ai::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use git_ai::authorship::signing::{
+    SignatureStatus, sign_authorship_log, verify_authorship_log_signature,
+};
+use git_ai::git::repository as GitAiRepository;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::path::Path;
+use std::process::Command;
+
+/// Create a throwaway keyring with one passphrase-less signing key and point the
+/// repo's `gpg.program` at a wrapper that uses it. Returns false when gpg is unavailable.
+#[cfg(unix)]
+fn configure_test_gpg(repo: &TestRepo, gnupg_home: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    if Command::new("gpg").arg("--version").output().is_err() {
+        return false;
+    }
+
+    let home = gnupg_home.to_str().unwrap();
+    let status = Command::new("gpg")
+        .args([
+            "--homedir",
+            home,
+            "--batch",
+            "--passphrase",
+            "",
+            "--quick-gen-key",
+            "Git AI Test <signer@example.com>",
+            "ed25519",
+            "sign",
+            "never",
+        ])
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "failed to generate test gpg key");
+
+    let wrapper = gnupg_home.join("gpg-wrapper.sh");
+    std::fs::write(
+        &wrapper,
+        format!("#!/bin/sh\nexec gpg --homedir '{}' \"$@\"\n", home),
+    )
+    .unwrap();
+    std::fs::set_permissions(&wrapper, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    repo.git_og(&["config", "gpg.program", wrapper.to_str().unwrap()])
+        .unwrap();
+    repo.git_og(&["config", "user.signingkey", "signer@example.com"])
+        .unwrap();
+    true
+}
+
+#[cfg(unix)]
+fn stop_gpg_agent(gnupg_home: &Path) {
+    let _ = Command::new("gpgconf")
+        .args(["--homedir", gnupg_home.to_str().unwrap()])
+        .args(["--kill", "gpg-agent"])
+        .output();
+}
+
+#[cfg(unix)]
+fn gpg_home() -> tempfile::TempDir {
+    // Short path: gpg-agent's socket path must fit in sun_path
+    tempfile::Builder::new()
+        .prefix("gpg")
+        .tempdir_in("/tmp")
+        .unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sign_authorship_signs_note_and_detects_tampering() {
+    let mut repo = TestRepo::new();
+    let gnupg_home = gpg_home();
+    if !configure_test_gpg(&repo, gnupg_home.path()) {
+        eprintln!("gpg not available, skipping");
+        return;
+    }
+    repo.patch_git_ai_config(|patch| {
+        patch.sign_authorship = Some(true);
+    });
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Signed commit").unwrap();
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    assert!(
+        log.metadata
+            .signature
+            .as_deref()
+            .is_some_and(|s| s.contains("BEGIN PGP SIGNATURE")),
+        "note should carry an armored signature: {}",
+        note
+    );
+
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    let signer = log
+        .metadata
+        .signer
+        .clone()
+        .expect("signer fingerprint recorded");
+    assert_eq!(
+        verify_authorship_log_signature(&gitai_repo, &log).unwrap(),
+        SignatureStatus::Valid { signer }
+    );
+
+    // Rewriting attribution after the fact invalidates the signature
+    let mut tampered = log.clone();
+    tampered.attestations.clear();
+    assert_eq!(
+        verify_authorship_log_signature(&gitai_repo, &tampered).unwrap(),
+        SignatureStatus::Invalid
+    );
+
+    stop_gpg_agent(gnupg_home.path());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_verify_reports_signed_and_tampered_notes() {
+    let mut repo = TestRepo::new();
+    let gnupg_home = gpg_home();
+    if !configure_test_gpg(&repo, gnupg_home.path()) {
+        eprintln!("gpg not available, skipping");
+        return;
+    }
+    repo.patch_git_ai_config(|patch| {
+        patch.sign_authorship = Some(true);
+    });
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Signed commit").unwrap();
+
+    let output = repo
+        .git_ai(&["verify", "--require-signed", &commit.commit_sha])
+        .unwrap();
+    assert!(
+        output.contains(&format!("{} signed (", commit.commit_sha)),
+        "{}",
+        output
+    );
+
+    // Drop the AI attestation from the stored note while keeping the old signature
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let mut log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    assert_eq!(log.metadata.schema_version, "authorship/3.0.0");
+    log.attestations.clear();
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    let err = repo.git_ai(&["verify", &commit.commit_sha]).unwrap_err();
+    assert!(
+        err.contains(&format!("{} invalid signature", commit.commit_sha)),
+        "{}",
+        err
+    );
+
+    stop_gpg_agent(gnupg_home.path());
+}
+
+/// Generate a passphrase-less ed25519 key and configure the repo to sign with it over SSH,
+/// trusting it through an allowed signers file. Returns false when ssh-keygen is unavailable.
+fn configure_test_ssh_signing(repo: &TestRepo, key_dir: &Path) -> bool {
+    let key = key_dir.join("id_ed25519");
+    let Ok(output) = Command::new("ssh-keygen")
+        .args([
+            "-q",
+            "-t",
+            "ed25519",
+            "-N",
+            "",
+            "-C",
+            "signer@example.com",
+            "-f",
+        ])
+        .arg(&key)
+        .output()
+    else {
+        return false;
+    };
+    assert!(output.status.success(), "failed to generate test ssh key");
+
+    let public_key = std::fs::read_to_string(key.with_extension("pub")).unwrap();
+    let allowed_signers = key_dir.join("allowed_signers");
+    std::fs::write(
+        &allowed_signers,
+        format!("signer@example.com {}", public_key),
+    )
+    .unwrap();
+
+    repo.git_og(&["config", "gpg.format", "ssh"]).unwrap();
+    repo.git_og(&["config", "user.signingkey", key.to_str().unwrap()])
+        .unwrap();
+    repo.git_og(&[
+        "config",
+        "gpg.ssh.allowedSignersFile",
+        allowed_signers.to_str().unwrap(),
+    ])
+    .unwrap();
+    true
+}
+
+#[test]
+fn test_ssh_signed_note_verifies_and_detects_tampering() {
+    let mut repo = TestRepo::new();
+    let key_dir = tempfile::tempdir().unwrap();
+    if !configure_test_ssh_signing(&repo, key_dir.path()) {
+        eprintln!("ssh-keygen not available, skipping");
+        return;
+    }
+    repo.patch_git_ai_config(|patch| {
+        patch.sign_authorship = Some(true);
+    });
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Signed commit").unwrap();
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let mut log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    assert!(
+        log.metadata
+            .signature
+            .as_deref()
+            .is_some_and(|s| s.contains("BEGIN SSH SIGNATURE")),
+        "note should carry an SSH signature: {}",
+        note
+    );
+    let signer = log
+        .metadata
+        .signer
+        .clone()
+        .expect("key fingerprint recorded");
+    assert!(signer.starts_with("SHA256:"), "{}", signer);
+
+    let output = repo
+        .git_ai(&["verify", "--signatures", &commit.commit_sha])
+        .unwrap();
+    assert!(
+        output.contains(&format!("{} signed ({})", commit.commit_sha, signer)),
+        "{}",
+        output
+    );
+
+    // Claim the AI line was human-written while keeping the old signature
+    log.attestations.clear();
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    let err = repo
+        .git_ai(&["verify", "--signatures", &commit.commit_sha])
+        .unwrap_err();
+    assert!(
+        err.contains(&format!("{} invalid signature", commit.commit_sha)),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_rebased_notes_are_signed() {
+    let mut repo = TestRepo::new();
+    let key_dir = tempfile::tempdir().unwrap();
+    if !configure_test_ssh_signing(&repo, key_dir.path()) {
+        eprintln!("ssh-keygen not available, skipping");
+        return;
+    }
+    repo.patch_git_ai_config(|patch| {
+        patch.sign_authorship = Some(true);
+    });
+
+    let mut main_file = repo.filename("main.txt");
+    main_file.set_contents(lines!["main line 1"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.txt");
+    feature.set_contents(lines!["AI feature line".ai()]);
+    repo.stage_all_and_commit("AI feature").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["other content"]);
+    repo.stage_all_and_commit("Main advances").unwrap();
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git(&["rebase", &default_branch]).unwrap();
+
+    let output = repo
+        .git_ai(&["verify", "--require-signed", "HEAD"])
+        .unwrap();
+    assert!(output.contains("signed"), "{}", output);
+    feature.assert_lines_and_blame(lines!["AI feature line".ai()]);
+}
+
+#[test]
+fn test_notes_merge_does_not_sign_unioned_notes() {
+    let mut repo = TestRepo::new();
+    let key_dir = tempfile::tempdir().unwrap();
+    if !configure_test_ssh_signing(&repo, key_dir.path()) {
+        eprintln!("ssh-keygen not available, skipping");
+        return;
+    }
+    repo.patch_git_ai_config(|patch| {
+        patch.sign_authorship = Some(true);
+    });
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["AI line".ai(), "Line 2"]);
+    let commit = repo.stage_all_and_commit("Signed commit").unwrap();
+    let note = |notes_ref: &str| {
+        let note = repo
+            .git_og(&[
+                "notes",
+                &format!("--ref={}", notes_ref),
+                "show",
+                &commit.commit_sha,
+            ])
+            .unwrap();
+        AuthorshipLog::deserialize_from_string(note.trim()).unwrap()
+    };
+    assert!(note("ai").metadata.signature.is_some());
+
+    // Someone else's unsigned note for the same commit attributes line 2
+    let mut theirs = note("ai");
+    theirs.metadata.signature = None;
+    theirs.metadata.signer = None;
+    let hash = theirs.attestations[0].entries[0].hash.clone();
+    theirs.attestations[0]
+        .entries
+        .push(AttestationEntry::new(hash, vec![LineRange::Single(2)]));
+    repo.git_og(&[
+        "notes",
+        "--ref=refs/notes/other",
+        "add",
+        "-f",
+        "-m",
+        &theirs.serialize_to_string().unwrap(),
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    repo.git_ai(&["notes-merge", "refs/notes/other"]).unwrap();
+    let merged = note("ai");
+    assert_eq!(
+        merged.attestations[0].entries[0].line_ranges,
+        vec![LineRange::Range(1, 2)],
+        "{:?}",
+        merged
+    );
+    assert!(merged.metadata.signature.is_none(), "{:?}", merged);
+    assert!(merged.metadata.signer.is_none(), "{:?}", merged);
+}
+
+#[test]
+fn test_commit_sign_authorship_flag_signs_note() {
+    let repo = TestRepo::new();
+    let key_dir = tempfile::tempdir().unwrap();
+    if !configure_test_ssh_signing(&repo, key_dir.path()) {
+        eprintln!("ssh-keygen not available, skipping");
+        return;
+    }
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["AI line".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git_ai(&["commit", "--sign-authorship", "-m", "Signed commit"])
+        .unwrap();
+
+    let head = repo.git_og(&["rev-parse", "HEAD"]).unwrap();
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", head.trim()])
+        .unwrap();
+    let log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    assert!(
+        log.metadata.signature.is_some(),
+        "note should be signed: {}",
+        note
+    );
+    repo.git_ai(&["verify", "--require-signed", "HEAD"])
+        .unwrap();
+
+    // Without the flag (and with sign_authorship off) notes stay unsigned
+    file.set_contents(lines!["AI line".ai(), "Another AI line".ai()]);
+    repo.stage_all_and_commit("Unsigned commit").unwrap();
+    repo.git_ai(&["verify", "--require-signed", "HEAD"])
+        .unwrap_err();
+}
+
+#[test]
+fn test_verify_unsigned_note_passes_unless_required() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Unsigned commit").unwrap();
+
+    let output = repo.git_ai(&["verify"]).unwrap();
+    assert!(
+        output.contains(&format!("{} unsigned", commit.commit_sha)),
+        "{}",
+        output
+    );
+
+    let err = repo.git_ai(&["verify", "--require-signed"]).unwrap_err();
+    assert!(
+        err.contains(&format!("{} unsigned", commit.commit_sha)),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_verify_reports_inconsistent_notes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(2, lines!["AI Line 3".ai()]);
+    let second = repo.stage_all_and_commit("Second").unwrap();
+
+    let range = format!("{}..HEAD", first.commit_sha);
+    let output = repo.git_ai(&["verify", &range]).unwrap();
+    assert!(
+        output.contains(&format!("{} unsigned", second.commit_sha)),
+        "{}",
+        output
+    );
+    assert!(!output.contains(&first.commit_sha), "{}", output);
+
+    // Corrupt the second note: a range past the end of the file, a session with no prompt
+    // record, and a base commit pointing elsewhere
+    let mut log = second.authorship_log.clone();
+    log.metadata.base_commit_sha = first.commit_sha.clone();
+    log.attestations[0].entries.push(AttestationEntry::new(
+        "feedfacefeedface".to_string(),
+        vec![LineRange::Range(3, 9)],
+    ));
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        &second.commit_sha,
+    ])
+    .unwrap();
+
+    let err = repo.git_ai(&["verify", &range]).unwrap_err();
+    for problem in [
+        format!("base_commit_sha is {}", first.commit_sha),
+        "test.txt: session feedfacefeedface missing from prompts".to_string(),
+        "test.txt: lines 3-9 out of range (file has 3 lines)".to_string(),
+    ] {
+        assert!(
+            err.contains(&format!("Error: {} {}", second.commit_sha, problem)),
+            "{}",
+            err
+        );
+    }
+
+    // Other notes still verify
+    repo.git_ai(&["verify", &first.commit_sha]).unwrap();
+}
+
+#[test]
+fn test_verify_reports_unreadable_note_and_checks_the_rest() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(2, lines!["AI Line 3".ai()]);
+    let second = repo.stage_all_and_commit("Second").unwrap();
+    file.insert_at(3, lines!["AI Line 4".ai()]);
+    let third = repo.stage_all_and_commit("Third").unwrap();
+
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        "not an authorship log",
+        &second.commit_sha,
+    ])
+    .unwrap();
+
+    let err = repo.git_ai(&["verify", "--all"]).unwrap_err();
+    assert!(
+        err.lines().any(
+            |line| line.starts_with(&format!("Error: {}", second.commit_sha))
+                && line.contains("Failed to parse authorship log")
+        ),
+        "{}",
+        err
+    );
+
+    // The commits on either side are still checked, and pass
+    for commit in [&first, &third] {
+        assert!(
+            !err.contains(&format!("Error: {}", commit.commit_sha)),
+            "{}",
+            err
+        );
+    }
+    repo.git_ai(&["verify", &first.commit_sha, &third.commit_sha])
+        .unwrap();
+}
+
+#[test]
+fn test_verify_accepts_prompts_stored_in_another_commits_note() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(2, lines!["AI Line 3".ai()]);
+    let second = repo.stage_all_and_commit("Second").unwrap();
+
+    // The second note credits its lines to the session whose prompt only the first note has
+    let first_hash = first
+        .authorship_log
+        .metadata
+        .prompts
+        .keys()
+        .next()
+        .unwrap()
+        .clone();
+    let mut log = second.authorship_log.clone();
+    log.metadata.prompts.clear();
+    for entry in &mut log.attestations[0].entries {
+        entry.hash = first_hash.clone();
+    }
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        &second.commit_sha,
+    ])
+    .unwrap();
+
+    let output = repo.git_ai(&["verify", &second.commit_sha]).unwrap();
+    assert!(!output.contains("missing from prompts"), "{}", output);
+}
+
+#[test]
+fn test_verify_fix_base_commit_rewrites_mismatched_sha() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(1, lines!["Line 1.5"]);
+    let second = repo.stage_all_and_commit("Second").unwrap();
+
+    // Simulate a note copied from another commit without updating its base
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &first.commit_sha])
+        .unwrap();
+    let mut log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    log.metadata.base_commit_sha = second.commit_sha.clone();
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &log.serialize_to_string().unwrap(),
+        &first.commit_sha,
+    ])
+    .unwrap();
+
+    let output = repo.git_ai(&["verify", "--fix-base-commit"]).unwrap();
+    assert!(
+        output.contains(&format!(
+            "{} base_commit_sha {} -> {}",
+            first.commit_sha, second.commit_sha, first.commit_sha
+        )),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("Fixed base_commit_sha on 1 of 2 note(s)"),
+        "{}",
+        output
+    );
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &first.commit_sha])
+        .unwrap();
+    let fixed = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    assert_eq!(fixed.metadata.base_commit_sha, first.commit_sha);
+    assert_eq!(fixed.attestations, log.attestations);
+    assert_eq!(fixed.metadata.prompts, log.metadata.prompts);
+
+    // Already-correct notes are left alone
+    let output = repo
+        .git_ai(&["verify", "--fix-base-commit", &first.commit_sha])
+        .unwrap();
+    assert!(
+        output.contains("Fixed base_commit_sha on 0 of 1 note(s)"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_verify_fix_base_commit_refuses_notes_signed_by_another_key() {
+    let mut repo = TestRepo::new();
+    let key_dir = tempfile::tempdir().unwrap();
+    if !configure_test_ssh_signing(&repo, key_dir.path()) {
+        eprintln!("ssh-keygen not available, skipping");
+        return;
+    }
+    repo.patch_git_ai_config(|patch| {
+        patch.sign_authorship = Some(true);
+    });
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(1, lines!["Line 1.5"]);
+    let second = repo.stage_all_and_commit("Second").unwrap();
+
+    // A note copied from another commit, signed by its author after the copy
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &first.commit_sha])
+        .unwrap();
+    let mut log = AuthorshipLog::deserialize_from_string(note.trim()).unwrap();
+    log.metadata.base_commit_sha = second.commit_sha.clone();
+    let gitai_repo =
+        GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    sign_authorship_log(&gitai_repo, &mut log).unwrap();
+    let copied = log.serialize_to_string().unwrap();
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &copied,
+        &first.commit_sha,
+    ])
+    .unwrap();
+
+    // Someone else, trusted for verification but holding a different key, can't re-sign it
+    let other_dir = tempfile::tempdir().unwrap();
+    let trusted = std::fs::read_to_string(key_dir.path().join("allowed_signers")).unwrap();
+    assert!(configure_test_ssh_signing(&repo, other_dir.path()));
+    let other_trusted = std::fs::read_to_string(other_dir.path().join("allowed_signers")).unwrap();
+    std::fs::write(
+        other_dir.path().join("allowed_signers"),
+        format!("{}{}", trusted, other_trusted),
+    )
+    .unwrap();
+    let err = repo
+        .git_ai(&["verify", "--fix-base-commit", &first.commit_sha])
+        .unwrap_err();
+    assert!(err.contains("not the local signing key"), "{}", err);
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &first.commit_sha])
+        .unwrap();
+    assert_eq!(note.trim(), copied.trim());
+
+    // The key that signed it can
+    repo.git_og(&[
+        "config",
+        "user.signingkey",
+        key_dir.path().join("id_ed25519").to_str().unwrap(),
+    ])
+    .unwrap();
+    repo.git_ai(&["verify", "--fix-base-commit", &first.commit_sha])
+        .unwrap();
+    repo.git_ai(&["verify", "--signatures", &first.commit_sha])
+        .unwrap();
+}
+
+#[test]
+fn test_verify_reachable_only_skips_orphaned_notes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    let kept = repo.stage_all_and_commit("Reachable commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "scratch"]).unwrap();
+    file.insert_at(2, lines!["AI Line 3".ai()]);
+    let orphaned = repo.stage_all_and_commit("Soon unreachable").unwrap();
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.git_og(&["branch", "-D", "scratch"]).unwrap();
+
+    let output = repo.git_ai(&["verify", "--all"]).unwrap();
+    assert!(output.contains(&kept.commit_sha), "{}", output);
+    assert!(output.contains(&orphaned.commit_sha), "{}", output);
+
+    let output = repo.git_ai(&["verify", "--reachable-only"]).unwrap();
+    assert!(
+        output.contains(&format!("{} unsigned", kept.commit_sha)),
+        "{}",
+        output
+    );
+    assert!(!output.contains(&orphaned.commit_sha), "{}", output);
+    assert!(
+        output.contains("Skipped 1 note(s) on unreachable commits"),
+        "{}",
+        output
+    );
+}
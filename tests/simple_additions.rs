@@ -1271,3 +1271,442 @@ fn test_ai_edits_file_with_spaces_in_filename() {
         "Line 3".human(),
     ]);
 }
+
+#[test]
+fn test_allow_empty_commit_through_proxy() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2".ai()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Pending AI work that is not part of the empty commit
+    let mut pending = repo.filename("pending.txt");
+    pending.set_contents_no_stage(lines!["Pending AI".ai()]);
+
+    repo.git(&["commit", "--allow-empty", "-m", "Empty commit"])
+        .expect("Empty commit should succeed");
+
+    // The empty commit gets a well-formed, empty authorship log
+    let note = repo
+        .git(&["notes", "--ref=ai", "show", "HEAD"])
+        .expect("Empty commit should have an authorship note");
+    let log =
+        git_ai::authorship::authorship_log_serialization::AuthorshipLog::deserialize_from_string(
+            note.trim(),
+        )
+        .expect("Empty commit note should deserialize");
+    assert!(log.attestations.is_empty());
+
+    // Attribution for the pending work survives the empty commit
+    repo.stage_all_and_commit("Pending work").unwrap();
+    pending.assert_lines_and_blame(lines!["Pending AI".ai()]);
+    file.assert_lines_and_blame(lines!["Line 1".human(), "Line 2".ai()]);
+}
+
+#[test]
+fn test_checkpoint_merge_into_unifies_fragmented_session() {
+    use git_ai::authorship::authorship_log_serialization::generate_short_hash;
+
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("test.txt");
+    fs::write(&file_path, "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Each mock_ai checkpoint uses a fresh session id, like an agent that restarted
+    fs::write(&file_path, "base\nAI line 1\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "test.txt"]).unwrap();
+    let first_agent = repo
+        .current_working_logs()
+        .read_all_checkpoints()
+        .unwrap()
+        .last()
+        .and_then(|c| c.agent_id.clone())
+        .unwrap();
+    let first_hash = generate_short_hash(&first_agent.id, &first_agent.tool);
+
+    let err = repo
+        .git_ai(&[
+            "checkpoint",
+            "mock_ai",
+            "--merge-into",
+            "missing0",
+            "test.txt",
+        ])
+        .unwrap_err();
+    assert!(
+        err.contains("No checkpoints found for session missing0"),
+        "{}",
+        err
+    );
+
+    fs::write(&file_path, "base\nAI line 1\nAI line 2\n").unwrap();
+    let output = repo
+        .git_ai(&[
+            "checkpoint",
+            "mock_ai",
+            "--merge-into",
+            &first_hash,
+            "test.txt",
+        ])
+        .unwrap();
+    assert!(
+        output.contains(&format!("into {}", first_hash)),
+        "{}",
+        output
+    );
+
+    let commit = repo.stage_all_and_commit("AI commit").unwrap();
+    let prompts = &commit.authorship_log.metadata.prompts;
+    assert_eq!(prompts.len(), 1, "{:?}", prompts);
+    assert_eq!(prompts[&first_hash].agent_id, first_agent);
+
+    let mut file = repo.filename("test.txt");
+    file.assert_lines_and_blame(lines!["base".human(), "AI line 1".ai(), "AI line 2".ai()]);
+}
+
+#[test]
+fn test_checkpoint_normalizes_empty_agent_tool() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("test.txt");
+    fs::write(&file_path, "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(&file_path, "base\nAgent line\n").unwrap();
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": ["test.txt"],
+        "transcript": { "messages": [] },
+        "agent_name": "",
+        "model": "some-model",
+        "conversation_id": "conv-1"
+    });
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+    ])
+    .unwrap();
+
+    let agent_id = repo
+        .current_working_logs()
+        .read_all_checkpoints()
+        .unwrap()
+        .last()
+        .and_then(|c| c.agent_id.clone())
+        .unwrap();
+    assert_eq!(agent_id.tool, "some-model");
+}
+
+#[test]
+fn test_checkpoint_dump_working_log_writes_checkpoints() {
+    use git_ai::commands::checkpoint::WorkingLogDump;
+
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("test.txt");
+    fs::write(&file_path, "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(&file_path, "base\nHuman line\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+
+    fs::write(&file_path, "base\nHuman line\nAgent line\n").unwrap();
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": ["test.txt"],
+        "transcript": { "messages": [{ "type": "user", "text": "secret prompt" }] },
+        "agent_name": "test-agent",
+        "model": "test-model",
+        "conversation_id": "conv-dump"
+    });
+    let dump_path = repo.path().join("working-log.json");
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+        "--dump-working-log",
+        dump_path.to_str().unwrap(),
+    ])
+    .unwrap();
+
+    let dump: WorkingLogDump =
+        serde_json::from_str(&fs::read_to_string(&dump_path).unwrap()).unwrap();
+    assert!(dump.transcripts_redacted);
+    assert_eq!(dump.checkpoints.len(), 2);
+    assert!(dump.checkpoints[0].agent_id.is_none());
+    let agent = dump.checkpoints[1].agent_id.as_ref().unwrap();
+    assert_eq!(agent.tool, "test-agent");
+    assert!(dump.checkpoints[1].transcript.is_none());
+    assert_eq!(dump.checkpoints[1].entries[0].file, "test.txt");
+    assert!(!dump.checkpoints[1].entries[0].line_attributions.is_empty());
+    assert!(
+        !fs::read_to_string(&dump_path)
+            .unwrap()
+            .contains("secret prompt")
+    );
+
+    // Dumping on its own checkpoint run, with transcripts kept
+    repo.git_ai(&[
+        "checkpoint",
+        "--dump-working-log",
+        dump_path.to_str().unwrap(),
+        "--include-transcripts",
+    ])
+    .unwrap();
+    let dump: WorkingLogDump =
+        serde_json::from_str(&fs::read_to_string(&dump_path).unwrap()).unwrap();
+    assert!(!dump.transcripts_redacted);
+    assert!(dump.checkpoints.iter().any(|c| {
+        c.transcript
+            .as_ref()
+            .is_some_and(|t| !t.messages().is_empty())
+    }));
+}
+
+#[test]
+fn test_checkpoint_ignore_unstaged_attributes_only_staged_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    let file_path = repo.path().join("test.txt");
+    fs::write(&file_path, "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(&file_path, "base\nStaged agent line\n").unwrap();
+    repo.git(&["add", "test.txt"]).unwrap();
+    fs::write(&file_path, "base\nStaged agent line\nUnstaged line\n").unwrap();
+    fs::write(repo.path().join("untracked.txt"), "Untracked line\n").unwrap();
+
+    repo.git_ai(&["checkpoint", "mock_ai", "--ignore-unstaged"])
+        .unwrap();
+
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    let checkpoint = checkpoints.last().unwrap();
+    assert_eq!(checkpoint.agent_id.as_ref().unwrap().tool, "mock_ai");
+    let files: Vec<&str> = checkpoint.entries.iter().map(|e| e.file.as_str()).collect();
+    assert_eq!(files, vec!["test.txt"]);
+
+    repo.stage_all_and_commit("Commit both edits").unwrap();
+    file.assert_lines_and_blame(lines![
+        "base".human(),
+        "Staged agent line".ai(),
+        "Unstaged line".human()
+    ]);
+}
+
+#[test]
+fn test_checkpoint_json_reports_recorded_attribution() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("test.txt");
+    fs::write(&file_path, "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(&file_path, "base\nAgent line 1\nAgent line 2\n").unwrap();
+    let output = repo.git_ai(&["checkpoint", "mock_ai", "--json"]).unwrap();
+
+    // The JSON is the only thing on stdout, which comes first in the combined output
+    let summary: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+    assert_eq!(summary["entries"], 1, "{}", output);
+    assert_eq!(summary["files"], 1, "{}", output);
+    assert_eq!(summary["kind"], "ai_agent", "{}", output);
+    assert_eq!(summary["agent"]["tool"], "mock_ai", "{}", output);
+    assert_eq!(summary["agent"]["model"], "unknown", "{}", output);
+    assert_eq!(
+        summary["working_log_len"],
+        repo.current_working_logs()
+            .read_all_checkpoints()
+            .unwrap()
+            .len(),
+    );
+    let files = summary["file_attributions"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "{}", output);
+    assert_eq!(files[0]["file"], "test.txt");
+    assert_eq!(files[0]["ai_lines"], 2);
+    assert_eq!(files[0]["lines_added"], 2);
+    assert_eq!(files[0]["lines_deleted"], 0);
+
+    // Without the flag, nothing is written to stdout
+    fs::write(&file_path, "base\nAgent line 1\nAgent line 2\nHuman line\n").unwrap();
+    let output = repo.git_ai(&["checkpoint"]).unwrap();
+    assert!(!output.contains("\"file_attributions\""), "{}", output);
+}
+
+#[test]
+fn test_checkpoint_concurrency_config_reported_in_summary() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("test.txt");
+    fs::write(&file_path, "base\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // The default isn't reported
+    fs::write(&file_path, "base\nAgent line 1\n").unwrap();
+    let output = repo.git_ai(&["checkpoint", "mock_ai", "--json"]).unwrap();
+    let summary: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+    assert!(summary.get("concurrency").is_none(), "{}", output);
+
+    repo.git_og(&["config", "gitai.checkpointConcurrency", "1"])
+        .unwrap();
+    fs::write(&file_path, "base\nAgent line 1\nAgent line 2\n").unwrap();
+    let output = repo.git_ai(&["checkpoint", "mock_ai", "--json"]).unwrap();
+    let summary: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+    assert_eq!(summary["concurrency"], 1, "{}", output);
+    assert_eq!(summary["entries"], 1, "{}", output);
+    assert!(output.contains("(1 files at a time)"), "{}", output);
+
+    // Invalid values fall back to the default
+    repo.git_og(&["config", "gitai.checkpointConcurrency", "0"])
+        .unwrap();
+    fs::write(
+        &file_path,
+        "base\nAgent line 1\nAgent line 2\nAgent line 3\n",
+    )
+    .unwrap();
+    let output = repo.git_ai(&["checkpoint", "mock_ai", "--json"]).unwrap();
+    let summary: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+    assert!(summary.get("concurrency").is_none(), "{}", output);
+}
+
+#[test]
+fn test_checkpoint_since_last_commit_resets_only_after_head_moves() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("README.md"), "# Test\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    fs::write(repo.path().join("notes.txt"), "draft\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    // HEAD hasn't moved, so checkpoints keep accumulating
+    fs::write(repo.path().join("notes.txt"), "draft\nmore\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "--since-last-commit"])
+        .unwrap();
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    assert_eq!(checkpoints.len(), 2);
+
+    // Checkpoint on another commit, then come back: the old checkpoints are stale
+    repo.git_og(&["checkout", "-b", "side"]).unwrap();
+    repo.git_og(&["commit", "--allow-empty", "-m", "side"])
+        .unwrap();
+    fs::write(repo.path().join("notes.txt"), "draft\nmore\nside\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+    repo.git_og(&["checkout", &default_branch]).unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "draft\nmore\nback\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "--since-last-commit"])
+        .unwrap();
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    assert_eq!(checkpoints.len(), 1);
+}
+
+#[test]
+fn test_checkpoint_since_last_commit_keeps_log_migrated_by_checkout() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("README.md"), "# Test\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+    repo.git(&["checkout", "-b", "side"]).unwrap();
+    repo.git(&["commit", "--allow-empty", "-m", "side"])
+        .unwrap();
+    repo.git(&["checkout", &default_branch]).unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "draft\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    // The checkout carries the uncommitted AI edit, and its checkpoint, over to side
+    repo.git(&["checkout", "side"]).unwrap();
+    fs::write(repo.path().join("notes.txt"), "draft\nmore\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "--since-last-commit"])
+        .unwrap();
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    assert_eq!(checkpoints.len(), 2);
+
+    let mut file = repo.filename("notes.txt");
+    repo.stage_all_and_commit("Notes").unwrap();
+    file.assert_lines_and_blame(lines!["draft".ai(), "more".ai()]);
+}
+
+#[test]
+fn test_global_quiet_and_verbose_flags() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("README.md"), "# Test\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("notes.txt"), "draft\n").unwrap();
+    let output = repo.git_ai(&["-q", "checkpoint", "mock_ai"]).unwrap();
+    assert_eq!(output, "");
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    assert_eq!(checkpoints.len(), 1);
+
+    fs::write(repo.path().join("notes.txt"), "draft\nmore\n").unwrap();
+    let output = repo
+        .git_ai_with_env(
+            &["--verbose", "checkpoint", "mock_ai"],
+            &[("GIT_AI_DEBUG", "0")],
+        )
+        .unwrap();
+    assert!(output.contains("[BENCHMARK]"), "{}", output);
+    assert!(output.contains("Checkpoint completed"), "{}", output);
+
+    // Errors still surface when quiet
+    let err = repo
+        .git_ai(&["--quiet", "blame", "missing.txt"])
+        .unwrap_err();
+    assert!(err.contains("Blame failed"), "{}", err);
+
+    // `quiet` in the config file is the same as passing the flag
+    let home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(home.path().join(".git-ai")).unwrap();
+    fs::write(
+        home.path().join(".git-ai").join("config.json"),
+        r#"{"quiet": true}"#,
+    )
+    .unwrap();
+    fs::write(repo.path().join("notes.txt"), "draft\nmore\nagain\n").unwrap();
+    let output = repo
+        .git_ai_with_env(
+            &["checkpoint", "mock_ai"],
+            &[
+                ("HOME", home.path().to_str().unwrap()),
+                ("GIT_AI_DEBUG", "0"),
+            ],
+        )
+        .unwrap();
+    assert_eq!(output, "");
+}
+
+#[test]
+fn test_human_author_is_canonicalized_through_mailmap() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join(".mailmap"),
+        "Canonical Name <canon@example.com> <test@example.com>\n",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Add mailmap").unwrap();
+
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "AI Line 2".ai()]);
+    repo.git_ai(&["checkpoint"]).unwrap();
+    let checkpoints = repo.current_working_logs().read_all_checkpoints().unwrap();
+    assert!(
+        checkpoints.iter().all(|c| c.author == "Canonical Name"),
+        "{:?}",
+        checkpoints.iter().map(|c| &c.author).collect::<Vec<_>>()
+    );
+
+    let commit = repo.stage_all_and_commit("AI commit").unwrap();
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("AI prompt recorded");
+    assert_eq!(
+        prompt.human_author.as_deref(),
+        Some("Canonical Name <canon@example.com>")
+    );
+}
@@ -293,3 +293,47 @@ fn test_prepare_working_log_squash_with_mixed_additions() {
         "Sum of accepted_lines across prompts should match ai_accepted stat"
     );
 }
+
+/// blame --squash-view lists each session that fed into a squash commit
+#[test]
+fn test_blame_squash_view_breaks_down_sessions() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+
+    file.set_contents(lines!["header", "body", "footer"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let default_branch = repo.current_branch();
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+
+    file.insert_at(1, lines!["// AI session 1a".ai(), "// AI session 1b".ai()]);
+    repo.stage_all_and_commit("AI session 1").unwrap();
+
+    file.insert_at(5, lines!["// AI session 2".ai()]);
+    repo.stage_all_and_commit("AI session 2").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.git(&["merge", "--squash", "feature"]).unwrap();
+    let squash = repo.commit("Squashed sessions").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--squash-view", &squash.commit_sha, "file.txt"])
+        .unwrap();
+    assert!(
+        output.contains(&format!(
+            "squash {} file.txt: 2 session(s)",
+            &squash.commit_sha[..7]
+        )),
+        "{}",
+        output
+    );
+    let sessions: Vec<&str> = output
+        .lines()
+        .filter(|l| l.starts_with("session "))
+        .collect();
+    assert_eq!(sessions.len(), 2, "{}", output);
+    assert!(sessions[0].ends_with(": 2 line(s)"), "{}", output);
+    assert!(sessions[1].ends_with(": 1 line(s)"), "{}", output);
+    assert!(output.contains("  lines 2-3\n"), "{}", output);
+    assert!(output.contains("  lines 6\n"), "{}", output);
+}
@@ -378,3 +378,313 @@ fn test_markdown_stats_formatting() {
     println!("{}", markdown);
     assert_debug_snapshot!(markdown);
 }
+
+#[test]
+fn test_stats_diff_gate_thresholds() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("gate.txt");
+    file.set_contents(lines!["Human 1", "Human end"]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    file.insert_at(1, lines!["AI 2".ai(), "AI 3".ai()]);
+    repo.stage_all_and_commit("AI lines").unwrap();
+    file.insert_at(3, lines!["Human 4", "AI 5".ai()]);
+    let head = repo.stage_all_and_commit("Mixed lines").unwrap();
+
+    // 3 of the 4 lines added across both commits are AI-authored
+    let range = format!("{}..{}", base.commit_sha, head.commit_sha);
+    let raw = repo.git_ai(&["stats", "--diff", &range, "--json"]).unwrap();
+    let share: git_ai::authorship::stats::DiffAiShare =
+        serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!((share.ai_lines, share.added_lines), (3, 4));
+
+    let output = repo
+        .git_ai(&[
+            "stats",
+            "--diff",
+            &range,
+            "--fail-over",
+            "80",
+            "--fail-under",
+            "75",
+        ])
+        .unwrap();
+    assert!(
+        output.contains("3 of 4 added lines AI-authored (75.0%)"),
+        "{}",
+        output
+    );
+
+    let err = repo
+        .git_ai(&["stats", "--diff", &range, "--fail-over", "70"])
+        .unwrap_err();
+    assert!(err.contains("75.0% is above --fail-over 70%"), "{}", err);
+
+    let err = repo
+        .git_ai(&["stats", "--diff", &range, "--fail-under", "80"])
+        .unwrap_err();
+    assert!(err.contains("75.0% is below --fail-under 80%"), "{}", err);
+}
+
+#[test]
+fn test_stats_diff_symmetric_range_starts_from_merge_base() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("gate.txt");
+    file.set_contents(lines!["Human 1", "Human end"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(1, lines!["AI 2".ai(), "AI 3".ai()]);
+    let feature = repo.stage_all_and_commit("AI lines").unwrap();
+
+    repo.git(&["checkout", "-"]).unwrap();
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["Human A", "Human B", "Human C"]);
+    let main = repo.stage_all_and_commit("Human lines").unwrap();
+
+    // Only the feature branch's additions count, not main's since the fork
+    let range = format!("{}...{}", main.commit_sha, feature.commit_sha);
+    let raw = repo.git_ai(&["stats", "--diff", &range, "--json"]).unwrap();
+    let share: git_ai::authorship::stats::DiffAiShare =
+        serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!((share.ai_lines, share.added_lines), (2, 2));
+    assert_eq!(share.head, feature.commit_sha);
+}
+
+#[test]
+fn test_stats_csv_header_toggle() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("gate.txt");
+    file.set_contents(lines!["Human 1", "Human end"]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+    file.insert_at(1, lines!["AI 2".ai(), "AI 3".ai()]);
+    let head = repo.stage_all_and_commit("AI lines").unwrap();
+
+    let output = repo.git_ai(&["stats", "--by-file", "--csv"]).unwrap();
+    let rows: Vec<&str> = output.lines().collect();
+    assert_eq!(rows[0], "file,ai_lines,dominant_model");
+    assert!(rows[1].starts_with("gate.txt,2,"), "{}", output);
+    assert_eq!(rows.len(), 2, "{}", output);
+
+    let output = repo
+        .git_ai(&["stats", "--by-file", "--csv", "--no-header"])
+        .unwrap();
+    let rows: Vec<&str> = output.lines().collect();
+    assert_eq!(rows.len(), 1, "{}", output);
+    assert!(rows[0].starts_with("gate.txt,2,"), "{}", output);
+
+    let range = format!("{}..{}", base.commit_sha, head.commit_sha);
+    let output = repo.git_ai(&["stats", "--diff", &range, "--csv"]).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec![
+            "base,head,ai_lines,added_lines,ai_percent".to_string(),
+            format!("{},{},2,2,100.0", base.commit_sha, head.commit_sha)
+        ]
+    );
+    let output = repo
+        .git_ai(&["stats", "--diff", &range, "--csv", "--no-header"])
+        .unwrap();
+    assert_eq!(output.lines().count(), 1, "{}", output);
+
+    let err = repo.git_ai(&["stats", "--csv"]).unwrap_err();
+    assert!(err.contains("--csv requires --by-file"), "{}", err);
+}
+
+#[test]
+fn test_commit_summary_only_prints_to_a_terminal() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Test output is piped, so the summary stays out of it
+    file.insert_at(1, lines!["AI 2".ai(), "AI 3".ai(), "Human 4"]);
+    repo.git(&["add", "-A"]).unwrap();
+    let output = repo.git(&["commit", "-m", "Mixed edits"]).unwrap();
+    assert!(!output.contains("git-ai:"), "{}", output);
+}
+
+#[test]
+fn test_stats_history_aggregates_models_and_present_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai(), "AI 3".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["Human A", "AI B".ai()]);
+    repo.stage_all_and_commit("Second").unwrap();
+
+    let output = repo.git_ai(&["stats", "--history", "--json"]).unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&output)).unwrap();
+    assert_eq!(stats["commits"], 2, "{}", output);
+    assert_eq!(stats["commits_with_notes"], 2, "{}", output);
+    assert_eq!(stats["ai_lines"], 3, "{}", output);
+    assert_eq!(stats["human_lines"], 2, "{}", output);
+    let models = stats["by_model"].as_array().unwrap();
+    assert_eq!(models.len(), 1, "{}", output);
+    assert_eq!(models[0]["name"], "mock_ai/unknown");
+    assert_eq!(models[0]["sessions"], 2);
+    assert_eq!(models[0]["accepted_lines"], 3);
+    assert_eq!(stats["by_author"].as_array().unwrap().len(), 1);
+
+    // --since only counts the sessions from later commits
+    let output = repo
+        .git_ai(&["stats", "--since", &first.commit_sha, "--json"])
+        .unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&output)).unwrap();
+    assert_eq!(stats["commits"], 1, "{}", output);
+    assert_eq!(stats["ai_lines"], 1, "{}", output);
+    assert_eq!(stats["human_lines"], 4, "{}", output);
+
+    // --model filters by model name prefix
+    let output = repo.git_ai(&["stats", "--model", "gpt", "--json"]).unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&output)).unwrap();
+    assert_eq!(stats["ai_lines"], 0, "{}", output);
+    assert!(stats["by_model"].as_array().unwrap().is_empty());
+
+    let output = repo.git_ai(&["stats", "--history"]).unwrap();
+    assert!(
+        output.contains("AI-authored lines at HEAD: 3 of 5 (60.0%), 2 human"),
+        "{}",
+        output
+    );
+    assert!(output.contains("mock_ai/unknown"), "{}", output);
+}
+
+#[test]
+fn test_stats_history_ignores_files_with_non_ascii_names() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai()]);
+    let mut notes = repo.filename("naïve.md");
+    notes.set_contents(lines!["Human A", "Human B", "Human C"]);
+    repo.stage_all_and_commit("First").unwrap();
+
+    let output = repo
+        .git_ai(&["stats", "--history", "--json", "--ignore", "*.md"])
+        .unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&output)).unwrap();
+    assert_eq!(stats["ai_lines"], 1, "{}", output);
+    assert_eq!(stats["human_lines"], 1, "{}", output);
+}
+
+#[test]
+fn test_stats_history_team_map_collapses_authors_into_teams() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    repo.git_og(&["config", "user.name", "Alice"]).unwrap();
+    repo.git_og(&["config", "user.email", "alice@example.com"])
+        .unwrap();
+    file.set_contents(lines!["Alice AI line".ai(), "Alice line"]);
+    repo.stage_all_and_commit("Alice commit").unwrap();
+
+    repo.git_og(&["config", "user.name", "Bob"]).unwrap();
+    repo.git_og(&["config", "user.email", "bob@example.com"])
+        .unwrap();
+    file.insert_at(0, lines!["Bob AI line".ai(), "Bob AI line 2".ai()]);
+    repo.stage_all_and_commit("Bob commit").unwrap();
+
+    let map_path = repo.path().join("teams.json");
+    std::fs::write(
+        &map_path,
+        r#"{"ALICE@example.com": "platform", "bob": "platform"}"#,
+    )
+    .unwrap();
+
+    let output = repo
+        .git_ai(&["stats", "--team-map", map_path.to_str().unwrap(), "--json"])
+        .unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&output)).unwrap();
+    assert_eq!(
+        stats["by_author"].as_array().unwrap().len(),
+        2,
+        "{}",
+        output
+    );
+    let teams = stats["by_team"].as_array().unwrap();
+    assert_eq!(teams.len(), 1, "{}", output);
+    assert_eq!(teams[0]["name"], "platform");
+    assert_eq!(teams[0]["sessions"], 2);
+    assert_eq!(teams[0]["accepted_lines"], 3);
+
+    let output = repo
+        .git_ai(&["stats", "--team-map", map_path.to_str().unwrap()])
+        .unwrap();
+    assert!(output.contains("By team:"), "{}", output);
+}
+
+#[test]
+fn test_stats_history_does_not_double_count_merged_sessions() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.txt");
+    feature.set_contents(lines!["AI 1".ai(), "AI 2".ai()]);
+    repo.stage_all_and_commit("Feature").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["Human A"]);
+    repo.stage_all_and_commit("Main").unwrap();
+    repo.git(&["merge", "--no-ff", "feature", "-m", "Merge feature"])
+        .unwrap();
+
+    let output = repo.git_ai(&["stats", "--history", "--json"]).unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&output)).unwrap();
+    assert_eq!(stats["ai_lines"], 2, "{}", output);
+    let models = stats["by_model"].as_array().unwrap();
+    assert_eq!(models.len(), 1, "{}", output);
+    assert_eq!(models[0]["sessions"], 1, "{}", output);
+    assert_eq!(models[0]["accepted_lines"], 2, "{}", output);
+    assert_eq!(models[0]["total_additions"], 2, "{}", output);
+}
+
+#[test]
+fn test_stats_history_stream_emits_one_json_line_per_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Human 1", "AI 2".ai(), "AI 3".ai()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+
+    repo.git_og(&["commit", "--allow-empty", "-m", "No note"])
+        .unwrap();
+
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["Human A", "AI B".ai()]);
+    let third = repo.stage_all_and_commit("Third").unwrap();
+
+    let output = repo.git_ai(&["stats", "--history", "--stream"]).unwrap();
+    let records: Vec<serde_json::Value> = output
+        .lines()
+        .filter(|l| l.starts_with('{'))
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records.len(), 3, "{}", output);
+
+    assert_eq!(records[0]["commit"], third.commit_sha.as_str());
+    assert_eq!(records[0]["has_note"], true);
+    let agents = records[0]["agents"].as_array().unwrap();
+    assert_eq!(agents.len(), 1, "{}", output);
+    assert_eq!(agents[0]["name"], "mock_ai/unknown");
+    assert_eq!(agents[0]["accepted_lines"], 1);
+    assert_eq!(agents[0]["overridden_lines"], 0);
+
+    assert_eq!(records[1]["has_note"], false, "{}", output);
+    assert!(records[1]["agents"].as_array().unwrap().is_empty());
+
+    assert_eq!(records[2]["commit"], first.commit_sha.as_str());
+    assert_eq!(records[2]["agents"][0]["accepted_lines"], 2, "{}", output);
+
+    // --since limits the walk like it does for --history
+    let output = repo
+        .git_ai(&["stats", "--stream", "--since", &first.commit_sha])
+        .unwrap();
+    assert_eq!(output.lines().filter(|l| l.starts_with('{')).count(), 2);
+}
@@ -1898,3 +1898,33 @@ subdir_test_variants! {
         );
     }
 }
+
+#[test]
+fn test_checkpoint_reads_files_from_core_worktree() {
+    // The repository's git dir lives in repo.path(), but its files live elsewhere
+    let repo = TestRepo::new();
+    let worktree = tempfile::tempdir().unwrap();
+    let worktree_path = worktree.path().canonicalize().unwrap();
+    repo.git_og(&["config", "core.worktree", worktree_path.to_str().unwrap()])
+        .unwrap();
+
+    fs::write(worktree_path.join("base.txt"), "base\n").unwrap();
+    repo.git_og(&["add", "base.txt"]).unwrap();
+    repo.git_og(&["commit", "-m", "initial commit"]).unwrap();
+
+    fs::write(worktree_path.join("example.txt"), "line 1\nline 2\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "example.txt"])
+        .expect("checkpoint should succeed");
+
+    let working_log = repo.current_working_logs();
+    let checkpoints = working_log.read_all_checkpoints().unwrap();
+    let entry = checkpoints
+        .iter()
+        .flat_map(|c| c.entries.iter())
+        .find(|e| e.file == "example.txt")
+        .expect("checkpoint should include example.txt");
+    assert_eq!(
+        working_log.get_file_version(&entry.blob_sha).unwrap(),
+        "line 1\nline 2\n"
+    );
+}
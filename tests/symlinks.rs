@@ -0,0 +1,28 @@
+mod repos;
+
+use repos::test_repo::TestRepo;
+use std::fs;
+
+#[cfg(unix)]
+#[test]
+fn test_checkpoint_stores_symlink_target_not_pointed_to_file() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("target.txt"), "real content\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    std::os::unix::fs::symlink("target.txt", repo.path().join("link.txt")).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "link.txt"]).unwrap();
+
+    let working_log = repo.current_working_logs();
+    let checkpoints = working_log.read_all_checkpoints().unwrap();
+    let entry = checkpoints
+        .iter()
+        .flat_map(|c| c.entries.iter())
+        .find(|e| e.file == "link.txt")
+        .expect("checkpoint should include link.txt");
+    // git stores a symlink as its target path, with no trailing newline
+    assert_eq!(
+        working_log.get_file_version(&entry.blob_sha).unwrap(),
+        "target.txt"
+    );
+}
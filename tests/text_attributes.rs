@@ -0,0 +1,73 @@
+#[macro_use]
+mod repos;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::collections::BTreeSet;
+use std::fs;
+
+#[test]
+fn test_checkpoint_classifies_files_by_git_attributes() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join(".gitattributes"),
+        "*.dat binary\n*.raw -text\n*.nul text\n",
+    )
+    .unwrap();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let files: [(&str, &[u8]); 4] = [
+        // Text content, but marked binary
+        ("fixture.dat", b"plain text\n"),
+        ("image.raw", b"more plain text\n"),
+        // Null bytes, but marked text
+        ("table.nul", b"col\0a\ncol\0b\n"),
+        ("main.rs", b"fn main() {}\n"),
+    ];
+    for (path, contents) in files {
+        fs::write(repo.path().join(path), contents).unwrap();
+    }
+    let mut args = vec!["checkpoint", "mock_ai"];
+    args.extend(files.iter().map(|(path, _)| *path));
+    repo.git_ai(&args).unwrap();
+
+    let checkpointed: BTreeSet<String> = repo
+        .current_working_logs()
+        .read_all_checkpoints()
+        .unwrap()
+        .iter()
+        .flat_map(|c| c.entries.iter().map(|e| e.file.clone()))
+        .collect();
+    assert_eq!(
+        checkpointed,
+        BTreeSet::from(["main.rs".to_string(), "table.nul".to_string()])
+    );
+
+    repo.stage_all_and_commit("Agent edit").unwrap();
+    let mut main_rs = repo.filename("main.rs");
+    main_rs.assert_lines_and_blame(lines!["fn main() {}".ai()]);
+}
+
+#[test]
+fn test_files_without_attributes_fall_back_to_null_bytes() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("blob.bin"), b"\0\x01\x02").unwrap();
+    fs::write(repo.path().join("notes.txt"), b"notes\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "blob.bin", "notes.txt"])
+        .unwrap();
+
+    let checkpointed: BTreeSet<String> = repo
+        .current_working_logs()
+        .read_all_checkpoints()
+        .unwrap()
+        .iter()
+        .flat_map(|c| c.entries.iter().map(|e| e.file.clone()))
+        .collect();
+    assert_eq!(checkpointed, BTreeSet::from(["notes.txt".to_string()]));
+}